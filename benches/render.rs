@@ -0,0 +1,53 @@
+//! `flush_diff` on synthetic frames: how much a frame costs to write out
+//! as a function of how much of it changed.
+
+use std::io;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use crossterm::style::Color;
+
+use noderunner::config::GameConfig;
+use noderunner::ui::renderer::Renderer;
+
+const W: usize = 200;
+const H: usize = 60;
+
+/// Frame `n`: rows of map-like glyphs in a few colours, with `churn` of
+/// every hundred cells differing from frame `n - 1`.
+fn frame(n: usize, churn: usize) -> Vec<(String, Color)> {
+    const GLYPHS: [char; 6] = ['#', 'H', '-', '$', ' ', '='];
+    const COLORS: [Color; 4] = [
+        Color::Rgb { r: 200, g: 80, b: 40 },
+        Color::Rgb { r: 80, g: 200, b: 255 },
+        Color::Rgb { r: 255, g: 220, b: 60 },
+        Color::White,
+    ];
+    (0..H).map(|y| {
+        let text = (0..W).map(|x| {
+            let cell = y * W + x;
+            let moving = (cell * 37) % 100 < churn;
+            GLYPHS[(cell + if moving { n } else { 0 }) % GLYPHS.len()]
+        }).collect();
+        (text, COLORS[y % COLORS.len()])
+    }).collect()
+}
+
+fn bench_flush(c: &mut Criterion) {
+    let config = GameConfig::builtin();
+    let mut group = c.benchmark_group(format!("flush_diff_{W}x{H}"));
+    for churn in [0, 5, 25, 100] {
+        let frames: Vec<_> = (0..8).map(|n| frame(n, churn)).collect();
+        let mut renderer = Renderer::offscreen(&config.display, Box::new(io::sink()), W, H);
+        group.bench_with_input(BenchmarkId::new("changed_pct", churn), &frames, |b, frames| {
+            let mut n = 0;
+            b.iter(|| {
+                n = (n + 1) % frames.len();
+                renderer.flush_lines(&frames[n]).unwrap();
+            });
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_flush);
+criterion_main!(benches);
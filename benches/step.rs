@@ -0,0 +1,88 @@
+//! `step()` on large generated levels with many sentinels.
+//!
+//! The player is walled into a corner the sentinels can't reach, so every
+//! tick of the run has them all searching the whole map for a way in.
+
+use criterion::{criterion_group, criterion_main, BatchSize, BenchmarkId, Criterion};
+
+use noderunner::config::GameConfig;
+use noderunner::domain::entity::FrameInput;
+use noderunner::sim::{self, level};
+use noderunner::sim::world::{Phase, WorldState};
+
+const TICKS: usize = 100;
+
+/// A `w`×`h` level of brick floors every six rows, joined by ladders and
+/// ropes, with tokens and `guards` sentinels spread over it.
+fn generate(w: usize, h: usize, guards: usize) -> String {
+    let mut rows: Vec<Vec<char>> = (0..h).map(|_| vec![' '; w]).collect();
+    for (y, row) in rows.iter_mut().enumerate() {
+        for (x, c) in row.iter_mut().enumerate() {
+            *c = match (y % 6, x) {
+                _ if y == h - 1 => '=',
+                (5, x) if x % 17 == 3 => 'H',
+                (5, x) if x % 23 == 11 => ' ',
+                (5, _) => '#',
+                (2, x) if (w / 3..2 * w / 3).contains(&x) => '-',
+                (4, x) if x % 11 == 5 => '$',
+                (_, x) if x % 17 == 3 => 'H',
+                _ => ' ',
+            };
+        }
+    }
+    // Sentinels along the floors, left to right, top to bottom
+    let spots: Vec<(usize, usize)> = (0..h - 1)
+        .filter(|y| y % 6 == 4)
+        .flat_map(|y| (8..w).step_by(9).map(move |x| (x, y)))
+        .filter(|&(x, _)| x % 17 != 3 && x % 11 != 5)
+        .collect();
+    for &(x, y) in spots.iter().cycle().take(guards.min(spots.len())) {
+        rows[y][x] = 'E';
+    }
+    // The player's pocket, bottom left
+    rows[h - 4][..4].fill('=');
+    for row in &mut rows[h - 4..h - 1] {
+        row[4] = '=';
+    }
+    rows[h - 2][1] = 'P';
+    rows[0][w - 2] = '^';
+
+    let mut text = format!("# Bench {w}x{h}, {guards} sentinels\n");
+    for row in rows {
+        text.extend(row);
+        text.push('\n');
+    }
+    text
+}
+
+fn start(text: &str, config: &GameConfig) -> WorldState {
+    let def = level::parse_level_file(text).expect("generated level parses");
+    let mut world = WorldState::new();
+    level::load_level_def(&mut world, &def, config);
+    world.phase = Phase::Playing;
+    world
+}
+
+fn bench_step(c: &mut Criterion) {
+    let config = GameConfig::builtin();
+    let idle = FrameInput { movement: None, dig: None };
+    let mut group = c.benchmark_group(format!("step_x{TICKS}"));
+    for (w, h, guards) in [(28, 16, 4), (100, 50, 4), (100, 50, 12), (100, 50, 24)] {
+        let text = generate(w, h, guards);
+        let id = BenchmarkId::from_parameter(format!("{w}x{h}_{guards}g"));
+        group.bench_with_input(id, &text, |b, text| {
+            b.iter_batched(
+                || start(text, &config),
+                |mut world| {
+                    sim::run_ticks(&mut world, std::iter::repeat(idle), TICKS);
+                    world
+                },
+                BatchSize::LargeInput,
+            );
+        });
+    }
+    group.finish();
+}
+
+criterion_group!(benches, bench_step);
+criterion_main!(benches);
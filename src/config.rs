@@ -2,9 +2,15 @@
 ///
 /// Reads `config.toml` from the executable's directory (or CWD).
 /// Falls back to sensible defaults if the file is missing or incomplete.
+///
+/// Values that can't be used (out of range, unknown names, unknown keys,
+/// a file that doesn't parse) are replaced by defaults and described in
+/// `GameConfig::problems`, which the game shows on its first screen.
 
 use serde::Deserialize;
-use std::path::PathBuf;
+use std::fmt::Display;
+use std::ops::RangeInclusive;
+use std::path::{Path, PathBuf};
 
 // ── Public Config Struct ──
 
@@ -12,7 +18,87 @@ use std::path::PathBuf;
 pub struct GameConfig {
     pub speed: SpeedConfig,
     pub gamepad: GamepadConfig,
+    pub display: DisplayConfig,
+    pub gameplay: GameplayConfig,
+    pub rules: RulesConfig,
+    pub ai: AiConfig,
+    pub online: OnlineConfig,
     pub levels_dir: PathBuf,
+    pub language: String,  // locale code, or "auto" to follow LANG
+    pub log_events: bool,  // write each session's events to a JSONL file
+    pub autosave_interval: u32,  // seconds of play between Continue saves; 0 = only the usual ones
+    /// The config.toml read, if any.
+    pub source: Option<PathBuf>,
+    /// What was wrong in it, and the default used instead.
+    pub problems: Vec<String>,
+}
+
+/// Global leaderboard (needs the `network` feature). Off unless opted in.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "network"), allow(dead_code))]
+pub struct OnlineConfig {
+    pub enabled: bool,
+    pub endpoint: String,  // base URL; scores go to `<endpoint>/scores`
+    pub name: String,      // shown on the global board; blank = "anonymous"
+}
+
+#[derive(Clone, Debug)]
+pub struct GameplayConfig {
+    /// Dig budget for levels without an `@digs` line; `None` = unlimited.
+    pub dig_limit: Option<u32>,
+    /// Light radius for levels without an `@dark` line; `None` = lit.
+    pub darkness: Option<u32>,
+    pub time_attack_seconds: u32,  // starting clock for a time attack run
+    pub time_bonus_token: u32,     // seconds added per token mined
+    pub time_bonus_trap: u32,      // seconds added per guard trapped in a hole
+}
+
+/// Largest light radius darkness allows (`darkness`, `@dark`, lanterns).
+pub const MAX_LIGHT: u32 = 30;
+
+/// Rules the Lode Runner variants differ on.
+#[derive(Clone, Debug)]
+pub struct RulesConfig {
+    pub guard_respawn: GuardRespawn,
+    pub hazards_kill_guards: bool,  // lasers and deadly tiles kill sentinels too
+}
+
+/// How hard sentinels look for the player: less on huge levels saves
+/// CPU, and it makes them duller or sharper on purpose.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct AiConfig {
+    pub max_depth: u32,     // steps out from the player the search goes; 0 = the whole level
+    pub repath_ticks: u32,  // ticks a search is kept though the player moves; 0 = search on every move
+}
+
+/// Where a sentinel killed in a sealed hole comes back.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GuardRespawn {
+    /// The top row, above its spawn column (default)
+    #[default]
+    Top,
+    /// Where the level placed it
+    Spawn,
+    /// The top row, in a column picked at random
+    RandomTop,
+    /// It stays dead
+    Never,
+}
+
+impl GuardRespawn {
+    /// Parse the `guard_respawn` config value. Unknown names fall back to `Top`.
+    fn from_name(name: &str, problems: &mut Vec<String>) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "" | "top" => GuardRespawn::Top,
+            "spawn" => GuardRespawn::Spawn,
+            "random_top" | "random-top" => GuardRespawn::RandomTop,
+            "none" | "never" => GuardRespawn::Never,
+            other => {
+                problems.push(format!("rules.guard_respawn: unknown policy \"{other}\", using \"top\""));
+                GuardRespawn::Top
+            }
+        }
+    }
 }
 
 #[derive(Clone, Debug)]
@@ -28,6 +114,23 @@ pub struct SpeedConfig {
     pub gold_carry_ticks: u32,   // max ticks a guard holds gold before dropping
 }
 
+impl SpeedConfig {
+    /// These speeds on New Game+ loop `n`: each loop, sentinels move, climb
+    /// out of holes and come back about 15% sooner. They never get as fast
+    /// as the player, and a trap always holds them a little while.
+    pub fn for_loop(&self, n: u32) -> SpeedConfig {
+        let scale = |ticks: u32, floor: u32| {
+            ((ticks as f64 * 0.85f64.powi(n as i32)).round() as u32).max(floor).min(ticks)
+        };
+        SpeedConfig {
+            guard_move_rate: scale(self.guard_move_rate, self.player_move_rate + 1),
+            trap_escape_ticks: scale(self.trap_escape_ticks, 20),
+            guard_respawn_ticks: scale(self.guard_respawn_ticks, 10),
+            ..self.clone()
+        }
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct GamepadConfig {
     pub hack_left: Vec<String>,
@@ -37,6 +140,163 @@ pub struct GamepadConfig {
     pub restart: Vec<String>,
 }
 
+#[derive(Clone, Debug)]
+pub struct DisplayConfig {
+    pub reduced_motion: bool,    // no blinking, intro reveal, death flash, or screen shake
+    pub screen_reader: bool,     // plain-text status line below the help bar
+    pub smooth_movement: bool,   // interpolate actors between cells (render only)
+    pub render_mode: RenderMode,
+    pub particles: bool,         // sparkle / debris / dust effects (off under reduced_motion)
+    pub intro_transition: Transition,
+    pub outro_transition: Transition,
+    pub hud_fields: HudFields,
+    pub perf_overlay: bool,      // FPS / step() timing in the HUD corner
+    pub hud_position: BarPosition,
+    pub message_position: BarPosition,
+    pub help_position: BarPosition,
+    pub compact_view: bool,      // start in one-column-per-cell view (N toggles)
+    pub frame_rate: u32,         // render fps, independent of tick_rate_ms; 0 = only on tick / input
+    pub stream_layout: bool,     // framed game area, enlarged HUD, blank side panel
+    pub stream_panel_width: usize,  // columns kept empty right of the frame
+    pub stream_accent: (u8, u8, u8),  // frame border colour
+}
+
+/// Parse a `#rrggbb` colour. Anything else is a problem, and `default`.
+fn parse_hex_color(s: &str, default: (u8, u8, u8), key: &str, problems: &mut Vec<String>) -> (u8, u8, u8) {
+    hex_color(s).unwrap_or_else(|| {
+        if !s.is_empty() {
+            let (r, g, b) = default;
+            problems.push(format!("{key}: \"{s}\" isn't a #rrggbb colour, using #{r:02x}{g:02x}{b:02x}"));
+        }
+        default
+    })
+}
+
+/// `#rrggbb` (the `#` is optional) as RGB.
+pub fn hex_color(s: &str) -> Option<(u8, u8, u8)> {
+    let hex = s.trim().trim_start_matches('#');
+    let channel = |i: usize| hex.get(i..i + 2).and_then(|h| u8::from_str_radix(h, 16).ok());
+    match (hex.len(), channel(0), channel(2), channel(4)) {
+        (6, Some(r), Some(g), Some(b)) => Some((r, g, b)),
+        _ => None,
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarPosition {
+    Top,       // above the map
+    Bottom,    // below the map
+    Hidden,
+}
+
+impl BarPosition {
+    /// Parse a `*_position` config value. Unknown names fall back to `default`.
+    fn from_name(name: &str, default: BarPosition, key: &str, problems: &mut Vec<String>) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "" => default,
+            "top" => BarPosition::Top,
+            "bottom" => BarPosition::Bottom,
+            "hidden" | "none" => BarPosition::Hidden,
+            other => {
+                problems.push(format!("{key}: unknown position \"{other}\", using \"{}\"", default.name()));
+                default
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            BarPosition::Top => "top",
+            BarPosition::Bottom => "bottom",
+            BarPosition::Hidden => "hidden",
+        }
+    }
+}
+
+/// Optional HUD fields after level / score / lives / gold.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct HudFields {
+    pub time: bool,     // level elapsed time (m:ss)
+    pub guards: bool,   // guards currently on the map
+    pub digs: bool,     // holes hacked this level
+}
+
+impl HudFields {
+    /// Parse the `hud_fields` list. Unknown names are problems, and ignored.
+    fn from_names(names: &[String], problems: &mut Vec<String>) -> Self {
+        let mut f = HudFields { time: false, guards: false, digs: false };
+        for name in names {
+            match name.to_ascii_lowercase().as_str() {
+                "time" => f.time = true,
+                "guards" => f.guards = true,
+                "digs" => f.digs = true,
+                other => problems.push(format!("display.hud_fields: unknown field \"{other}\", ignored")),
+            }
+        }
+        f
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Transition {
+    Rows,      // row by row from the bottom
+    Dissolve,  // scattered cells
+    Sweep,     // column by column from the left
+    Instant,   // no map animation
+}
+
+impl Transition {
+    /// Parse a transition config value. Unknown names fall back to `default`.
+    fn from_name(name: &str, default: Transition, key: &str, problems: &mut Vec<String>) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "" => default,
+            "rows" => Transition::Rows,
+            "dissolve" => Transition::Dissolve,
+            "sweep" => Transition::Sweep,
+            "instant" => Transition::Instant,
+            other => {
+                problems.push(format!("{key}: unknown transition \"{other}\", using \"{}\"", default.name()));
+                default
+            }
+        }
+    }
+
+    fn name(self) -> &'static str {
+        match self {
+            Transition::Rows => "rows",
+            Transition::Dissolve => "dissolve",
+            Transition::Sweep => "sweep",
+            Transition::Instant => "instant",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RenderMode {
+    Cells,      // one glyph / emoji per game cell (default)
+    HalfBlock,  // ▀ pseudo-pixels, 2×2 per game cell
+    Graphics,   // kitty / sixel image if the terminal supports it, else Cells
+    Kitty,      // force the kitty graphics protocol
+    Sixel,      // force sixel
+}
+
+impl RenderMode {
+    /// Parse the `render_mode` config value. Unknown names fall back to `Cells`.
+    fn from_name(name: &str, problems: &mut Vec<String>) -> Self {
+        match name.to_ascii_lowercase().as_str() {
+            "" | "cells" => RenderMode::Cells,
+            "halfblock" | "half_block" | "half-block" => RenderMode::HalfBlock,
+            "graphics" => RenderMode::Graphics,
+            "kitty" => RenderMode::Kitty,
+            "sixel" => RenderMode::Sixel,
+            other => {
+                problems.push(format!("display.render_mode: unknown mode \"{other}\", using \"cells\""));
+                RenderMode::Cells
+            }
+        }
+    }
+}
+
 // ── TOML Schema (with serde defaults) ──
 
 #[derive(Deserialize, Debug, Default)]
@@ -47,6 +307,16 @@ struct TomlConfig {
     gamepad: TomlGamepad,
     #[serde(default)]
     general: TomlGeneral,
+    #[serde(default)]
+    display: TomlDisplay,
+    #[serde(default)]
+    gameplay: TomlGameplay,
+    #[serde(default)]
+    rules: TomlRules,
+    #[serde(default)]
+    ai: TomlAi,
+    #[serde(default)]
+    online: TomlOnline,
 }
 
 #[derive(Deserialize, Debug)]
@@ -89,6 +359,90 @@ struct TomlGamepad {
 struct TomlGeneral {
     #[serde(default = "default_levels_dir")]
     levels_dir: String,
+    #[serde(default)]
+    language: String,
+    #[serde(default)]
+    log_events: bool,
+    #[serde(default)]
+    autosave_interval: u32,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct TomlOnline {
+    #[serde(default)]
+    enabled: bool,
+    #[serde(default)]
+    endpoint: String,
+    #[serde(default)]
+    name: String,
+}
+
+#[derive(Deserialize, Debug)]
+struct TomlGameplay {
+    #[serde(default)]
+    dig_limit: u32,
+    #[serde(default)]
+    darkness: u32,
+    #[serde(default = "default_time_attack_seconds")]
+    time_attack_seconds: u32,
+    #[serde(default = "default_time_bonus_token")]
+    time_bonus_token: u32,
+    #[serde(default = "default_time_bonus_trap")]
+    time_bonus_trap: u32,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct TomlRules {
+    #[serde(default)]
+    guard_respawn: String,
+    #[serde(default)]
+    hazards_kill_guards: bool,
+}
+
+#[derive(Deserialize, Debug, Default)]
+struct TomlAi {
+    #[serde(default)]
+    max_depth: u32,
+    #[serde(default)]
+    repath_ticks: u32,
+}
+
+#[derive(Deserialize, Debug)]
+struct TomlDisplay {
+    #[serde(default)]
+    reduced_motion: bool,
+    #[serde(default)]
+    screen_reader: bool,
+    #[serde(default)]
+    smooth_movement: bool,
+    #[serde(default)]
+    render_mode: String,
+    #[serde(default = "default_particles")]
+    particles: bool,
+    #[serde(default)]
+    intro_transition: String,
+    #[serde(default)]
+    outro_transition: String,
+    #[serde(default = "default_hud_fields")]
+    hud_fields: Vec<String>,
+    #[serde(default)]
+    perf_overlay: bool,
+    #[serde(default)]
+    hud_position: String,
+    #[serde(default)]
+    message_position: String,
+    #[serde(default)]
+    help_position: String,
+    #[serde(default)]
+    compact_view: bool,
+    #[serde(default = "default_frame_rate")]
+    frame_rate: u32,
+    #[serde(default)]
+    stream_layout: bool,
+    #[serde(default = "default_stream_panel_width")]
+    stream_panel_width: usize,
+    #[serde(default)]
+    stream_accent: String,
 }
 
 // ── Defaults ──
@@ -109,6 +463,13 @@ fn default_confirm() -> Vec<String> { vec!["Start".into()] }
 fn default_cancel() -> Vec<String> { vec!["Select".into()] }
 fn default_restart() -> Vec<String> { vec!["Start".into()] }
 fn default_levels_dir() -> String { "levels".into() }
+fn default_particles() -> bool { true }
+fn default_frame_rate() -> u32 { 60 }
+fn default_stream_panel_width() -> usize { 40 }
+fn default_time_attack_seconds() -> u32 { 180 }
+fn default_time_bonus_token() -> u32 { 5 }
+fn default_time_bonus_trap() -> u32 { 2 }
+fn default_hud_fields() -> Vec<String> { vec!["time".into(), "guards".into(), "digs".into()] }
 
 impl Default for TomlSpeed {
     fn default() -> Self {
@@ -142,6 +503,45 @@ impl Default for TomlGeneral {
     fn default() -> Self {
         TomlGeneral {
             levels_dir: default_levels_dir(),
+            language: String::new(),
+            log_events: false,
+            autosave_interval: 0,
+        }
+    }
+}
+
+impl Default for TomlGameplay {
+    fn default() -> Self {
+        TomlGameplay {
+            dig_limit: 0,
+            darkness: 0,
+            time_attack_seconds: default_time_attack_seconds(),
+            time_bonus_token: default_time_bonus_token(),
+            time_bonus_trap: default_time_bonus_trap(),
+        }
+    }
+}
+
+impl Default for TomlDisplay {
+    fn default() -> Self {
+        TomlDisplay {
+            reduced_motion: false,
+            screen_reader: false,
+            smooth_movement: false,
+            render_mode: String::new(),
+            particles: default_particles(),
+            intro_transition: String::new(),
+            outro_transition: String::new(),
+            hud_fields: default_hud_fields(),
+            perf_overlay: false,
+            hud_position: String::new(),
+            message_position: String::new(),
+            help_position: String::new(),
+            compact_view: false,
+            frame_rate: default_frame_rate(),
+            stream_layout: false,
+            stream_panel_width: default_stream_panel_width(),
+            stream_accent: String::new(),
         }
     }
 }
@@ -153,10 +553,20 @@ impl GameConfig {
     /// Search order: (1) exe directory, (2) current working directory.
     /// Missing file or missing keys gracefully fall back to defaults.
     pub fn load() -> Self {
+        Self::load_for(None)
+    }
+
+    /// As `load`, but a profile's own config.toml (in `profile_dir`)
+    /// comes before the shared one.
+    pub fn load_for(profile_dir: Option<&Path>) -> Self {
         let search_dirs = candidate_dirs();
 
         // Find config.toml
-        let toml_cfg = load_toml(&search_dirs);
+        let config_dirs: Vec<PathBuf> = profile_dir.map(Path::to_path_buf).into_iter()
+            .chain(search_dirs.iter().cloned())
+            .collect();
+        let mut problems = vec![];
+        let (toml_cfg, source) = load_toml(&config_dirs, &mut problems);
 
         // Resolve levels directory
         let levels_dir_str = &toml_cfg.general.levels_dir;
@@ -172,18 +582,35 @@ impl GameConfig {
                     PathBuf::from(levels_dir_str)
                 })
         };
+        Self::from_toml(toml_cfg, levels_dir, source, problems)
+    }
+
+    /// The built-in defaults, as if there were no config.toml, with levels
+    /// from `levels/`: for tests and benches that mustn't pick up a local
+    /// config.
+    pub fn builtin() -> Self {
+        Self::from_toml(TomlConfig::default(), PathBuf::from("levels"), None, vec![])
+    }
 
+    /// Settings from a parsed config.toml, out-of-range values put back to
+    /// their defaults with a note in `problems`.
+    fn from_toml(toml_cfg: TomlConfig, levels_dir: PathBuf, source: Option<PathBuf>, mut problems: Vec<String>) -> Self {
+        let p = &mut problems;
+        let speed = &toml_cfg.speed;
+        let display = &toml_cfg.display;
+        let gameplay = &toml_cfg.gameplay;
+        let ticks = 1..=10_000;
         GameConfig {
             speed: SpeedConfig {
-                tick_rate_ms: toml_cfg.speed.tick_rate_ms,
-                player_move_rate: toml_cfg.speed.player_move_rate,
-                guard_move_rate: toml_cfg.speed.guard_move_rate,
-                dig_duration: toml_cfg.speed.dig_duration,
-                hole_open_ticks: toml_cfg.speed.hole_open_ticks,
-                hole_close_ticks: toml_cfg.speed.hole_close_ticks,
-                trap_escape_ticks: toml_cfg.speed.trap_escape_ticks,
-                guard_respawn_ticks: toml_cfg.speed.guard_respawn_ticks,
-                gold_carry_ticks: toml_cfg.speed.gold_carry_ticks,
+                tick_rate_ms: in_range("speed.tick_rate_ms", speed.tick_rate_ms, 10..=1000, default_tick_rate(), p),
+                player_move_rate: in_range("speed.player_move_rate", speed.player_move_rate, 1..=20, default_player_move(), p),
+                guard_move_rate: in_range("speed.guard_move_rate", speed.guard_move_rate, 1..=20, default_guard_move(), p),
+                dig_duration: in_range("speed.dig_duration", speed.dig_duration, 1..=100, default_dig_duration(), p),
+                hole_open_ticks: in_range("speed.hole_open_ticks", speed.hole_open_ticks, ticks.clone(), default_hole_open(), p),
+                hole_close_ticks: in_range("speed.hole_close_ticks", speed.hole_close_ticks, ticks.clone(), default_hole_close(), p),
+                trap_escape_ticks: in_range("speed.trap_escape_ticks", speed.trap_escape_ticks, ticks.clone(), default_trap_escape(), p),
+                guard_respawn_ticks: in_range("speed.guard_respawn_ticks", speed.guard_respawn_ticks, ticks.clone(), default_guard_respawn(), p),
+                gold_carry_ticks: in_range("speed.gold_carry_ticks", speed.gold_carry_ticks, ticks, default_gold_carry(), p),
             },
             gamepad: GamepadConfig {
                 hack_left: toml_cfg.gamepad.hack_left,
@@ -192,13 +619,57 @@ impl GameConfig {
                 cancel: toml_cfg.gamepad.cancel,
                 restart: toml_cfg.gamepad.restart,
             },
+            display: DisplayConfig {
+                reduced_motion: display.reduced_motion,
+                screen_reader: display.screen_reader,
+                smooth_movement: display.smooth_movement,
+                render_mode: RenderMode::from_name(&display.render_mode, p),
+                particles: display.particles,
+                intro_transition: Transition::from_name(&display.intro_transition, Transition::Rows, "display.intro_transition", p),
+                outro_transition: Transition::from_name(&display.outro_transition, Transition::Instant, "display.outro_transition", p),
+                hud_fields: HudFields::from_names(&display.hud_fields, p),
+                perf_overlay: display.perf_overlay,
+                hud_position: BarPosition::from_name(&display.hud_position, BarPosition::Top, "display.hud_position", p),
+                message_position: BarPosition::from_name(&display.message_position, BarPosition::Bottom, "display.message_position", p),
+                help_position: BarPosition::from_name(&display.help_position, BarPosition::Bottom, "display.help_position", p),
+                compact_view: display.compact_view,
+                frame_rate: in_range("display.frame_rate", display.frame_rate, 0..=240, default_frame_rate(), p),
+                stream_layout: display.stream_layout,
+                stream_panel_width: in_range("display.stream_panel_width", display.stream_panel_width, 0..=200, default_stream_panel_width(), p),
+                stream_accent: parse_hex_color(&display.stream_accent, (145, 70, 255), "display.stream_accent", p),
+            },
+            gameplay: GameplayConfig {
+                dig_limit: (gameplay.dig_limit > 0).then_some(gameplay.dig_limit),
+                darkness: (gameplay.darkness > 0).then_some(gameplay.darkness.min(MAX_LIGHT)),
+                time_attack_seconds: in_range("gameplay.time_attack_seconds", gameplay.time_attack_seconds, 10..=3600, default_time_attack_seconds(), p),
+                time_bonus_token: in_range("gameplay.time_bonus_token", gameplay.time_bonus_token, 0..=600, default_time_bonus_token(), p),
+                time_bonus_trap: in_range("gameplay.time_bonus_trap", gameplay.time_bonus_trap, 0..=600, default_time_bonus_trap(), p),
+            },
+            rules: RulesConfig {
+                guard_respawn: GuardRespawn::from_name(&toml_cfg.rules.guard_respawn, p),
+                hazards_kill_guards: toml_cfg.rules.hazards_kill_guards,
+            },
+            ai: AiConfig {
+                max_depth: in_range("ai.max_depth", toml_cfg.ai.max_depth, 0..=10_000, 0, p),
+                repath_ticks: in_range("ai.repath_ticks", toml_cfg.ai.repath_ticks, 0..=600, 0, p),
+            },
+            online: OnlineConfig {
+                enabled: toml_cfg.online.enabled,
+                endpoint: toml_cfg.online.endpoint.trim_end_matches('/').to_string(),
+                name: toml_cfg.online.name,
+            },
             levels_dir,
+            language: toml_cfg.general.language,
+            log_events: toml_cfg.general.log_events,
+            autosave_interval: in_range("general.autosave_interval", toml_cfg.general.autosave_interval, 0..=3600, 0, p),
+            source,
+            problems: std::mem::take(p),
         }
     }
 }
 
 /// Candidate directories to search: exe dir + CWD + system paths (deduplicated).
-fn candidate_dirs() -> Vec<PathBuf> {
+pub fn candidate_dirs() -> Vec<PathBuf> {
     let mut dirs = vec![];
 
     // 1. Directory of the running executable
@@ -240,25 +711,50 @@ fn candidate_dirs() -> Vec<PathBuf> {
     dirs
 }
 
-/// Search for config.toml in candidate directories.
-fn load_toml(search_dirs: &[PathBuf]) -> TomlConfig {
+/// Search for config.toml in candidate directories: the settings and the
+/// file they came from. What's wrong with it goes in `problems`.
+fn load_toml(search_dirs: &[PathBuf], problems: &mut Vec<String>) -> (TomlConfig, Option<PathBuf>) {
     for dir in search_dirs {
         let path = dir.join("config.toml");
-        if path.exists() {
-            match std::fs::read_to_string(&path) {
-                Ok(text) => match toml::from_str::<TomlConfig>(&text) {
-                    Ok(cfg) => return cfg,
-                    Err(e) => {
-                        eprintln!("Warning: config.toml parse error: {e}");
-                        eprintln!("Using default settings.");
-                        return TomlConfig::default();
-                    }
-                },
-                Err(e) => {
-                    eprintln!("Warning: could not read {}: {e}", path.display());
-                }
-            }
+        if !path.exists() {
+            continue;
         }
+        let text = match std::fs::read_to_string(&path) {
+            Ok(text) => text,
+            Err(e) => {
+                problems.push(format!("could not read {}: {e}", path.display()));
+                continue;
+            }
+        };
+        let mut unknown = vec![];
+        let parsed = serde_ignored::deserialize(toml::Deserializer::new(&text), |key| {
+            unknown.push(key.to_string());
+        });
+        return match parsed {
+            Ok(cfg) => {
+                problems.extend(unknown.into_iter().map(|key| format!("{key}: unknown setting, ignored")));
+                (cfg, Some(path))
+            }
+            Err(e) => {
+                let line = e.span().map_or(0, |span| text[..span.start].lines().count().max(1));
+                problems.push(format!("line {line}: {}; using default settings", e.message().trim_end()));
+                (TomlConfig::default(), Some(path))
+            }
+        };
+    }
+    (TomlConfig::default(), None)
+}
+
+/// `value` if it's in `range`; otherwise a problem, and `default`.
+fn in_range<T>(key: &str, value: T, range: RangeInclusive<T>, default: T, problems: &mut Vec<String>) -> T
+where
+    T: PartialOrd + Display + Copy,
+{
+    if range.contains(&value) {
+        return value;
     }
-    TomlConfig::default()
+    problems.push(format!(
+        "{key} = {value} is outside {}..{}, using {default}", range.start(), range.end(),
+    ));
+    default
 }
@@ -1,7 +1,9 @@
 /// Guard AI — BFS pathfinding using terrain + occupancy.
 ///
 /// Two modes:
-///   1. **Chase** — normal BFS toward player (default).
+///   1. **Chase** — step down a `FlowField`: one BFS backwards from the
+///      player, shared by every guard (default). A guard with no way to
+///      the player patrols its platform instead, edge to edge.
 ///   2. **Separation** — move away from nearest guard to avoid clustering.
 ///      Activated when `guard.separation_timer > 0`.
 ///
@@ -10,23 +12,25 @@
 
 use std::collections::VecDeque;
 
-use super::entity::{ActorState, Guard};
+use super::entity::{ActorState, Facing, Guard};
 use super::physics;
+use super::grid::{BitGrid, Grid};
+use super::occupancy::Occupancy;
 use super::tile::Tile;
 
-const BFS_MAX_DEPTH: usize = 300;
 const DIRS: [(i32, i32); 4] = [(-1, 0), (1, 0), (0, -1), (0, 1)];
 
 /// How many ticks guards spend in separation mode after contact.
 pub const SEPARATION_TICKS: u32 = 10;
 
 /// Context for physics queries (hole_grid for O(1) lookup).
-struct Ctx<'a> {
-    tiles: &'a [Vec<Tile>],
-    width: usize,
-    height: usize,
-    hole_grid: &'a [Vec<bool>],
-    guards: &'a [Guard],
+pub struct Ctx<'a> {
+    pub tiles: &'a Grid<Tile>,
+    pub width: usize,
+    pub height: usize,
+    pub hole_grid: &'a BitGrid,
+    pub guards: &'a [Guard],
+    pub occupancy: &'a Occupancy,
 }
 
 impl<'a> Ctx<'a> {
@@ -35,7 +39,7 @@ impl<'a> Ctx<'a> {
     }
 
     fn support(&self, x: usize, y: usize) -> bool {
-        physics::has_support(self.tiles, self.width, self.height, self.hole_grid, self.guards, x, y)
+        physics::has_support(self.tiles, self.width, self.height, self.hole_grid, self.occupancy, x, y)
     }
 
     fn can_enter(&self, x: usize, y: usize) -> bool {
@@ -45,61 +49,119 @@ impl<'a> Ctx<'a> {
 
 // ── Chase mode (normal) ──
 
-pub fn find_direction(
-    tiles: &[Vec<Tile>],
-    width: usize,
-    height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
-    gx: usize, gy: usize,
-    gstate: ActorState,
-    px: usize, py: usize,
-) -> (i32, i32) {
-    if gstate == ActorState::InHole || gstate == ActorState::Dead { return (0, 0); }
-    if gx == px && gy == py { return (0, 0); }
+/// Ticks a flow field is kept while the player and the holes stay put,
+/// so tile changes (the exit ladders, scripts) reach the guards.
+pub const FLOW_REFRESH_TICKS: u64 = 30;
 
-    let ctx = Ctx { tiles, width, height, hole_grid, guards };
-    let mut visited = vec![vec![false; width]; height];
-    visited[gy][gx] = true;
+const UNREACHED: u32 = u32::MAX;
 
-    let mut queue: VecDeque<(usize, usize, i32, i32)> = VecDeque::with_capacity(256);
+/// Steps to the player from every cell, by one search backwards from the
+/// player's cell that all guards read their moves from.
+#[derive(Clone, Debug, Default)]
+pub struct FlowField {
+    width: usize,
+    dist: Vec<u32>,  // row-major; UNREACHED = no way to the player
+    pub target: (usize, usize),
+    /// Tick of the search; None = never searched.
+    pub built: Option<u64>,
+    /// Open holes and trapped guards it was searched with.
+    pub blockers: Vec<(usize, usize)>,
+}
 
-    for &(dx, dy) in &DIRS {
-        if let Some((nx, ny)) = try_move(&ctx, gx, gy, dx, dy) {
-            if nx == px && ny == py { return (dx, dy); }
-            if !visited[ny][nx] {
-                visited[ny][nx] = true;
-                queue.push_back((nx, ny, dx, dy));
+impl FlowField {
+    /// Search every cell's way to `to`, backwards along the moves a
+    /// guard can make, out to `max_depth` steps (0 = no limit). Cells
+    /// further away have no way.
+    pub fn build(ctx: &Ctx, to: (usize, usize), tick: u64, blockers: Vec<(usize, usize)>, max_depth: u32) -> FlowField {
+        let mut dist = vec![UNREACHED; ctx.width * ctx.height];
+        let mut queue: VecDeque<(usize, usize)> = VecDeque::with_capacity(256);
+        if to.0 < ctx.width && to.1 < ctx.height {
+            dist[to.1 * ctx.width + to.0] = 0;
+            queue.push_back(to);
+        }
+        while let Some((cx, cy)) = queue.pop_front() {
+            let d = dist[cy * ctx.width + cx];
+            if max_depth > 0 && d >= max_depth { continue; }
+            for &(dx, dy) in &DIRS {
+                let (px, py) = (cx as i32 - dx, cy as i32 - dy);
+                if px < 0 || py < 0 || px as usize >= ctx.width || py as usize >= ctx.height { continue; }
+                let (px, py) = (px as usize, py as usize);
+                if dist[py * ctx.width + px] != UNREACHED { continue; }
+                if step_from(ctx, (px, py), false, dx, dy) != Some((cx, cy)) { continue; }
+                dist[py * ctx.width + px] = d + 1;
+                queue.push_back((px, py));
             }
         }
+        FlowField { width: ctx.width, dist, target: to, built: Some(tick), blockers }
     }
 
-    let mut steps = 0;
-    while let Some((cx, cy, fdx, fdy)) = queue.pop_front() {
-        steps += 1;
-        if steps > BFS_MAX_DEPTH { break; }
+    /// Steps from (x, y) to the player, if there's a way.
+    pub fn dist(&self, x: usize, y: usize) -> Option<u32> {
+        if x >= self.width { return None; }
+        self.dist.get(y * self.width + x).copied().filter(|&d| d != UNREACHED)
+    }
 
-        if !ctx.support(cx, cy) {
-            if cy + 1 < height && ctx.can_enter(cx, cy + 1) && !visited[cy + 1][cx] {
-                if cx == px && cy + 1 == py { return (fdx, fdy); }
-                visited[cy + 1][cx] = true;
-                queue.push_back((cx, cy + 1, fdx, fdy));
-            }
-            continue;
+    /// Whether it was searched on a map of this size.
+    pub fn fits(&self, width: usize, height: usize) -> bool {
+        self.width == width && self.dist.len() == width * height
+    }
+
+    /// Chase mode: the move from `from` that gets closest to the player,
+    /// or a patrol step (going the way it `facing`) when there's no way.
+    pub fn direction(&self, ctx: &Ctx, from: (usize, usize), gstate: ActorState, facing: Facing) -> (i32, i32) {
+        if gstate == ActorState::InHole || gstate == ActorState::Dead { return (0, 0); }
+        if from == self.target { return (0, 0); }
+        match self.next(ctx, from, true) {
+            Some(((dx, dy), _)) => (dx, dy),
+            None => patrol(ctx, from, facing),
         }
+    }
 
-        for &(dx, dy) in &DIRS {
-            if let Some((nx, ny)) = try_move(&ctx, cx, cy, dx, dy) {
-                if !visited[ny][nx] {
-                    if nx == px && ny == py { return (fdx, fdy); }
-                    visited[ny][nx] = true;
-                    queue.push_back((nx, ny, fdx, fdy));
-                }
-            }
+    /// The cells a guard at `from` passes through to the player (excluding
+    /// `from`, ending at the player); empty if there's no way.
+    pub fn path(&self, ctx: &Ctx, from: (usize, usize)) -> Vec<(usize, usize)> {
+        let mut path = vec![];
+        let mut at = from;
+        while at != self.target {
+            let Some((_, next)) = self.next(ctx, at, at == from) else { return vec![] };
+            path.push(next);
+            at = next;
         }
+        path
     }
 
-    fallback_chase(&ctx, gx, gy, px, py)
+    /// The closest-to-the-player move out of `at`; ties go in `DIRS` order.
+    fn next(&self, ctx: &Ctx, at: (usize, usize), start: bool) -> Option<((i32, i32), (usize, usize))> {
+        let here = self.dist(at.0, at.1).unwrap_or(UNREACHED);
+        DIRS.iter()
+            .filter_map(|&(dx, dy)| step_from(ctx, at, start, dx, dy).map(|to| ((dx, dy), to)))
+            .filter_map(|(dir, to)| self.dist(to.0, to.1).map(|d| (d, dir, to)))
+            .filter(|&(d, ..)| start || d < here)
+            .min_by_key(|&(d, ..)| d)
+            .map(|(_, dir, to)| (dir, to))
+    }
+}
+
+/// A move a guard can make from `at`. Unsupported cells only fall, except
+/// where the guard is now (`start`), which moves freely.
+fn step_from(ctx: &Ctx, at: (usize, usize), start: bool, dx: i32, dy: i32) -> Option<(usize, usize)> {
+    let (x, y) = at;
+    if !start && !ctx.support(x, y) {
+        let falls = (dx, dy) == (0, 1) && y + 1 < ctx.height && ctx.can_enter(x, y + 1);
+        return falls.then_some((x, y + 1));
+    }
+    try_move(ctx, x, y, dx, dy)
+}
+
+// ── Patrol (no way to the player) ──
+
+/// Walk the platform: on the way the guard faces, turning back at a wall
+/// or a drop. Still if it can go neither way.
+fn patrol(ctx: &Ctx, (x, y): (usize, usize), facing: Facing) -> (i32, i32) {
+    let ahead = match facing { Facing::Left => -1, Facing::Right => 1 };
+    [ahead, -ahead].into_iter()
+        .find(|&dx| try_move(ctx, x, y, dx, 0).is_some_and(|(nx, ny)| ctx.support(nx, ny)))
+        .map_or((0, 0), |dx| (dx, 0))
 }
 
 // ── Separation mode ──
@@ -109,25 +171,22 @@ pub fn find_direction(
 /// one that maximizes distance from the nearest active guard.
 /// Falls back to the normal chase direction if no separation move helps.
 pub fn find_separation_direction(
-    tiles: &[Vec<Tile>],
-    width: usize,
-    height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
+    ctx: &Ctx,
+    flow: &FlowField,
     guard_idx: usize,
-    gx: usize, gy: usize,
+    from: (usize, usize),
     gstate: ActorState,
-    px: usize, py: usize,
+    facing: Facing,
 ) -> (i32, i32) {
     if gstate == ActorState::InHole || gstate == ActorState::Dead { return (0, 0); }
-
-    let ctx = Ctx { tiles, width, height, hole_grid, guards };
+    let (gx, gy) = from;
+    let (px, py) = flow.target;
 
     // Find nearest active guard (not self)
     let mut nearest_dist = i32::MAX;
     let mut nearest_x = gx;
     let mut nearest_y = gy;
-    for (j, other) in guards.iter().enumerate() {
+    for (j, other) in ctx.guards.iter().enumerate() {
         if j == guard_idx { continue; }
         if other.state == ActorState::Dead || other.state == ActorState::InHole { continue; }
         let dist = (other.x as i32 - gx as i32).abs() + (other.y as i32 - gy as i32).abs();
@@ -140,7 +199,7 @@ pub fn find_separation_direction(
 
     // If no nearby guard found, chase normally
     if nearest_dist > 3 {
-        return find_direction(tiles, width, height, hole_grid, guards, gx, gy, gstate, px, py);
+        return flow.direction(ctx, from, gstate, facing);
     }
 
     // Try each direction: pick the one that maximizes distance from nearest guard
@@ -150,7 +209,7 @@ pub fn find_separation_direction(
     let mut best_score: i32 = i32::MIN;
 
     for &(dx, dy) in &DIRS {
-        if let Some((nx, ny)) = try_move(&ctx, gx, gy, dx, dy) {
+        if let Some((nx, ny)) = try_move(ctx, gx, gy, dx, dy) {
             let guard_dist = manhattan(nx, ny, nearest_x, nearest_y);
             let player_dist = manhattan(nx, ny, px, py);
             let current_player_dist = manhattan(gx, gy, px, py);
@@ -167,7 +226,7 @@ pub fn find_separation_direction(
     }
 
     if best_dir == (0, 0) {
-        return find_direction(tiles, width, height, hole_grid, guards, gx, gy, gstate, px, py);
+        return flow.direction(ctx, from, gstate, facing);
     }
 
     best_dir
@@ -209,24 +268,3 @@ fn try_move(ctx: &Ctx, x: usize, y: usize, dx: i32, dy: i32) -> Option<(usize, u
 
     Some((nx, ny))
 }
-
-fn fallback_chase(ctx: &Ctx, gx: usize, gy: usize, px: usize, py: usize) -> (i32, i32) {
-    let dx = if px > gx { 1 } else if px < gx { -1 } else { 0 };
-    if dx != 0 {
-        let nx = (gx as i32 + dx) as usize;
-        if nx < ctx.width && ctx.can_enter(nx, gy) {
-            return (dx, 0);
-        }
-    }
-    let here = ctx.terrain(gx, gy);
-    if here.climbable {
-        let dy = if py > gy { 1 } else if py < gy { -1 } else { 0 };
-        if dy != 0 {
-            let ny = (gy as i32 + dy) as usize;
-            if ny < ctx.height && ctx.can_enter(gx, ny) {
-                return (0, dy);
-            }
-        }
-    }
-    (0, 0)
-}
@@ -30,7 +30,7 @@ pub enum MoveDir {
 
 /// Frame input: separates movement from dig so both can fire in one tick.
 /// Movement = continuous (held key), Dig = edge-triggered (fresh press).
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub struct FrameInput {
     pub movement: Option<MoveDir>,
     pub dig: Option<Facing>,
@@ -66,6 +66,7 @@ pub struct Guard {
     pub facing: Facing,
     pub state: ActorState,
     pub carry_gold: bool,
+    pub carry_tier: u8,         // the kind of token carried (`Tile::Gold`'s)
     pub carry_gold_timer: u32,  // ticks since picking up gold; 0 if not carrying
     pub stuck_timer: u32,      // remaining ticks trapped in hole
     pub move_cooldown: u32,    // ticks until next move
@@ -73,6 +74,7 @@ pub struct Guard {
     pub spawn_y: usize,
     pub respawn_timer: u32,    // ticks until respawn after death
     pub separation_timer: u32, // >0: avoidance mode, move away from nearest guard
+    pub stepped_at: u64,       // tick of the last move to another cell
 }
 
 impl Guard {
@@ -82,6 +84,7 @@ impl Guard {
             facing: Facing::Left,
             state: ActorState::OnGround,
             carry_gold: false,
+            carry_tier: 0,
             carry_gold_timer: 0,
             stuck_timer: 0,
             move_cooldown: 0,
@@ -89,8 +92,47 @@ impl Guard {
             spawn_y: y,
             respawn_timer: 0,
             separation_timer: 0,
+            stepped_at: 0,
         }
     }
+
+    /// Trap progress: 0.0 (just fell in) → 1.0 (about to climb out).
+    /// Only meaningful while `state == InHole`.
+    pub fn escape_progress(&self, escape_ticks: u32) -> f32 {
+        if escape_ticks == 0 { return 1.0; }
+        1.0 - (self.stuck_timer.min(escape_ticks) as f32 / escape_ticks as f32)
+    }
+}
+
+/// Sentinel spawner: sends out a new sentinel every `every` ticks, up to
+/// `cap` in all per attempt. A due one waits while its cell is taken.
+#[derive(Clone, Debug)]
+pub struct Spawner {
+    pub x: usize,
+    pub y: usize,
+    pub every: u32,
+    pub cap: u32,
+    pub timer: u32,    // ticks since the last one (or the start)
+    pub spawned: u32,
+}
+
+impl Spawner {
+    pub fn new(x: usize, y: usize, every: u32, cap: u32) -> Self {
+        Spawner { x, y, every, cap, timer: 0, spawned: 0 }
+    }
+
+    /// Advance one tick. True while a sentinel is due.
+    pub fn tick(&mut self) -> bool {
+        if self.spawned >= self.cap { return false; }
+        self.timer = (self.timer + 1).min(self.every);
+        self.timer >= self.every
+    }
+
+    /// Back to the start of an attempt.
+    pub fn reset(&mut self) {
+        self.timer = 0;
+        self.spawned = 0;
+    }
 }
 
 /// Hole entity: tracks a dug brick through its lifecycle.
@@ -125,6 +167,12 @@ impl Hole {
         self.open_remaining == 0 && self.close_remaining > 0
     }
 
+    /// Open-phase progress: 0.0 (just dug) → 1.0 (about to start closing).
+    pub fn open_progress(&self, total_open: u32) -> f32 {
+        if total_open == 0 { return 1.0; }
+        1.0 - (self.open_remaining.min(total_open) as f32 / total_open as f32)
+    }
+
     /// Closing progress: 0.0 (just started closing) → 1.0 (about to seal).
     /// Only meaningful when `is_closing()` is true.
     pub fn close_progress(&self, total_close: u32) -> f32 {
@@ -233,6 +281,28 @@ mod tests {
         assert!((h.close_progress(10) - 0.9).abs() < 0.01);
     }
 
+    #[test]
+    fn hole_open_progress() {
+        let mut h = Hole::new(0, 0, 8, 2);
+        assert!((h.open_progress(8) - 0.0).abs() < 0.01);
+        for _ in 0..6 { h.tick(); }
+        assert!((h.open_progress(8) - 0.75).abs() < 0.01);
+        for _ in 0..2 { h.tick(); }
+        // Closing phase: open part is over
+        assert!((h.open_progress(8) - 1.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn guard_escape_progress() {
+        let mut g = Guard::new(0, 0, 0);
+        g.stuck_timer = 40;
+        assert!((g.escape_progress(40) - 0.0).abs() < 0.01);
+        g.stuck_timer = 10;
+        assert!((g.escape_progress(40) - 0.75).abs() < 0.01);
+        // No escape time configured: always about to escape
+        assert!((g.escape_progress(0) - 1.0).abs() < 0.01);
+    }
+
     #[test]
     fn hole_zero_close_progress() {
         let h = Hole::new(0, 0, 5, 0); // 5 open, 0 close
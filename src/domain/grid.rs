@@ -0,0 +1,142 @@
+//! Map-sized grids stored flat, row-major, indexed by `(x, y)`.
+//!
+//! `Grid<Tile>` holds a map's tiles and `BitGrid` its open holes: one
+//! allocation each instead of one per row, so a lookup is a single
+//! multiply-add and neighbouring cells share cache lines.
+
+use std::ops::{Index, IndexMut};
+
+/// A `width` × `height` grid of `T`.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T: Copy> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Grid { width, height, cells: vec![fill; width * height] }
+    }
+
+    /// From rows of equal length (the first row's sets the width).
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Self {
+        let width = rows.first().map_or(0, Vec::len);
+        let height = rows.len();
+        Grid { width, height, cells: rows.into_iter().flatten().collect() }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// The cell at (x, y), or `None` off the grid.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> Option<T> {
+        (x < self.width && y < self.height).then(|| self.cells[y * self.width + x])
+    }
+
+    /// Every cell, row by row.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    pub fn rows(&self) -> std::slice::ChunksExact<'_, T> {
+        self.cells.chunks_exact(self.width.max(1))
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    #[inline]
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        debug_assert!(x < self.width, "x {x} off a grid {} wide", self.width);
+        &self.cells[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    #[inline]
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        debug_assert!(x < self.width, "x {x} off a grid {} wide", self.width);
+        &mut self.cells[y * self.width + x]
+    }
+}
+
+/// A `width` × `height` grid of flags, one bit per cell.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct BitGrid {
+    width: usize,
+    height: usize,
+    bits: Vec<u64>,
+}
+
+impl BitGrid {
+    pub fn new(width: usize, height: usize) -> Self {
+        BitGrid { width, height, bits: vec![0; (width * height).div_ceil(64)] }
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether (x, y) is set; off the grid is never set.
+    #[inline]
+    pub fn get(&self, x: usize, y: usize) -> bool {
+        if x >= self.width || y >= self.height { return false; }
+        let i = y * self.width + x;
+        self.bits[i / 64] & (1 << (i % 64)) != 0
+    }
+
+    /// Set or clear (x, y); off the grid is ignored.
+    #[inline]
+    pub fn set(&mut self, x: usize, y: usize, on: bool) {
+        if x >= self.width || y >= self.height { return; }
+        let i = y * self.width + x;
+        if on {
+            self.bits[i / 64] |= 1 << (i % 64);
+        } else {
+            self.bits[i / 64] &= !(1 << (i % 64));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn grid_indexes_by_x_then_y() {
+        let mut g = Grid::from_rows(vec![vec![0, 1, 2], vec![3, 4, 5]]);
+        assert_eq!((g.width(), g.height()), (3, 2));
+        assert_eq!(g[(2, 0)], 2);
+        assert_eq!(g[(0, 1)], 3);
+        g[(1, 1)] = 9;
+        assert_eq!(g.get(1, 1), Some(9));
+        assert_eq!(g.get(3, 0), None);
+        assert_eq!(g.rows().nth(1), Some(&[3, 9, 5][..]));
+    }
+
+    #[test]
+    fn bit_grid_spans_words() {
+        let mut b = BitGrid::new(100, 3);
+        b.set(99, 0, true);
+        b.set(0, 1, true);
+        b.set(50, 2, true);
+        assert!(b.get(99, 0) && b.get(0, 1) && b.get(50, 2));
+        assert!(!b.get(98, 0) && !b.get(1, 1));
+        b.set(0, 1, false);
+        assert!(!b.get(0, 1));
+        // Off the grid
+        assert!(!b.get(100, 0) && !b.get(0, 3));
+    }
+}
@@ -3,3 +3,5 @@ pub mod entity;
 pub mod rules;
 pub mod ai;
 pub mod physics;
+pub mod grid;
+pub mod occupancy;
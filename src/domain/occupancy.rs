@@ -0,0 +1,135 @@
+//! Which guards are in which cell, for the occupancy queries in
+//! `physics` and the renderer's per-cell lookups.
+//!
+//! Each cell heads a short list of the guards in it (usually none or one),
+//! linked through per-guard entries that also copy the guard's state, so
+//! a query reads one cell instead of scanning every guard. Dead guards
+//! aren't listed. The sim re-indexes a guard with `update` whenever it
+//! changes its cell or state, and `rebuild`s after adding or removing
+//! guards.
+
+use super::entity::{ActorState, Guard};
+use super::grid::Grid;
+
+const NONE: u32 = u32::MAX;
+
+#[derive(Clone, Copy, Debug)]
+struct Entry {
+    cell: Option<(usize, usize)>,  // None = not listed (dead)
+    state: ActorState,
+    next: u32,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct Occupancy {
+    head: Grid<u32>,
+    entries: Vec<Entry>,
+}
+
+impl Occupancy {
+    pub fn new(guards: &[Guard], width: usize, height: usize) -> Self {
+        let mut occ = Occupancy::default();
+        occ.rebuild(guards, width, height);
+        occ
+    }
+
+    /// Index `guards` afresh, on a map of this size.
+    pub fn rebuild(&mut self, guards: &[Guard], width: usize, height: usize) {
+        if self.head.width() != width || self.head.height() != height {
+            self.head = Grid::new(width, height, NONE);
+        } else {
+            for e in &self.entries {
+                if let Some((x, y)) = e.cell { self.head[(x, y)] = NONE; }
+            }
+        }
+        self.entries.clear();
+        for (i, g) in guards.iter().enumerate() {
+            self.update(i, g);
+        }
+    }
+
+    /// Re-index guard `i`, now at `g`'s cell and state.
+    pub fn update(&mut self, i: usize, g: &Guard) {
+        if i >= self.entries.len() {
+            self.entries.resize(i + 1, Entry { cell: None, state: ActorState::Dead, next: NONE });
+        }
+        self.unlink(i);
+        self.entries[i].state = g.state;
+        if g.state == ActorState::Dead || self.head.get(g.x, g.y).is_none() { return; }
+        self.entries[i].cell = Some((g.x, g.y));
+        self.entries[i].next = self.head[(g.x, g.y)];
+        self.head[(g.x, g.y)] = i as u32;
+    }
+
+    fn unlink(&mut self, i: usize) {
+        let Some((x, y)) = self.entries[i].cell.take() else { return };
+        let next = self.entries[i].next;
+        if self.head[(x, y)] == i as u32 {
+            self.head[(x, y)] = next;
+            return;
+        }
+        let mut at = self.head[(x, y)];
+        while at != NONE {
+            let e = &mut self.entries[at as usize];
+            if e.next == i as u32 {
+                e.next = next;
+                return;
+            }
+            at = e.next;
+        }
+    }
+
+    /// The guards at (x, y) and their states, latest listed first.
+    pub fn at(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, ActorState)> + '_ {
+        let mut at = self.head.get(x, y).unwrap_or(NONE);
+        std::iter::from_fn(move || {
+            if at == NONE { return None; }
+            let i = at as usize;
+            at = self.entries[i].next;
+            Some((i, self.entries[i].state))
+        })
+    }
+
+    /// The first guard at (x, y) in `guards` order, as drawn.
+    pub fn first(&self, x: usize, y: usize) -> Option<usize> {
+        self.at(x, y).map(|(i, _)| i).min()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn guard_at(id: usize, x: usize, y: usize, state: ActorState) -> Guard {
+        let mut g = Guard::new(id, x, y);
+        g.state = state;
+        g
+    }
+
+    #[test]
+    fn follows_moves_and_shared_cells() {
+        let mut guards = vec![
+            guard_at(0, 1, 1, ActorState::OnGround),
+            guard_at(1, 1, 1, ActorState::Falling),
+            guard_at(2, 2, 0, ActorState::Dead),
+        ];
+        let mut occ = Occupancy::new(&guards, 4, 3);
+        assert_eq!(occ.first(1, 1), Some(0));
+        assert_eq!(occ.at(1, 1).count(), 2);
+        assert_eq!(occ.at(2, 0).count(), 0);
+
+        guards[0].x = 3;
+        guards[0].state = ActorState::InHole;
+        occ.update(0, &guards[0]);
+        assert_eq!(occ.at(1, 1).collect::<Vec<_>>(), vec![(1, ActorState::Falling)]);
+        assert_eq!(occ.at(3, 1).collect::<Vec<_>>(), vec![(0, ActorState::InHole)]);
+
+        guards[2].state = ActorState::OnGround;
+        occ.update(2, &guards[2]);
+        assert_eq!(occ.first(2, 0), Some(2));
+
+        occ.rebuild(&guards[..1], 4, 3);
+        assert_eq!(occ.at(1, 1).count(), 0);
+        assert_eq!(occ.first(3, 1), Some(0));
+    }
+}
@@ -11,9 +11,14 @@
 ///
 /// ## Hole Grid (O(1) lookup)
 ///
-/// Holes are tracked in a boolean grid (`hole_grid[y][x]`) rather than
+/// Holes are tracked in a bit grid (`hole_grid.get(x, y)`) rather than
 /// a list of positions. This gives O(1) terrain_at queries instead of O(n).
 ///
+/// ## Occupancy index
+///
+/// Likewise guards are found through an `Occupancy` index of who is in
+/// each cell, kept up to date by the sim as they move.
+///
 /// ## Support Specification
 ///
 /// An actor has SUPPORT (will not fall) if ANY of:
@@ -29,8 +34,10 @@
 ///   - None of the above support conditions are met
 ///   - Actor is not Dead or InHole
 
+use super::grid::{BitGrid, Grid};
 use super::tile::Tile;
-use super::entity::{ActorState, Guard};
+use super::entity::ActorState;
+use super::occupancy::Occupancy;
 
 // ══════════════════════════════════════════════════════════════
 // Layer 1: Terrain (tile + hole — NO entities)
@@ -52,14 +59,14 @@ pub struct TerrainCell {
 /// Query terrain at (x, y). Considers tiles and hole_grid only.
 /// Holes override the tile (a dug brick becomes passable empty space).
 ///
-/// `hole_grid` is a 2D boolean grid: `true` = active hole at that cell.
+/// `hole_grid` has a bit set at every active hole.
 /// O(1) lookup instead of linear scan.
 #[inline]
 pub fn terrain_at(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
+    hole_grid: &BitGrid,
     x: usize,
     y: usize,
 ) -> TerrainCell {
@@ -68,11 +75,11 @@ pub fn terrain_at(
     }
 
     // O(1) hole check
-    if y < hole_grid.len() && x < hole_grid[y].len() && hole_grid[y][x] {
+    if hole_grid.get(x, y) {
         return TerrainCell { passable: true, climbable: false, hangable: false, hole: true };
     }
 
-    let tile = tiles[y][x];
+    let tile = tiles[(x, y)];
     TerrainCell {
         passable: tile.is_passable(),
         climbable: tile.is_climbable(),
@@ -89,10 +96,10 @@ pub fn terrain_at(
 ///   - Bottom of map
 #[inline]
 pub fn terrain_support(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
+    hole_grid: &BitGrid,
     x: usize,
     y: usize,
 ) -> bool {
@@ -112,43 +119,31 @@ pub fn terrain_support(
 // ══════════════════════════════════════════════════════════════
 
 /// Is there a trapped (InHole) guard at (x, y)?
-pub fn has_trapped_guard(guards: &[Guard], x: usize, y: usize) -> bool {
-    guards.iter().any(|g| g.x == x && g.y == y && g.state == ActorState::InHole)
+pub fn has_trapped_guard(occ: &Occupancy, x: usize, y: usize) -> bool {
+    occ.at(x, y).any(|(_, state)| state == ActorState::InHole)
 }
 
 /// Is there a trapped guard at (x, y), excluding guard at index `skip`?
-pub fn has_trapped_guard_except(guards: &[Guard], x: usize, y: usize, skip: usize) -> bool {
-    guards.iter().enumerate().any(|(j, g)| {
-        j != skip && g.x == x && g.y == y && g.state == ActorState::InHole
-    })
+pub fn has_trapped_guard_except(occ: &Occupancy, x: usize, y: usize, skip: usize) -> bool {
+    occ.at(x, y).any(|(j, state)| j != skip && state == ActorState::InHole)
 }
 
 /// Is there an active (non-Dead, non-InHole) guard at (x, y)?
 #[allow(dead_code)]
-pub fn has_active_guard(guards: &[Guard], x: usize, y: usize) -> bool {
-    guards.iter().any(|g| {
-        g.x == x && g.y == y
-        && g.state != ActorState::Dead
-        && g.state != ActorState::InHole
-    })
+pub fn has_active_guard(occ: &Occupancy, x: usize, y: usize) -> bool {
+    occ.at(x, y).any(|(_, state)| state != ActorState::Dead && state != ActorState::InHole)
 }
 
 /// Is there any non-dead, non-falling guard at (x, y)?
 /// These guards act as solid floor for the player (head-walking).
-pub fn has_standing_guard(guards: &[Guard], x: usize, y: usize) -> bool {
-    guards.iter().any(|g| {
-        g.x == x && g.y == y
-        && g.state != ActorState::Dead
-        && g.state != ActorState::Falling
-    })
+pub fn has_standing_guard(occ: &Occupancy, x: usize, y: usize) -> bool {
+    occ.at(x, y).any(|(_, state)| state != ActorState::Dead && state != ActorState::Falling)
 }
 
 /// Is there an active guard at (x, y), excluding guard at index `skip`?
-pub fn has_active_guard_except(guards: &[Guard], x: usize, y: usize, skip: usize) -> bool {
-    guards.iter().enumerate().any(|(j, g)| {
-        j != skip && g.x == x && g.y == y
-        && g.state != ActorState::Dead
-        && g.state != ActorState::InHole
+pub fn has_active_guard_except(occ: &Occupancy, x: usize, y: usize, skip: usize) -> bool {
+    occ.at(x, y).any(|(j, state)| {
+        j != skip && state != ActorState::Dead && state != ActorState::InHole
     })
 }
 
@@ -158,11 +153,11 @@ pub fn has_active_guard_except(guards: &[Guard], x: usize, y: usize, skip: usize
 
 /// Full support check: terrain support OR trapped guard below acting as floor.
 pub fn has_support(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
+    hole_grid: &BitGrid,
+    occ: &Occupancy,
     x: usize,
     y: usize,
 ) -> bool {
@@ -170,7 +165,7 @@ pub fn has_support(
         return true;
     }
     // Trapped guard directly below = floor
-    if y + 1 < height && has_trapped_guard(guards, x, y + 1) {
+    if y + 1 < height && has_trapped_guard(occ, x, y + 1) {
         return true;
     }
     false
@@ -180,11 +175,11 @@ pub fn has_support(
 /// In original Lode Runner, the player can walk on enemies' heads.
 /// Standing = not dead, not falling (InHole counts — trapped guard is solid).
 pub fn has_support_for_player(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
+    hole_grid: &BitGrid,
+    occ: &Occupancy,
     x: usize,
     y: usize,
 ) -> bool {
@@ -192,7 +187,7 @@ pub fn has_support_for_player(
         return true;
     }
     // Any standing guard below acts as floor for the player
-    if y + 1 < height && has_standing_guard(guards, x, y + 1) {
+    if y + 1 < height && has_standing_guard(occ, x, y + 1) {
         return true;
     }
     false
@@ -200,11 +195,11 @@ pub fn has_support_for_player(
 
 /// Full support check for a specific guard (excludes self from trapped check).
 pub fn has_support_for_guard(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
+    hole_grid: &BitGrid,
+    occ: &Occupancy,
     x: usize,
     y: usize,
     guard_idx: usize,
@@ -212,7 +207,7 @@ pub fn has_support_for_guard(
     if terrain_support(tiles, width, height, hole_grid, x, y) {
         return true;
     }
-    if y + 1 < height && has_trapped_guard_except(guards, x, y + 1, guard_idx) {
+    if y + 1 < height && has_trapped_guard_except(occ, x, y + 1, guard_idx) {
         return true;
     }
     false
@@ -227,11 +222,11 @@ pub fn has_support_for_guard(
 ///   Has support   → OnGround
 ///   Otherwise     → Falling
 pub fn resolve_state(
-    tiles: &[Vec<Tile>],
+    tiles: &Grid<Tile>,
     width: usize,
     height: usize,
-    hole_grid: &[Vec<bool>],
-    guards: &[Guard],
+    hole_grid: &BitGrid,
+    occ: &Occupancy,
     x: usize,
     y: usize,
     current: ActorState,
@@ -243,7 +238,7 @@ pub fn resolve_state(
     let here = terrain_at(tiles, width, height, hole_grid, x, y);
     if here.climbable { return ActorState::OnLadder; }
     if here.hangable { return ActorState::OnRope; }
-    if has_support(tiles, width, height, hole_grid, guards, x, y) {
+    if has_support(tiles, width, height, hole_grid, occ, x, y) {
         return ActorState::OnGround;
     }
 
@@ -254,13 +249,13 @@ pub fn resolve_state(
 // Hole grid construction
 // ══════════════════════════════════════════════════════════════
 
-/// Build a boolean grid from a list of Hole entities.
-/// `true` at (x, y) means there's an active hole there.
-pub fn build_hole_grid(holes: &[super::entity::Hole], width: usize, height: usize) -> Vec<Vec<bool>> {
-    let mut grid = vec![vec![false; width]; height];
+/// Build a bit grid from a list of Hole entities.
+/// Set at (x, y) means there's an active hole there.
+pub fn build_hole_grid(holes: &[super::entity::Hole], width: usize, height: usize) -> BitGrid {
+    let mut grid = BitGrid::new(width, height);
     for h in holes {
-        if h.x < width && h.y < height && h.is_active() {
-            grid[h.y][h.x] = true;
+        if h.is_active() {
+            grid.set(h.x, h.y, true);
         }
     }
     grid
@@ -276,13 +271,13 @@ mod tests {
     use crate::domain::entity::{Facing, Guard};
     use crate::domain::tile::Tile;
 
-    fn tiles_from(rows: &[&str]) -> (Vec<Vec<Tile>>, usize, usize) {
+    fn tiles_from(rows: &[&str]) -> (Grid<Tile>, usize, usize) {
         let h = rows.len();
         let w = rows[0].len();
-        let mut t = vec![vec![Tile::Empty; w]; h];
+        let mut t = Grid::new(w, h, Tile::Empty);
         for (y, row) in rows.iter().enumerate() {
             for (x, ch) in row.chars().enumerate() {
-                t[y][x] = match ch {
+                t[(x, y)] = match ch {
                     '#' => Tile::Brick,
                     '=' => Tile::Concrete,
                     'H' => Tile::Ladder,
@@ -294,16 +289,20 @@ mod tests {
         (t, w, h)
     }
 
-    fn empty_grid(w: usize, h: usize) -> Vec<Vec<bool>> {
-        vec![vec![false; w]; h]
+    fn empty_grid(w: usize, h: usize) -> BitGrid {
+        BitGrid::new(w, h)
     }
 
-    fn hole_grid_at(w: usize, h: usize, holes: &[(usize, usize)]) -> Vec<Vec<bool>> {
+    fn hole_grid_at(w: usize, h: usize, holes: &[(usize, usize)]) -> BitGrid {
         let mut g = empty_grid(w, h);
-        for &(x, y) in holes { g[y][x] = true; }
+        for &(x, y) in holes { g.set(x, y, true); }
         g
     }
 
+    fn occ(guards: &[Guard], w: usize, h: usize) -> Occupancy {
+        Occupancy::new(guards, w, h)
+    }
+
     fn guard_at(id: usize, x: usize, y: usize, state: ActorState) -> Guard {
         let mut g = Guard::new(id, x, y);
         g.state = state;
@@ -398,14 +397,14 @@ mod tests {
         let guards = vec![guard_at(0, 0, 1, ActorState::InHole)];
         let hg = empty_grid(w, h);
         assert!(!terrain_support(&t, w, h, &hg, 0, 0));
-        assert!(has_support(&t, w, h, &hg, &guards, 0, 0));
+        assert!(has_support(&t, w, h, &hg, &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
     fn active_guard_not_a_bridge_for_guards() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::OnGround)];
-        assert!(!has_support(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(!has_support(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     // ── Player head-walking ──
@@ -414,35 +413,35 @@ mod tests {
     fn active_guard_is_floor_for_player() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::OnGround)];
-        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
     fn falling_guard_not_floor_for_player() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::Falling)];
-        assert!(!has_support_for_player(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(!has_support_for_player(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
     fn dead_guard_not_floor_for_player() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::Dead)];
-        assert!(!has_support_for_player(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(!has_support_for_player(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
     fn trapped_guard_is_floor_for_player() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::InHole)];
-        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
     fn on_rope_guard_is_floor_for_player() {
         let (t, w, h) = tiles_from(&[" ", "-"]);
         let guards = vec![guard_at(0, 0, 1, ActorState::OnRope)];
-        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(has_support_for_player(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
@@ -453,19 +452,20 @@ mod tests {
             guard_at(2, 3, 1, ActorState::Dead),
             guard_at(3, 4, 1, ActorState::InHole),
         ];
+        let index = occ(&guards, 6, 6);
 
-        assert!(has_standing_guard(&guards, 1, 1));
-        assert!(!has_standing_guard(&guards, 2, 1));
-        assert!(!has_standing_guard(&guards, 3, 1));
-        assert!(has_standing_guard(&guards, 4, 1));
-        assert!(!has_standing_guard(&guards, 5, 5));
+        assert!(has_standing_guard(&index, 1, 1));
+        assert!(!has_standing_guard(&index, 2, 1));
+        assert!(!has_standing_guard(&index, 3, 1));
+        assert!(has_standing_guard(&index, 4, 1));
+        assert!(!has_standing_guard(&index, 5, 5));
     }
 
     #[test]
     fn dead_guard_not_a_bridge() {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::Dead)];
-        assert!(!has_support(&t, w, h, &empty_grid(w, h), &guards, 0, 0));
+        assert!(!has_support(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0));
     }
 
     #[test]
@@ -476,8 +476,8 @@ mod tests {
             guard_at(1, 0, 1, ActorState::InHole),
         ];
         let hg = empty_grid(w, h);
-        assert!(has_support_for_guard(&t, w, h, &hg, &guards, 0, 0, 0));
-        assert!(!has_support_for_guard(&t, w, h, &hg, &guards, 0, 1, 1));
+        assert!(has_support_for_guard(&t, w, h, &hg, &occ(&guards, w, h), 0, 0, 0));
+        assert!(!has_support_for_guard(&t, w, h, &hg, &occ(&guards, w, h), 0, 1, 1));
     }
 
     // ── resolve_state ──
@@ -487,7 +487,7 @@ mod tests {
         let (t, w, h) = tiles_from(&[" ", " ", "#"]);
         let guards: Vec<Guard> = vec![];
         assert_eq!(
-            resolve_state(&t, w, h, &empty_grid(w, h), &guards, 0, 0, ActorState::OnGround),
+            resolve_state(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0, ActorState::OnGround),
             ActorState::Falling
         );
     }
@@ -497,7 +497,7 @@ mod tests {
         let (t, w, h) = tiles_from(&[" ", " "]);
         let guards = vec![guard_at(0, 0, 1, ActorState::InHole)];
         assert_eq!(
-            resolve_state(&t, w, h, &empty_grid(w, h), &guards, 0, 0, ActorState::Falling),
+            resolve_state(&t, w, h, &empty_grid(w, h), &occ(&guards, w, h), 0, 0, ActorState::Falling),
             ActorState::OnGround
         );
     }
@@ -512,9 +512,9 @@ mod tests {
             Hole::new(7, 2, 50, 30),
         ];
         let grid = build_hole_grid(&holes, 10, 8);
-        assert!(grid[5][3]);
-        assert!(grid[2][7]);
-        assert!(!grid[0][0]);
-        assert!(!grid[5][4]);
+        assert!(grid.get(3, 5));
+        assert!(grid.get(7, 2));
+        assert!(!grid.get(0, 0));
+        assert!(!grid.get(4, 5));
     }
 }
@@ -67,11 +67,12 @@
 /// └─────────────────────────────┴──────────────┘
 
 use super::entity::{ActorState, Facing};
+use super::grid::Grid;
 use super::tile::Tile;
 
 /// Immutable view of the tile map for rule queries.
 pub struct MapView<'a> {
-    pub tiles: &'a Vec<Vec<Tile>>,
+    pub tiles: &'a Grid<Tile>,
     pub width: usize,
     pub height: usize,
 }
@@ -81,7 +82,7 @@ impl<'a> MapView<'a> {
         if x >= self.width || y >= self.height {
             return Tile::Concrete; // out of bounds = wall
         }
-        self.tiles[y][x]
+        self.tiles[(x, y)]
     }
 
     pub fn is_passable(&self, x: usize, y: usize) -> bool {
@@ -218,20 +219,21 @@ mod tests {
 
     /// Helper: build a MapView from a string diagram.
     /// Legend:  '#'=Brick  '='=Concrete  'H'=Ladder  '-'=Rope
-    ///         '$'=Gold  'T'=TrapBrick  ' '=Empty
-    fn map_from(rows: &[&str]) -> (Vec<Vec<Tile>>, usize, usize) {
+    ///         '$'=Gold  'T'=TrapBrick  'S'=FakeBrick  ' '=Empty
+    fn map_from(rows: &[&str]) -> (Grid<Tile>, usize, usize) {
         let height = rows.len();
         let width = rows[0].len();
-        let mut tiles = vec![vec![Tile::Empty; width]; height];
+        let mut tiles = Grid::new(width, height, Tile::Empty);
         for (y, row) in rows.iter().enumerate() {
             for (x, ch) in row.chars().enumerate() {
-                tiles[y][x] = match ch {
+                tiles[(x, y)] = match ch {
                     '#' => Tile::Brick,
                     '=' => Tile::Concrete,
                     'H' => Tile::Ladder,
                     '-' => Tile::Rope,
-                    '$' => Tile::Gold,
+                    '$' => Tile::Gold(0),
                     'T' => Tile::TrapBrick,
+                    'S' => Tile::FakeBrick,
                     _   => Tile::Empty,
                 };
             }
@@ -239,7 +241,7 @@ mod tests {
         (tiles, width, height)
     }
 
-    fn mv(tiles: &Vec<Vec<Tile>>, w: usize, h: usize) -> MapView {
+    fn mv(tiles: &Grid<Tile>, w: usize, h: usize) -> MapView<'_> {
         MapView { tiles, width: w, height: h }
     }
 
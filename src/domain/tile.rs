@@ -1,6 +1,12 @@
-/// Tile types and their properties.
-/// Properties are queried via methods, not stored as flags,
-/// so tile semantics are centralized here.
+//! Tile types and their properties.
+//! Properties are queried via methods, not stored as flags,
+//! so tile semantics are centralized here.
+//!
+//! Packs can add tiles of their own (`## Tile:` lines, see `sim::level`).
+//! Those are `Tile::Custom(id)`, with their properties looked up in the
+//! custom tile registry, which holds the current pack's definitions.
+
+use std::cell::RefCell;
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
 pub enum Tile {
@@ -9,30 +15,145 @@ pub enum Tile {
     Concrete,     // Solid only
     Ladder,       // Climbable
     Rope,         // Hangable (horizontal bar)
-    Gold,         // Pickup target
+    Gold(u8),     // Pickup target; index into `TOKENS`
     HiddenLadder, // Appears when all gold collected
     TrapBrick,    // Looks like Brick, collapses when stepped on
+    FakeBrick,    // Looks like Brick, but is passable; found by going through
+    Laser,        // Passable, but kills the player (and guards, by the rules) on touch
+    Spawner,      // Passable; sends out sentinels (`world.spawners` keeps the timers)
+    Lantern,      // Pickup; lights further in darkness
+    Door,         // Passable; stepping onto it moves to the linked room
+    Custom(u8),   // Pack-defined; index into the custom tile registry
+}
+
+/// A pack-defined tile: the level-file char it's written as, how it's
+/// drawn, and which built-in behaviours it has.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TileDef {
+    pub ch: char,
+    pub glyph: char,
+    pub color: (u8, u8, u8),
+    pub solid: bool,
+    pub climbable: bool,
+    pub diggable: bool,
+    pub hangable: bool,
+    pub deadly: bool,  // kills the player on touch (in it, or standing on it)
+}
+
+/// A kind of token: the level-file char it's written as, its points, and
+/// how it's drawn (`glyph` where emoji fit, `narrow` where they don't).
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TokenKind {
+    pub ch: char,
+    pub points: u32,
+    pub glyph: char,
+    pub narrow: char,
+    pub color: (u8, u8, u8),
+}
+
+/// `Tile::Gold(i)` is `TOKENS[i]`: worth more the riskier a level puts it.
+pub const TOKENS: [TokenKind; 3] = [
+    TokenKind { ch: '$', points: 100, glyph: '💰', narrow: '$', color: (255, 210, 60) },
+    TokenKind { ch: '%', points: 250, glyph: '💎', narrow: '%', color: (80, 220, 255) },
+    TokenKind { ch: '&', points: 500, glyph: '👑', narrow: '*', color: (230, 110, 255) },
+];
+
+/// The token written as `ch`, if it's one.
+pub fn token_for(ch: char) -> Option<Tile> {
+    TOKENS.iter().position(|k| k.ch == ch).map(|i| Tile::Gold(i as u8))
+}
+
+thread_local! {
+    /// The current pack's custom tiles; `Tile::Custom(i)` is entry `i`.
+    /// Per thread, as each game runs on one (`--serve-telnet` plays a
+    /// game per connection, each on its own thread).
+    static CUSTOM_TILES: RefCell<Vec<TileDef>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Replace the custom tile registry (on level load). At most 256 are kept.
+pub fn set_custom_tiles(defs: &[TileDef]) {
+    CUSTOM_TILES.with_borrow_mut(|tiles| {
+        tiles.clear();
+        tiles.extend(defs.iter().take(256));
+    });
+}
+
+/// The tile written as `ch` in the current pack, if it defines one.
+pub fn custom_tile_for(ch: char) -> Option<Tile> {
+    CUSTOM_TILES.with_borrow(|tiles| tiles.iter().position(|d| d.ch == ch).map(|i| Tile::Custom(i as u8)))
 }
 
 impl Tile {
+    /// Definition of a custom tile (`None` for built-ins, or an id the
+    /// registry doesn't hold, which behaves like `Empty`).
+    pub fn custom_def(self) -> Option<TileDef> {
+        let Tile::Custom(id) = self else { return None };
+        CUSTOM_TILES.with_borrow(|tiles| tiles.get(id as usize).copied())
+    }
+
     /// Can an entity stand on top of this tile? (i.e. is it a floor)
     pub fn is_solid(self) -> bool {
-        matches!(self, Tile::Brick | Tile::Concrete | Tile::TrapBrick)
+        match self {
+            Tile::Custom(_) => self.custom_def().is_some_and(|d| d.solid),
+            _ => matches!(self, Tile::Brick | Tile::Concrete | Tile::TrapBrick),
+        }
     }
 
     /// Can this tile be dug?
     pub fn is_diggable(self) -> bool {
-        matches!(self, Tile::Brick)
+        match self {
+            Tile::Custom(_) => self.custom_def().is_some_and(|d| d.diggable),
+            _ => matches!(self, Tile::Brick),
+        }
     }
 
     /// Can an entity climb (move up/down) on this tile?
     pub fn is_climbable(self) -> bool {
-        matches!(self, Tile::Ladder | Tile::HiddenLadder)
+        match self {
+            Tile::Custom(_) => self.custom_def().is_some_and(|d| d.climbable),
+            _ => matches!(self, Tile::Ladder | Tile::HiddenLadder),
+        }
     }
 
     /// Can an entity hang and move horizontally on this tile?
     pub fn is_hangable(self) -> bool {
-        matches!(self, Tile::Rope)
+        match self {
+            Tile::Custom(_) => self.custom_def().is_some_and(|d| d.hangable),
+            _ => matches!(self, Tile::Rope),
+        }
+    }
+
+    /// Stable number for hashing maps, never reused so golden replay
+    /// hashes hold: 0-12 for the built-ins as listed below (not their
+    /// declaration order), the first token tier 5 and the others
+    /// 12 + tier, custom tiles from 256 up.
+    pub fn code(self) -> u64 {
+        match self {
+            Tile::Empty => 0,
+            Tile::Brick => 1,
+            Tile::Concrete => 2,
+            Tile::Ladder => 3,
+            Tile::Rope => 4,
+            Tile::Gold(0) => 5,
+            Tile::HiddenLadder => 6,
+            Tile::TrapBrick => 7,
+            Tile::Door => 8,
+            Tile::FakeBrick => 9,
+            Tile::Laser => 10,
+            Tile::Spawner => 11,
+            Tile::Lantern => 12,
+            Tile::Gold(tier) => 12 + tier as u64,
+            Tile::Custom(id) => 256 + id as u64,
+        }
+    }
+
+    /// Does touching this tile kill the player? Lasers do, and custom
+    /// tiles flagged `deadly`.
+    pub fn is_deadly(self) -> bool {
+        match self {
+            Tile::Custom(_) => self.custom_def().is_some_and(|d| d.deadly),
+            _ => matches!(self, Tile::Laser),
+        }
     }
 
     /// Is this tile passable (entity can occupy this cell)?
@@ -41,9 +162,14 @@ impl Tile {
     }
 
     /// Is this a gold pickup?
-    #[allow(dead_code)]
     pub fn is_gold(self) -> bool {
-        matches!(self, Tile::Gold)
+        matches!(self, Tile::Gold(_))
+    }
+
+    /// The kind of token this is, if it is one (an unknown tier as `$`).
+    pub fn token(self) -> Option<TokenKind> {
+        let Tile::Gold(tier) = self else { return None };
+        Some(TOKENS.get(tier as usize).copied().unwrap_or(TOKENS[0]))
     }
 
     /// Is this a trap brick? (looks like brick but collapses)
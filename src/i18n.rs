@@ -0,0 +1,108 @@
+//! UI strings by language.
+//!
+//! Strings live in `locales/<lang>.toml`, one table per screen, and are
+//! looked up by dotted key (`"title.new_game"`). English and Japanese are
+//! compiled in; a `locales/<lang>.toml` next to the executable adds a
+//! language or overrides built-in strings. Keys a locale leaves out fall
+//! back to English, so a partial translation still shows every label.
+//!
+//! `{name}` placeholders are filled by `t!("key", name = value)`.
+
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::sync::OnceLock;
+
+const EN: &str = include_str!("../locales/en.toml");
+const JA: &str = include_str!("../locales/ja.toml");
+
+static STRINGS: OnceLock<HashMap<String, String>> = OnceLock::new();
+
+/// Look up `key`; with arguments, fill in its `{name}` placeholders.
+#[macro_export]
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($name:ident = $val:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::tr($key), &[$((stringify!($name), &$val as &dyn std::fmt::Display)),+])
+    };
+}
+pub use crate::t;
+
+/// Pick the language once at startup. `""` / `"auto"` follows `LANG`.
+/// Lookups before this (or without it) use English.
+pub fn init(language: &str) {
+    let auto = matches!(language.trim(), "" | "auto");
+    let _ = STRINGS.set(load(&resolve(language), !auto));
+}
+
+/// String for `key`, or the key itself if no locale defines it.
+pub fn tr(key: &'static str) -> &'static str {
+    let strings = STRINGS.get_or_init(|| load("en", false));
+    strings.get(key).map_or(key, String::as_str)
+}
+
+/// Replace each `{name}` in `template` with its value.
+pub fn fill(template: &str, args: &[(&str, &dyn Display)]) -> String {
+    let mut out = template.to_string();
+    for (name, value) in args {
+        out = out.replace(&format!("{{{name}}}"), &value.to_string());
+    }
+    out
+}
+
+fn resolve(language: &str) -> String {
+    let lang = language.trim().to_ascii_lowercase();
+    if !lang.is_empty() && lang != "auto" {
+        return lang;
+    }
+    ["LC_ALL", "LC_MESSAGES", "LANG"].iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .find(|v| !v.is_empty())
+        .and_then(|v| v.split(['_', '.', '-']).next().map(str::to_ascii_lowercase))
+        .filter(|l| !l.is_empty() && l != "c" && l != "posix")
+        .unwrap_or_else(|| "en".to_string())
+}
+
+/// English, then the built-in locale, then a `locales/<lang>.toml` on disk.
+/// `warn`: the language was asked for by name, so say if it's missing.
+fn load(lang: &str, warn: bool) -> HashMap<String, String> {
+    let mut strings = HashMap::new();
+    merge(&mut strings, EN);
+    if lang == "ja" {
+        merge(&mut strings, JA);
+    }
+    let file = format!("{lang}.toml");
+    if let Some(content) = crate::config::candidate_dirs().iter()
+        .map(|d| d.join("locales").join(&file))
+        .find_map(|p| std::fs::read_to_string(p).ok())
+    {
+        merge(&mut strings, &content);
+    } else if warn && lang != "en" && lang != "ja" {
+        eprintln!("Warning: no locale \"{lang}\", using English.");
+    }
+    strings
+}
+
+/// Flatten `[table] key = "..."` into `"table.key"` entries.
+fn merge(strings: &mut HashMap<String, String>, content: &str) {
+    let table: toml::Table = match content.parse() {
+        Ok(t) => t,
+        Err(e) => {
+            eprintln!("Warning: locale file parse error: {e}");
+            return;
+        }
+    };
+    flatten(strings, "", &table);
+}
+
+fn flatten(strings: &mut HashMap<String, String>, prefix: &str, table: &toml::Table) {
+    for (key, value) in table {
+        let key = if prefix.is_empty() { key.clone() } else { format!("{prefix}.{key}") };
+        match value {
+            toml::Value::String(s) => { strings.insert(key, s.clone()); }
+            toml::Value::Table(t) => flatten(strings, &key, t),
+            _ => {}
+        }
+    }
+}
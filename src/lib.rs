@@ -0,0 +1,7 @@
+//! The game's modules, shared by the binary (`main.rs`) and the benches.
+
+pub mod config;
+pub mod domain;
+pub mod i18n;
+pub mod sim;
+pub mod ui;
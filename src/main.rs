@@ -1,33 +1,275 @@
 /// Entry point and game loop.
 
-mod config;
-mod domain;
-mod sim;
-mod ui;
+use noderunner::{config, domain, i18n, sim, ui};
 
+use std::io::{self, IsTerminal, Write};
+use std::net::{Shutdown, TcpListener, TcpStream};
+use std::sync::Arc;
+use std::thread;
+use std::rc::Rc;
 use std::time::{Duration, Instant};
 
-use crossterm::event::KeyCode;
+use crossterm::event::{KeyCode, KeyEventKind, KeyModifiers};
 
-use config::GameConfig;
+use config::{GameConfig, RenderMode};
 use domain::entity::{Facing, FrameInput, MoveDir};
-use sim::event::GameEvent;
-use sim::level::{load_level, scan_packs, switch_pack};
-use sim::save;
+use i18n::t;
+use sim::credits;
+use sim::event::{EventBus, GameEvent};
+use sim::event_log::EventLog;
+use sim::level::{
+    self, check_solvable, delete_pack, is_pack_file, level_files, load_level, pack_preview, rename_pack,
+    scan_packs, switch_pack, write_pack,
+};
+use sim::level_code;
+use sim::online::Online;
+use sim::randomize::Randomizer;
+use sim::run_code::{self, CodeError, Playback, RunCode, Verdict};
+use sim::save::{self, LoadError};
+use sim::spectate::{self, Host, Viewer};
 use sim::step;
-use sim::world::{Phase, WorldState};
+use sim::story;
+use sim::world::{ConfigReport, GameMode, OverwriteSlot, PackBuilder, PackDialog, PauseItem, PauseSetting, Phase, RunBests, RunStats, Severity, ShareDialog,
+    SlotInfo, TimeAttack,
+    WorldState,};
 use ui::gamepad::GamepadState;
 use ui::input::InputState;
+use ui::perf::PerfMeter;
 use ui::renderer::Renderer;
-use ui::sound::SoundEngine;
+use ui::telnet;
+use ui::sound::{SoundEngine, SoundEvents};
+use ui::terminal::Signals;
+use ui::transition;
+
+/// Most sim ticks run back-to-back before input and frames get a turn.
+const MAX_CATCH_UP_TICKS: u32 = 4;
+/// Sim time owed beyond this is dropped: a hiccup is caught up, a stall
+/// (suspend, laptop sleep) isn't fast-forwarded through.
+const MAX_BACKLOG: Duration = Duration::from_secs(1);
+
+/// Command-line options.
+#[derive(Default)]
+struct Args {
+    /// `--log-events`: export this session's events (see `sim::event_log`).
+    log_events: bool,
+    /// `--profile <name>` / `--profile=<name>`: play as this profile.
+    profile: Option<String>,
+    /// `--migrate-saves`: upgrade old save files, then exit.
+    migrate_saves: bool,
+    /// `--spectate-host [addr]`: stream the game to viewers at this
+    /// address (all interfaces if none).
+    spectate_host: Option<String>,
+    /// `--spectate <addr>`: watch the game streamed from this address.
+    spectate: Option<String>,
+    /// `--serve-telnet <[addr:]port>`: play a game on each telnet
+    /// connection (on this machine only unless an address is given).
+    serve_telnet: Option<String>,
+    /// `--cp437`: encode served games for CP437 (BBS) terminals.
+    cp437: bool,
+    /// `--agent <level>`: play a level file, or a node of the pack, over
+    /// stdin/stdout (see `sim::agent`).
+    agent: Option<String>,
+}
 
-const FRAME_SLEEP: Duration = Duration::from_millis(5);
+fn parse_args() -> Args {
+    let mut args = Args::default();
+    let mut it = std::env::args().skip(1).peekable();
+    while let Some(arg) = it.next() {
+        match arg.as_str() {
+            "--log-events" => args.log_events = true,
+            "--migrate-saves" => args.migrate_saves = true,
+            "--profile" => args.profile = it.next(),
+            "--spectate" => args.spectate = it.next(),
+            "--serve-telnet" => args.serve_telnet = it.next(),
+            "--cp437" => args.cp437 = true,
+            "--agent" => args.agent = it.next(),
+            "--spectate-host" => {
+                let addr = it.next_if(|a| !a.starts_with("--"));
+                args.spectate_host = Some(addr.unwrap_or_else(|| "0.0.0.0".to_string()));
+            }
+            other => {
+                if let Some(name) = other.strip_prefix("--profile=") {
+                    args.profile = Some(name.to_string());
+                } else if let Some(addr) = other.strip_prefix("--spectate-host=") {
+                    args.spectate_host = Some(addr.to_string());
+                } else if let Some(addr) = other.strip_prefix("--spectate=") {
+                    args.spectate = Some(addr.to_string());
+                } else if let Some(port) = other.strip_prefix("--serve-telnet=") {
+                    args.serve_telnet = Some(port.to_string());
+                } else if let Some(level) = other.strip_prefix("--agent=") {
+                    args.agent = Some(level.to_string());
+                }
+            }
+        }
+    }
+    args
+}
+
+/// Ask which profile to play as, when there are some and someone to ask.
+/// A new name makes a new profile; an empty answer plays without one.
+fn choose_profile() -> Option<String> {
+    let names = save::profile_names();
+    if names.is_empty() || !io::stdin().is_terminal() {
+        return None;
+    }
+    println!("{}", t!("profile.header"));
+    for (i, name) in names.iter().enumerate() {
+        println!("  {}) {name}", i + 1);
+    }
+    loop {
+        print!("{}", t!("profile.prompt"));
+        let _ = io::stdout().flush();
+        let mut line = String::new();
+        if io::stdin().read_line(&mut line).unwrap_or(0) == 0 {
+            return None;
+        }
+        let answer = line.trim();
+        if answer.is_empty() {
+            return None;
+        }
+        match answer.parse::<usize>() {
+            Ok(n) if (1..=names.len()).contains(&n) => return Some(names[n - 1].clone()),
+            Err(_) if save::valid_profile_name(answer) => return Some(answer.to_string()),
+            _ => println!("{}", t!("profile.invalid")),
+        }
+    }
+}
+
+/// `--migrate-saves`: upgrade every old-format save and say what happened.
+fn migrate_saves() {
+    let done = save::migrate_saves();
+    if done.is_empty() {
+        println!("{}", t!("migrate.none"));
+    }
+    for (path, outcome) in done {
+        let path = path.display();
+        match outcome {
+            save::Migration::Upgraded => println!("{}", t!("migrate.upgraded", path = path)),
+            save::Migration::Current => println!("{}", t!("migrate.current", path = path)),
+            save::Migration::Failed(why) => println!("{}", t!("migrate.failed", path = path, why = why)),
+        }
+    }
+}
 
 fn main() {
-    let config = GameConfig::load();
+    let args = parse_args();
+    let shared = GameConfig::load();
+    // The language is the shared config's: the profile prompt needs it
+    i18n::init(&shared.language);
+    if args.migrate_saves {
+        migrate_saves();
+        return;
+    }
+    let mut config = shared;
+    // A viewer plays nothing, and a server or an agent has no one at its
+    // terminal to ask
+    let ask = args.spectate.is_none() && args.serve_telnet.is_none() && args.agent.is_none();
+    let profile = if ask { args.profile.or_else(choose_profile) } else { args.profile };
+    if let Some(name) = profile {
+        match save::use_profile(&name) {
+            Ok(dir) => config = GameConfig::load_for(Some(&dir)),
+            Err(e) => {
+                eprintln!("{e}");
+                return;
+            }
+        }
+    }
+    config.log_events |= args.log_events;
+    sim::timing::enable(config.display.perf_overlay);
+
+    if let Some(port) = &args.serve_telnet {
+        serve_telnet(port, args.cp437, config);
+        return;
+    }
+    if let Some(level) = &args.agent {
+        play_agent(level, &config);
+        return;
+    }
+
+    let mut world = new_world(&config);
+    if let Some(name) = save::profile() {
+        world.log(Severity::Info, &t!("profile.active", name = name));
+    }
+
+    let mut viewer = None;
+    if let Some(addr) = &args.spectate {
+        match Viewer::connect(addr) {
+            Ok(v) => viewer = Some(v),
+            Err(e) => {
+                eprintln!("{}", t!("spectate.connect_failed", addr = addr, err = e));
+                return;
+            }
+        }
+    }
+    let mut host = None;
+    if let Some(addr) = &args.spectate_host {
+        match Host::bind(addr) {
+            Ok(h) => {
+                world.log(Severity::Info, &t!("spectate.hosting", addr = h.addr));
+                host = Some(h);
+            }
+            Err(e) => {
+                eprintln!("{}", t!("spectate.bind_failed", addr = spectate::with_port(addr), err = e));
+                return;
+            }
+        }
+    }
+
+    let mut renderer = Renderer::new(&config.display);
+    let signals = ui::terminal::install();
+
+    if let Err(e) = renderer.init() {
+        eprintln!("Terminal init failed: {e}");
+        return;
+    }
+
+    if let Some(mut viewer) = viewer {
+        let result = spectate_loop(&mut world, &mut renderer, &mut viewer, &config, &signals);
+        if let Err(e) = renderer.cleanup() {
+            eprintln!("Terminal cleanup failed: {e}");
+        }
+        if let Err(e) = result {
+            eprintln!("Game error: {e}");
+        }
+        return;
+    }
+
+    let sound = SoundEngine::new().map(Rc::new);
+    let mut events = EventBus::default();
+    if let Some(sfx) = &sound {
+        events.subscribe(SoundEvents(Rc::clone(sfx)));
+    }
+    if config.log_events {
+        match EventLog::open() {
+            Ok(log) => {
+                world.log(Severity::Info, &t!("log.export", path = log.path().display()));
+                events.subscribe(log);
+            }
+            Err(e) => world.log(Severity::Warning, &t!("log.export_failed", err = e)),
+        }
+    }
+
+    let io = GameIo { kb: InputState::new(), gp: GamepadState::new(), sound, events, spectate: host };
+    let result = game_loop(&mut world, &mut renderer, io, &config, &signals);
+
+    if let Err(e) = renderer.cleanup() {
+        eprintln!("Terminal cleanup failed: {e}");
+    }
 
+    if let Err(e) = result {
+        eprintln!("Game error: {e}");
+    }
+
+    println!();
+    println!("{}", t!("msg.thanks"));
+    println!("{}", t!("msg.final_score", score = world.score));
+}
+
+/// A world at the title screen, for `config`.
+fn new_world(config: &GameConfig) -> WorldState {
     let mut world = WorldState::new();
     world.speed = config.speed.clone();
+    world.compact_view = config.display.compact_view;
 
     // Auto-detect initial level source: levels/ dir takes priority if it has files
     if config.levels_dir.is_dir() {
@@ -45,162 +287,455 @@ fn main() {
     }
 
     // Pre-load level list for title/select screens
-    world.level_names = sim::level::get_level_list_for_pack(&world, &config);
+    world.level_names = sim::level::get_level_list_for_pack(&world, config);
     world.total_levels = world.level_names.len();
     world.has_save = save::has_save();
+    world.suspended = save::load_suspend().ok().map(|d| d.level);
+    world.ng_plus = save::pack_cleared(&world.active_pack_path, world.total_levels);
+    world
+}
 
-    let mut renderer = Renderer::new();
-
-    if let Err(e) = renderer.init() {
-        eprintln!("Terminal init failed: {e}");
+/// `--agent`: play `level`, a level file or a node number of the pack,
+/// over stdin/stdout.
+fn play_agent(level: &str, config: &GameConfig) {
+    let def = match std::fs::read_to_string(level) {
+        Ok(text) => sim::level::parse_level_file(&text),
+        Err(_) => level.parse::<usize>().ok()
+            .and_then(|n| sim::level::active_level(&new_world(config), n.checked_sub(1)?, config)),
+    };
+    let Some(def) = def else {
+        eprintln!("{}", t!("agent.no_level", level = level));
         return;
-    }
-
-    let sound = SoundEngine::new();
-
-    let result = game_loop(&mut world, &mut renderer, sound.as_ref(), &config);
-
-    if let Err(e) = renderer.cleanup() {
-        eprintln!("Terminal cleanup failed: {e}");
-    }
-
-    if let Err(e) = result {
+    };
+    if let Err(e) = sim::agent::run(&def, config, io::stdin().lock(), io::stdout().lock()) {
         eprintln!("Game error: {e}");
     }
+}
 
-    println!();
-    println!("Thanks for playing Node Runner: Mainnet Protocol!");
-    println!("Final Score: {}", world.score);
+/// Where a game's input comes from and, besides the screen, where its
+/// output goes.
+struct GameIo {
+    kb: InputState,
+    gp: GamepadState,
+    sound: Option<Rc<SoundEngine>>,
+    /// Subscribers to sim events and phase changes (sound, ...).
+    events: EventBus,
+    /// Viewers' connections, with `--spectate-host`.
+    spectate: Option<Host>,
 }
 
 fn game_loop(
     world: &mut WorldState,
     renderer: &mut Renderer,
-    sound: Option<&SoundEngine>,
+    io: GameIo,
     config: &GameConfig,
+    signals: &Signals,
 ) -> Result<(), Box<dyn std::error::Error>> {
-    let mut kb = InputState::new();
-    let mut gp = GamepadState::new();
-    gp.load_button_config(&config.gamepad);
-    let mut last_tick = Instant::now();
+    let GameIo { mut kb, mut gp, sound, events, spectate } = io;
+    let sound = sound.as_deref();
+    let mut problems = config.problems.clone();
+    problems.extend(gp.load_button_config(&config.gamepad));
+    if !problems.is_empty() {
+        let path = config.source.as_ref().map_or("config.toml".into(), |p| p.display().to_string());
+        world.config_report = Some(ConfigReport { path, problems });
+    }
     let tick_rate = Duration::from_millis(config.speed.tick_rate_ms);
+    let frame_interval = match config.display.frame_rate {
+        0 => Duration::ZERO,
+        fps => Duration::from_secs(1) / fps,
+    };
 
-    let mut pending_dig: Option<Facing> = None;
-    let mut prev_intro_rows: usize = 0;
+    let mut state = LoopState {
+        pending_dig: None,
+        prev_intro_step: 0,
+        perf: PerfMeter::default(),
+        online: Online::new(&config.online),
+        events,
+        spectate,
+    };
+    // Fixed step: real time accrues as sim time owed, paid in whole ticks
+    // at tick_rate regardless of how often frames are drawn, and vice versa.
+    let mut owed = Duration::ZERO;
+    let mut last_accrued = Instant::now();
+    let mut next_frame = Instant::now();
+    // Something changed since the last frame (on-demand screens only)
+    let mut dirty = true;
 
     loop {
         kb.drain_events();
         gp.update();
 
-        if kb.ctrl_c_pressed() {
+        if kb.ctrl_c_pressed() || signals.quit() {
+            suspend(world);
             break;
         }
+        // Ctrl+Z, or a stop/continue from outside (kill -TSTP, fg after SIGSTOP)
+        let stopped = (kb.ctrl_z_pressed() || signals.take_stop()) && renderer.suspend();
+        if signals.take_cont() || stopped {
+            renderer.resume()?;
+            auto_pause(world);
+            dirty = true;
+        }
+        if kb.focus_lost || renderer.too_small() {
+            auto_pause(world);
+        }
+        // The key that dismisses the config report does nothing else
+        if world.config_report.is_some() && kb.any_key_pressed() {
+            world.config_report = None;
+            dirty = true;
+            continue;
+        }
+        // The key that resumes an auto-pause does nothing else
+        if world.paused && world.auto_paused && !renderer.too_small() && kb.any_key_pressed() {
+            world.paused = false;
+            world.auto_paused = false;
+            world.clear_messages();
+            dirty = true;
+            continue;
+        }
         if handle_meta(world, sound, &kb, &gp, config) {
             break;
         }
+        record_time_attack(world);
+        record_run_bests(world);
+        if let Some(online) = state.online.as_mut() {
+            online.poll(world);
+        }
+
+        // Reduced motion: skip the row-reveal intro, go straight to the static ready screen
+        if config.display.reduced_motion && world.phase == Phase::LevelIntro {
+            world.phase = Phase::LevelReady;
+            world.anim_tick = 0;
+        }
+
+        // The overview only exists while playing
+        if world.phase != Phase::Playing {
+            world.show_overview = false;
+        }
 
-        if world.phase == Phase::Playing && !world.paused {
+        // A terminal below the minimum size holds the game like a pause
+        let held = world.paused || world.show_overview || renderer.too_small();
+
+        if world.phase == Phase::Playing && !held {
             if let Some(dir) = detect_dig_press(&kb, &gp) {
-                pending_dig = Some(dir);
+                state.pending_dig = Some(dir);
             }
         }
 
-        if last_tick.elapsed() >= tick_rate {
-            // Pause blocks simulation but allows anim_tick for blink
-            if world.paused {
-                world.anim_tick = world.anim_tick.wrapping_add(1);
-                if world.message_timer > 0 {
-                    world.message_timer -= 1;
-                    if world.message_timer == 0 { world.message.clear(); }
-                }
-                last_tick = Instant::now();
+        // Run the ticks that have come due, a few at a time so a backlog
+        // is caught up over the next passes without starving input and
+        // frames. After a long stall drop it rather than fast-forwarding.
+        let now = Instant::now();
+        owed += now - last_accrued;
+        last_accrued = now;
+        if owed > MAX_BACKLOG {
+            owed = tick_rate;
+        }
+        let mut ticks = 0;
+        while owed >= tick_rate && ticks < MAX_CATCH_UP_TICKS {
+            if held {
+                held_tick(world);
             } else {
-            match world.phase {
-                Phase::Playing => {
-                    let frame_input = FrameInput {
-                        movement: detect_movement(&kb, &gp),
-                        dig: pending_dig.take(),
-                    };
-                    let events = step::step(world, frame_input);
-                    process_sound_events(sound, &events);
-
-                    // Camera follows player
-                    world.camera.follow(
-                        world.player.x, world.player.y,
-                        world.width, world.height,
-                    );
-                }
-                Phase::LevelIntro => {
-                    tick_level_intro(world);
-                    if let Some(sfx) = sound {
-                        let rows_visible = calc_intro_rows_visible(world);
-                        if rows_visible > prev_intro_rows && rows_visible <= world.height {
-                            sfx.play_intro_blip(rows_visible, world.height);
-                        }
-                        prev_intro_rows = rows_visible;
-                    }
-                }
-                Phase::LevelReady => {
-                    world.anim_tick += 1;
-                    prev_intro_rows = 0;
-                }
-                Phase::LevelOutro => {
-                    tick_level_outro(world);
-                }
-                Phase::Dying => {
-                    tick_dying(world, sound);
-                }
-                Phase::LevelSelect => {
-                    world.anim_tick += 1;
-                }
-                Phase::PackSelect => {
-                    world.anim_tick += 1;
-                }
-                _ => {}
+                sim_tick(world, renderer, sound, config, &kb, &gp, &mut state);
             }
-
-            // Global: tick message timer (works in all phases)
-            if world.message_timer > 0 {
-                world.message_timer -= 1;
-                if world.message_timer == 0 { world.message.clear(); }
+            owed -= tick_rate;
+            ticks += 1;
+        }
+        if ticks > 0 {
+            if let Some(host) = state.spectate.as_mut() {
+                host.send(world, config);
+            }
+        }
+        // Phase changes made outside a sim step (menus, loading)
+        state.events.publish(world, &mut Vec::new(), renderer);
+
+        // Screens that only change on a tick or a key don't need frames
+        // in between; drawing them on demand lets the loop sleep.
+        let on_demand = frame_interval.is_zero() || renders_on_demand(world);
+        dirty |= ticks > 0 || !kb.raw_events.is_empty();
+        let now = Instant::now();
+        if now >= next_frame && (dirty || !on_demand) {
+            if config.display.perf_overlay {
+                state.perf.record_frame();
+                renderer.set_perf(state.perf.readout());
+            }
+            renderer.render(world)?;
+            dirty = false;
+            // Slow terminals stretch the interval; the sim keeps its tick
+            let interval = frame_interval.max(renderer.min_frame_interval());
+            next_frame += interval;
+            if next_frame < now {
+                // More than a frame behind: restart the cadence, don't burst
+                next_frame = now + interval;
             }
-
-            last_tick = Instant::now();
-            } // else !paused
         }
 
-        renderer.render(world)?;
-        std::thread::sleep(FRAME_SLEEP);
+        // Block until input arrives or the next tick / frame is due
+        let next_tick = last_accrued + tick_rate.saturating_sub(owed);
+        let wake = if on_demand && !dirty { next_tick } else { next_tick.min(next_frame) };
+        kb.wait(wake.saturating_duration_since(Instant::now()));
     }
 
     Ok(())
 }
 
-fn calc_intro_rows_visible(world: &WorldState) -> usize {
-    let tick = world.anim_tick;
-    if tick <= INTRO_NAME_TICKS {
-        0
-    } else {
-        ((tick - INTRO_NAME_TICKS) / INTRO_ROW_INTERVAL).min(world.height as u32) as usize
+/// How often a viewer checks for frames and keys.
+const SPECTATE_POLL: Duration = Duration::from_millis(10);
+const KEYS_STOP_WATCHING: &[KeyCode] = &[KeyCode::Esc, KeyCode::Char('q'), KeyCode::Char('Q')];
+
+/// `--spectate`: show the host's game, frame by frame as it arrives,
+/// until the viewer quits. Nothing the viewer presses reaches the host.
+fn spectate_loop(
+    world: &mut WorldState,
+    renderer: &mut Renderer,
+    viewer: &mut Viewer,
+    config: &GameConfig,
+    signals: &Signals,
+) -> Result<(), Box<dyn std::error::Error>> {
+    let mut kb = InputState::new();
+    viewer.start(world);
+    let mut dirty = true;
+    loop {
+        kb.drain_events();
+        if kb.ctrl_c_pressed() || signals.quit() || kb.any_pressed(KEYS_STOP_WATCHING) {
+            break;
+        }
+        let stopped = (kb.ctrl_z_pressed() || signals.take_stop()) && renderer.suspend();
+        if signals.take_cont() || stopped {
+            renderer.resume()?;
+            dirty = true;
+        }
+        dirty |= viewer.poll(world, config) || !kb.raw_events.is_empty();
+        if dirty {
+            renderer.render(world)?;
+            dirty = false;
+        }
+        kb.wait(SPECTATE_POLL);
     }
+    Ok(())
 }
 
-fn process_sound_events(sound: Option<&SoundEngine>, events: &[GameEvent]) {
-    let sfx = match sound {
-        Some(s) => s,
-        None => return,
+/// `--serve-telnet`: play a game on every connection, each on its own
+/// thread, until the server is stopped. Players can't touch the host's
+/// files: there are no saves, and packs can only be played.
+fn serve_telnet(port: &str, cp437: bool, mut config: GameConfig) {
+    // Other machines only get in with an address asked for outright
+    let addr = if port.contains(':') { port.to_string() } else { format!("127.0.0.1:{port}") };
+    let listener = match TcpListener::bind(&addr) {
+        Ok(l) => l,
+        Err(e) => {
+            eprintln!("{}", t!("telnet.bind_failed", addr = addr, err = e));
+            return;
+        }
     };
-    for event in events {
-        match event {
-            GameEvent::GoldPicked { .. } => sfx.play_gold(),
-            GameEvent::HoleCreated { .. } => sfx.play_dig(),
-            GameEvent::PlayerFallStart => sfx.play_fall(),
-            GameEvent::PlayerKilled => sfx.play_die(),
-            GameEvent::AllGoldCollected => sfx.play_all_gold(),
-            GameEvent::StageCleared => sfx.play_clear(),
-            _ => {}
+    // What a client's terminal can show can't be told from here
+    if !matches!(config.display.render_mode, RenderMode::Cells | RenderMode::HalfBlock) {
+        config.display.render_mode = RenderMode::Cells;
+    }
+    save::serve_only();
+    let config = Arc::new(config);
+    println!("{}", t!("telnet.serving", addr = listener.local_addr().map_or(addr, |a| a.to_string())));
+    for stream in listener.incoming().flatten() {
+        let peer = stream.peer_addr().map_or("?".to_string(), |a| a.to_string());
+        let config = Arc::clone(&config);
+        thread::spawn(move || {
+            println!("{}", t!("telnet.joined", peer = peer));
+            if let Err(e) = play_telnet(stream, cp437, &config) {
+                eprintln!("{}", t!("telnet.session_error", peer = peer, err = e));
+            }
+            println!("{}", t!("telnet.left", peer = peer));
+        });
+    }
+}
+
+/// One connection's game, from the title screen until it quits or the
+/// client goes.
+fn play_telnet(stream: TcpStream, cp437: bool, config: &GameConfig) -> Result<(), Box<dyn std::error::Error>> {
+    let closer = stream.try_clone()?;
+    let session = telnet::Session::open(stream, cp437)?;
+    sim::timing::enable(config.display.perf_overlay);
+    let mut world = new_world(config);
+    world.served = true;
+    let mut renderer = Renderer::with_screen(&config.display, session.out, Box::new(session.screen));
+    renderer.init()?;
+    let io = GameIo {
+        kb: InputState::with_source(Box::new(session.input)),
+        gp: GamepadState::absent(),
+        sound: None,
+        events: EventBus::default(),
+        spectate: None,
+    };
+    let result = game_loop(&mut world, &mut renderer, io, config, &Signals::default());
+    let _ = renderer.cleanup();
+    let _ = closer.shutdown(Shutdown::Both);
+    result
+}
+
+/// Whether the current screen is static between ticks: menus, end
+/// screens, the ready prompt, and pause all animate off `anim_tick` only.
+fn renders_on_demand(world: &WorldState) -> bool {
+    world.paused || matches!(world.phase,
+        Phase::Title | Phase::LevelSelect | Phase::PackSelect | Phase::PackBuilder
+        | Phase::LevelReady | Phase::GameOver)
+}
+
+/// Game-loop state carried across ticks.
+struct LoopState {
+    pending_dig: Option<Facing>,
+    prev_intro_step: usize,
+    perf: PerfMeter,
+    /// Global leaderboard client; `None` unless opted in.
+    online: Option<Online>,
+    /// Subscribers to sim events and phase changes (sound, ...).
+    events: EventBus,
+    /// Viewers' connections, with `--spectate-host`.
+    spectate: Option<Host>,
+}
+
+/// Pause play for something outside the game (a shrunk or unfocused
+/// terminal, a suspend), so it isn't lost while the player can't see it.
+/// The next key resumes.
+fn auto_pause(world: &mut WorldState) {
+    if world.phase == Phase::Playing && !world.paused {
+        world.paused = true;
+        world.auto_paused = true;
+        world.log_open = false;
+        world.push_message(Severity::Info, t!("msg.auto_paused"), 0);
+    }
+}
+
+/// A tick while paused, in the overview, or on a too-small terminal:
+/// the sim is held but blinking and messages keep time.
+fn held_tick(world: &mut WorldState) {
+    world.anim_tick = world.anim_tick.wrapping_add(1);
+    world.tick_messages();
+}
+
+/// One fixed simulation tick for the current phase.
+fn sim_tick(
+    world: &mut WorldState,
+    renderer: &mut Renderer,
+    sound: Option<&SoundEngine>,
+    config: &GameConfig,
+    kb: &InputState,
+    gp: &GamepadState,
+    state: &mut LoopState,
+) {
+    match world.phase {
+        Phase::Playing => {
+            let frame_input = match world.playback.as_mut() {
+                Some(playback) => match playback.next_input() {
+                    Some(input) => input,
+                    None => {
+                        end_playback(world, false);
+                        return;
+                    }
+                },
+                None => FrameInput {
+                    movement: detect_movement(kb, gp),
+                    dig: state.pending_dig.take(),
+                },
+            };
+            if let Some(recording) = world.recording.as_mut() {
+                recording.inputs.push(frame_input);
+            }
+            world.restart_confirm = world.restart_confirm.saturating_sub(1);
+            let step_start = Instant::now();
+            let mut events = step::step(world, frame_input);
+            state.perf.record_step(step_start.elapsed());
+            state.events.publish(world, &mut events, renderer);
+            world.log_events(&events);
+            world.count_run(&events);
+            let cleared = events.iter().any(|e| matches!(e, GameEvent::StageCleared));
+            if world.playback.is_some() {
+                if world.phase != Phase::Playing {
+                    end_playback(world, cleared);
+                    return;
+                }
+            } else if cleared {
+                record_clear(world, state.online.as_mut());
+                share_run(world, config);
+            } else if world.phase == Phase::Playing && config.autosave_interval > 0
+                && world.tick.is_multiple_of(world.secs_to_ticks(config.autosave_interval).max(1) as u64)
+            {
+                let snap = resumable_snapshot(world);
+                autosave(world, world.current_level, snap.as_ref());
+            }
+            if let Some(level) = events.iter().find_map(|e| match e {
+                GameEvent::HubDoorEntered { level } => Some(*level),
+                _ => None,
+            }) {
+                load_level(world, level, config);
+                return;
+            }
+
+            // Camera follows player (plus any Shift+arrow peek)
+            world.camera.update_peek(detect_peek(kb));
+            world.camera.follow(
+                world.player.x, world.player.y,
+                world.width, world.height,
+            );
+        }
+        Phase::Cutscene => {
+            story::tick(world);
+            // Reduced motion: no typewriter, the whole page at once
+            if config.display.reduced_motion {
+                if let Some(page) = world.story.first() {
+                    world.anim_tick = world.anim_tick.max(page.typing_ticks());
+                }
+            }
+        }
+        Phase::LevelIntro => {
+            tick_level_intro(world, config);
+            if let Some(sfx) = sound {
+                let (step, total) = calc_intro_step(world, config);
+                if step > state.prev_intro_step {
+                    sfx.play_intro_blip(step, total);
+                }
+                state.prev_intro_step = step;
+            }
         }
+        Phase::LevelReady => {
+            world.anim_tick += 1;
+            state.prev_intro_step = 0;
+        }
+        Phase::LevelOutro => {
+            tick_level_outro(world, config);
+        }
+        Phase::Dying => {
+            tick_dying(world, sound);
+        }
+        Phase::LevelSelect => {
+            world.anim_tick += 1;
+        }
+        Phase::PackSelect | Phase::PackBuilder => {
+            world.anim_tick += 1;
+        }
+        Phase::GameComplete => {
+            // Hold the count once the credits are in place
+            let rows = credits::roll(world).len();
+            if !credits::finished(world.anim_tick, rows) {
+                world.anim_tick += 1;
+            }
+        }
+        _ => {}
     }
+
+    // Global: tick message timer (works in all phases)
+    world.tick_messages();
+}
+
+/// Intro reveal progress in blip steps: (current step, total steps).
+fn calc_intro_step(world: &WorldState, config: &GameConfig) -> (usize, usize) {
+    let reveal = transition::strategy(config.display.intro_transition);
+    let total = reveal.duration(world.width, world.height).div_ceil(transition::STEP_TICKS);
+    let tick = world.anim_tick;
+    let step = if tick <= transition::NAME_CARD_TICKS {
+        0
+    } else {
+        ((tick - transition::NAME_CARD_TICKS) / transition::STEP_TICKS).min(total)
+    };
+    (step as usize, total as usize)
 }
 
 // ── Key Constants ──
@@ -212,7 +747,49 @@ const KEYS_DOWN: &[KeyCode] = &[KeyCode::Down, KeyCode::Char('s'), KeyCode::Char
 const KEYS_DIG_L: &[KeyCode] = &[KeyCode::Char('z'), KeyCode::Char('Z'), KeyCode::Char('q'), KeyCode::Char('Q')];
 const KEYS_DIG_R: &[KeyCode] = &[KeyCode::Char('x'), KeyCode::Char('X'), KeyCode::Char('e'), KeyCode::Char('E')];
 const KEYS_RESTART: &[KeyCode] = &[KeyCode::Char('r'), KeyCode::Char('R')];
+/// How long R waits for the second press that confirms a restart.
+const RESTART_CONFIRM_SECS: u32 = 2;
 const KEYS_CONFIRM: &[KeyCode] = &[KeyCode::Enter, KeyCode::Char(' ')];
+const KEYS_MINIMAP: &[KeyCode] = &[KeyCode::Char('m'), KeyCode::Char('M')];
+const KEYS_LOG: &[KeyCode] = &[KeyCode::Char('l'), KeyCode::Char('L')];
+const KEYS_EDGE_MARKERS: &[KeyCode] = &[KeyCode::Char('i'), KeyCode::Char('I')];
+const KEYS_GUARD_PATHS: &[KeyCode] = &[KeyCode::Char('g'), KeyCode::Char('G')];
+const KEYS_OVERVIEW: &[KeyCode] = &[KeyCode::Char('v'), KeyCode::Char('V')];
+const KEYS_PACK_DELETE: &[KeyCode] = &[KeyCode::Delete, KeyCode::Char('d'), KeyCode::Char('D')];
+const KEYS_PACK_RENAME: &[KeyCode] = &[KeyCode::F(2), KeyCode::Char('r'), KeyCode::Char('R')];
+const KEYS_PACK_PATH: &[KeyCode] = &[KeyCode::Char('p'), KeyCode::Char('P')];
+const KEYS_YES: &[KeyCode] = &[KeyCode::Char('y'), KeyCode::Char('Y')];
+const KEYS_NO: &[KeyCode] = &[KeyCode::Char('n'), KeyCode::Char('N')];
+const KEYS_PACK_BUILD: &[KeyCode] = &[KeyCode::Char('b'), KeyCode::Char('B')];
+/// Title screen: pick up the game suspended by quitting.
+const KEYS_RESUME: &[KeyCode] = &[KeyCode::Char('s'), KeyCode::Char('S')];
+/// Title screen: paste a run code to watch or verify.
+const KEYS_PLAY_CODE: &[KeyCode] = &[KeyCode::Char('p'), KeyCode::Char('P')];
+/// Level select and pack builder: show the highlighted level's code /
+/// paste one in.
+const KEYS_SHARE_EXPORT: &[KeyCode] = &[KeyCode::Char('c'), KeyCode::Char('C')];
+const KEYS_SHARE_IMPORT: &[KeyCode] = &[KeyCode::Char('i'), KeyCode::Char('I')];
+/// Longest text the "Play code" prompt takes (a code is ~2 chars a move).
+const MAX_CODE: usize = 16_384;
+/// Pack builder: move the highlighted level earlier / later in the pack.
+const KEYS_ORDER_EARLIER: &[KeyCode] = &[KeyCode::Char('['), KeyCode::PageUp];
+const KEYS_ORDER_LATER: &[KeyCode] = &[KeyCode::Char(']'), KeyCode::PageDown];
+/// Pack builder: edit the name, author, description.
+const KEYS_BUILDER_FIELDS: [&[KeyCode]; 3] = [
+    &[KeyCode::Char('n'), KeyCode::Char('N')],
+    &[KeyCode::Char('a'), KeyCode::Char('A')],
+    &[KeyCode::Char('e'), KeyCode::Char('E')],
+];
+const KEYS_BUILDER_WRITE: &[KeyCode] = &[KeyCode::Char('w'), KeyCode::Char('W'), KeyCode::F(2)];
+/// Longest pack file name the rename dialog takes.
+const MAX_PACK_NAME: usize = 40;
+/// Longest name a save slot can be given.
+const MAX_SLOT_NAME: usize = 20;
+/// Longest pack builder name / author / description.
+const MAX_PACK_FIELD: usize = 80;
+const KEYS_COMPACT: &[KeyCode] = &[KeyCode::Char('n'), KeyCode::Char('N')];
+/// Held with Shift, these scout the camera ahead instead of moving.
+const KEYS_PEEK: &[KeyCode] = &[KeyCode::Left, KeyCode::Right, KeyCode::Up, KeyCode::Down];
 
 fn detect_dig_press(kb: &InputState, gp: &GamepadState) -> Option<Facing> {
     if kb.any_pressed(KEYS_DIG_L) || gp.dig_left_pressed() {
@@ -225,19 +802,35 @@ fn detect_dig_press(kb: &InputState, gp: &GamepadState) -> Option<Facing> {
 }
 
 fn detect_movement(kb: &InputState, gp: &GamepadState) -> Option<MoveDir> {
-    if kb.any_held(KEYS_UP) || kb.any_pressed(KEYS_UP) || gp.up_held() {
+    if move_key(kb, KEYS_UP) || gp.up_held() {
         Some(MoveDir::Up)
-    } else if kb.any_held(KEYS_DOWN) || kb.any_pressed(KEYS_DOWN) || gp.down_held() {
+    } else if move_key(kb, KEYS_DOWN) || gp.down_held() {
         Some(MoveDir::Down)
-    } else if kb.any_held(KEYS_LEFT) || kb.any_pressed(KEYS_LEFT) || gp.left_held() {
+    } else if move_key(kb, KEYS_LEFT) || gp.left_held() {
         Some(MoveDir::Left)
-    } else if kb.any_held(KEYS_RIGHT) || kb.any_pressed(KEYS_RIGHT) || gp.right_held() {
+    } else if move_key(kb, KEYS_RIGHT) || gp.right_held() {
         Some(MoveDir::Right)
     } else {
         None
     }
 }
 
+/// Held or just pressed, leaving out Shift+arrow (that's a camera peek).
+fn move_key(kb: &InputState, keys: &[KeyCode]) -> bool {
+    keys.iter().any(|&k| {
+        (kb.is_held(k) || kb.was_pressed(k)) && !(KEYS_PEEK.contains(&k) && kb.is_held_shifted(k))
+    })
+}
+
+/// Shift+arrow peek direction as a unit (dx, dy); (0, 0) when released.
+fn detect_peek(kb: &InputState) -> (i32, i32) {
+    let held = |k: KeyCode| kb.is_held_shifted(k) as i32;
+    (
+        held(KeyCode::Right) - held(KeyCode::Left),
+        held(KeyCode::Down) - held(KeyCode::Up),
+    )
+}
+
 /// Reset to title screen, preserving config and level list.
 fn return_to_title(world: &mut WorldState) {
     let speed = world.speed.clone();
@@ -245,13 +838,27 @@ fn return_to_title(world: &mut WorldState) {
     let total = world.total_levels;
     let active_pack = std::mem::take(&mut world.active_pack);
     let active_pack_path = std::mem::take(&mut world.active_pack_path);
+    let show_minimap = world.show_minimap;
+    let show_edge_markers = world.show_edge_markers;
+    let show_guard_paths = world.show_guard_paths;
+    let compact_view = world.compact_view;
+    let (invisible_guards, mirror, randomizer) = (world.invisible_guards, world.mirror, world.randomizer);
     *world = WorldState::new();
     world.speed = speed;
     world.level_names = names;
     world.total_levels = total;
     world.active_pack = active_pack;
     world.active_pack_path = active_pack_path;
+    world.show_minimap = show_minimap;
+    world.show_edge_markers = show_edge_markers;
+    world.show_guard_paths = show_guard_paths;
+    world.compact_view = compact_view;
     world.has_save = save::has_save();
+    world.suspended = save::load_suspend().ok().map(|d| d.level);
+    world.ng_plus = save::pack_cleared(&world.active_pack_path, total);
+    world.invisible_guards = invisible_guards && world.ng_plus;
+    world.mirror = mirror;
+    world.randomizer = randomizer;
     world.paused = false;
     world.phase = Phase::Title;
 }
@@ -260,9 +867,162 @@ fn return_to_title(world: &mut WorldState) {
 fn start_new_game(world: &mut WorldState, config: &GameConfig) {
     world.score = 0;
     world.lives = 5;
+    world.run = RunStats::default();
+    load_level(world, 0, config);
+}
+
+/// Go round the pack again on the next New Game+ loop, keeping the score.
+fn start_next_loop(world: &mut WorldState, config: &GameConfig) {
+    world.ng_loop += 1;
+    world.lives = 5;
+    world.run = RunStats::default();
     load_level(world, 0, config);
 }
 
+/// Start a time attack run from level 0 with a fresh clock.
+fn start_time_attack(world: &mut WorldState, config: &GameConfig) {
+    let g = &config.gameplay;
+    world.mode = GameMode::TimeAttack;
+    world.time_attack = TimeAttack {
+        clock: world.secs_to_ticks(g.time_attack_seconds),
+        token_bonus: world.secs_to_ticks(g.time_bonus_token),
+        trap_bonus: world.secs_to_ticks(g.time_bonus_trap),
+        ..TimeAttack::default()
+    };
+    start_new_game(world, config);
+}
+
+/// Save for Continue (C). Time attack runs are never saved: they're
+/// ranked, so they can't be resumed.
+fn autosave(world: &WorldState, level: usize, snap: Option<&save::Snapshot>) {
+    if world.mode == GameMode::Normal {
+        let _ = save::save_game(level, world.score, world.lives, snap);
+    }
+}
+
+/// Quitting mid-node keeps the game for "Resume" on the next title
+/// screen. Only normal play: time attack runs are never saved.
+fn suspend(world: &WorldState) {
+    let in_play = matches!(world.phase, Phase::LevelIntro | Phase::LevelReady | Phase::Playing | Phase::Dying);
+    if in_play && world.mode == GameMode::Normal && world.playback.is_none() {
+        let snap = snapshot_if_playing(world);
+        let _ = save::save_suspend(world.current_level, world.score, world.lives, snap.as_ref());
+    }
+}
+
+/// Pick the suspended game back up. It's only ever resumed once, so
+/// it's gone as soon as it's read.
+fn resume_suspended(world: &mut WorldState, config: &GameConfig) {
+    let read = save::load_suspend();
+    save::delete_suspend();
+    world.suspended = None;
+    match read {
+        Ok(data) => load_save_data(world, &data, config),
+        Err(_) => world.push_message(Severity::Warning, t!("msg.suspend_damaged"), 60),
+    }
+}
+
+/// A finished normal run uses up the Continue save; a time attack run
+/// leaves it alone.
+fn delete_autosave(world: &WorldState) {
+    if world.mode == GameMode::Normal {
+        save::delete_save();
+    }
+}
+
+/// Put a node clear on the node's local leaderboard.
+fn record_clear(world: &mut WorldState, online: Option<&mut Online>) {
+    let score = match world.mode {
+        GameMode::Normal => Some(world.score - world.level_start_score),
+        GameMode::TimeAttack => None,
+    };
+    let time_ms = world.tick * world.speed.tick_rate_ms;
+    world.board = save::record_board(&world.active_pack_path, world.current_level, score, time_ms);
+    if let Some(online) = online {
+        online.submit(world, score, time_ms);
+    }
+}
+
+/// Save the node just cleared as a run code, if it was played straight
+/// through in normal mode.
+fn share_run(world: &mut WorldState, config: &GameConfig) {
+    if world.mode != GameMode::Normal || world.served { return; }
+    let Some(code) = run_code::from_recording(world, config) else { return };
+    match run_code::save(&code) {
+        Ok(path) => world.push_message(Severity::Info, &t!("code.saved", path = path.display()), 150),
+        Err(e) => world.push_message(Severity::Warning, &t!("code.save_failed", err = e), 90),
+    }
+}
+
+/// Keys in the title screen's "Play code" prompt: paste, then ENTER to
+/// watch the run or TAB to verify it.
+fn handle_code_prompt(world: &mut WorldState, kb: &InputState, config: &GameConfig) {
+    let Some(text) = world.code_prompt.as_mut() else { return };
+    type_into(text, kb, MAX_CODE);
+    let watch = kb.any_pressed(&[KeyCode::Enter]);
+    let check = kb.any_pressed(&[KeyCode::Tab]);
+    if kb.any_pressed(&[KeyCode::Esc]) {
+        world.code_prompt = None;
+        return;
+    }
+    if !watch && !check { return; }
+    let text = world.code_prompt.take().unwrap_or_default();
+    let result = RunCode::decode(&text).and_then(|code| {
+        if check {
+            let verdict = run_code::verify(&code, config)?;
+            world.push_message(Severity::Info, verdict_text(verdict), 150);
+        } else {
+            run_code::begin(world, &code, config)?;
+            if run_code::seed(world) != code.seed {
+                world.push_message(Severity::Warning, t!("code.other_settings"), 150);
+            }
+            world.playback = Some(Playback { code, pos: 0 });
+        }
+        Ok(())
+    });
+    match result {
+        Ok(()) => {}
+        Err(CodeError::Malformed) => world.push_message(Severity::Warning, t!("code.malformed"), 90),
+        Err(CodeError::NoPack) => world.push_message(Severity::Warning, t!("code.no_pack"), 90),
+    }
+}
+
+/// A watched run code is over: back to the title with how it went.
+fn end_playback(world: &mut WorldState, cleared: bool) {
+    let Some(playback) = world.playback.take() else { return };
+    let verdict = run_code::verdict(world, &playback.code, playback.pos, cleared);
+    return_to_title(world);
+    world.push_message(Severity::Info, verdict_text(verdict), 150);
+}
+
+fn verdict_text(verdict: Verdict) -> &'static str {
+    match verdict {
+        Verdict::Verified => t!("code.verified"),
+        Verdict::Differs => t!("code.differs"),
+        Verdict::OtherSettings => t!("code.other_settings"),
+    }
+}
+
+/// Rank a time attack run once it has ended (time up or every node cleared).
+fn record_time_attack(world: &mut WorldState) {
+    if world.mode != GameMode::TimeAttack || world.time_attack.recorded { return; }
+    if !matches!(world.phase, Phase::GameOver | Phase::GameComplete) { return; }
+    let run = save::TimeAttackRun {
+        cleared: world.time_attack.cleared,
+        secs_left: world.clock_secs(),
+    };
+    world.time_attack.rank = save::record_time_attack(&world.active_pack_path, run);
+    world.time_attack.recorded = true;
+}
+
+/// Compare a lost normal run with the pack's best runs, and keep its
+/// results among them.
+fn record_run_bests(world: &mut WorldState) {
+    if world.mode != GameMode::Normal || world.phase != Phase::GameOver || world.run_bests.is_some() { return; }
+    let run = RunBests::of(world.score, &world.run);
+    world.run_bests = Some(save::record_run_bests(&world.active_pack_path, run));
+}
+
 /// Start game from a specific level.
 fn start_from_level(world: &mut WorldState, level: usize, score: u32, lives: u32, config: &GameConfig) {
     world.score = score;
@@ -270,18 +1030,122 @@ fn start_from_level(world: &mut WorldState, level: usize, score: u32, lives: u32
     load_level(world, level, config);
 }
 
+/// Level to play after clearing the current one. Hub packs record the
+/// clear and go back to the hub; escaping the hub itself ends the pack.
+fn next_level(world: &WorldState) -> usize {
+    if !world.hub.enabled {
+        world.current_level + 1
+    } else if world.in_hub() {
+        world.total_levels
+    } else {
+        let _ = save::mark_cleared(&world.active_pack_path, world.current_level);
+        0
+    }
+}
+
 /// Capture snapshot only if currently in Playing phase.
 /// Non-playing phases return None → load will restart level from scratch.
 fn snapshot_if_playing(world: &WorldState) -> Option<save::Snapshot> {
     if world.phase == Phase::Playing {
-        Some(save::capture_snapshot(world))
+        resumable_snapshot(world)
     } else {
         None
     }
 }
 
+/// Mid-level snapshot, or None for multi-room levels: a snapshot only
+/// holds one map, so those saves restart the node instead.
+fn resumable_snapshot(world: &WorldState) -> Option<save::Snapshot> {
+    if world.is_multi_room() {
+        None
+    } else {
+        Some(save::capture_snapshot(world))
+    }
+}
+
+/// Save the game to `slot` (1–4) under `name`, once it's been asked
+/// whether to save over a game the slot holds.
+fn save_to_slot(world: &mut WorldState, slot: u8, name: String) {
+    if world.mode == GameMode::TimeAttack {
+        world.push_message(Severity::Warning, t!("msg.no_save_time_attack"), 40);
+        return;
+    }
+    if world.served {
+        world.push_message(Severity::Warning, t!("msg.no_save_served"), 40);
+        return;
+    }
+    match held_slot(slot) {
+        Some(held) => world.pause_menu.overwrite = Some(OverwriteSlot { slot, held, name }),
+        None => write_slot(world, slot, &name),
+    }
+}
+
+/// What `slot` (1–4, or 0 for the Continue save) holds, if it holds a game.
+fn held_slot(slot: u8) -> Option<SlotInfo> {
+    let read = if slot == 0 { save::load_save() } else { save::load_slot(slot) };
+    read.ok().map(|data| SlotInfo { name: data.name, level: data.level, score: data.score })
+}
+
+/// Read what every slot holds, for the pause menu (in its order: the
+/// Continue save last).
+fn refresh_slots(world: &mut WorldState) {
+    for (i, slot) in [1, 2, 3, 4, 0].into_iter().enumerate() {
+        world.pause_menu.slots[i] = held_slot(slot);
+    }
+}
+
+/// Write the game to `slot` (1–4) under `name`, mid-level if it can be
+/// resumed there.
+fn write_slot(world: &mut WorldState, slot: u8, name: &str) {
+    let snap = resumable_snapshot(world);
+    let level = world.current_level;
+    let saved = save::save_slot(slot, name, level, world.score, world.lives, snap.as_ref());
+    refresh_slots(world);
+    match saved {
+        Ok(_) if snap.is_none() => world.push_message(
+            Severity::Info, &t!("msg.saved_restart", slot = slot, n = level + 1), 40,
+        ),
+        Ok(_) => world.push_message(
+            Severity::Info, &t!("msg.saved_mid_game", slot = slot, n = level + 1), 40,
+        ),
+        Err(_) => world.push_message(Severity::Warning, t!("msg.save_failed"), 40),
+    }
+}
+
+/// Load the game from `slot` (1–4, or 0 for the Continue save) and play
+/// on from it.
+fn load_from_slot(world: &mut WorldState, slot: u8, offer: Option<u8>, config: &GameConfig) {
+    if let Some(data) = read_slot(world, slot, offer) {
+        world.paused = false;
+        load_save_data(world, &data, config);
+        if slot > 0 {
+            world.push_message(Severity::Info, &t!("msg.loaded", slot = slot), 40);
+        }
+    }
+}
+
+/// Restart the level from the pause menu, and play.
+fn restart_from_pause(world: &mut WorldState) {
+    if !matches!(world.phase, Phase::Playing | Phase::LevelReady) { return; }
+    world.paused = false;
+    world.clear_messages();
+    step::restart_level(world);
+    world.phase = Phase::Playing;
+    world.push_message(Severity::Info, t!("msg.restarted"), 30);
+}
+
+/// Leave the paused game for the title, saving where it was for Continue.
+fn quit_from_pause(world: &mut WorldState) {
+    let snap = resumable_snapshot(world);
+    world.paused = false;
+    autosave(world, world.current_level, snap.as_ref());
+    return_to_title(world);
+}
+
 /// Load from SaveData: restore snapshot if present, otherwise start level fresh.
 fn load_save_data(world: &mut WorldState, data: &save::SaveData, config: &GameConfig) {
+    // Saves are only ever made in normal mode
+    world.mode = GameMode::Normal;
     world.score = data.score;
     world.lives = data.lives;
     load_level(world, data.level, config);
@@ -289,11 +1153,51 @@ fn load_save_data(world: &mut WorldState, data: &save::SaveData, config: &GameCo
     if let Some(ref snap) = data.snapshot {
         // Restore mid-game state on top of the freshly loaded level
         save::restore_snapshot(world, snap);
+        world.story.clear();
         world.phase = Phase::Playing;
     }
     // If no snapshot, load_level already set Phase::LevelIntro → normal start
 }
 
+/// Read save `slot` (0 = the Continue save), or its backup if the key
+/// just before this found it damaged and offered that. Why nothing
+/// could be read goes in the messages.
+fn read_slot(world: &mut WorldState, slot: u8, offer: Option<u8>) -> Option<save::SaveData> {
+    let backup = offer == Some(slot);
+    let read = match (slot, backup) {
+        (0, false) => save::load_save(),
+        (0, true) => save::load_save_backup(),
+        (_, false) => save::load_slot(slot),
+        (_, true) => save::load_slot_backup(slot),
+    };
+    let warn = |world: &mut WorldState, text: &str| world.push_message(Severity::Warning, text, 60);
+    match read {
+        Ok(data) => {
+            if backup {
+                world.log(Severity::Info, t!("msg.backup_loaded"));
+            }
+            return Some(data);
+        }
+        Err(LoadError::Missing) if slot == 0 => {}
+        Err(LoadError::Missing) => warn(world, &t!("msg.slot_empty", slot = slot)),
+        Err(LoadError::Corrupt { backup: true }) if !backup => {
+            world.backup_offer = Some(slot);
+            match slot {
+                0 => warn(world, t!("msg.autosave_damaged_backup")),
+                _ => warn(world, &t!("msg.save_damaged_backup", slot = slot)),
+            }
+        }
+        Err(LoadError::Corrupt { .. }) => match slot {
+            0 => warn(world, t!("msg.autosave_damaged")),
+            _ => warn(world, &t!("msg.save_damaged", slot = slot)),
+        },        Err(LoadError::Newer) => match slot {
+            0 => warn(world, t!("msg.autosave_newer")),
+            _ => warn(world, &t!("msg.save_newer", slot = slot)),
+        },
+    }
+    None
+}
+
 /// Open the pack select screen (F3 filer).
 fn open_pack_select(world: &mut WorldState, config: &GameConfig) {
     world.pack_list = scan_packs(config);
@@ -302,13 +1206,261 @@ fn open_pack_select(world: &mut WorldState, config: &GameConfig) {
         .position(|p| p.path == world.active_pack_path)
         .unwrap_or(0);
     world.pack_scroll = 0;
+    world.pack_dialog = None;
     world.phase = Phase::PackSelect;
     world.anim_tick = 0;
 }
 
+/// Keys while a pack selector dialog is open.
+fn handle_pack_dialog(world: &mut WorldState, kb: &InputState, gp: &GamepadState, config: &GameConfig) {
+    let Some(pack) = world.pack_list.get(world.pack_cursor).cloned() else {
+        world.pack_dialog = None;
+        return;
+    };
+    let confirm = kb.any_pressed(&[KeyCode::Enter]) || gp.confirm_pressed();
+    let cancel = kb.any_pressed(&[KeyCode::Esc]) || gp.cancel_pressed();
+    let was_active = pack.path == world.active_pack_path;
+
+    match world.pack_dialog.as_mut() {
+        Some(PackDialog::Delete) => {
+            if confirm || kb.any_pressed(KEYS_YES) {
+                world.pack_dialog = None;
+                match delete_pack(&pack) {
+                    Ok(()) => {
+                        rescan_packs(world, config, None);
+                        if was_active {
+                            let builtin = world.pack_list[0].clone();
+                            switch_pack(world, &builtin, config);
+                        }
+                        world.push_message(Severity::Info, &t!("msg.pack_deleted", pack = pack.name), 60);
+                    }
+                    Err(e) => world.push_message(Severity::Warning, &t!("msg.pack_error", error = e), 90),
+                }
+            } else if cancel || kb.any_pressed(KEYS_NO) {
+                world.pack_dialog = None;
+            }
+        }
+        Some(PackDialog::Rename { name }) => {
+            type_into(name, kb, MAX_PACK_NAME);
+            if confirm {
+                let name = name.clone();
+                world.pack_dialog = None;
+                match rename_pack(&pack, &name) {
+                    Ok(path) => {
+                        let path = path.to_string_lossy().to_string();
+                        rescan_packs(world, config, Some(&path));
+                        if was_active {
+                            if let Some(renamed) = world.pack_list.iter().find(|p| p.path == path).cloned() {
+                                switch_pack(world, &renamed, config);
+                            }
+                        }
+                        world.push_message(Severity::Info, &t!("msg.pack_renamed", name = name), 60);
+                    }
+                    Err(e) => world.push_message(Severity::Warning, &t!("msg.pack_error", error = e), 90),
+                }
+            } else if cancel {
+                world.pack_dialog = None;
+            }
+        }
+        Some(PackDialog::Path { .. }) if confirm || cancel => world.pack_dialog = None,
+        Some(PackDialog::Path { .. }) | None => {}
+    }
+}
+
+/// Apply this frame's typing (characters, Backspace) to a text field of
+/// at most `max` characters.
+fn type_into(text: &mut String, kb: &InputState, max: usize) {
+    for key in &kb.raw_events {
+        if key.kind == KeyEventKind::Release { continue; }
+        match key.code {
+            KeyCode::Char(c) if !key.modifiers.contains(KeyModifiers::CONTROL)
+                && text.chars().count() < max => text.push(c),
+            KeyCode::Backspace => { text.pop(); }
+            _ => {}
+        }
+    }
+}
+
+/// Open the pack builder on the `levels/` files, if there are any.
+fn open_pack_builder(world: &mut WorldState, config: &GameConfig) {
+    let files = level_files(config);
+    if files.is_empty() {
+        let dir = config.levels_dir.display().to_string();
+        world.push_message(Severity::Warning, &t!("msg.builder_no_files", dir = dir), 90);
+        return;
+    }
+    world.builder = PackBuilder { files, ..PackBuilder::default() };
+    world.phase = Phase::PackBuilder;
+    world.anim_tick = 0;
+}
+
+/// Keys in the pack builder.
+fn handle_pack_builder(world: &mut WorldState, kb: &InputState, gp: &GamepadState, config: &GameConfig) {
+    let confirm = kb.any_pressed(&[KeyCode::Enter]) || gp.confirm_pressed();
+    let esc = kb.any_pressed(&[KeyCode::Esc]) || gp.cancel_pressed();
+    if world.share.is_some() {
+        handle_share_dialog(world, kb, config);
+        return;
+    }
+    if kb.any_pressed(KEYS_SHARE_EXPORT) && world.builder.editing.is_none() {
+        let Some(file) = world.builder.files.get(world.builder.cursor) else { return };
+        let def = std::fs::read_to_string(config.levels_dir.join(&file.file)).ok()
+            .and_then(|text| level::parse_level_file(&text));
+        match def {
+            Some(def) => share_level(world, &def),
+            None => world.push_message(Severity::Warning, t!("share.unreadable"), 60),
+        }
+        return;
+    }
+    if kb.any_pressed(KEYS_SHARE_IMPORT) && world.builder.editing.is_none() {
+        world.share = Some(ShareDialog::Import { text: String::new() });
+        return;
+    }
+    let b = &mut world.builder;
+
+    // Typing into a metadata field: ENTER / ESC finish it
+    if let Some(field) = b.editing {
+        if confirm || esc {
+            b.editing = None;
+        } else {
+            type_into(&mut b.fields[field], kb, MAX_PACK_FIELD);
+        }
+        return;
+    }
+
+    let total = b.files.len();
+    let visible = 12_usize;
+    let picked = b.order.iter().position(|&i| i == b.cursor);
+    if kb.any_pressed(&[KeyCode::Up]) || gp.up_held() {
+        b.cursor = b.cursor.saturating_sub(1);
+        b.scroll = b.scroll.min(b.cursor);
+    } else if kb.any_pressed(&[KeyCode::Down]) || gp.down_held() {
+        if b.cursor + 1 < total {
+            b.cursor += 1;
+            if b.cursor >= b.scroll + visible {
+                b.scroll = b.cursor - visible + 1;
+            }
+        }
+    } else if kb.any_pressed(&[KeyCode::Char(' ')]) || gp.confirm_pressed() {
+        // Pick (to the end of the pack) or drop the highlighted level
+        match picked {
+            Some(at) => { b.order.remove(at); }
+            None => b.order.push(b.cursor),
+        }
+        b.confirm_write = false;
+    } else if kb.any_pressed(KEYS_ORDER_EARLIER) {
+        if let Some(at) = picked.filter(|&at| at > 0) {
+            b.order.swap(at, at - 1);
+        }
+    } else if kb.any_pressed(KEYS_ORDER_LATER) {
+        if let Some(at) = picked.filter(|&at| at + 1 < b.order.len()) {
+            b.order.swap(at, at + 1);
+        }
+    } else if let Some(field) = KEYS_BUILDER_FIELDS.iter().position(|keys| kb.any_pressed(keys)) {
+        b.editing = Some(field);
+    } else if kb.any_pressed(KEYS_BUILDER_WRITE) {
+        check_solvable(config, b);
+        if b.fields[0].trim().is_empty() {
+            world.push_message(Severity::Warning, t!("msg.builder_no_name"), 60);
+        } else if b.order.is_empty() {
+            world.push_message(Severity::Warning, t!("msg.builder_no_levels"), 60);
+        } else if !b.confirm_write && b.order.iter().any(|&i| !b.files[i].warnings.is_empty()) {
+            // Levels with problems: say so, and write on the next W
+            let n = b.order.iter().filter(|&&i| !b.files[i].warnings.is_empty()).count();
+            b.confirm_write = true;
+            world.push_message(Severity::Warning, &t!("msg.builder_warnings", n = n), 120);
+        } else {
+            match write_pack(config, b) {
+                Ok(path) => {
+                    let name = b.fields[0].trim().to_string();
+                    open_pack_select(world, config);
+                    let path = path.to_string_lossy().to_string();
+                    rescan_packs(world, config, Some(&path));
+                    world.push_message(Severity::Achievement, &t!("msg.builder_written", pack = name), 90);
+                }
+                Err(e) => world.push_message(Severity::Warning, &t!("msg.pack_error", error = e), 90),
+            }
+        }
+    } else if esc {
+        open_pack_select(world, config);
+    }
+}
+
+/// Show `def`'s level code, and offer it to the clipboard.
+fn share_level(world: &mut WorldState, def: &level::LevelDef) {
+    let code = level_code::encode(&level::share_text(def));
+    world.clipboard = Some(code.clone());
+    world.share = Some(ShareDialog::Export { name: def.name.clone(), code });
+}
+
+/// Keys in a level code dialog: ESC closes; while importing, paste and
+/// ENTER writes the level into `levels/`.
+fn handle_share_dialog(world: &mut WorldState, kb: &InputState, config: &GameConfig) {
+    if kb.any_pressed(&[KeyCode::Esc]) {
+        world.share = None;
+        return;
+    }
+    let Some(ShareDialog::Import { text }) = world.share.as_mut() else { return };
+    type_into(text, kb, MAX_CODE);
+    if !kb.any_pressed(&[KeyCode::Enter]) { return; }
+    let Some(level) = level_code::decode(text) else {
+        world.push_message(Severity::Warning, t!("share.malformed"), 90);
+        return;
+    };
+    world.share = None;
+    let file = match level::import_level(config, &level) {
+        Ok(file) => file,
+        Err(e) => {
+            world.push_message(Severity::Warning, &t!("share.import_failed", err = e), 90);
+            return;
+        }
+    };
+    // Show it where it now is: in the builder's list, or the levels/ pack
+    if world.phase == Phase::PackBuilder {
+        let b = &mut world.builder;
+        let picked: Vec<String> = b.order.iter().map(|&i| b.files[i].file.clone()).collect();
+        b.files = level_files(config);
+        b.order = picked.iter().filter_map(|f| b.files.iter().position(|x| x.file == *f)).collect();
+        b.cursor = b.files.iter().position(|x| x.file == file).unwrap_or(0);
+        b.scroll = b.cursor.saturating_sub(12 - 1);
+    } else if let Some(pack) = scan_packs(config).into_iter().find(|p| p.path == "__levels__") {
+        switch_pack(world, &pack, config);
+        // The levels/ pack is its files in name order, as level_files lists them
+        world.select_cursor = level_files(config).iter().position(|f| f.file == file).unwrap_or(0);
+        world.select_scroll = world.select_cursor.saturating_sub(16 - 1);
+    }
+    world.push_message(Severity::Info, &t!("share.imported", file = file), 120);
+}
+
+/// Re-read the pack list after a change on disk, keeping the cursor on
+/// `select` if given (else where it was).
+fn rescan_packs(world: &mut WorldState, config: &GameConfig, select: Option<&str>) {
+    world.pack_list = scan_packs(config);
+    let last = world.pack_list.len().saturating_sub(1);
+    world.pack_cursor = select
+        .and_then(|path| world.pack_list.iter().position(|p| p.path == path))
+        .unwrap_or(world.pack_cursor)
+        .min(last);
+    world.pack_scroll = world.pack_scroll.min(world.pack_cursor);
+    let visible = 12_usize;
+    if world.pack_cursor >= world.pack_scroll + visible {
+        world.pack_scroll = world.pack_cursor - visible + 1;
+    }
+    world.pack_preview = None;
+}
+
+/// Load the highlighted pack's preview, unless it's already shown.
+fn refresh_pack_preview(world: &mut WorldState, config: &GameConfig) {
+    let Some(pack) = world.pack_list.get(world.pack_cursor) else { return };
+    if world.pack_preview.as_ref().is_some_and(|p| p.path == pack.path) { return; }
+    world.pack_preview = Some(pack_preview(&pack.path, config));
+}
+
 fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputState, gp: &GamepadState, config: &GameConfig) -> bool {
     let confirm = kb.any_pressed(KEYS_CONFIRM) || gp.confirm_pressed();
     let esc = kb.any_pressed(&[KeyCode::Esc]) || gp.cancel_pressed();
+    // A damaged save's backup is offered to the very next key only
+    let offer = if kb.any_key_pressed() { world.backup_offer.take() } else { None };
 
     // ── F-key handling (works in Playing, Paused, LevelReady, LevelIntro) ──
     let in_game = matches!(world.phase,
@@ -316,66 +1468,149 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         | Phase::LevelOutro | Phase::LevelComplete
     );
 
+    // Watching a run code: ESC leaves, and the rest is the code's
+    if world.playback.is_some() && in_game {
+        if esc {
+            world.playback = None;
+            return_to_title(world);
+        } else if world.phase == Phase::LevelReady {
+            world.phase = Phase::Playing;
+            world.clear_messages();
+        }
+        return false;
+    }
+
     if in_game || world.paused {
         // F1: Pause / Resume
         if kb.any_pressed(&[KeyCode::F(1)]) {
             world.paused = !world.paused;
+            world.auto_paused = false;
             if world.paused {
-                world.set_message("PAUSED  [F1] Resume", 0);
+                world.log_open = false;
+                world.pause_menu.open();
+                refresh_slots(world);
+                world.push_message(Severity::Info, t!("msg.paused"), 0);
             } else {
-                world.message.clear();
-                world.message_timer = 0;
+                world.clear_messages();
             }
             return false;
         }
 
-        // While paused: F1/F3/F5-F12 respond
+        // While paused: the menu, and F1/F3/F5-F12 and L as shortcuts
         if world.paused {
+            // Event log open: it takes the arrows and ESC until closed
+            if world.log_open {
+                let last = world.log.len().saturating_sub(1);
+                if kb.any_pressed(KEYS_LOG) || kb.any_pressed(&[KeyCode::Esc]) || gp.cancel_pressed() {
+                    world.log_open = false;
+                } else if kb.any_pressed(&[KeyCode::Up]) || gp.up_held() {
+                    world.log_scroll = (world.log_scroll + 1).min(last);
+                } else if kb.any_pressed(&[KeyCode::Down]) || gp.down_held() {
+                    world.log_scroll = world.log_scroll.saturating_sub(1);
+                } else if kb.any_pressed(&[KeyCode::PageUp]) {
+                    world.log_scroll = (world.log_scroll + 10).min(last);
+                } else if kb.any_pressed(&[KeyCode::PageDown]) {
+                    world.log_scroll = world.log_scroll.saturating_sub(10);
+                }
+                return false;
+            }
+            if kb.any_pressed(KEYS_LOG) {
+                world.log_open = true;
+                world.log_scroll = 0;
+                return false;
+            }
             // F3: Pack select (works while paused)
             if kb.any_pressed(&[KeyCode::F(3)]) {
-                let snap = save::capture_snapshot(world);
+                let snap = resumable_snapshot(world);
                 world.paused = false;
-                let _ = save::save_game(world.current_level, world.score, world.lives, Some(&snap));
+                autosave(world, world.current_level, snap.as_ref());
                 open_pack_select(world, config);
                 return false;
             }
             // F5-F8: Save to slot (works while paused — snapshot captured)
             for slot in 1..=4u8 {
-                let fkey = KeyCode::F(slot + 4);
-                if kb.any_pressed(&[fkey]) {
-                    let snap = save::capture_snapshot(world);
-                    let level = world.current_level;
-                    match save::save_slot(slot, level, world.score, world.lives, Some(&snap)) {
-                        Ok(_) => world.set_message(
-                            &format!("Mid-game Saved Slot {} (Node {})", slot, level + 1), 40,
-                        ),
-                        Err(_) => world.set_message("Save failed!", 40),
-                    }
+                if kb.any_pressed(&[KeyCode::F(slot + 4)]) {
+                    save_to_slot(world, slot, String::new());
                     return false;
                 }
             }
             // F9-F12: Load from slot (works while paused)
             for slot in 1..=4u8 {
-                let fkey = KeyCode::F(slot + 8);
-                if kb.any_pressed(&[fkey]) {
-                    if let Some(data) = save::load_slot(slot) {
-                        world.paused = false;
-                        load_save_data(world, &data, config);
-                        world.set_message(&format!("Loaded Slot {}", slot), 40);
-                    } else {
-                        world.set_message(&format!("Slot {} is empty", slot), 40);
-                    }
+                if kb.any_pressed(&[KeyCode::F(slot + 8)]) {
+                    load_from_slot(world, slot, offer, config);
                     return false;
                 }
             }
-            // ESC while paused: save snapshot and return to title
-            if kb.any_pressed(&[KeyCode::Esc]) || gp.cancel_pressed() {
-                let snap = save::capture_snapshot(world);
-                world.paused = false;
-                let _ = save::save_game(world.current_level, world.score, world.lives, Some(&snap));
-                return_to_title(world);
+            // Naming a save: typed in, ENTER saves under it
+            if let Some(name) = world.pause_menu.naming.as_mut() {
+                if kb.any_pressed(&[KeyCode::Enter]) || gp.confirm_pressed() {
+                    let name = name.trim().to_string();
+                    world.pause_menu.naming = None;
+                    save_to_slot(world, world.pause_menu.slot_number(), name);
+                } else if esc {
+                    world.pause_menu.naming = None;
+                } else {
+                    type_into(name, kb, MAX_SLOT_NAME);
+                }
                 return false;
             }
+            // Saving over a slot that holds a game: Y / N first
+            if let Some(over) = world.pause_menu.overwrite.clone() {
+                if confirm || kb.any_pressed(KEYS_YES) {
+                    world.pause_menu.overwrite = None;
+                    write_slot(world, over.slot, &over.name);
+                } else if esc || kb.any_pressed(KEYS_NO) {
+                    world.pause_menu.overwrite = None;
+                }
+                return false;
+            }
+            // The menu: arrows pick, Left/Right pick the slot, ENTER does it
+            let menu = &mut world.pause_menu;
+            if kb.any_pressed(&[KeyCode::Up]) || gp.up_held() {
+                menu.move_cursor(-1);
+            } else if kb.any_pressed(&[KeyCode::Down]) || gp.down_held() {
+                menu.move_cursor(1);
+            } else if menu.settings {
+                if esc {
+                    menu.toggle_settings();
+                } else if confirm || kb.any_pressed(&[KeyCode::Left, KeyCode::Right]) || gp.left_held() || gp.right_held() {
+                    match menu.setting() {
+                        Some(PauseSetting::Minimap) => world.show_minimap = !world.show_minimap,
+                        Some(PauseSetting::EdgeMarkers) => world.show_edge_markers = !world.show_edge_markers,
+                        Some(PauseSetting::GuardPaths) => world.show_guard_paths = !world.show_guard_paths,
+                        Some(PauseSetting::CompactView) => world.compact_view = !world.compact_view,
+                        Some(PauseSetting::Back) if confirm => menu.toggle_settings(),
+                        _ => {}
+                    }
+                }
+            } else if kb.any_pressed(&[KeyCode::Left]) || gp.left_held() {
+                menu.move_slot(-1);
+            } else if kb.any_pressed(&[KeyCode::Right]) || gp.right_held() {
+                menu.move_slot(1);
+            } else if confirm {
+                let slot = menu.slot_number();
+                match menu.item() {
+                    Some(PauseItem::Resume) => {
+                        world.paused = false;
+                        world.clear_messages();
+                    }
+                    Some(PauseItem::Restart) => restart_from_pause(world),
+                    Some(PauseItem::Settings) => menu.toggle_settings(),
+                    Some(PauseItem::Save) if slot == 0 => {
+                        world.push_message(Severity::Warning, t!("msg.autosave_slot"), 40);
+                    }
+                    Some(PauseItem::Save) => {
+                        // Named as it's saved, starting from the name it has
+                        menu.naming = Some(menu.slot_info().map_or(String::new(), |s| s.name.clone()));
+                    }
+                    Some(PauseItem::Load) => load_from_slot(world, slot, offer, config),
+                    Some(PauseItem::Quit) => quit_from_pause(world),
+                    None => {}
+                }
+            } else if esc {
+                // ESC while paused: save snapshot and return to title
+                quit_from_pause(world);
+            }
             return false; // Block all other input while paused
         }
 
@@ -384,7 +1619,7 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
             if world.phase == Phase::Playing || world.phase == Phase::LevelReady {
                 step::restart_level(world);
                 world.phase = Phase::Playing;
-                world.set_message("Level Restarted", 30);
+                world.push_message(Severity::Info, t!("msg.restarted"), 30);
             }
             return false;
         }
@@ -392,15 +1627,19 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         // F3: Pack select
         if kb.any_pressed(&[KeyCode::F(3)]) {
             let snap = snapshot_if_playing(world);
-            let _ = save::save_game(world.current_level, world.score, world.lives, snap.as_ref());
+            autosave(world, world.current_level, snap.as_ref());
             open_pack_select(world, config);
             return false;
         }
 
         // F4: Change Level (go to level select)
         if kb.any_pressed(&[KeyCode::F(4)]) {
+            if world.mode == GameMode::TimeAttack {
+                world.push_message(Severity::Warning, t!("msg.no_select_time_attack"), 40);
+                return false;
+            }
             let snap = snapshot_if_playing(world);
-            let _ = save::save_game(world.current_level, world.score, world.lives, snap.as_ref());
+            autosave(world, world.current_level, snap.as_ref());
             world.phase = Phase::LevelSelect;
             world.paused = false;
             world.select_cursor = world.current_level;
@@ -418,18 +1657,34 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         for slot in 1..=4u8 {
             let fkey = KeyCode::F(slot + 4); // F5=slot1, F6=slot2, F7=slot3, F8=slot4
             if kb.any_pressed(&[fkey]) {
+                if world.mode == GameMode::TimeAttack {
+                    world.push_message(Severity::Warning, t!("msg.no_save_time_attack"), 40);
+                    return false;
+                }
+                if world.served {
+                    world.push_message(Severity::Warning, t!("msg.no_save_served"), 40);
+                    return false;
+                }
+                // A slot holding a game is asked about over the paused game
+                if let Some(held) = held_slot(slot) {
+                    world.paused = true;
+                    world.auto_paused = false;
+                    world.log_open = false;
+                    world.pause_menu.open();
+                    refresh_slots(world);
+                    world.pause_menu.overwrite = Some(OverwriteSlot { slot, held, name: String::new() });
+                    return false;
+                }
                 let level = world.current_level;
                 let score = world.score;
                 let lives = world.lives;
                 let snap = snapshot_if_playing(world);
-                match save::save_slot(slot, level, score, lives, snap.as_ref()) {
+                match save::save_slot(slot, "", level, score, lives, snap.as_ref()) {
                     Ok(_) => {
-                        let kind = if snap.is_some() { "Mid-game" } else { "Level" };
-                        world.set_message(
-                            &format!("{} Saved Slot {} (Node {})", kind, slot, level + 1), 40,
-                        );
+                        let key = if snap.is_some() { "msg.saved_mid_game" } else { "msg.saved_level" };
+                        world.push_message(Severity::Info, &t!(key, slot = slot, n = level + 1), 40);
                     }
-                    Err(_) => world.set_message("Save failed!", 40),
+                    Err(_) => world.push_message(Severity::Warning, t!("msg.save_failed"), 40),
                 }
                 return false;
             }
@@ -439,13 +1694,11 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         for slot in 1..=4u8 {
             let fkey = KeyCode::F(slot + 8); // F9=slot1, F10=slot2, F11=slot3, F12=slot4
             if kb.any_pressed(&[fkey]) {
-                if let Some(data) = save::load_slot(slot) {
+                if let Some(data) = read_slot(world, slot, offer) {
                     let has_snap = data.snapshot.is_some();
                     load_save_data(world, &data, config);
-                    let kind = if has_snap { "Resumed" } else { "Loaded" };
-                    world.set_message(&format!("{} Slot {}", kind, slot), 40);
-                } else {
-                    world.set_message(&format!("Slot {} is empty", slot), 40);
+                    let key = if has_snap { "msg.resumed" } else { "msg.loaded" };
+                    world.push_message(Severity::Info, &t!(key, slot = slot), 40);
                 }
                 return false;
             }
@@ -455,12 +1708,29 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
     match world.phase {
         // ── Title Screen ──
         Phase::Title => {
+            if world.code_prompt.is_some() {
+                handle_code_prompt(world, kb, config);
+                return false;
+            }
             if confirm {
                 start_new_game(world, config);
             } else if kb.any_pressed(&[KeyCode::Char('c'), KeyCode::Char('C')]) {
-                if let Some(data) = save::load_save() {
+                if let Some(data) = read_slot(world, 0, offer) {
                     load_save_data(world, &data, config);
                 }
+            } else if world.suspended.is_some() && kb.any_pressed(KEYS_RESUME) {
+                resume_suspended(world, config);
+            } else if kb.any_pressed(&[KeyCode::Char('t'), KeyCode::Char('T')]) {
+                start_time_attack(world, config);
+            } else if kb.any_pressed(&[KeyCode::Char('m'), KeyCode::Char('M')]) {
+                world.mirror = !world.mirror;
+            } else if kb.any_pressed(&[KeyCode::Char('r'), KeyCode::Char('R')]) {
+                world.randomizer = match world.randomizer {
+                    Some(_) => None,
+                    None => Some(Randomizer::from_clock()),
+                };
+            } else if world.ng_plus && kb.any_pressed(&[KeyCode::Char('n'), KeyCode::Char('N')]) {
+                world.invisible_guards = !world.invisible_guards;
             } else if kb.any_pressed(&[KeyCode::Char('l'), KeyCode::Char('L')]) {
                 world.phase = Phase::LevelSelect;
                 world.select_cursor = 0;
@@ -468,6 +1738,8 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                 world.anim_tick = 0;
             } else if kb.any_pressed(&[KeyCode::F(3)]) {
                 open_pack_select(world, config);
+            } else if kb.any_pressed(KEYS_PLAY_CODE) {
+                world.code_prompt = Some(String::new());
             } else if kb.any_pressed(&[KeyCode::Char('q'), KeyCode::Char('Q')]) || esc {
                 return true;
             }
@@ -475,11 +1747,9 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
             for slot in 1..=4u8 {
                 let fkey = KeyCode::F(slot + 8);
                 if kb.any_pressed(&[fkey]) {
-                    if let Some(data) = save::load_slot(slot) {
+                    if let Some(data) = read_slot(world, slot, offer) {
                         load_save_data(world, &data, config);
-                        world.set_message(&format!("Loaded Slot {}", slot), 40);
-                    } else {
-                        world.set_message(&format!("Slot {} is empty", slot), 40);
+                        world.push_message(Severity::Info, &t!("msg.loaded", slot = slot), 40);
                     }
                     return false;
                 }
@@ -488,6 +1758,10 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
 
         // ── Level Select ──
         Phase::LevelSelect => {
+            if world.share.is_some() {
+                handle_share_dialog(world, kb, config);
+                return false;
+            }
             let total = world.total_levels;
             if total == 0 {
                 return_to_title(world);
@@ -522,6 +1796,12 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                 }
             } else if confirm {
                 start_from_level(world, world.select_cursor, 0, 5, config);
+            } else if kb.any_pressed(KEYS_SHARE_EXPORT) {
+                if let Some(def) = level::active_level(world, world.select_cursor, config) {
+                    share_level(world, &def);
+                }
+            } else if kb.any_pressed(KEYS_SHARE_IMPORT) && !world.served {
+                world.share = Some(ShareDialog::Import { text: String::new() });
             } else if kb.any_pressed(&[KeyCode::F(3)]) {
                 open_pack_select(world, config);
             } else if esc {
@@ -536,6 +1816,16 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                 return_to_title(world);
                 return false;
             }
+            if world.pack_dialog.is_some() {
+                handle_pack_dialog(world, kb, gp, config);
+                return false;
+            }
+            if kb.any_pressed(KEYS_PACK_BUILD) && !world.served {
+                open_pack_builder(world, config);
+                return false;
+            }
+            let pack = world.pack_list[world.pack_cursor].clone();
+            let managed = is_pack_file(&pack) && !world.served;
 
             if kb.any_pressed(&[KeyCode::Up]) || gp.up_held() {
                 if world.pack_cursor > 0 {
@@ -552,16 +1842,40 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                         world.pack_scroll = world.pack_cursor - visible + 1;
                     }
                 }
+            } else if managed && kb.any_pressed(KEYS_PACK_DELETE) {
+                world.pack_dialog = Some(PackDialog::Delete);
+            } else if managed && kb.any_pressed(KEYS_PACK_RENAME) {
+                let stem = std::path::Path::new(&pack.path).file_stem()
+                    .unwrap_or_default().to_string_lossy().to_string();
+                world.pack_dialog = Some(PackDialog::Rename { name: stem });
+            } else if managed && kb.any_pressed(KEYS_PACK_PATH) {
+                let path = std::fs::canonicalize(&pack.path)
+                    .map_or(pack.path.clone(), |p| p.to_string_lossy().to_string());
+                world.pack_dialog = Some(PackDialog::Path { path });
             } else if confirm {
                 // Switch to selected pack
-                let pack = world.pack_list[world.pack_cursor].clone();
                 switch_pack(world, &pack, config);
                 let pack_name = pack.name.clone();
                 return_to_title(world);
-                world.set_message(&format!("Pack: {}", pack_name), 60);
+                world.push_message(Severity::Info, &t!("msg.pack", pack = pack_name), 60);
             } else if esc {
                 return_to_title(world);
             }
+            if world.phase == Phase::PackSelect {
+                refresh_pack_preview(world, config);
+            }
+        }
+
+        // ── Pack builder ──
+        Phase::PackBuilder => handle_pack_builder(world, kb, gp, config),
+
+        // ── Story pages ──
+        Phase::Cutscene => {
+            if confirm {
+                story::confirm(world);
+            } else if esc {
+                story::skip(world);
+            }
         }
 
         // ── Level Intro ──
@@ -570,7 +1884,7 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                 world.phase = Phase::LevelReady;
                 world.anim_tick = 0;
             } else if esc {
-                let _ = save::save_game(world.current_level, world.score, world.lives, None);
+                autosave(world, world.current_level, None);
                 return_to_title(world);
             }
         }
@@ -589,31 +1903,61 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
                 || gp.confirm_pressed();
             if any_key {
                 world.phase = Phase::Playing;
-                world.message.clear();
-                world.message_timer = 0;
+                world.clear_messages();
             } else if esc {
-                let _ = save::save_game(world.current_level, world.score, world.lives, None);
+                autosave(world, world.current_level, None);
                 return_to_title(world);
             }
         }
 
         // ── Playing ──
         Phase::Playing => {
+            // Overview open: V / ESC close it, everything else waits
+            if world.show_overview {
+                if esc || kb.any_pressed(KEYS_OVERVIEW) {
+                    world.show_overview = false;
+                }
+                return false;
+            }
+            if kb.any_pressed(KEYS_OVERVIEW) {
+                world.show_overview = true;
+                return false;
+            }
             if esc {
-                let snap = save::capture_snapshot(world);
-                let _ = save::save_game(world.current_level, world.score, world.lives, Some(&snap));
+                let snap = resumable_snapshot(world);
+                autosave(world, world.current_level, snap.as_ref());
                 return_to_title(world);
             }
+            // A stray R mustn't throw the attempt away: it asks, and a
+            // second press while it's asking restarts
             if kb.any_pressed(KEYS_RESTART) || gp.restart_pressed() {
-                step::restart_level(world);
+                if world.restart_confirm > 0 {
+                    step::restart_level(world);
+                    world.push_message(Severity::Info, t!("msg.restarted"), 30);
+                } else {
+                    world.restart_confirm = world.secs_to_ticks(RESTART_CONFIRM_SECS);
+                    world.push_message(Severity::Warning, t!("msg.restart_confirm"), world.restart_confirm);
+                }
+            }
+            if kb.any_pressed(KEYS_MINIMAP) {
+                world.show_minimap = !world.show_minimap;
+            }
+            if kb.any_pressed(KEYS_EDGE_MARKERS) {
+                world.show_edge_markers = !world.show_edge_markers;
+            }
+            if kb.any_pressed(KEYS_GUARD_PATHS) {
+                world.show_guard_paths = !world.show_guard_paths;
+            }
+            if kb.any_pressed(KEYS_COMPACT) {
+                world.compact_view = !world.compact_view;
             }
         }
 
         // ── Level Outro ──
         Phase::LevelOutro => {
             if esc {
-                let next = world.current_level + 1;
-                let _ = save::save_game(next, world.score, world.lives, None);
+                let next = next_level(world);
+                autosave(world, next, None);
                 return_to_title(world);
             }
         }
@@ -621,12 +1965,12 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         // ── Level Complete ──
         Phase::LevelComplete => {
             if confirm {
-                let next = world.current_level + 1;
-                let _ = save::save_game(next, world.score, world.lives, None);
+                let next = next_level(world);
+                autosave(world, next, None);
                 load_level(world, next, config);
             } else if esc {
-                let next = world.current_level + 1;
-                let _ = save::save_game(next, world.score, world.lives, None);
+                let next = next_level(world);
+                autosave(world, next, None);
                 return_to_title(world);
             }
         }
@@ -639,30 +1983,58 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
         // ── Game Over ──
         Phase::GameOver => {
             if confirm {
-                save::delete_save();
+                delete_autosave(world);
+                let mode = world.mode;
                 let speed = world.speed.clone();
                 let names = std::mem::take(&mut world.level_names);
                 let total = world.total_levels;
                 let active_pack = std::mem::take(&mut world.active_pack);
                 let active_pack_path = std::mem::take(&mut world.active_pack_path);
+                let show_minimap = world.show_minimap;
+                let show_edge_markers = world.show_edge_markers;
+                let show_guard_paths = world.show_guard_paths;
+                let compact_view = world.compact_view;
+                let (ng_plus, invisible_guards, mirror) = (world.ng_plus, world.invisible_guards, world.mirror);
+                let randomizer = world.randomizer;
                 *world = WorldState::new();
                 world.speed = speed;
                 world.level_names = names;
                 world.total_levels = total;
                 world.active_pack = active_pack;
                 world.active_pack_path = active_pack_path;
+                world.show_minimap = show_minimap;
+                world.show_edge_markers = show_edge_markers;
+                world.show_guard_paths = show_guard_paths;
+                world.compact_view = compact_view;
+                world.ng_plus = ng_plus;
+                world.invisible_guards = invisible_guards;
+                world.mirror = mirror;
+                world.randomizer = randomizer;
                 world.has_save = false;
-                start_new_game(world, config);
+                match mode {
+                    GameMode::Normal => start_new_game(world, config),
+                    GameMode::TimeAttack => start_time_attack(world, config),
+                }
             } else if esc {
-                save::delete_save();
+                delete_autosave(world);
                 return_to_title(world);
             }
         }
 
         // ── Game Complete ──
+        // ── Ending credits: ENTER finishes the roll, then leaves ──
+        // (or G goes round the pack again as New Game+)
         Phase::GameComplete => {
-            if confirm || esc {
-                save::delete_save();
+            let rows = credits::roll(world).len();
+            let finished = credits::finished(world.anim_tick, rows);
+            if confirm && !finished {
+                world.anim_tick = credits::end_ticks(rows);
+            } else if finished && world.mode == GameMode::Normal
+                && kb.any_pressed(&[KeyCode::Char('g'), KeyCode::Char('G')])
+            {
+                start_next_loop(world, config);
+            } else if confirm || esc {
+                delete_autosave(world);
                 return_to_title(world);
             }
         }
@@ -673,25 +2045,33 @@ fn handle_meta(world: &mut WorldState, _sound: Option<&SoundEngine>, kb: &InputS
 
 // ── Animation tick functions ──
 
-const INTRO_NAME_TICKS: u32 = 8;
-const INTRO_ROW_INTERVAL: u32 = 2;
-const INTRO_TOTAL: u32 = INTRO_NAME_TICKS + 16 * INTRO_ROW_INTERVAL + 4;
+/// Fully revealed map is held this long before "PRESS ANY KEY".
+const INTRO_HOLD_TICKS: u32 = 4;
 
-fn tick_level_intro(world: &mut WorldState) {
+fn tick_level_intro(world: &mut WorldState, config: &GameConfig) {
+    let reveal = transition::strategy(config.display.intro_transition);
+    let total = transition::NAME_CARD_TICKS
+        + reveal.duration(world.width, world.height)
+        + INTRO_HOLD_TICKS;
     world.anim_tick += 1;
-    if world.anim_tick >= INTRO_TOTAL {
+    if world.anim_tick >= total {
         world.phase = Phase::LevelReady;
         world.anim_tick = 0;
     }
 }
 
-fn tick_level_outro(world: &mut WorldState) {
+fn tick_level_outro(world: &mut WorldState, config: &GameConfig) {
     world.anim_tick += 1;
     if world.anim_tick % 3 == 0 {
         world.anim_player_y -= 1;
     }
-    if world.anim_player_y < -2 {
+    // Wait for both the climb and the map wipe to finish
+    let wipe = transition::strategy(config.display.outro_transition);
+    let wiped = world.anim_tick >= wipe.duration(world.width, world.height);
+    if world.anim_player_y < -2 && wiped {
         world.phase = Phase::LevelComplete;
+        // Cleared is cleared, even if the game goes before a key is pressed
+        autosave(world, next_level(world), None);
     }
 }
 
@@ -700,10 +2080,13 @@ const DYING_TICKS: u32 = 18;
 fn tick_dying(world: &mut WorldState, _sound: Option<&SoundEngine>) {
     world.anim_tick += 1;
     if world.anim_tick >= DYING_TICKS {
-        world.lives = world.lives.saturating_sub(1);
+        // Time attack: a death only costs the time it took
+        if world.mode == GameMode::Normal {
+            world.lives = world.lives.saturating_sub(1);
+        }
         if world.lives == 0 {
             world.phase = Phase::GameOver;
-            world.set_message("CONNECTION LOST", 120);
+            world.push_message(Severity::Warning, t!("msg.connection_lost"), 120);
         } else {
             step::restart_level(world);
             world.phase = Phase::LevelReady;  // wait for key input before restarting
@@ -0,0 +1,245 @@
+//! The agent protocol: a level played over stdin/stdout instead of a
+//! terminal, by bots and learning agents (`--agent <level>`).
+//!
+//! Every tick the game writes one `Observation` as a line of JSON, then
+//! reads one line of JSON with the input for the next tick:
+//!
+//! ```text
+//! > {"episode":0,"tick":0,"done":false,"outcome":null,...,"tiles":[" $      ",...],...}
+//! < {"move":"left","dig":null}
+//! ```
+//!
+//! `move` is `left`, `right`, `up` or `down`, `dig` is `left` or `right`;
+//! either may be left out or `null`, and an empty line waits a tick. A
+//! line that can't be read is answered with `{"error":"..."}` and the
+//! game waits for another. When the level is cleared or lost, the
+//! observation says `done` and how (`cleared`, `died`, `time_up`), and the
+//! next one is the level started again as the next episode. The game ends
+//! when stdin does.
+//!
+//! Tiles are the level-file chars (`save`'s, and a pack tile's own char);
+//! the player, guards and holes are listed apart from them.
+
+use std::io::{self, BufRead, Write};
+
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::config::GameConfig;
+use crate::domain::entity::{ActorState, Facing, FrameInput, MoveDir};
+use crate::sim::event::GameEvent;
+use crate::sim::level::{self, LevelDef};
+use crate::sim::save;
+use crate::sim::step::{self, Ticks};
+use crate::sim::world::{Phase, WorldState};
+
+/// What an agent sees of the game after a tick.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Observation {
+    pub episode: u32,
+    pub tick: u64,
+    /// The episode ended on this tick.
+    pub done: bool,
+    pub outcome: Option<&'static str>,
+    pub room: usize,
+    pub score: u32,
+    pub gold_left: usize,
+    pub exit_open: bool,
+    pub digs_left: Option<u32>,
+    /// One string per row, top first.
+    pub tiles: Vec<String>,
+    pub player: Actor,
+    pub guards: Vec<Actor>,
+    pub holes: Vec<OpenHole>,
+}
+
+/// The player or a guard. `id` and `carrying_gold` are a guard's only.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct Actor {
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub id: Option<usize>,
+    pub x: usize,
+    pub y: usize,
+    #[serde(serialize_with = "facing_name")]
+    pub facing: Facing,
+    #[serde(serialize_with = "state_name")]
+    pub state: ActorState,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub carrying_gold: Option<bool>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize)]
+pub struct OpenHole {
+    pub x: usize,
+    pub y: usize,
+    pub closing: bool,
+}
+
+impl Observation {
+    pub fn of(world: &WorldState, episode: u32, outcome: Option<&'static str>) -> Self {
+        let tiles = (0..world.height)
+            .map(|y| (0..world.width).map(|x| tile_char(world, x, y)).collect())
+            .collect();
+        let p = &world.player;
+        Observation {
+            episode,
+            tick: world.tick,
+            done: outcome.is_some(),
+            outcome,
+            room: world.current_room,
+            score: world.score,
+            gold_left: world.gold_remaining,
+            exit_open: world.exit_enabled,
+            digs_left: world.digs_left,
+            tiles,
+            player: Actor { id: None, x: p.x, y: p.y, facing: p.facing, state: p.state, carrying_gold: None },
+            guards: world.guards.iter()
+                .map(|g| Actor {
+                    id: Some(g.id), x: g.x, y: g.y, facing: g.facing, state: g.state, carrying_gold: Some(g.carry_gold),
+                })
+                .collect(),
+            holes: world.holes.iter().map(|h| OpenHole { x: h.x, y: h.y, closing: h.is_closing() }).collect(),
+        }
+    }
+
+    /// As one line of JSON.
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).expect("observations always serialize")
+    }
+}
+
+fn facing_name<S: Serializer>(facing: &Facing, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(match facing { Facing::Left => "left", Facing::Right => "right" })
+}
+
+fn state_name<S: Serializer>(state: &ActorState, s: S) -> Result<S::Ok, S::Error> {
+    s.serialize_str(match state {
+        ActorState::OnGround => "on_ground",
+        ActorState::Falling => "falling",
+        ActorState::OnLadder => "on_ladder",
+        ActorState::OnRope => "on_rope",
+        ActorState::InHole => "in_hole",
+        ActorState::Dead => "dead",
+    })
+}
+
+fn tile_char(world: &WorldState, x: usize, y: usize) -> char {
+    let tile = world.terrain_at(x, y);
+    tile.custom_def().map_or_else(|| save::tile_to_char(tile), |d| d.ch)
+}
+
+/// An action line: `move` and `dig`, either left out or null.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Action {
+    #[serde(default, rename = "move")]
+    movement: Option<Move>,
+    #[serde(default)]
+    dig: Option<Side>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Move { Left, Right, Up, Down }
+
+#[derive(Deserialize)]
+#[serde(rename_all = "lowercase")]
+enum Side { Left, Right }
+
+/// The input an action line asks for, or why it can't be read.
+pub fn parse_action(line: &str) -> Result<FrameInput, String> {
+    let line = line.trim();
+    if line.is_empty() {
+        return Ok(FrameInput { movement: None, dig: None });
+    }
+    let action: Action = serde_json::from_str(line).map_err(|e| e.to_string())?;
+    Ok(FrameInput {
+        movement: action.movement.map(|m| match m {
+            Move::Left => MoveDir::Left,
+            Move::Right => MoveDir::Right,
+            Move::Up => MoveDir::Up,
+            Move::Down => MoveDir::Down,
+        }),
+        dig: action.dig.map(|d| match d {
+            Side::Left => Facing::Left,
+            Side::Right => Facing::Right,
+        }),
+    })
+}
+
+/// `level`, ready to play from its start.
+fn start(world: &mut WorldState, level: &LevelDef, config: &GameConfig) {
+    world.score = 0;
+    level::load_level_def(world, level, config);
+    world.phase = Phase::Playing;
+}
+
+/// How a tick that ended the episode ended it.
+fn outcome(world: &WorldState, played: &Ticks) -> Option<&'static str> {
+    if world.phase == Phase::Playing { return None; }
+    Some(if played.cleared() {
+        "cleared"
+    } else if played.events.iter().any(|e| matches!(e, GameEvent::TimeUp)) {
+        "time_up"
+    } else {
+        "died"
+    })
+}
+
+/// Play `level` with input from `input`, observations to `out`, until
+/// `input` ends.
+pub fn run(level: &LevelDef, config: &GameConfig, input: impl BufRead, mut out: impl Write) -> io::Result<()> {
+    let mut world = WorldState::new();
+    world.speed = config.speed.clone();
+    start(&mut world, level, config);
+    let mut episode = 0;
+    writeln!(out, "{}", Observation::of(&world, episode, None).to_json())?;
+    out.flush()?;
+    for line in input.lines() {
+        let action = match parse_action(&line?) {
+            Ok(action) => action,
+            Err(e) => {
+                writeln!(out, "{}", serde_json::json!({ "error": e }))?;
+                out.flush()?;
+                continue;
+            }
+        };
+        let played = step::run_ticks(&mut world, [action], 1);
+        let ended = outcome(&world, &played);
+        writeln!(out, "{}", Observation::of(&world, episode, ended).to_json())?;
+        if ended.is_some() {
+            episode += 1;
+            start(&mut world, level, config);
+            writeln!(out, "{}", Observation::of(&world, episode, None).to_json())?;
+        }
+        out.flush()?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn agents_play_episodes_over_json_lines() {
+        let config = GameConfig::builtin();
+        let def = level::parse_level_file("# Bot\nP$     E\n########\n========\n").unwrap();
+        let input = "nonsense\n{\"move\": \"sideways\"}\n{\"move\":\"right\", \"dig\": null}\n\n";
+        let mut out = Vec::new();
+        run(&def, &config, input.as_bytes(), &mut out).unwrap();
+        let text = String::from_utf8(out).unwrap();
+        let lines: Vec<&str> = text.lines().collect();
+
+        assert!(lines[0].starts_with(r#"{"episode":0,"tick":0,"done":false,"outcome":null,"room":0,"score":0,"gold_left":1,"#));
+        assert!(lines[0].contains("\"tiles\":[\" $      \",\"########\",\"========\"]"));
+        assert!(lines[0].contains(r#""player":{"x":0,"y":0,"facing":"right","state":"on_ground"}"#));
+        assert!(lines[0].contains(r#""guards":[{"id":0,"x":7,"y":0,"facing":"left","state":"on_ground","carrying_gold":false}]"#));
+        assert_eq!(lines[1], r#"{"error":"expected ident at line 1 column 2"}"#);
+        assert!(lines[2].starts_with(r#"{"error":"unknown variant `sideways`"#));
+        assert!(lines[3].starts_with(r#"{"episode":0,"tick":1,"done":true,"outcome":"cleared""#));
+        assert!(lines[4].starts_with(r#"{"episode":1,"tick":0,"done":false"#));
+        assert!(lines[5].starts_with(r#"{"episode":1,"tick":1,"done":false"#));
+        assert_eq!(lines.len(), 6);
+        assert_eq!(parse_action(r#"{"dig":"left"}"#), Ok(FrameInput { movement: None, dig: Some(Facing::Left) }));
+    }
+}
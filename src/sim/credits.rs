@@ -0,0 +1,113 @@
+//! Ending credits: the lines that roll up the screen once a pack is
+//! complete — the pack's own credits, the run's totals, and the game's.
+//!
+//! Packs add credit lines with `## Credit: <text>` metadata, one per line
+//! (`## Credit: Level design - Alice`).
+
+use crate::i18n::t;
+use crate::sim::world::{GameMode, WorldState};
+
+/// Ticks per row scrolled.
+pub const TICKS_PER_ROW: u32 = 3;
+
+/// The people behind the game itself.
+const CONTRIBUTORS: &[&str] = &["fukuyori"];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CreditStyle {
+    Title,
+    Heading,
+    Text,
+    Hint,
+}
+
+pub struct CreditLine {
+    pub text: String,
+    pub style: CreditStyle,
+}
+
+/// Rows scrolled `ticks` into the roll; it stops once every line is on
+/// screen (`roll.len()` rows in).
+pub fn scrolled(ticks: u32, rows: usize) -> usize {
+    (ticks / TICKS_PER_ROW).min(rows as u32) as usize
+}
+
+/// Whether the roll has finished scrolling.
+pub fn finished(ticks: u32, rows: usize) -> bool {
+    scrolled(ticks, rows) >= rows
+}
+
+/// Ticks at which a roll of `rows` lines has finished.
+pub fn end_ticks(rows: usize) -> u32 {
+    rows as u32 * TICKS_PER_ROW
+}
+
+/// Every line of the roll, top to bottom.
+pub fn roll(w: &WorldState) -> Vec<CreditLine> {
+    let mut lines = vec![];
+    let mut push = |text: String, style| lines.push(CreditLine { text, style });
+    let blank = || String::new();
+
+    push(t!("complete.title").to_string(), CreditStyle::Title);
+    push(blank(), CreditStyle::Text);
+
+    // ── The pack ──
+    push(w.active_pack.clone(), CreditStyle::Heading);
+    if let Some(pack) = &w.completed_pack {
+        if !pack.author.is_empty() {
+            push(t!("credits.by", author = pack.author), CreditStyle::Text);
+        }
+        if !pack.description.is_empty() {
+            push(pack.description.clone(), CreditStyle::Text);
+        }
+        if !pack.credits.is_empty() {
+            push(blank(), CreditStyle::Text);
+            for credit in &pack.credits {
+                push(credit.clone(), CreditStyle::Text);
+            }
+        }
+    }
+    push(blank(), CreditStyle::Text);
+
+    // ── The run ──
+    push(t!("credits.run").to_string(), CreditStyle::Heading);
+    if w.mode == GameMode::TimeAttack {
+        let secs = w.clock_secs();
+        push(t!("time_attack.result",
+                n = w.time_attack.cleared, clock = format!("{}:{:02}", secs / 60, secs % 60)),
+             CreditStyle::Text);
+        push(match w.time_attack.rank {
+            Some(1) => t!("time_attack.best").to_string(),
+            Some(n) => t!("time_attack.ranked", n = n),
+            None => t!("time_attack.unranked").to_string(),
+        }, CreditStyle::Text);
+    } else {
+        push(t!("over.score", score = w.score), CreditStyle::Text);
+        push(t!("complete.all_cleared", n = w.total_levels), CreditStyle::Text);
+    }
+    let run = &w.run;
+    let secs = run.play_ticks * w.speed.tick_rate_ms / 1000;
+    push(t!("credits.time", clock = format!("{}:{:02}", secs / 60, secs % 60)), CreditStyle::Text);
+    push(t!("credits.tokens", n = run.tokens), CreditStyle::Text);
+    push(t!("credits.hacks", n = run.hacks), CreditStyle::Text);
+    push(t!("credits.trapped", n = run.guards_trapped), CreditStyle::Text);
+    push(t!("credits.secrets", n = run.secrets), CreditStyle::Text);
+    push(t!("credits.deaths", n = run.deaths), CreditStyle::Text);
+    push(blank(), CreditStyle::Text);
+
+    // ── The game ──
+    push(t!("credits.game").to_string(), CreditStyle::Heading);
+    for name in CONTRIBUTORS {
+        push(name.to_string(), CreditStyle::Text);
+    }
+    push(blank(), CreditStyle::Text);
+    push(blank(), CreditStyle::Text);
+
+    push(t!("credits.thanks").to_string(), CreditStyle::Title);
+    push(blank(), CreditStyle::Text);
+    if w.mode == GameMode::Normal {
+        push(t!("complete.next_loop", n = w.ng_loop + 1), CreditStyle::Hint);
+    }
+    push(t!("complete.back").to_string(), CreditStyle::Hint);
+    lines
+}
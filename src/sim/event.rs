@@ -1,5 +1,12 @@
-/// Events emitted during a simulation step.
-/// The presentation layer consumes these for animation/sound.
+//! Events emitted during a simulation step.
+//! The presentation layer consumes these for animation/sound.
+//!
+//! Consumers implement `EventSink` and register with an `EventBus`, which
+//! hands each tick's events to all of them. The bus also reports phase
+//! changes, wherever they were made (sim, menus, loading).
+
+use crate::domain::entity::ActorState;
+use super::world::{Phase, WorldState};
 
 #[derive(Clone, Debug)]
 #[allow(dead_code)]
@@ -10,11 +17,56 @@ pub enum GameEvent {
     GuardTrapped { id: usize, x: usize, y: usize },
     GuardKilled { id: usize, x: usize, y: usize },
     GuardRespawned { id: usize },
-    GuardDroppedGold { x: usize, y: usize },
+    GuardSpawned { id: usize, x: usize, y: usize },
+    GuardStepped { id: usize, x: usize, y: usize },
+    GuardDroppedGold { id: usize, x: usize, y: usize },
     PlayerKilled,
     PlayerFallStart,
+    PlayerLanded { x: usize, y: usize },
     ExitEnabled,
     StageCleared,
     AllGoldCollected,
     TrapCollapsed { x: usize, y: usize },
+    SecretFound { x: usize, y: usize },
+    LanternPicked { x: usize, y: usize },
+    RoomEntered { room: usize },
+    HubDoorEntered { level: usize },
+    TimeUp,
+    PlayerStateChanged { from: ActorState, to: ActorState },
+    ScoreChanged { from: u32, to: u32 },
+    PhaseChanged { from: Phase, to: Phase },
+}
+
+/// A consumer of the event stream: sound, effects, stats, achievements.
+pub trait EventSink {
+    fn on_events(&mut self, world: &WorldState, events: &[GameEvent]);
+}
+
+/// Hands events to every registered sink, in registration order.
+#[derive(Default)]
+pub struct EventBus {
+    sinks: Vec<Box<dyn EventSink>>,
+    phase: Option<Phase>,  // as of the last publish
+}
+
+impl EventBus {
+    pub fn subscribe(&mut self, sink: impl EventSink + 'static) {
+        self.sinks.push(Box::new(sink));
+    }
+
+    /// Add a `PhaseChanged` if the phase moved since the last call, then
+    /// deliver `events` to `ui` and the registered sinks. The renderer is
+    /// borrowed for drawing between ticks, so it's passed in as `ui`
+    /// rather than registered.
+    pub fn publish(&mut self, world: &WorldState, events: &mut Vec<GameEvent>, ui: &mut dyn EventSink) {
+        if let Some(from) = self.phase.filter(|&p| p != world.phase) {
+            events.push(GameEvent::PhaseChanged { from, to: world.phase });
+        }
+        self.phase = Some(world.phase);
+        if events.is_empty() { return; }
+        ui.on_events(world, events);
+        for sink in &mut self.sinks {
+            sink.on_events(world, events);
+        }
+    }
 }
@@ -0,0 +1,139 @@
+//! Event log export: every `GameEvent` of a session, one JSON object per
+//! line, for analysing runs outside the game.
+//!
+//! A line is the tick and where it happened, then the event's name and
+//! fields:
+//!
+//! ```text
+//! {"tick":412,"level":3,"room":0,"event":"GuardTrapped","id":1,"x":7,"y":12}
+//! ```
+//!
+//! Enabled by `[general] log_events` or `--log-events`; each run writes a
+//! new `events/session-<unix time>.jsonl` under the save directory.
+
+use std::fmt::Write as _;
+use std::fs::File;
+use std::io::{self, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use super::event::{EventSink, GameEvent};
+use super::save;
+use super::world::WorldState;
+
+pub struct EventLog {
+    path: PathBuf,
+    out: BufWriter<File>,
+    failed: bool,  // a write failed; stop trying
+}
+
+impl EventLog {
+    /// Start this session's log in the save directory.
+    pub fn open() -> io::Result<Self> {
+        let dir = save::save_dir().join("events");
+        std::fs::create_dir_all(&dir)?;
+        let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+        Self::create(&dir.join(format!("session-{secs}.jsonl")))
+    }
+
+    pub fn create(path: &Path) -> io::Result<Self> {
+        let out = BufWriter::new(File::create(path)?);
+        Ok(EventLog { path: path.to_path_buf(), out, failed: false })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl EventSink for EventLog {
+    fn on_events(&mut self, world: &WorldState, events: &[GameEvent]) {
+        if self.failed { return; }
+        let mut lines = String::new();
+        for e in events {
+            lines.push_str(&line(world, e));
+            lines.push('\n');
+        }
+        // Flushed every tick so a crash loses nothing already played.
+        if self.out.write_all(lines.as_bytes()).and_then(|_| self.out.flush()).is_err() {
+            self.failed = true;
+        }
+    }
+}
+
+/// `e` as one JSON object, stamped with `world`'s tick, level and room.
+fn line(world: &WorldState, e: &GameEvent) -> String {
+    use GameEvent::*;
+    let (name, fields): (&str, Vec<(&str, String)>) = match *e {
+        GoldPicked { x, y } => ("GoldPicked", xy(x, y)),
+        HoleCreated { x, y } => ("HoleCreated", xy(x, y)),
+        HoleFilled { x, y } => ("HoleFilled", xy(x, y)),
+        GuardTrapped { id, x, y } => ("GuardTrapped", id_xy(id, x, y)),
+        GuardKilled { id, x, y } => ("GuardKilled", id_xy(id, x, y)),
+        GuardRespawned { id } => ("GuardRespawned", vec![("id", id.to_string())]),
+        GuardSpawned { id, x, y } => ("GuardSpawned", id_xy(id, x, y)),
+        GuardStepped { id, x, y } => ("GuardStepped", id_xy(id, x, y)),
+        GuardDroppedGold { id, x, y } => ("GuardDroppedGold", id_xy(id, x, y)),
+        PlayerKilled => ("PlayerKilled", vec![]),
+        PlayerFallStart => ("PlayerFallStart", vec![]),
+        PlayerLanded { x, y } => ("PlayerLanded", xy(x, y)),
+        ExitEnabled => ("ExitEnabled", vec![]),
+        StageCleared => ("StageCleared", vec![]),
+        AllGoldCollected => ("AllGoldCollected", vec![]),
+        TrapCollapsed { x, y } => ("TrapCollapsed", xy(x, y)),
+        SecretFound { x, y } => ("SecretFound", xy(x, y)),
+        LanternPicked { x, y } => ("LanternPicked", xy(x, y)),
+        RoomEntered { room } => ("RoomEntered", vec![("to", room.to_string())]),
+        HubDoorEntered { level } => ("HubDoorEntered", vec![("to", level.to_string())]),
+        TimeUp => ("TimeUp", vec![]),
+        PlayerStateChanged { from, to } => ("PlayerStateChanged", from_to(from, to)),
+        ScoreChanged { from, to } => ("ScoreChanged", vec![("from", from.to_string()), ("to", to.to_string())]),
+        PhaseChanged { from, to } => ("PhaseChanged", from_to(from, to)),
+    };
+    let mut out = format!(
+        r#"{{"tick":{},"level":{},"room":{},"event":"{name}""#,
+        world.tick, world.current_level, world.current_room,
+    );
+    for (key, value) in fields {
+        let _ = write!(out, r#","{key}":{value}"#);
+    }
+    out.push('}');
+    out
+}
+
+fn xy(x: usize, y: usize) -> Vec<(&'static str, String)> {
+    vec![("x", x.to_string()), ("y", y.to_string())]
+}
+
+fn id_xy(id: usize, x: usize, y: usize) -> Vec<(&'static str, String)> {
+    let mut fields = vec![("id", id.to_string())];
+    fields.extend(xy(x, y));
+    fields
+}
+
+/// Fieldless enums (states, phases), as JSON strings of their names.
+fn from_to(from: impl std::fmt::Debug, to: impl std::fmt::Debug) -> Vec<(&'static str, String)> {
+    vec![("from", format!("\"{from:?}\"")), ("to", format!("\"{to:?}\""))]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::ActorState;
+
+    #[test]
+    fn lines_carry_tick_place_and_fields() {
+        let mut world = WorldState::new();
+        world.tick = 42;
+        world.current_level = 3;
+        assert_eq!(
+            line(&world, &GameEvent::GuardTrapped { id: 1, x: 7, y: 12 }),
+            r#"{"tick":42,"level":3,"room":0,"event":"GuardTrapped","id":1,"x":7,"y":12}"#,
+        );
+        assert_eq!(
+            line(&world, &GameEvent::PlayerStateChanged { from: ActorState::OnRope, to: ActorState::Falling }),
+            r#"{"tick":42,"level":3,"room":0,"event":"PlayerStateChanged","from":"OnRope","to":"Falling"}"#,
+        );
+        assert_eq!(line(&world, &GameEvent::TimeUp), r#"{"tick":42,"level":3,"room":0,"event":"TimeUp"}"#);
+    }
+}
@@ -1,52 +1,145 @@
-/// Level loader with pack support.
-///
-/// ## Sources (priority order):
-///   1. Active pack file (`.nlp` format)
-///   2. `levels/` directory (individual `.txt` files)
-///   3. Built-in embedded levels
-///
-/// ## Pack format (`.nlp` — NodeRunner Level Pack):
-///   ```
-///   ## Pack Name
-///   ## Author: name
-///   ## Description: blah blah
-///   ---
-///   # Level 1 - Name
-///   @ 1,2 3,4
-///   <16 map rows>
-///   ---
-///   # Level 2 - Name
-///   <16 map rows>
-///   ```
-///
-/// Levels are separated by a line containing only `---`.
-/// Pack metadata lines start with `##`.
-///
-/// ## Single-level format (`.txt`):
-///   Line 1: `# Level Name`
-///   Optional: `@ x1,y1 x2,y2 ...` (hidden ladder metadata)
-///   Lines: map rows
-///
-/// ## Tile legend:
-///   '#' = Firewall (diggable)    '=' = Concrete (indestructible)
-///   'H' = Ladder                 '-' = Rope
-///   '$' = Token                  'P' = Player spawn
-///   'E' = Sentinel spawn         '^' = Exit ladder column marker
-///   '~' = Hidden ladder          'T' = Trap brick
-///   ' ' = Empty
+//! Level loader with pack support.
+//!
+//! ## Sources (priority order):
+//!   1. Active pack file (`.nlp` format)
+//!   2. `levels/` directory (individual `.txt` files)
+//!   3. Built-in embedded levels
+//!
+//! ## Pack format (`.nlp` — NodeRunner Level Pack):
+//!   ```text
+//!   ## Pack Name
+//!   ## Author: name
+//!   ## Description: blah blah
+//!   ## Credit: Level design - name   (any number; shown in the ending)
+//!   ## Tile: X glyph=▒ color=#ff4040 solid deadly
+//!   ---
+//!   # Level 1 - Name
+//!   @ 1,2 3,4
+//!   <16 map rows>
+//!   ---
+//!   # Level 2 - Name
+//!   <16 map rows>
+//!   ```
+//!
+//! Levels are separated by a line containing only `---`.
+//! A section starting `@story` is a story page instead (see `sim::story`).
+//! The built-in tutorial (`sim::tutorial`) is itself a pack in this format.
+//! Pack metadata lines start with `##`.
+//!
+//! ## Rooms:
+//!   A `[Room Name]` line inside a level starts another room of the same
+//!   node, with its own map rows and `@` lines. Digits `1`-`9` are doors:
+//!   stepping onto door `3` moves the player to door `3` in the other room.
+//!   Tokens are counted across all rooms, and `P` is only read from the
+//!   first room.
+//!   ```text
+//!   # Level 7 - Annex
+//!   <rows with a 1>
+//!   [Server Room]
+//!   <rows with a 1>
+//!   ```
+//!
+//! ## Single-level format (`.txt`):
+//!   Line 1: `# Level Name`
+//!   Optional: `@ x1,y1 x2,y2 ...` (hidden ladder metadata)
+//!   Optional: `@digs N` (puzzle mode: at most N digs per attempt)
+//!   Optional: `@dark N` (darkness: only cells within N of the runner show)
+//!   Optional: `@spawn TICKS CAP` (each `G` spawner: a sentinel every
+//!             TICKS ticks, CAP in all; default 100 and 3)
+//!   Optional: `@par SECONDS` (par time, shown when the level is cleared)
+//!   Optional: `@tag name [text]`, `@tip x,y text` (tutorial prompts)
+//!   Optional: `@on trigger code` (Rhai level script, see `sim::script`)
+//!   Optional: `## Tile: ...` (a custom tile of its own, as in a pack)
+//!   Lines: map rows
+//!
+//! ## Tile legend:
+//!   '#' = Firewall (diggable)    '=' = Concrete (indestructible)
+//!   'H' = Ladder                 '-' = Rope
+//!   '$' = Token (100)            'P' = Player spawn
+//!   '%' = Token (250)            '&' = Token (500)
+//!   'E' = Sentinel spawn         '^' = Exit ladder column marker
+//!   '~' = Hidden ladder          'T' = Trap brick
+//!   'S' = Fake brick (looks like '#', but is a secret passage)
+//!   '!' = Laser (kills the player on touch; guards too, by `[rules]`)
+//!   '1'-'9' = Door               ' ' = Empty
+//!   'R' = Sentinel respawn point (killed sentinels come back at each in turn)
+//!   'G' = Sentinel spawner (sends out new sentinels, see `@spawn`)
+//!   'L' = Lantern (lights further in darkness, see `@dark`)
+//!
+//! ## Custom tiles:
+//!   A `## Tile: <char> ...` metadata line gives the pack a tile of its own,
+//!   written as `<char>` in its maps (any ASCII char the legend doesn't
+//!   use). Options: `glyph=<char>` (drawn twice, defaults to the char),
+//!   `color=#rrggbb`, and the flags `solid`, `climbable`, `diggable`,
+//!   `hangable` and `deadly` (kills the player in it or standing on it).
+//!   A tile without flags is decoration the player walks through.
+//!
+//! ## Hub packs:
+//!   A `## Hub` metadata line makes the pack's first level a hub. Its door
+//!   `N` launches level `N` of the pack instead of linking rooms; clearing
+//!   that level returns the player to the door. The hub's exit opens once
+//!   every door's level has been cleared, and escaping it ends the pack.
 
 use std::path::{Path, PathBuf};
 
-use crate::config::GameConfig;
-use crate::domain::entity::{Guard, Player};
-use crate::domain::tile::Tile;
-use crate::sim::world::{PackInfo, Phase, WorldState};
+use crate::config::{GameConfig, MAX_LIGHT};
+use crate::domain::entity::{Guard, Player, Spawner};
+use crate::domain::grid::Grid;
+use crate::i18n::t;
+use crate::domain::tile::{self, Tile, TileDef};
+use crate::sim::randomize;
+use crate::sim::run_code::Recording;
+use crate::sim::save;
+use crate::sim::script::{self, ScriptDef, Scripts};
+use crate::sim::solver;
+use crate::sim::step;
+use crate::sim::story::{self, StoryPage};
+use crate::sim::tutorial;
+use crate::sim::world::{Door, LevelFile, PackBuilder, PackInfo, PackPreview, Phase, Room, RunStats, Severity, Tip, TipTrigger, WorldState};
 
 /// Runtime level data (owned strings, loaded from file or embedded).
+/// `name`/`rows`/`extra_hidden_ladders` describe the first room.
 pub struct LevelDef {
     pub name: String,
     pub rows: Vec<String>,
     pub extra_hidden_ladders: Vec<(usize, usize)>,
+    /// Further `[Room Name]` sections, in file order.
+    pub rooms: Vec<RoomDef>,
+    /// First level of a `## Hub` pack.
+    pub hub: bool,
+    /// `@digs N`: dig budget for the whole level (all rooms).
+    pub dig_limit: Option<u32>,
+    /// `@dark N`: light radius, for a level played in darkness.
+    pub darkness: Option<u32>,
+    /// `@spawn TICKS CAP`: how often and how many times each spawner
+    /// sends out a sentinel.
+    pub spawn: Option<(u32, u32)>,
+    /// `@par SECONDS`: par time, compared with the clear time.
+    pub par: Option<u32>,
+    /// `@tag` / `@tip` tutorial prompts, in file order.
+    pub tips: Vec<Tip>,
+    /// `@on` level scripts, in file order.
+    pub scripts: Vec<ScriptDef>,
+    /// The pack's `## Tile:` definitions (`Tile::Custom(i)` is entry `i`).
+    pub tiles: Vec<TileDef>,
+    /// `@story` pages shown before this level.
+    pub story: Vec<StoryPage>,
+    /// `@story` pages after the pack's last level (only set on that level).
+    pub epilogue: Vec<StoryPage>,
+    /// The level's text as written (its section, in a pack).
+    pub source: String,
+}
+
+pub struct RoomDef {
+    pub name: String,
+    pub rows: Vec<String>,
+    pub extra_hidden_ladders: Vec<(usize, usize)>,
+}
+
+impl RoomDef {
+    fn new(name: &str) -> Self {
+        RoomDef { name: name.to_string(), rows: vec![], extra_hidden_ladders: vec![] }
+    }
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -58,84 +151,249 @@ pub fn load_level(world: &mut WorldState, level_idx: usize, config: &GameConfig)
     let levels = load_levels_for_active_pack(world, config);
 
     if level_idx >= levels.len() {
-        world.phase = Phase::GameComplete;
+        world.completed_pack = scan_packs(config).into_iter()
+            .find(|p| p.path == world.active_pack_path);
+        world.anim_tick = 0;
+        let epilogue = levels.last().map_or(&[][..], |l| &l.epilogue[..]);
+        story::start(world, epilogue, Phase::GameComplete);
         return;
     }
 
     let def = &levels[level_idx];
     world.current_level = level_idx;
     world.total_levels = levels.len();
+    world.speed = config.speed.for_loop(world.ng_loop);
+    load_level_def(world, def, config);
+
+    world.level_start_score = world.score;
+    world.node_run = RunStats::default();
+    world.board = save::load_board(&world.active_pack_path, level_idx);
+
+    world.hub.enabled = levels[0].hub;
+    // Back at the hub from one of its levels: its story has been told
+    let returning = world.in_hub() && world.hub.return_door.is_some();
+    if world.in_hub() {
+        enter_hub(world);
+        // How the hub plays depends on saved progress: no run codes
+        world.recording = None;
+    }
+    if !returning {
+        story::start(world, &def.story, Phase::LevelIntro);
+    }
+
+    step::refresh_tip(world);
+
+    world.camera.center_on(
+        world.player.x, world.player.y,
+        world.width, world.height,
+    );
+}
+
+/// Build the world for one level definition, wherever it came from (a
+/// pack, or one held in memory), and start its intro. Pack-level state —
+/// the level index, leaderboard, hub and story — is left to the caller.
+pub fn load_level_def(world: &mut WorldState, def: &LevelDef, config: &GameConfig) {
     world.level_name = def.name.clone();
 
-    let height = def.rows.len();
-    let width = if height > 0 { def.rows[0].len() } else { 28 };
-    world.width = width;
-    world.height = height;
-    world.tiles = vec![vec![Tile::Empty; width]; height];
-    world.guards.clear();
-    world.holes.clear();
-    world.digs.clear();
-    world.exit_columns.clear();
-    world.hidden_ladder_positions.clear();
+    world.rooms.clear();
+    world.door_armed = true;
     world.gold_remaining = 0;
     world.exit_enabled = false;
+    world.digs_made = 0;
+    world.dig_limit = def.dig_limit.or(config.gameplay.dig_limit);
+    world.digs_left = world.dig_limit;
+    world.darkness = def.darkness.or(config.gameplay.darkness);
+    world.light_radius = world.darkness;
+    world.par_secs = def.par;
+    world.guard_respawn = config.rules.guard_respawn;
+    world.hazards_kill_guards = config.rules.hazards_kill_guards;
+    world.ai = config.ai;
+    world.respawns = 0;
+    world.tips = def.tips.clone();
     world.tick = 0;
+    world.restart_confirm = 0;
+    world.recording = Some(Recording { start_score: world.score, inputs: vec![] });
+    tile::set_custom_tiles(&def.tiles);
 
     let mut guard_id = 0;
+    let spawn = def.spawn.unwrap_or(DEFAULT_SPAWN);
+    let mirror = world.mirror;
+    let flip = |rows: &[String], ladders: &[(usize, usize)]| {
+        if mirror { mirror_rows(rows, ladders) } else { (rows.to_vec(), ladders.to_vec()) }
+    };
+    let (rows, ladders) = flip(&def.rows, &def.extra_hidden_ladders);
+    let mut rooms = vec![build_room(world, &def.name, &rows, &ladders, true, &mut guard_id, spawn, config)];
+    for r in &def.rooms {
+        let (rows, ladders) = flip(&r.rows, &r.extra_hidden_ladders);
+        rooms.push(build_room(world, &r.name, &rows, &ladders, false, &mut guard_id, spawn, config));
+    }
+    if mirror {
+        let width = rooms[0].width;
+        for tip in &mut world.tips {
+            if let TipTrigger::At { x, .. } = &mut tip.trigger {
+                *x = width.saturating_sub(1 + *x);
+            }
+        }
+    }
+    world.rooms = rooms;
+    world.current_room = 0;
+    world.enter_room(0);
+    world.level_guards = guard_id;
+    world.script_spawn_cap = spawn.1;
+    world.script_spawned = 0;
+
+    world.gold_total = world.gold_remaining;
+    world.phase = Phase::LevelIntro;
+    world.anim_tick = 0;
+    world.push_message(Severity::Info, &def.name, 80);
+    if let Some(r) = &mut world.randomizer {
+        r.attempt = 0;
+        randomize::deal(world);
+    }
+    let (scripts, errors) = Scripts::load(&def.scripts);
+    world.scripts = scripts;
+    for error in errors {
+        world.push_message(Severity::Warning, &error, 120);
+    }
+}
+
+/// Hub arrival: refresh cleared doors from saved progress, and put the
+/// player back on the door they left through.
+fn enter_hub(world: &mut WorldState) {
+    world.hub.cleared = save::load_cleared(&world.active_pack_path);
+    if let Some(id) = world.hub.return_door.take() {
+        if let Some(door) = world.doors().iter().find(|d| d.id == id).copied() {
+            world.player.x = door.x;
+            world.player.y = door.y;
+            world.door_armed = false;
+        }
+    }
+    step::refresh_hub_exit(world);
+    let cleared = world.doors().iter()
+        .filter(|d| world.hub.cleared.contains(&(d.id as usize)))
+        .count();
+    let msg = t!("msg.hub", name = world.level_name, n = cleared, total = world.doors().len());
+    world.push_message(Severity::Info, &msg, 80);
+}
+
+/// Mirror mode: `rows` and their hidden ladders flipped left to right.
+/// Rows are padded to the first one's width so columns stay in line.
+fn mirror_rows(rows: &[String], ladders: &[(usize, usize)]) -> (Vec<String>, Vec<(usize, usize)>) {
+    let width = rows.first().map_or(0, |r| r.chars().count());
+    let rows = rows.iter()
+        .map(|r| {
+            let mut cells: Vec<char> = r.chars().chain(std::iter::repeat(' ')).take(width).collect();
+            cells.reverse();
+            cells.into_iter().collect()
+        })
+        .collect();
+    let ladders = ladders.iter()
+        .filter(|&&(x, _)| x < width)
+        .map(|&(x, y)| (width - 1 - x, y))
+        .collect();
+    (rows, ladders)
+}
 
-    for (y, row) in def.rows.iter().enumerate() {
+/// Spawner ticks and cap for levels without an `@spawn` line.
+const DEFAULT_SPAWN: (u32, u32) = (100, 3);
+
+/// Parse one room's map rows. Tokens are added to `world.gold_remaining`;
+/// the player spawn is only taken from the first room.
+#[allow(clippy::too_many_arguments)]
+fn build_room(
+    world: &mut WorldState,
+    name: &str,
+    rows: &[String],
+    extra_hidden_ladders: &[(usize, usize)],
+    first: bool,
+    guard_id: &mut usize,
+    (every, cap): (u32, u32),
+    config: &GameConfig,
+) -> Room {
+    let height = rows.len();
+    let width = if height > 0 { rows[0].len() } else { 28 };
+    let mut room = Room {
+        name: name.to_string(),
+        tiles: Grid::new(width, height, Tile::Empty),
+        width,
+        height,
+        ..Room::default()
+    };
+
+    for (y, row) in rows.iter().enumerate() {
         for (x, ch) in row.chars().enumerate() {
             if x >= width { break; }
             match ch {
-                '#' => world.tiles[y][x] = Tile::Brick,
-                '=' => world.tiles[y][x] = Tile::Concrete,
-                'H' => world.tiles[y][x] = Tile::Ladder,
-                '-' => world.tiles[y][x] = Tile::Rope,
-                '$' => {
-                    world.tiles[y][x] = Tile::Gold;
+                '#' => room.tiles[(x, y)] = Tile::Brick,
+                '=' => room.tiles[(x, y)] = Tile::Concrete,
+                'H' => room.tiles[(x, y)] = Tile::Ladder,
+                '-' => room.tiles[(x, y)] = Tile::Rope,
+                '$' | '%' | '&' => {
+                    room.tiles[(x, y)] = tile::token_for(ch).unwrap_or(Tile::Gold(0));
                     world.gold_remaining += 1;
                 }
-                'P' => {
+                'P' if first => {
                     world.player = Player::new(x, y);
                     world.player_spawn = (x, y);
                 }
                 'E' => {
-                    let mut g = Guard::new(guard_id, x, y);
+                    let mut g = Guard::new(*guard_id, x, y);
                     g.move_cooldown = config.speed.guard_move_rate;
-                    world.guards.push(g);
-                    guard_id += 1;
+                    room.guards.push(g);
+                    *guard_id += 1;
                 }
                 '^' => {
-                    if !world.exit_columns.contains(&x) {
-                        world.exit_columns.push(x);
+                    if !room.exit_columns.contains(&x) {
+                        room.exit_columns.push(x);
                     }
                 }
-                'T' => world.tiles[y][x] = Tile::TrapBrick,
+                'T' => room.tiles[(x, y)] = Tile::TrapBrick,
+                'L' => room.tiles[(x, y)] = Tile::Lantern,
+                'S' => room.tiles[(x, y)] = Tile::FakeBrick,
+                '!' => room.tiles[(x, y)] = Tile::Laser,
                 '~' => {
-                    world.hidden_ladder_positions.push((x, y));
+                    room.hidden_ladder_positions.push((x, y));
+                }
+                'R' => room.respawn_points.push((x, y)),
+                'G' => {
+                    room.tiles[(x, y)] = Tile::Spawner;
+                    room.spawners.push(Spawner::new(x, y, every, cap));
+                }
+                '1'..='9' => {
+                    room.tiles[(x, y)] = Tile::Door;
+                    room.doors.push(Door { id: ch as u8 - b'0', x, y });
+                }
+                _ => {
+                    if let Some(t) = tile::custom_tile_for(ch) {
+                        room.tiles[(x, y)] = t;
+                    }
                 }
-                _ => {}
             }
         }
     }
 
-    for &(x, y) in &def.extra_hidden_ladders {
-        if !world.hidden_ladder_positions.contains(&(x, y)) {
-            world.hidden_ladder_positions.push((x, y));
+    for &(x, y) in extra_hidden_ladders {
+        if !room.hidden_ladder_positions.contains(&(x, y)) {
+            room.hidden_ladder_positions.push((x, y));
         }
     }
 
-    world.gold_total = world.gold_remaining;
-    world.base_tiles = world.tiles.clone();
-    world.rebuild_hole_grid(); // empty grid for fresh level
-    world.phase = Phase::LevelIntro;
-    world.anim_tick = 0;
-    world.set_message(&def.name, 80);
+    room.base_tiles = room.tiles.clone();
+    room
+}
 
-    world.camera.center_on(
-        world.player_spawn.0, world.player_spawn.1,
-        world.width, world.height,
-    );
+/// Level names to list for the pack selector's preview.
+const PREVIEW_NAMES: usize = 5;
+
+/// Preview of the pack at `path`: its first level's map and first few
+/// level names.
+pub fn pack_preview(path: &str, config: &GameConfig) -> PackPreview {
+    let levels = load_pack(path, config);
+    PackPreview {
+        path: path.to_string(),
+        names: levels.iter().take(PREVIEW_NAMES).map(|l| l.name.clone()).collect(),
+        rows: levels.first().map(|l| l.rows.clone()).unwrap_or_default(),
+    }
 }
 
 /// Get list of level names for the currently active pack.
@@ -153,12 +411,23 @@ pub fn scan_packs(config: &GameConfig) -> Vec<PackInfo> {
     packs.push(PackInfo {
         name: "Built-in Levels".to_string(),
         author: "NodeRunner".to_string(),
-        description: format!("{} levels included with the game", embedded.len()),
+        description: t!("packs.builtin_desc", n = embedded.len()),
         level_count: embedded.len(),
         path: "__embedded__".to_string(),
+        credits: vec![],
     });
 
-    // 2. levels/ directory (individual .txt files)
+    // 2. Built-in tutorial
+    packs.push(PackInfo {
+        name: tutorial::PACK_NAME.to_string(),
+        author: "NodeRunner".to_string(),
+        description: t!("packs.tutorial_desc").to_string(),
+        level_count: parse_pack_levels(tutorial::PACK).len(),
+        path: "__tutorial__".to_string(),
+        credits: vec![],
+    });
+
+    // 3. levels/ directory (individual .txt files)
     let dir = &config.levels_dir;
     if dir.is_dir() {
         let dir_levels = load_from_directory(dir);
@@ -170,14 +439,15 @@ pub fn scan_packs(config: &GameConfig) -> Vec<PackInfo> {
             packs.push(PackInfo {
                 name: format!("{}/  (individual files)", dir_name),
                 author: String::new(),
-                description: format!("{} levels from {}/", dir_levels.len(), dir_name),
+                description: t!("packs.dir_desc", n = dir_levels.len(), dir = dir_name),
                 level_count: dir_levels.len(),
                 path: "__levels__".to_string(),
+                credits: vec![],
             });
         }
     }
 
-    // 3. .nlp pack files from packs/ directory
+    // 4. .nlp pack files from packs/ directory
     let search_dirs = pack_search_dirs();
     for base in &search_dirs {
         let packs_dir = base.join("packs");
@@ -200,12 +470,262 @@ pub fn scan_packs(config: &GameConfig) -> Vec<PackInfo> {
     packs
 }
 
+/// FNV-1a hash of the levels of the pack at `path` (names and maps), so a
+/// pack is known by what's in it rather than where it's installed.
+pub fn pack_hash(path: &str, config: &GameConfig) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |text: &str| {
+        for b in text.bytes().chain([0xff]) {
+            h ^= b as u64;
+            h = h.wrapping_mul(0x0100_0000_01b3);
+        }
+    };
+    for level in load_pack(path, config) {
+        feed(&level.name);
+        level.rows.iter().for_each(|r| feed(r));
+        for room in &level.rooms {
+            feed(&room.name);
+            room.rows.iter().for_each(|r| feed(r));
+        }
+    }
+    h
+}
+
+/// The installed pack with this `pack_hash`.
+pub fn find_pack(hash: u64, config: &GameConfig) -> Option<PackInfo> {
+    scan_packs(config).into_iter().find(|p| pack_hash(&p.path, config) == hash)
+}
+
 /// Switch active pack, reload level list.
 pub fn switch_pack(world: &mut WorldState, pack: &PackInfo, config: &GameConfig) {
     world.active_pack = pack.name.clone();
     world.active_pack_path = pack.path.clone();
     world.level_names = get_level_list_for_pack(world, config);
     world.total_levels = world.level_names.len();
+    world.ng_plus = save::pack_cleared(&world.active_pack_path, world.total_levels);
+    world.invisible_guards &= world.ng_plus;
+}
+
+// ══════════════════════════════════════════════════════════════
+// Pack management (pack selector)
+// ══════════════════════════════════════════════════════════════
+
+/// Whether `pack` is a `.nlp` file on disk (the built-ins can't be managed).
+pub fn is_pack_file(pack: &PackInfo) -> bool {
+    !pack.path.starts_with("__")
+}
+
+/// Delete a pack file.
+pub fn delete_pack(pack: &PackInfo) -> std::io::Result<()> {
+    std::fs::remove_file(&pack.path)
+}
+
+/// Rename a pack file to `<stem>.nlp` in the same folder. Returns the new
+/// path. Refuses names with path separators and existing files.
+pub fn rename_pack(pack: &PackInfo, stem: &str) -> std::io::Result<PathBuf> {
+    use std::io::{Error, ErrorKind};
+    let stem = stem.trim();
+    if stem.is_empty() || stem.starts_with('.') || stem.contains(['/', '\\']) {
+        return Err(Error::new(ErrorKind::InvalidInput, t!("packs.bad_name")));
+    }
+    let from = Path::new(&pack.path);
+    let to = from.with_file_name(format!("{stem}.nlp"));
+    if to.exists() {
+        return Err(Error::new(ErrorKind::AlreadyExists, t!("packs.exists")));
+    }
+    std::fs::rename(from, &to)?;
+    Ok(to)
+}
+
+// ══════════════════════════════════════════════════════════════
+// Pack builder
+// ══════════════════════════════════════════════════════════════
+
+/// Individual level files the pack builder can use, checked, sorted by
+/// file name.
+pub fn level_files(config: &GameConfig) -> Vec<LevelFile> {
+    let mut files: Vec<LevelFile> = load_from_directory(&config.levels_dir)
+        .into_iter()
+        .map(|(file, def)| LevelFile {
+            file,
+            warnings: validate_level(&def),
+            name: def.name,
+            solver_tried: false,
+        })
+        .collect();
+    files.sort_by(|a, b| a.file.cmp(&b.file));
+    files
+}
+
+/// Problems that would spoil a level in play: the spawn, tokens, exit,
+/// doors, and sentinels placed on top of the player.
+pub fn validate_level(def: &LevelDef) -> Vec<String> {
+    let mut warnings = vec![];
+    let rooms: Vec<&[String]> = std::iter::once(&def.rows[..])
+        .chain(def.rooms.iter().map(|r| &r.rows[..]))
+        .collect();
+    let cells = |rows: &[String], want: char| -> Vec<(usize, usize)> {
+        rows.iter().enumerate()
+            .flat_map(|(y, row)| row.chars().enumerate()
+                .filter(move |&(_, c)| c == want)
+                .map(move |(x, _)| (x, y)))
+            .collect()
+    };
+
+    // The spawn is only read from the first room
+    let spawns = cells(rooms[0], 'P');
+    match spawns.len() {
+        0 => warnings.push(t!("check.no_spawn").to_string()),
+        1 => {}
+        n => warnings.push(t!("check.many_spawns", n = n)),
+    }
+
+    if rooms.iter().all(|rows| tile::TOKENS.iter().all(|k| cells(rows, k.ch).is_empty())) {
+        warnings.push(t!("check.no_tokens").to_string());
+    }
+
+    // The exit extends `^` columns, `~` cells, or else every ladder
+    let exit = ['^', '~', 'H'].iter().any(|&c| rooms.iter().any(|rows| !cells(rows, c).is_empty()));
+    if !exit {
+        warnings.push(t!("check.no_exit").to_string());
+    }
+
+    // A sentinel on or next to the spawn catches the player at once
+    if let [(px, py)] = spawns[..] {
+        if cells(rooms[0], 'E').iter().any(|&(x, y)| x.abs_diff(px) <= 1 && y.abs_diff(py) <= 1) {
+            warnings.push(t!("check.guard_at_spawn").to_string());
+        }
+    }
+
+    // Doors link rooms in pairs (hub doors launch levels instead)
+    if !def.hub && rooms.len() > 1 {
+        for id in '1'..='9' {
+            let count: usize = rooms.iter().map(|rows| cells(rows, id).len()).sum();
+            if count == 1 {
+                warnings.push(t!("check.door_unpaired", id = id));
+            }
+        }
+    }
+    warnings
+}
+
+/// Run the solver over the builder's picked levels that have no other
+/// warnings, noting those it couldn't verify can be cleared: it gives up
+/// on some levels that can, so this is a prompt to play it. It takes up to a few tenths
+/// of a second a level, so this waits for W rather than the file list, and
+/// each file is only tried once.
+pub fn check_solvable(config: &GameConfig, builder: &mut PackBuilder) {
+    for &i in &builder.order {
+        let level = &mut builder.files[i];
+        if level.solver_tried || !level.warnings.is_empty() { continue; }
+        level.solver_tried = true;
+        let def = std::fs::read_to_string(config.levels_dir.join(&level.file))
+            .ok()
+            .and_then(|content| parse_level_file(&content));
+        let Some(def) = def else { continue };
+        if !def.hub && def.rooms.is_empty() && solver::solve(&def, config).is_none() {
+            level.warnings.push(t!("check.unverified").to_string());
+        }
+    }
+}
+
+/// Write the builder's pack into a `packs/` folder: its metadata, then
+/// each chosen level file as written, in order. Returns the new path.
+/// An existing pack file is never overwritten.
+pub fn write_pack(config: &GameConfig, builder: &PackBuilder) -> std::io::Result<PathBuf> {
+    use std::io::{Error, ErrorKind};
+    let [name, author, description] = builder.fields.each_ref().map(|f| f.trim());
+
+    let mut out = format!("## {name}\n");
+    if !author.is_empty() {
+        out.push_str(&format!("## Author: {author}\n"));
+    }
+    if !description.is_empty() {
+        out.push_str(&format!("## Description: {description}\n"));
+    }
+    for &i in &builder.order {
+        let file = &builder.files[i].file;
+        let content = std::fs::read_to_string(config.levels_dir.join(file))?;
+        out.push_str("---\n");
+        out.push_str(content.trim_end());
+        out.push('\n');
+    }
+
+    let stem = file_stem(name, "pack");
+    let dir = pack_search_dirs().into_iter()
+        .map(|d| d.join("packs"))
+        .find(|d| d.is_dir())
+        .unwrap_or_else(|| PathBuf::from("packs"));
+    std::fs::create_dir_all(&dir)?;
+    let path = dir.join(format!("{stem}.nlp"));
+    if path.exists() {
+        return Err(Error::new(ErrorKind::AlreadyExists, t!("packs.exists")));
+    }
+    std::fs::write(&path, out)?;
+    Ok(path)
+}
+
+// ══════════════════════════════════════════════════════════════
+// Level sharing
+// ══════════════════════════════════════════════════════════════
+
+/// Level `idx` of the active pack.
+pub fn active_level(world: &WorldState, idx: usize, config: &GameConfig) -> Option<LevelDef> {
+    load_levels_for_active_pack(world, config).into_iter().nth(idx)
+}
+
+/// `def` as a single-level file that stands on its own: its text, after
+/// the pack's custom tiles its map uses (those it doesn't define itself).
+pub fn share_text(def: &LevelDef) -> String {
+    let used = |ch: char| std::iter::once(&def.rows).chain(def.rooms.iter().map(|r| &r.rows))
+        .any(|rows| rows.iter().any(|row| row.contains(ch)));
+    let own = |ch: char| def.source.lines()
+        .filter_map(|l| l.trim().strip_prefix("## Tile:"))
+        .any(|rest| rest.trim_start().starts_with(ch));
+    let mut out = String::new();
+    for t in def.tiles.iter().filter(|t| used(t.ch) && !own(t.ch)) {
+        let (r, g, b) = t.color;
+        out.push_str(&format!("## Tile: {} glyph={} color=#{r:02x}{g:02x}{b:02x}", t.ch, t.glyph));
+        for (on, flag) in [(t.solid, "solid"), (t.climbable, "climbable"), (t.diggable, "diggable"),
+                           (t.hangable, "hangable"), (t.deadly, "deadly")] {
+            if on {
+                out.push(' ');
+                out.push_str(flag);
+            }
+        }
+        out.push('\n');
+    }
+    out.push_str(&def.source);
+    out.push('\n');
+    out
+}
+
+/// Write a shared level into `levels/` as a file named after it (with a
+/// number added if that's taken). Returns the file name.
+pub fn import_level(config: &GameConfig, text: &str) -> std::io::Result<String> {
+    use std::io::{Error, ErrorKind};
+    let def = parse_level_file(text)
+        .ok_or_else(|| Error::new(ErrorKind::InvalidData, t!("share.no_level")))?;
+    let dir = &config.levels_dir;
+    std::fs::create_dir_all(dir)?;
+    let stem = file_stem(&def.name, "level");
+    let file = (1..).map(|n| if n == 1 { format!("{stem}.txt") } else { format!("{stem}-{n}.txt") })
+        .find(|f| !dir.join(f).exists())
+        .unwrap();
+    std::fs::write(dir.join(&file), text.trim_end().to_string() + "\n")?;
+    Ok(file)
+}
+
+/// `name` as a file name stem: lowercase letters, digits and `-`, the
+/// rest `_`; `fallback` if nothing's left.
+fn file_stem(name: &str, fallback: &str) -> String {
+    let stem: String = name.chars()
+        .map(|c| if c.is_alphanumeric() || c == '-' { c.to_ascii_lowercase() } else { '_' })
+        .collect();
+    match stem.trim_matches('_') {
+        "" => fallback.to_string(),
+        s => s.to_string(),
+    }
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -213,8 +733,14 @@ pub fn switch_pack(world: &mut WorldState, pack: &PackInfo, config: &GameConfig)
 // ══════════════════════════════════════════════════════════════
 
 fn load_levels_for_active_pack(world: &WorldState, config: &GameConfig) -> Vec<LevelDef> {
-    match world.active_pack_path.as_str() {
+    load_pack(&world.active_pack_path, config)
+}
+
+/// Levels of the pack at `path` (a `PackInfo::path`).
+fn load_pack(path: &str, config: &GameConfig) -> Vec<LevelDef> {
+    match path {
         "__embedded__" => embedded_levels(),
+        "__tutorial__" => parse_pack_levels(tutorial::PACK),
         "__levels__" => {
             let dir = &config.levels_dir;
             if dir.is_dir() {
@@ -248,14 +774,19 @@ fn parse_pack_info(content: &str, path: &Path) -> PackInfo {
     let mut name = String::new();
     let mut author = String::new();
     let mut description = String::new();
+    let mut credits = vec![];
 
     // Read metadata from `##` lines at the top
     for line in content.lines() {
         let trimmed = line.trim();
-        if trimmed.starts_with("## Author:") {
+        if trimmed == "## Hub" || trimmed.starts_with("## Tile:") {
+            continue;
+        } else if trimmed.starts_with("## Author:") {
             author = trimmed["## Author:".len()..].trim().to_string();
         } else if trimmed.starts_with("## Description:") {
             description = trimmed["## Description:".len()..].trim().to_string();
+        } else if let Some(credit) = trimmed.strip_prefix("## Credit:") {
+            credits.push(credit.trim().to_string());
         } else if trimmed.starts_with("##") {
             if name.is_empty() {
                 name = trimmed[2..].trim().to_string();
@@ -265,10 +796,14 @@ fn parse_pack_info(content: &str, path: &Path) -> PackInfo {
         }
     }
 
-    // Count levels by counting `---` separators
+    // Count levels by counting `---` separators, less the story pages
+    let stories = content.lines()
+        .filter(|l| l.trim() == "@story" || l.trim().starts_with("@story "))
+        .count();
     let level_count = content.lines()
         .filter(|l| l.trim() == "---")
         .count()
+        .saturating_sub(stories)
         .max(1); // At least 1 level if file exists
 
     // Fallback name from filename
@@ -285,6 +820,7 @@ fn parse_pack_info(content: &str, path: &Path) -> PackInfo {
         description,
         level_count,
         path: path.to_string_lossy().to_string(),
+        credits,
     }
 }
 
@@ -293,16 +829,30 @@ fn parse_pack_levels(content: &str) -> Vec<LevelDef> {
     let mut levels = vec![];
     let mut current_section = String::new();
     let mut in_levels = false;
+    let mut hub = false;
+    let mut story: Vec<StoryPage> = vec![];
+    let mut tiles: Vec<TileDef> = vec![];
 
     for line in content.lines() {
         let trimmed = line.trim();
 
+        if !in_levels && trimmed == "## Hub" {
+            hub = true;
+            continue;
+        }
+        if let Some(rest) = trimmed.strip_prefix("## Tile:").filter(|_| !in_levels) {
+            if let Some(def) = parse_tile_def(rest) {
+                if tiles.len() < 256 && tiles.iter().all(|t| t.ch != def.ch) {
+                    tiles.push(def);
+                }
+            }
+            continue;
+        }
+
         if trimmed == "---" {
-            // Flush previous section as a level
+            // Flush previous section as a story page or a level
             if in_levels && !current_section.is_empty() {
-                if let Some(def) = parse_level_file(&current_section) {
-                    levels.push(def);
-                }
+                flush_section(&current_section, &mut levels, &mut story);
             }
             current_section.clear();
             in_levels = true;
@@ -320,27 +870,118 @@ fn parse_pack_levels(content: &str) -> Vec<LevelDef> {
 
     // Flush last section
     if !current_section.is_empty() {
-        if let Some(def) = parse_level_file(&current_section) {
-            levels.push(def);
-        }
+        flush_section(&current_section, &mut levels, &mut story);
+    }
+
+    if let Some(first) = levels.first_mut() {
+        first.hub = hub;
+    }
+    if let Some(last) = levels.last_mut() {
+        last.epilogue = story;
+    }
+    for level in &mut levels {
+        level.tiles = tiles.clone();
     }
 
     levels
 }
 
+/// A pack section is a story page (kept for the next level) or a level.
+fn flush_section(section: &str, levels: &mut Vec<LevelDef>, story: &mut Vec<StoryPage>) {
+    if let Some(page) = story::parse_page(section) {
+        story.push(page);
+    } else if let Some(mut def) = parse_level_file(section) {
+        def.story = std::mem::take(story);
+        levels.push(def);
+    }
+}
+
+/// Map chars the tile legend already uses (or that start other lines).
+const RESERVED_TILE_CHARS: &str = "#=H-$%&PE^~TS!RGL123456789D.@[";
+
+/// Parse the rest of a `## Tile:` line: `X glyph=▒ color=#ff4040 solid`.
+/// Unknown options are ignored; a reserved or non-ASCII char is rejected.
+fn parse_tile_def(rest: &str) -> Option<TileDef> {
+    let mut words = rest.split_whitespace();
+    let mut chars = words.next()?.chars();
+    let ch = chars.next()?;
+    if chars.next().is_some() || !ch.is_ascii_graphic() || RESERVED_TILE_CHARS.contains(ch) {
+        return None;
+    }
+
+    let mut def = TileDef {
+        ch,
+        glyph: ch,
+        color: (200, 200, 200),
+        solid: false,
+        climbable: false,
+        diggable: false,
+        hangable: false,
+        deadly: false,
+    };
+    for word in words {
+        match word.split_once('=') {
+            Some(("glyph", g)) => def.glyph = g.chars().next().unwrap_or(ch),
+            Some(("color", c)) => def.color = crate::config::hex_color(c).unwrap_or(def.color),
+            _ => match word {
+                "solid" => def.solid = true,
+                "climbable" => def.climbable = true,
+                "diggable" => def.diggable = true,
+                "hangable" => def.hangable = true,
+                "deadly" => def.deadly = true,
+                _ => {}
+            },
+        }
+    }
+    Some(def)
+}
+
 // ══════════════════════════════════════════════════════════════
 // Single-level file parsing
 // ══════════════════════════════════════════════════════════════
 
 /// Parse a single level from text content.
-fn parse_level_file(content: &str) -> Option<LevelDef> {
+pub fn parse_level_file(content: &str) -> Option<LevelDef> {
     let mut name = String::new();
-    let mut rows = vec![];
-    let mut extra_hidden_ladders = vec![];
+    // One section per room; the first is the level itself
+    let mut sections = vec![RoomDef::new("")];
+    let mut dig_limit = None;
+    let mut darkness = None;
+    let mut spawn = None;
+    let mut par = None;
+    let mut tips = vec![];
+    let mut scripts = vec![];
+    let mut tiles: Vec<TileDef> = vec![];
 
     for line in content.lines() {
-        if line.starts_with('#') && name.is_empty() && is_name_line(line) {
+        let RoomDef { rows, extra_hidden_ladders, .. } = sections.last_mut().unwrap();
+        if let Some(rest) = line.trim().strip_prefix("## Tile:") {
+            if let Some(def) = parse_tile_def(rest) {
+                if tiles.len() < 256 && tiles.iter().all(|t| t.ch != def.ch) {
+                    tiles.push(def);
+                }
+            }
+        } else if line.starts_with('#') && name.is_empty() && is_name_line(line) {
             name = line[1..].trim().to_string();
+        } else if let Some(room) = room_header(line) {
+            sections.push(RoomDef::new(room));
+        } else if let Some(n) = line.strip_prefix("@digs ") {
+            dig_limit = n.trim().parse().ok();
+        } else if let Some(n) = line.strip_prefix("@dark ") {
+            darkness = n.trim().parse::<u32>().ok().map(|n| n.clamp(1, MAX_LIGHT));
+        } else if let Some(rest) = line.strip_prefix("@spawn ") {
+            let mut n = rest.split_whitespace().map(|v| v.parse::<u32>());
+            if let (Some(Ok(every)), Some(Ok(cap))) = (n.next(), n.next()) {
+                spawn = Some((every.max(1), cap));
+            }
+        } else if let Some(n) = line.strip_prefix("@par ") {
+            par = n.trim().parse().ok();
+        } else if let Some(rest) = line.strip_prefix("@tag ") {
+            tips.extend(tutorial::parse_tag(rest));
+        } else if let Some(rest) = line.strip_prefix("@tip ") {
+            tips.extend(tutorial::parse_tip(rest));
+        } else if let Some(rest) = line.strip_prefix("@on ") {
+            scripts.extend(script::parse_on(rest));
         } else if line.starts_with("@ ") {
             for pair in line[2..].split_whitespace() {
                 let parts: Vec<&str> = pair.split(',').collect();
@@ -355,26 +996,56 @@ fn parse_level_file(content: &str) -> Option<LevelDef> {
         }
     }
 
-    while rows.last().map_or(false, |r| r.trim().is_empty()) {
-        rows.pop();
+    for room in &mut sections {
+        normalize_rows(&mut room.rows);
     }
-
-    if rows.is_empty() {
+    sections.retain(|room| !room.rows.is_empty());
+    if sections.is_empty() {
         return None;
     }
 
+    if name.is_empty() {
+        name = "Unnamed Node".to_string();
+    }
+
+    let first = sections.remove(0);
+    Some(LevelDef {
+        name,
+        rows: first.rows,
+        extra_hidden_ladders: first.extra_hidden_ladders,
+        rooms: sections,
+        hub: false,
+        dig_limit,
+        darkness,
+        spawn,
+        par,
+        tips,
+        scripts,
+        tiles,
+        story: vec![],
+        epilogue: vec![],
+        source: content.trim_end().to_string(),
+    })
+}
+
+/// `[Server Room]` → `Some("Server Room")`.
+fn room_header(line: &str) -> Option<&str> {
+    let name = line.trim().strip_prefix('[')?.strip_suffix(']')?.trim();
+    (!name.is_empty()).then_some(name)
+}
+
+/// Drop trailing blank rows and pad the rest to a common width.
+fn normalize_rows(rows: &mut Vec<String>) {
+    while rows.last().map_or(false, |r| r.trim().is_empty()) {
+        rows.pop();
+    }
+
     let max_width = rows.iter().map(|r| r.len()).max().unwrap_or(0);
-    for row in &mut rows {
+    for row in rows.iter_mut() {
         if row.len() < max_width {
             row.extend(std::iter::repeat(' ').take(max_width - row.len()));
         }
     }
-
-    if name.is_empty() {
-        name = "Unnamed Node".to_string();
-    }
-
-    Some(LevelDef { name, rows, extra_hidden_ladders })
 }
 
 /// Distinguish `#Level Name` from `############################` (level data).
@@ -559,5 +1230,17 @@ fn make_embedded(name: &str, map: &[&str]) -> LevelDef {
         name: name.to_string(),
         rows: map.iter().map(|s| s.to_string()).collect(),
         extra_hidden_ladders: vec![],
+        rooms: vec![],
+        hub: false,
+        dig_limit: None,
+        darkness: None,
+        spawn: None,
+        par: None,
+        tips: vec![],
+        scripts: vec![],
+        tiles: vec![],
+        story: vec![],
+        epilogue: vec![],
+        source: format!("# {name}\n{}", map.join("\n")),
     }
 }
@@ -0,0 +1,78 @@
+//! Level codes: one level as a line of text, for sharing in chat.
+//!
+//! A code is a level file (`level::share_text`: the level as written,
+//! with any custom tiles it uses) run-length encoded and sealed like a run
+//! code (`run_code::seal`: CRC-32, base64). Maps are mostly runs of the
+//! same tile, so a 28×16 level comes to a few hundred characters.
+//!
+//! Run-length encoding: `0x00 n b` is `n` (4-255) copies of byte `b`;
+//! any other byte is itself. Level text never holds a NUL.
+
+use crate::sim::run_code::{seal, unseal};
+
+/// First byte of a level code (a run code's is 1).
+const FORMAT: u8 = b'L';
+/// Shortest run worth encoding as one.
+const MIN_RUN: usize = 4;
+
+pub fn encode(text: &str) -> String {
+    let bytes = text.as_bytes();
+    let mut out = vec![FORMAT];
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i];
+        let n = bytes[i..].iter().take(255).take_while(|&&x| x == b).count();
+        if n >= MIN_RUN {
+            out.extend([0, n as u8, b]);
+        } else {
+            out.extend(std::iter::repeat_n(b, n));
+        }
+        i += n;
+    }
+    seal(out)
+}
+
+/// The level text in `code`, if it's an intact level code.
+pub fn decode(code: &str) -> Option<String> {
+    let bytes = unseal(code)?;
+    let (&FORMAT, mut rest) = bytes.split_first()? else { return None };
+    let mut out = vec![];
+    while let Some((&b, tail)) = rest.split_first() {
+        if b == 0 {
+            let (&[n, b], tail) = tail.split_first_chunk()?;
+            out.extend(std::iter::repeat_n(b, n as usize));
+            rest = tail;
+        } else {
+            out.push(b);
+            rest = tail;
+        }
+    }
+    String::from_utf8(out).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::level;
+
+    #[test]
+    fn levels_survive_a_code() {
+        let text = "## Tile: X glyph=▒ color=#ff4040 solid deadly\n\
+                    # Vault – 金庫\n\
+                    @digs 3\n\
+                    P      $      XX\n\
+                    ##########H#####\n\
+                    ================\n";
+        let code = encode(text);
+        assert!(code.len() < text.len() * 4 / 3, "{code}");
+        assert_eq!(decode(&code).as_deref(), Some(text));
+
+        let def = level::parse_level_file(&decode(&code).unwrap()).unwrap();
+        assert_eq!(def.name, "Vault – 金庫");
+        assert_eq!((def.dig_limit, def.tiles.len()), (Some(3), 1));
+        assert_eq!(level::share_text(&def), text);
+
+        assert_eq!(decode(&code[..code.len() - 2]), None);
+        assert_eq!(decode(&crate::sim::run_code::seal(vec![1, 2, 3])), None);
+    }
+}
@@ -1,5 +1,27 @@
+pub mod agent;
+pub mod credits;
 pub mod event;
+pub mod event_log;
 pub mod level;
+pub mod level_code;
+pub mod online;
+pub mod randomize;
+#[cfg(test)]
+pub mod replay;
+pub mod run_code;
 pub mod save;
+pub mod script;
+pub mod solver;
+pub mod spectate;
 pub mod step;
+pub mod story;
+pub mod system;
+pub mod timing;
+pub mod tutorial;
+#[cfg(feature = "verify")]
+pub mod verify;
 pub mod world;
+
+pub use step::run_ticks;
+#[cfg(feature = "verify")]
+pub use verify::verify;
@@ -0,0 +1,183 @@
+//! Opt-in global leaderboard over HTTPS (`network` feature).
+//!
+//! Requests run on a background thread so a slow or unreachable server
+//! never stalls the game loop; `poll` picks up whatever has arrived.
+//! Without the feature, or with `[online] enabled = false`, `Online::new`
+//! returns `None` and nothing ever leaves the machine.
+
+use crate::config::OnlineConfig;
+use crate::sim::world::{GameMode, Phase, WorldState};
+
+#[cfg(feature = "network")]
+mod inner {
+    use std::sync::mpsc::{self, Receiver, Sender};
+    use std::thread;
+    use std::time::Duration;
+
+    use serde::{Deserialize, Serialize};
+
+    use crate::config::OnlineConfig;
+    use crate::sim::world::GlobalEntry;
+
+    /// Identifies a node's board on the server: normal and time attack
+    /// clears are ranked apart.
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct NodeKey {
+        pub pack: String,
+        pub level: usize,
+        pub level_hash: u64,
+        pub mode: &'static str,
+    }
+
+    /// A clear; `score` is null in time attack, which ranks on time alone.
+    #[derive(Serialize)]
+    struct Submission<'a> {
+        pack: &'a str,
+        level: usize,
+        level_hash: String,
+        mode: &'a str,
+        name: &'a str,
+        score: Option<u32>,
+        time_ms: u64,
+    }
+
+    #[derive(Deserialize)]
+    struct Row {
+        name: String,
+        #[serde(default)]
+        score: Option<u32>,
+        time_ms: u64,
+    }
+
+    pub struct Client {
+        endpoint: String,
+        name: String,
+        tx: Sender<(NodeKey, Vec<GlobalEntry>)>,
+        rx: Receiver<(NodeKey, Vec<GlobalEntry>)>,
+    }
+
+    impl Client {
+        pub fn new(cfg: &OnlineConfig) -> Option<Self> {
+            if !cfg.enabled || cfg.endpoint.is_empty() { return None; }
+            let (tx, rx) = mpsc::channel();
+            let name = if cfg.name.trim().is_empty() { "anonymous" } else { cfg.name.trim() };
+            Some(Client { endpoint: cfg.endpoint.clone(), name: name.to_string(), tx, rx })
+        }
+
+        /// Submit a clear (if any), then fetch the node's top 10.
+        pub fn request(&self, key: NodeKey, clear: Option<(Option<u32>, u64)>) {
+            let url = format!("{}/scores", self.endpoint);
+            let name = self.name.clone();
+            let tx = self.tx.clone();
+            thread::spawn(move || {
+                let agent = ureq::AgentBuilder::new().timeout(Duration::from_secs(5)).build();
+                let hash = format!("{:016x}", key.level_hash);
+                if let Some((score, time_ms)) = clear {
+                    let sub = Submission {
+                        pack: &key.pack,
+                        level: key.level,
+                        level_hash: hash.clone(),
+                        mode: key.mode,
+                        name: &name,
+                        score,
+                        time_ms,
+                    };
+                    // Failed submissions are dropped: the board is best-effort
+                    let _ = agent.post(&url).send_json(&sub);
+                }
+                let Ok(resp) = agent.get(&url)
+                    .query("pack", &key.pack)
+                    .query("level", &key.level.to_string())
+                    .query("level_hash", &hash)
+                    .query("mode", key.mode)
+                    .call()
+                else { return };
+                let Ok(rows) = resp.into_json::<Vec<Row>>() else { return };
+                let top = rows.into_iter()
+                    .take(10)
+                    .map(|r| GlobalEntry { name: r.name, score: r.score, time_ms: r.time_ms })
+                    .collect();
+                let _ = tx.send((key, top));
+            });
+        }
+
+        pub fn try_recv(&self) -> Option<(NodeKey, Vec<GlobalEntry>)> {
+            self.rx.try_recv().ok()
+        }
+    }
+}
+
+#[cfg(not(feature = "network"))]
+mod inner {
+    use crate::config::OnlineConfig;
+    use crate::sim::world::GlobalEntry;
+
+    #[derive(Clone, Debug, PartialEq, Eq)]
+    pub struct NodeKey {
+        pub pack: String,
+        pub level: usize,
+        pub level_hash: u64,
+        pub mode: &'static str,
+    }
+
+    pub struct Client;
+
+    impl Client {
+        pub fn new(_cfg: &OnlineConfig) -> Option<Self> { None }
+        pub fn request(&self, _key: NodeKey, _clear: Option<(Option<u32>, u64)>) {}
+        pub fn try_recv(&self) -> Option<(NodeKey, Vec<GlobalEntry>)> { None }
+    }
+}
+
+use inner::{Client, NodeKey};
+
+/// Keeps `world.global_board` in step with the node being played.
+pub struct Online {
+    client: Client,
+    /// Node the board currently shows (or was last requested for).
+    current: Option<NodeKey>,
+}
+
+impl Online {
+    pub fn new(cfg: &OnlineConfig) -> Option<Self> {
+        Client::new(cfg).map(|client| Online { client, current: None })
+    }
+
+    fn key(world: &WorldState) -> NodeKey {
+        NodeKey {
+            pack: world.active_pack.clone(),
+            level: world.current_level,
+            level_hash: world.level_hash(),
+            mode: match world.mode {
+                GameMode::Normal => "normal",
+                GameMode::TimeAttack => "time_attack",
+            },
+        }
+    }
+
+    /// Call every loop: fetches the board when a new node starts and
+    /// applies responses for the node still on screen.
+    pub fn poll(&mut self, world: &mut WorldState) {
+        while let Some((key, top)) = self.client.try_recv() {
+            if self.current.as_ref() == Some(&key) {
+                world.global_board = top;
+            }
+        }
+        let on_node = matches!(world.phase,
+            Phase::LevelIntro | Phase::LevelReady | Phase::Playing | Phase::LevelComplete);
+        if !on_node || world.in_hub() { return; }
+        let key = Self::key(world);
+        if self.current.as_ref() != Some(&key) {
+            world.global_board.clear();
+            self.client.request(key.clone(), None);
+            self.current = Some(key);
+        }
+    }
+
+    /// Send a clear of the current node. `score` is None in time attack.
+    pub fn submit(&mut self, world: &WorldState, score: Option<u32>, time_ms: u64) {
+        let key = Self::key(world);
+        self.client.request(key.clone(), Some((score, time_ms)));
+        self.current = Some(key);
+    }
+}
@@ -0,0 +1,142 @@
+//! Randomizer mode: every attempt at a level deals its tokens and
+//! sentinels out to new cells, so a level known by heart plays fresh.
+//!
+//! A pass over the level once it's loaded (and again on each restart):
+//! tokens keep their kinds and sentinels their ids, only the cells change.
+//! Tokens go to open cells something can stand on; sentinels to such
+//! cells well away from the player. Each deal is checked with a short
+//! solver search (`solver::reachable`) and, if that finds no way through,
+//! dealt again; after `MAX_DEALS` the level keeps its own layout. Deals follow from the run's seed, the
+//! level and the attempt, so the same seed deals the same levels.
+//!
+//! Single-room levels only, like the solver.
+
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::domain::tile::Tile;
+use crate::sim::solver;
+use crate::sim::world::WorldState;
+
+/// Deals tried before giving up on an attempt.
+const MAX_DEALS: u64 = 4;
+
+/// How near (in moves, ignoring walls) a sentinel may be dealt to the
+/// player's start.
+const GUARD_CLEARANCE: usize = 6;
+
+type Cell = (usize, usize);
+
+/// The run's seed and how many attempts the current level has had.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Randomizer {
+    pub seed: u64,
+    pub attempt: u32,
+}
+
+impl Randomizer {
+    /// A new run's randomizer, seeded from the clock. Seeds fit in 32
+    /// bits so the one on the title screen is the whole of it.
+    pub fn from_clock() -> Self {
+        let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.subsec_nanos() ^ d.as_secs() as u32);
+        Randomizer { seed: nanos as u64, attempt: 0 }
+    }
+}
+
+/// Deal the current level out afresh. False if it kept its own layout.
+pub fn deal(world: &mut WorldState) -> bool {
+    let Some(r) = world.randomizer else { return false };
+    if world.is_multi_room() { return false; }
+    let level_seed = mix(mix(r.seed ^ world.current_level as u64) ^ r.attempt as u64);
+
+    let tokens: Vec<(Cell, Tile)> = cells(world)
+        .map(|c| (c, world.base_tiles[c]))
+        .filter(|(_, t)| t.is_gold())
+        .collect();
+    if tokens.is_empty() { return false; }
+    let level_guards = world.level_guards;
+    let guards: Vec<(usize, Cell)> = world.guards.iter().enumerate()
+        .filter(|(_, g)| g.id < level_guards)
+        .map(|(i, g)| (i, (g.spawn_x, g.spawn_y)))
+        .collect();
+
+    for n in 0..MAX_DEALS {
+        let Some((token_cells, guard_cells)) = layout(world, tokens.len(), guards.len(), mix(level_seed + n)) else {
+            return false;
+        };
+        place(world, &tokens.iter().map(|&(_, t)| t).zip(token_cells).collect::<Vec<_>>(),
+              &guards.iter().map(|&(i, _)| i).zip(guard_cells).collect::<Vec<_>>());
+        if solver::reachable(world) { return true; }
+    }
+    // Nothing dealt holds up: back as it was
+    place(world, &tokens.iter().map(|&(c, t)| (t, c)).collect::<Vec<_>>(), &guards);
+    false
+}
+
+fn cells(world: &WorldState) -> impl Iterator<Item = Cell> {
+    let width = world.width;
+    (0..world.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+}
+
+/// Cells for `tokens` tokens and `guards` sentinels, shuffled by `seed`,
+/// or `None` if the level hasn't room for them.
+fn layout(world: &WorldState, tokens: usize, guards: usize, seed: u64)
+    -> Option<(Vec<Cell>, Vec<Cell>)>
+{
+    let spawn = world.player_spawn;
+    let mut open: Vec<Cell> = cells(world)
+        .filter(|&c| c != spawn && standable(world, c))
+        .collect();
+    // Fisher–Yates
+    let mut state = seed;
+    for i in (1..open.len()).rev() {
+        state = mix(state);
+        open.swap(i, (state % (i as u64 + 1)) as usize);
+    }
+    if open.len() < tokens { return None; }
+    let guard_cells: Vec<Cell> = open[tokens..].iter()
+        .copied()
+        .filter(|&(x, y)| x.abs_diff(spawn.0) + y.abs_diff(spawn.1) >= GUARD_CLEARANCE)
+        .take(guards)
+        .collect();
+    if guard_cells.len() < guards { return None; }
+    open.truncate(tokens);
+    Some((open, guard_cells))
+}
+
+/// Open now or under a token, with ground, a ladder or the floor of the
+/// level beneath it.
+fn standable(world: &WorldState, (x, y): Cell) -> bool {
+    let tile = world.base_tiles[(x, y)];
+    if tile != Tile::Empty && !tile.is_gold() { return false; }
+    y + 1 == world.height || {
+        let below = world.base_tiles[(x, y + 1)];
+        below.is_solid() || below.is_climbable()
+    }
+}
+
+/// Put tokens and sentinels (by index into `world.guards`) on their cells,
+/// as the level's own layout from here on.
+fn place(world: &mut WorldState, tokens: &[(Tile, Cell)], guards: &[(usize, Cell)]) {
+    for (x, y) in cells(world).collect::<Vec<_>>() {
+        if world.base_tiles[(x, y)].is_gold() {
+            world.base_tiles[(x, y)] = Tile::Empty;
+        }
+    }
+    for &(tile, c) in tokens {
+        world.base_tiles[c] = tile;
+    }
+    world.reset_tiles();
+    for &(i, (x, y)) in guards {
+        let g = &mut world.guards[i];
+        (g.x, g.y, g.spawn_x, g.spawn_y) = (x, y, x, y);
+    }
+    world.rebuild_occupancy();
+}
+
+/// splitmix64's finalizer.
+fn mix(mut z: u64) -> u64 {
+    z = z.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+}
@@ -0,0 +1,228 @@
+//! Golden replays: regression tests for the simulation.
+//!
+//! A replay in `replays/` names a file in `levels/` and gives the input for
+//! every tick, and the hash of the world after each tick as it was when
+//! the replay was recorded. Running it through `step` again has to give
+//! the same hashes, so a change to `step.rs`, `physics.rs` or `ai.rs` that
+//! alters play in any way fails the test, at the tick it first shows.
+//!
+//! ```text
+//! level=012.txt
+//! inputs=3R 1z 12. 4U …
+//! golden=9f3a01c2 77d0e4a1 …
+//! ```
+//!
+//! Inputs are `<ticks><move><hack>`: a move of `L` `R` `U` `D` or `.` for
+//! none, then `z` / `x` to hack left / right. `golden` may span lines.
+//! When a change to play is meant, re-record the hashes with
+//! `NODERUNNER_BLESS=1 cargo test golden` and commit them with it.
+
+use std::path::{Path, PathBuf};
+
+use crate::config::GameConfig;
+use crate::domain::entity::{Facing, FrameInput, MoveDir};
+use crate::sim::level;
+use crate::sim::step;
+use crate::sim::world::{Phase, WorldState};
+
+/// Hashes per `golden=` line when writing.
+const HASHES_PER_LINE: usize = 12;
+
+#[derive(Clone, Debug, Default)]
+pub struct Replay {
+    pub level: String,
+    pub inputs: Vec<FrameInput>,
+    pub golden: Vec<u32>,
+}
+
+impl Replay {
+    pub fn parse(text: &str) -> Option<Replay> {
+        let mut replay = Replay::default();
+        for line in text.lines().map(str::trim) {
+            if let Some(file) = line.strip_prefix("level=") {
+                replay.level = file.trim().to_string();
+            } else if let Some(runs) = line.strip_prefix("inputs=") {
+                for run in runs.split_whitespace() {
+                    let (ticks, input) = parse_run(run)?;
+                    replay.inputs.extend(std::iter::repeat_n(input, ticks));
+                }
+            } else if let Some(hashes) = line.strip_prefix("golden=") {
+                for hash in hashes.split_whitespace() {
+                    replay.golden.push(u32::from_str_radix(hash, 16).ok()?);
+                }
+            }
+        }
+        (!replay.level.is_empty()).then_some(replay)
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut runs: Vec<(usize, FrameInput)> = vec![];
+        for &input in &self.inputs {
+            match runs.last_mut() {
+                Some((n, last)) if *last == input => *n += 1,
+                _ => runs.push((1, input)),
+            }
+        }
+        let runs: Vec<String> = runs.iter().map(|&(n, input)| format!("{n}{}", input_code(input))).collect();
+        let mut out = format!("level={}\ninputs={}\n", self.level, runs.join(" "));
+        for chunk in self.golden.chunks(HASHES_PER_LINE) {
+            let hashes: Vec<String> = chunk.iter().map(|h| format!("{h:08x}")).collect();
+            out.push_str(&format!("golden={}\n", hashes.join(" ")));
+        }
+        out
+    }
+}
+
+fn parse_run(run: &str) -> Option<(usize, FrameInput)> {
+    let split = run.find(|c: char| !c.is_ascii_digit())?;
+    let (ticks, code) = run.split_at(split);
+    let mut chars = code.chars();
+    let movement = match chars.next()? {
+        'L' => Some(MoveDir::Left),
+        'R' => Some(MoveDir::Right),
+        'U' => Some(MoveDir::Up),
+        'D' => Some(MoveDir::Down),
+        '.' => None,
+        _ => return None,
+    };
+    let dig = match chars.next() {
+        Some('z') => Some(Facing::Left),
+        Some('x') => Some(Facing::Right),
+        None => None,
+        _ => return None,
+    };
+    Some((ticks.parse().ok()?, FrameInput { movement, dig }))
+}
+
+fn input_code(input: FrameInput) -> String {
+    let movement = match input.movement {
+        Some(MoveDir::Left) => 'L',
+        Some(MoveDir::Right) => 'R',
+        Some(MoveDir::Up) => 'U',
+        Some(MoveDir::Down) => 'D',
+        None => '.',
+    };
+    match input.dig {
+        Some(Facing::Left) => format!("{movement}z"),
+        Some(Facing::Right) => format!("{movement}x"),
+        None => movement.to_string(),
+    }
+}
+
+/// FNV-1a over everything play can change: the map, every actor and
+/// hole, the tokens, score and phase. Folded to 32 bits to keep golden
+/// files small.
+pub fn world_hash(world: &WorldState) -> u32 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    let mut feed = |v: u64| {
+        h ^= v;
+        h = h.wrapping_mul(0x0100_0000_01b3);
+    };
+
+    feed(world.tick);
+    feed(world.phase as u64);
+    feed(world.score as u64);
+    feed(world.gold_remaining as u64);
+    feed(world.exit_enabled as u64);
+    feed(world.current_room as u64);
+    feed(world.digs_left.map_or(u64::MAX, u64::from));
+
+    let p = &world.player;
+    for v in [p.x as u64, p.y as u64, p.facing as u64, p.state as u64, p.alive as u64, p.move_cooldown as u64] {
+        feed(v);
+    }
+    for g in &world.guards {
+        for v in [
+            g.id as u64, g.x as u64, g.y as u64, g.facing as u64, g.state as u64,
+            g.carry_gold as u64, g.carry_gold_timer as u64, g.stuck_timer as u64,
+            g.move_cooldown as u64, g.respawn_timer as u64, g.separation_timer as u64,
+        ] {
+            feed(v);
+        }
+    }
+    for hole in &world.holes {
+        for v in [hole.x as u64, hole.y as u64, hole.open_remaining as u64, hole.close_remaining as u64] {
+            feed(v);
+        }
+    }
+    for dig in &world.digs {
+        for v in [dig.x as u64, dig.y as u64, dig.ticks_remaining as u64] {
+            feed(v);
+        }
+    }
+    for &t in world.tiles.iter() {
+        feed(t.code());
+    }
+    (h ^ (h >> 32)) as u32
+}
+
+/// The world a replay starts from: its level, begun, on `config`. The
+/// golden runs pass `GameConfig::builtin()`, so `config.toml` can't
+/// change the outcome.
+fn start(replay: &Replay, levels_dir: &Path, config: &GameConfig) -> Option<WorldState> {
+    let text = std::fs::read_to_string(levels_dir.join(&replay.level)).ok()?;
+    let def = level::parse_level_file(&text)?;
+    let mut world = WorldState::new();
+    level::load_level_def(&mut world, &def, config);
+    world.phase = Phase::Playing;
+    Some(world)
+}
+
+/// The world hash after every tick of the replay. Stops early once the
+/// level is no longer in play (cleared, or the player caught).
+pub fn run(replay: &Replay, levels_dir: &Path, config: &GameConfig) -> Option<Vec<u32>> {
+    let mut world = start(replay, levels_dir, config)?;
+    let mut hashes = vec![];
+    for &input in &replay.inputs {
+        if step::run_ticks(&mut world, [input], 1).ticks == 0 { break; }
+        hashes.push(world_hash(&world));
+    }
+    Some(hashes)
+}
+
+/// Where a run first differs from the golden hashes: the tick (from 1),
+/// or `None` if it matches throughout.
+pub fn divergence(replay: &Replay, hashes: &[u32]) -> Option<usize> {
+    let same = hashes.iter().zip(&replay.golden).take_while(|(a, b)| a == b).count();
+    (same < hashes.len().max(replay.golden.len())).then_some(same + 1)
+}
+
+/// The bundled replays, by path.
+pub fn bundled(dir: &Path) -> Vec<PathBuf> {
+    let mut paths: Vec<PathBuf> = std::fs::read_dir(dir)
+        .into_iter()
+        .flatten()
+        .flatten()
+        .map(|e| e.path())
+        .filter(|p| p.extension().is_some_and(|e| e == "nrr"))
+        .collect();
+    paths.sort();
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn golden_replays_match() {
+        let root = Path::new(env!("CARGO_MANIFEST_DIR"));
+        let config = GameConfig::builtin();
+        let bless = std::env::var_os("NODERUNNER_BLESS").is_some();
+
+        let paths = bundled(&root.join("replays"));
+        assert!(!paths.is_empty(), "no replays in replays/");
+        for path in paths {
+            let text = std::fs::read_to_string(&path).unwrap();
+            let mut replay = Replay::parse(&text).unwrap_or_else(|| panic!("{}: not a replay", path.display()));
+            let hashes = run(&replay, &root.join("levels"), &config)
+                .unwrap_or_else(|| panic!("{}: can't load {}", path.display(), replay.level));
+            if bless {
+                replay.golden = hashes;
+                std::fs::write(&path, replay.to_text()).unwrap();
+            } else if let Some(tick) = divergence(&replay, &hashes) {
+                panic!("{}: play differs from the golden run at tick {tick}", path.display());
+            }
+        }
+    }
+}
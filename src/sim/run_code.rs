@@ -0,0 +1,315 @@
+//! Run codes: a cleared node as one line of text, for sharing a run
+//! without sending files.
+//!
+//! A code is the base64 of:
+//!   - a format byte, then the pack's hash (`level::pack_hash`) and the
+//!     node's index in it
+//!   - the seed: the sim draws no random numbers, so what else decides
+//!     how a run plays out is the speed settings; the seed is their hash
+//!   - the score the run made, then its input for every tick, run-length
+//!     encoded (an input byte and a count)
+//!   - a CRC-32 of all that, so a code cut short in pasting is refused
+//!
+//! A clear writes its code to `runs/` under the save directory.
+//! Only an attempt played straight through from the node's start makes a
+//! code: a restart, a death or a resumed save leave state behind that the
+//! node's start doesn't have. Pasted on the title screen (P), a code is
+//! watched, or verified without being shown: played again from the
+//! node's start, it has to clear the node on its last tick with the same
+//! score.
+
+use std::io;
+use std::path::PathBuf;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+
+use crate::config::{AiConfig, GameConfig, GuardRespawn};
+use crate::domain::entity::{Facing, FrameInput, MoveDir};
+use crate::sim::level;
+use crate::sim::save;
+use crate::sim::step;
+use crate::sim::world::{GameMode, Phase, WorldState};
+
+const FORMAT: u8 = 1;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct RunCode {
+    pub pack_hash: u64,
+    pub level: usize,
+    pub seed: u32,
+    pub score: u32,
+    pub inputs: Vec<FrameInput>,
+}
+
+/// The attempt being played, as it may become a code.
+#[derive(Clone, Debug, Default)]
+pub struct Recording {
+    pub start_score: u32,
+    pub inputs: Vec<FrameInput>,
+}
+
+/// A code being watched: it supplies the input in place of the player.
+#[derive(Clone, Debug)]
+pub struct Playback {
+    pub code: RunCode,
+    pub pos: usize,
+}
+
+impl Playback {
+    /// The next tick's input, or `None` once the run is over.
+    pub fn next_input(&mut self) -> Option<FrameInput> {
+        let input = self.code.inputs.get(self.pos).copied()?;
+        self.pos += 1;
+        Some(input)
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CodeError {
+    /// Not a run code, or damaged.
+    Malformed,
+    /// None of the installed packs is the one it was played in.
+    NoPack,
+}
+
+/// How playing a code again turned out.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Verified,
+    /// It didn't clear the node when and as the code says.
+    Differs,
+    /// It was played at other speed settings, so it can't be checked here.
+    OtherSettings,
+}
+
+impl RunCode {
+    pub fn encode(&self) -> String {
+        let mut bytes = vec![FORMAT];
+        bytes.extend(self.pack_hash.to_le_bytes());
+        put_varint(&mut bytes, self.level as u64);
+        bytes.extend(self.seed.to_le_bytes());
+        put_varint(&mut bytes, self.score as u64);
+        let mut i = 0;
+        while i < self.inputs.len() {
+            let input = self.inputs[i];
+            let n = self.inputs[i..].iter().take_while(|&&x| x == input).count();
+            bytes.push(input_byte(input));
+            put_varint(&mut bytes, n as u64);
+            i += n;
+        }
+        seal(bytes)
+    }
+
+    pub fn decode(text: &str) -> Result<RunCode, CodeError> {
+        let body = unseal(text).filter(|b| b.first() == Some(&FORMAT)).ok_or(CodeError::Malformed)?;
+        let mut r = Reader(&body[1..]);
+        let pack_hash = u64::from_le_bytes(r.take()?);
+        let level = r.varint()? as usize;
+        let seed = u32::from_le_bytes(r.take()?);
+        let score = u32::try_from(r.varint()?).map_err(|_| CodeError::Malformed)?;
+        let mut inputs = vec![];
+        while let Ok([byte]) = r.take() {
+            let input = byte_input(byte).ok_or(CodeError::Malformed)?;
+            let n = r.varint()? as usize;
+            inputs.extend(std::iter::repeat_n(input, n));
+        }
+        Ok(RunCode { pack_hash, level, seed, score, inputs })
+    }
+}
+
+/// The finished attempt as a code, if it was recorded from the node's start.
+pub fn from_recording(world: &WorldState, config: &GameConfig) -> Option<RunCode> {
+    let rec = world.recording.as_ref()?;
+    Some(RunCode {
+        pack_hash: level::pack_hash(&world.active_pack_path, config),
+        level: world.current_level,
+        seed: seed(world),
+        score: world.score - rec.start_score,
+        inputs: rec.inputs.clone(),
+    })
+}
+
+/// Write `code` to `runs/node-<n>-<unix time>.txt` under the save
+/// directory, for copying out.
+pub fn save(code: &RunCode) -> io::Result<PathBuf> {
+    let dir = save::save_dir().join("runs");
+    std::fs::create_dir_all(&dir)?;
+    let secs = SystemTime::now().duration_since(UNIX_EPOCH).map_or(0, |d| d.as_secs());
+    let path = dir.join(format!("node-{}-{secs}.txt", code.level + 1));
+    std::fs::write(&path, code.encode() + "\n")?;
+    Ok(path)
+}
+
+/// Hash of the settings a run plays out under.
+pub fn seed(world: &WorldState) -> u32 {
+    let s = &world.speed;
+    let mut h: u32 = 0x811c_9dc5;
+    for v in [
+        s.player_move_rate, s.guard_move_rate, s.dig_duration, s.hole_open_ticks, s.hole_close_ticks,
+        s.trap_escape_ticks, s.guard_respawn_ticks, s.gold_carry_ticks, world.dig_limit.unwrap_or(u32::MAX),
+    ] {
+        h = (h ^ v).wrapping_mul(0x0100_0193);
+    }
+    // Only when it isn't the default, so codes made before it was a
+    // setting keep their seed
+    if world.guard_respawn != GuardRespawn::Top {
+        h = (h ^ (world.guard_respawn as u32 + 1)).wrapping_mul(0x0100_0193);
+    }
+    if world.hazards_kill_guards {
+        h = (h ^ 0x4841_5a44).wrapping_mul(0x0100_0193);
+    }
+    if world.mirror {
+        h = (h ^ 0x4d49_5252).wrapping_mul(0x0100_0193);
+    }
+    if world.ai != AiConfig::default() {
+        h = (h ^ 0x4149 ^ world.ai.max_depth).wrapping_mul(0x0100_0193);
+        h = (h ^ world.ai.repath_ticks).wrapping_mul(0x0100_0193);
+    }
+    if let Some(r) = world.randomizer {
+        h = (h ^ 0x5241_4e44 ^ r.seed as u32).wrapping_mul(0x0100_0193);
+    }
+    h
+}
+
+/// Load the code's node into `world` from its start, at no score, in
+/// normal mode (switching to its pack), as it was when the run began.
+pub fn begin(world: &mut WorldState, code: &RunCode, config: &GameConfig) -> Result<(), CodeError> {
+    let pack = level::find_pack(code.pack_hash, config).ok_or(CodeError::NoPack)?;
+    if code.level >= pack.level_count {
+        return Err(CodeError::NoPack);
+    }
+    level::switch_pack(world, &pack, config);
+    world.mode = GameMode::Normal;
+    world.score = 0;
+    world.lives = 1;
+    level::load_level(world, code.level, config);
+    Ok(())
+}
+
+/// The verdict on a run played from `begin`: `ticks` of the code's input
+/// played, `cleared` if the last of them cleared the node.
+pub fn verdict(world: &WorldState, code: &RunCode, ticks: usize, cleared: bool) -> Verdict {
+    if seed(world) != code.seed {
+        Verdict::OtherSettings
+    } else if cleared && ticks == code.inputs.len() && world.score == code.score {
+        Verdict::Verified
+    } else {
+        Verdict::Differs
+    }
+}
+
+/// Play the code again without showing it.
+pub fn verify(code: &RunCode, config: &GameConfig) -> Result<Verdict, CodeError> {
+    let mut world = WorldState::new();
+    world.speed = config.speed.clone();
+    begin(&mut world, code, config)?;
+    world.phase = Phase::Playing;
+    let played = step::run_ticks(&mut world, code.inputs.iter().copied(), code.inputs.len());
+    Ok(verdict(&world, code, played.ticks, played.cleared()))
+}
+
+/// `bytes` with their CRC-32, in base64: the text of a code.
+pub(crate) fn seal(mut bytes: Vec<u8>) -> String {
+    bytes.extend(save::crc32(&bytes).to_le_bytes());
+    STANDARD.encode(bytes)
+}
+
+/// The bytes `seal` made `text` from, if it's intact. Whitespace (line
+/// breaks from pasting) is ignored.
+pub(crate) fn unseal(text: &str) -> Option<Vec<u8>> {
+    let text: String = text.split_whitespace().collect();
+    let mut bytes = STANDARD.decode(text).ok()?;
+    let at = bytes.len().checked_sub(4)?;
+    let sum = bytes.split_off(at);
+    (save::crc32(&bytes).to_le_bytes()[..] == sum[..]).then_some(bytes)
+}
+
+/// Movement (none, L, R, U, D) plus five times the hack (none, left, right).
+fn input_byte(input: FrameInput) -> u8 {
+    let movement = match input.movement {
+        None => 0,
+        Some(MoveDir::Left) => 1,
+        Some(MoveDir::Right) => 2,
+        Some(MoveDir::Up) => 3,
+        Some(MoveDir::Down) => 4,
+    };
+    let dig = match input.dig {
+        None => 0,
+        Some(Facing::Left) => 1,
+        Some(Facing::Right) => 2,
+    };
+    movement + 5 * dig
+}
+
+fn byte_input(byte: u8) -> Option<FrameInput> {
+    let movement = match byte % 5 {
+        0 => None,
+        1 => Some(MoveDir::Left),
+        2 => Some(MoveDir::Right),
+        3 => Some(MoveDir::Up),
+        _ => Some(MoveDir::Down),
+    };
+    let dig = match byte / 5 {
+        0 => None,
+        1 => Some(Facing::Left),
+        2 => Some(Facing::Right),
+        _ => return None,
+    };
+    Some(FrameInput { movement, dig })
+}
+
+/// LEB128: seven bits a byte, low first.
+fn put_varint(out: &mut Vec<u8>, mut v: u64) {
+    while v >= 0x80 {
+        out.push(v as u8 | 0x80);
+        v >>= 7;
+    }
+    out.push(v as u8);
+}
+
+struct Reader<'a>(&'a [u8]);
+
+impl Reader<'_> {
+    fn take<const N: usize>(&mut self) -> Result<[u8; N], CodeError> {
+        let (head, rest) = self.0.split_first_chunk().ok_or(CodeError::Malformed)?;
+        self.0 = rest;
+        Ok(*head)
+    }
+
+    fn varint(&mut self) -> Result<u64, CodeError> {
+        let mut v = 0u64;
+        for shift in (0..64).step_by(7) {
+            let [b] = self.take()?;
+            v |= ((b & 0x7f) as u64) << shift;
+            if b & 0x80 == 0 { return Ok(v); }
+        }
+        Err(CodeError::Malformed)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn codes_round_trip_and_refuse_damage() {
+        let step = |movement, dig| FrameInput { movement, dig };
+        let mut inputs = vec![step(None, None); 3];
+        inputs.extend(vec![step(Some(MoveDir::Right), None); 200]);
+        inputs.push(step(Some(MoveDir::Down), Some(Facing::Left)));
+        inputs.push(step(None, Some(Facing::Right)));
+        let code = RunCode { pack_hash: 0x0123_4567_89ab_cdef, level: 12, seed: 7, score: 1450, inputs };
+
+        let text = code.encode();
+        assert_eq!(RunCode::decode(&text), Ok(code.clone()));
+        let (a, b) = text.split_at(text.len() / 2);
+        assert_eq!(RunCode::decode(&format!("{a}\n  {b}")), Ok(code));
+
+        assert_eq!(RunCode::decode(&text[..text.len() - 4]), Err(CodeError::Malformed));
+        let flipped = text.replacen(&text[8..9], if &text[8..9] == "A" { "B" } else { "A" }, 1);
+        assert_eq!(RunCode::decode(&flipped), Err(CodeError::Malformed));
+        assert_eq!(RunCode::decode("not a code"), Err(CodeError::Malformed));
+    }
+}
@@ -6,22 +6,52 @@
 ///     Stores level/score/lives. On load, the level starts fresh.
 ///
 ///   **Snapshot save** (Playing phase):
-///     Stores complete game state: tiles, player, guards, holes, digs,
+///     Stores complete game state: tiles, player, guards, holes, digs, spawners,
 ///     gold status, tick count. On load, gameplay resumes exactly.
 ///
 /// ## File format:
-///   Key-value lines. Snapshot data follows `has_snapshot=1`.
+///   Key-value lines, starting with `version=<SAVE_VERSION>`. Snapshot
+///   data follows `has_snapshot=1`. Files without a version line are the
+///   original format (version 1): they load, and are rewritten in the
+///   current one as they do (or all at once by `--migrate-saves`).
 ///
 /// Slots 1-4 stored as save_1.dat .. save_4.dat.
-/// Legacy save.dat (auto-save via ESC) is separate.
+/// Legacy save.dat (auto-save via ESC) is separate, and so is
+/// suspend.dat: the game in play when the game was quit, kept until it's
+/// resumed.
+///
+/// Each save ends with a `checksum=<crc32 hex>` line over everything
+/// before it; a save that doesn't match is refused rather than loaded
+/// into a world it could break. Saving keeps the save it replaces (if
+/// intact) as `<file>.bak`, which the game offers to load instead.
+/// Version 1 saves predate checksums and load without one.
+///
+/// Per-pack progress (levels cleared, used by hub packs) lives in
+/// progress.dat, one `<pack path>\t<level>,<level>,...` line per pack.
+/// Time attack rankings live in timeattack.dat, one
+/// `<pack path>\t<nodes cleared>\t<seconds left>` line per run.
+/// Per-node best scores and times live in leaderboard.dat, one
+/// `<pack path>\t<level>\t<score|time>\t<value>` line per result.
+/// Best normal runs live in runbests.dat, one
+/// `<pack path>\t<score>\t<cleared>\t<tokens>\t<trapped>` line per pack.
+///
+/// With a profile (`use_profile`), all of these live in
+/// `profiles/<name>/` under the data directory instead, along with the
+/// profile's own config.toml if it has one.
+///
+/// Served games (`serve_only`) have no saves at all: none are read, and
+/// nothing is written, records included.
 
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 use crate::domain::entity::{
     ActorState, DigInProgress, Facing, Guard, Hole, Player,
 };
-use crate::domain::tile::Tile;
-use crate::sim::world::WorldState;
+use crate::domain::grid::Grid;
+use crate::domain::tile::{token_for, Tile, TOKENS};
+use crate::sim::world::{LevelBoard, RunBests, WorldState};
 
 // ══════════════════════════════════════════════════════════════
 // Public types
@@ -33,6 +63,8 @@ pub struct SaveData {
     pub level: usize,
     pub score: u32,
     pub lives: u32,
+    /// The player's name for a slot save ("" if it has none).
+    pub name: String,
     pub snapshot: Option<Snapshot>,
 }
 
@@ -42,19 +74,41 @@ pub struct Snapshot {
     pub tick: u64,
     pub width: usize,
     pub height: usize,
-    pub tiles: Vec<Vec<Tile>>,
+    pub tiles: Grid<Tile>,
     pub player: SnapshotPlayer,
     pub guards: Vec<SnapshotGuard>,
     pub holes: Vec<SnapshotHole>,
     pub digs: Vec<SnapshotDig>,
+    pub spawners: Vec<SnapshotSpawner>,
     pub gold_remaining: usize,
     pub gold_total: usize,
     pub exit_enabled: bool,
+    pub digs_made: u32,
+    pub digs_left: Option<u32>,
+    pub light_radius: Option<u32>,
     pub exit_columns: Vec<usize>,
     pub hidden_ladder_positions: Vec<(usize, usize)>,
     pub player_spawn: (usize, usize),
 }
 
+impl Snapshot {
+    /// Whether its map is the size it says, with every actor, hole and
+    /// marker on it: anything else would be indexed off the grid.
+    fn fits(&self) -> bool {
+        let on = |x: usize, y: usize| x < self.width && y < self.height;
+        self.width > 0 && self.height > 0
+            && self.tiles.width() == self.width && self.tiles.height() == self.height
+            && on(self.player.x, self.player.y)
+            && on(self.player_spawn.0, self.player_spawn.1)
+            && self.guards.iter().all(|g| on(g.x, g.y) && on(g.spawn_x, g.spawn_y))
+            && self.holes.iter().all(|h| on(h.x, h.y))
+            && self.digs.iter().all(|d| on(d.x, d.y))
+            && self.spawners.iter().all(|s| on(s.x, s.y))
+            && self.hidden_ladder_positions.iter().all(|&(x, y)| on(x, y))
+            && self.exit_columns.iter().all(|&x| x < self.width)
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct SnapshotPlayer {
     pub x: usize,
@@ -72,6 +126,7 @@ pub struct SnapshotGuard {
     pub facing: Facing,
     pub state: ActorState,
     pub carry_gold: bool,
+    pub carry_tier: u8,
     pub carry_gold_timer: u32,
     pub stuck_timer: u32,
     pub move_cooldown: u32,
@@ -97,13 +152,63 @@ pub struct SnapshotDig {
     pub total_ticks: u32,
 }
 
+/// A spawner's progress; the level says where spawners are and how they go.
+#[derive(Clone, Debug)]
+pub struct SnapshotSpawner {
+    pub x: usize,
+    pub y: usize,
+    pub timer: u32,
+    pub spawned: u32,
+}
+
 // ══════════════════════════════════════════════════════════════
 // Paths
 // ══════════════════════════════════════════════════════════════
 
 const LEGACY_SAVE: &str = "save.dat";
+const SUSPEND_SAVE: &str = "suspend.dat";
+const PROGRESS_FILE: &str = "progress.dat";
+const TIME_ATTACK_FILE: &str = "timeattack.dat";
+const LEADERBOARD_FILE: &str = "leaderboard.dat";
+const RUN_BESTS_FILE: &str = "runbests.dat";
+/// Results kept per node in each leaderboard column.
+const BOARD_KEEP: usize = 5;
+/// Runs kept per pack in the time attack ranking.
+const TIME_ATTACK_KEEP: usize = 10;
+
+const PROFILES_DIR: &str = "profiles";
+const PROFILE_NAME_MAX: usize = 32;
 
-fn save_dir() -> PathBuf {
+/// The player profile in use, if any; set once at startup.
+static PROFILE: OnceLock<String> = OnceLock::new();
+
+/// Set once for `--serve-telnet`, before any game starts.
+static SERVED: AtomicBool = AtomicBool::new(false);
+
+/// Keep remote players off the host's files from now on: saves read as
+/// missing and every write is dropped.
+pub fn serve_only() {
+    SERVED.store(true, Ordering::Relaxed);
+}
+
+fn served() -> bool {
+    SERVED.load(Ordering::Relaxed)
+}
+
+/// Where this player's files go: the profile's directory, or the data
+/// directory itself without a profile.
+pub(crate) fn save_dir() -> PathBuf {
+    match PROFILE.get() {
+        Some(name) => {
+            let dir = data_dir().join(PROFILES_DIR).join(name);
+            let _ = std::fs::create_dir_all(&dir);
+            dir
+        }
+        None => data_dir(),
+    }
+}
+
+fn data_dir() -> PathBuf {
     // 1. Try exe directory (works for local/portable installs)
     if let Ok(exe) = std::env::current_exe() {
         let resolved = exe.canonicalize().unwrap_or(exe);
@@ -129,6 +234,40 @@ fn save_dir() -> PathBuf {
     std::env::current_dir().unwrap_or_else(|_| PathBuf::from("."))
 }
 
+/// Letters, digits, `-` and `_`, so a name is always a plain directory.
+pub fn valid_profile_name(name: &str) -> bool {
+    !name.is_empty() && name.len() <= PROFILE_NAME_MAX
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// Existing profiles, by name.
+pub fn profile_names() -> Vec<String> {
+    let Ok(entries) = std::fs::read_dir(data_dir().join(PROFILES_DIR)) else { return vec![] };
+    let mut names: Vec<String> = entries.flatten()
+        .filter(|e| e.path().is_dir())
+        .filter_map(|e| e.file_name().into_string().ok())
+        .filter(|n| valid_profile_name(n))
+        .collect();
+    names.sort();
+    names
+}
+
+/// Play as profile `name` from now on, creating it if it's new. Returns
+/// its directory. Only the first call takes effect.
+pub fn use_profile(name: &str) -> Result<PathBuf, String> {
+    if !valid_profile_name(name) {
+        return Err(format!("invalid profile name '{name}' (use letters, digits, - and _)"));
+    }
+    let dir = data_dir().join(PROFILES_DIR).join(name);
+    std::fs::create_dir_all(&dir).map_err(|e| format!("{}: {e}", dir.display()))?;
+    let _ = PROFILE.set(name.to_string());
+    Ok(dir)
+}
+
+pub fn profile() -> Option<&'static str> {
+    PROFILE.get().map(String::as_str)
+}
+
 fn slot_filename(slot: u8) -> String {
     format!("save_{}.dat", slot)
 }
@@ -137,6 +276,12 @@ fn slot_path(slot: u8) -> PathBuf {
     save_dir().join(slot_filename(slot))
 }
 
+fn backup_path(path: &Path) -> PathBuf {
+    let mut name = path.as_os_str().to_owned();
+    name.push(".bak");
+    PathBuf::from(name)
+}
+
 fn legacy_path() -> PathBuf {
     save_dir().join(LEGACY_SAVE)
 }
@@ -165,6 +310,7 @@ pub fn capture_snapshot(w: &WorldState) -> Snapshot {
             facing: g.facing,
             state: g.state,
             carry_gold: g.carry_gold,
+            carry_tier: g.carry_tier,
             carry_gold_timer: g.carry_gold_timer,
             stuck_timer: g.stuck_timer,
             move_cooldown: g.move_cooldown,
@@ -183,9 +329,17 @@ pub fn capture_snapshot(w: &WorldState) -> Snapshot {
             ticks_remaining: d.ticks_remaining,
             total_ticks: d.total_ticks(),
         }).collect(),
+        spawners: w.spawners.iter().map(|s| SnapshotSpawner {
+            x: s.x, y: s.y,
+            timer: s.timer,
+            spawned: s.spawned,
+        }).collect(),
         gold_remaining: w.gold_remaining,
         gold_total: w.gold_total,
         exit_enabled: w.exit_enabled,
+        digs_made: w.digs_made,
+        digs_left: w.digs_left,
+        light_radius: w.light_radius,
         exit_columns: w.exit_columns.clone(),
         hidden_ladder_positions: w.hidden_ladder_positions.clone(),
         player_spawn: w.player_spawn,
@@ -197,6 +351,7 @@ pub fn capture_snapshot(w: &WorldState) -> Snapshot {
 /// then this function overwrites the runtime state.
 pub fn restore_snapshot(w: &mut WorldState, snap: &Snapshot) {
     w.tick = snap.tick;
+    w.recording = None;
     w.width = snap.width;
     w.height = snap.height;
     w.tiles = snap.tiles.clone();
@@ -216,6 +371,7 @@ pub fn restore_snapshot(w: &mut WorldState, snap: &Snapshot) {
         facing: g.facing,
         state: g.state,
         carry_gold: g.carry_gold,
+        carry_tier: g.carry_tier,
         carry_gold_timer: g.carry_gold_timer,
         stuck_timer: g.stuck_timer,
         move_cooldown: g.move_cooldown,
@@ -223,6 +379,7 @@ pub fn restore_snapshot(w: &mut WorldState, snap: &Snapshot) {
         spawn_y: g.spawn_y,
         respawn_timer: g.respawn_timer,
         separation_timer: g.separation_timer,
+        stepped_at: 0,
     }).collect();
 
     w.holes = snap.holes.iter().map(|h| Hole::new(
@@ -233,15 +390,30 @@ pub fn restore_snapshot(w: &mut WorldState, snap: &Snapshot) {
         d.x, d.y, d.ticks_remaining, d.total_ticks,
     )).collect();
 
+    for s in &mut w.spawners {
+        if let Some(saved) = snap.spawners.iter().find(|p| (p.x, p.y) == (s.x, s.y)) {
+            s.timer = saved.timer;
+            s.spawned = saved.spawned;
+        }
+    }
+
     w.gold_remaining = snap.gold_remaining;
     w.gold_total = snap.gold_total;
     w.exit_enabled = snap.exit_enabled;
+    w.digs_made = snap.digs_made;
+    if snap.digs_left.is_some() {
+        w.digs_left = snap.digs_left;
+    }
+    if snap.light_radius.is_some() {
+        w.light_radius = snap.light_radius;
+    }
     w.exit_columns = snap.exit_columns.clone();
     w.hidden_ladder_positions = snap.hidden_ladder_positions.clone();
     w.player_spawn = snap.player_spawn;
 
     // Rebuild derived data
     w.rebuild_hole_grid();
+    w.rebuild_occupancy();
 
     // Camera: center on player
     w.camera.center_on(w.player.x, w.player.y, w.width, w.height);
@@ -251,37 +423,100 @@ pub fn restore_snapshot(w: &mut WorldState, snap: &Snapshot) {
 // Slot operations (F5-F12)
 // ══════════════════════════════════════════════════════════════
 
-/// Save to a numbered slot (1-4). Pass snapshot=None for level-start save.
-pub fn save_slot(slot: u8, level: usize, score: u32, lives: u32,
+/// Why a save couldn't be loaded.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LoadError {
+    Missing,
+    /// Its checksum doesn't match, or it doesn't parse. `backup`: the
+    /// save it replaced is intact, for `load_*_backup`.
+    Corrupt { backup: bool },
+    /// Written by a later version of the game, in a format this one
+    /// doesn't know.
+    Newer,
+}
+
+/// Write a save to `path` with its checksum, keeping the save it replaces
+/// as the backup if that one is intact.
+fn write_save(path: &Path, content: &str) -> std::io::Result<()> {
+    if served() {
+        return Err(std::io::Error::new(std::io::ErrorKind::PermissionDenied, "saving is off on this server"));
+    }
+    if std::fs::read_to_string(path).is_ok_and(|old| verified(&old).is_some()) {
+        let _ = std::fs::rename(path, backup_path(path));
+    }
+    std::fs::write(path, with_checksum(content))
+}
+
+/// The first of `candidates` that exists, checked and parsed. A version 1
+/// save is upgraded in place as it's read.
+fn read_save(candidates: &[PathBuf]) -> Result<SaveData, LoadError> {
+    if served() {
+        return Err(LoadError::Missing);
+    }
+    for path in candidates {
+        let Ok(content) = std::fs::read_to_string(path) else { continue };
+        let data = decode(&content)
+            .map_err(|e| match e {
+                LoadError::Corrupt { .. } => LoadError::Corrupt { backup: read_backup(candidates).is_ok() },
+                e => e,
+            })?;
+        if save_version(&content) < SAVE_VERSION {
+            let _ = write_save(path, &serialize_data(&data));
+        }
+        return Ok(data);
+    }
+    Err(LoadError::Missing)
+}
+
+/// A save file's contents, checked and parsed. A version 1 save that
+/// says it has a snapshot but whose snapshot doesn't parse is corrupt too,
+/// so upgrading it can't quietly drop the snapshot.
+fn decode(content: &str) -> Result<SaveData, LoadError> {
+    let corrupt = LoadError::Corrupt { backup: false };
+    if save_version(content) > SAVE_VERSION {
+        return Err(LoadError::Newer);
+    }
+    let data = verified(content).and_then(parse_save).ok_or(corrupt)?;
+    let claims_snapshot = content.lines().any(|l| l.trim() == "has_snapshot=1");
+    if claims_snapshot && data.snapshot.is_none() {
+        return Err(corrupt);
+    }
+    Ok(data)
+}
+
+/// The backup of the first of `candidates` that exists.
+fn read_backup(candidates: &[PathBuf]) -> Result<SaveData, LoadError> {
+    let path = candidates.iter().find(|p| p.exists()).ok_or(LoadError::Missing)?;
+    read_save(&[backup_path(path)])
+}
+
+/// Save to a numbered slot (1-4), under `name` ("" for none). Pass
+/// snapshot=None for level-start save.
+pub fn save_slot(slot: u8, name: &str, level: usize, score: u32, lives: u32,
                  snapshot: Option<&Snapshot>) -> Result<(), String> {
-    let content = serialize(level, score, lives, snapshot);
-    let path = slot_path(slot);
-    std::fs::write(&path, content)
+    let content = serialize(level, score, lives, name, snapshot);
+    write_save(&slot_path(slot), &content)
         .map_err(|e| format!("Save slot {} failed: {}", slot, e))
 }
 
+fn slot_candidates(slot: u8) -> [PathBuf; 2] {
+    [slot_path(slot), PathBuf::from(slot_filename(slot))]
+}
+
 /// Load from a numbered slot (1-4).
-pub fn load_slot(slot: u8) -> Option<SaveData> {
-    let candidates = [
-        slot_path(slot),
-        PathBuf::from(slot_filename(slot)),
-    ];
-    for path in &candidates {
-        if let Ok(content) = std::fs::read_to_string(path) {
-            return parse_save(&content);
-        }
-    }
-    None
+pub fn load_slot(slot: u8) -> Result<SaveData, LoadError> {
+    read_save(&slot_candidates(slot))
+}
+
+/// Load the save a numbered slot held before its last save.
+pub fn load_slot_backup(slot: u8) -> Result<SaveData, LoadError> {
+    read_backup(&slot_candidates(slot))
 }
 
 /// Check if a numbered slot has data.
 #[allow(dead_code)]
 pub fn has_slot(slot: u8) -> bool {
-    let candidates = [
-        slot_path(slot),
-        PathBuf::from(slot_filename(slot)),
-    ];
-    candidates.iter().any(|p| p.exists())
+    !served() && slot_candidates(slot).iter().any(|p| p.exists())
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -290,52 +525,357 @@ pub fn has_slot(slot: u8) -> bool {
 
 pub fn save_game(level: usize, score: u32, lives: u32,
                  snapshot: Option<&Snapshot>) -> Result<(), String> {
-    let content = serialize(level, score, lives, snapshot);
-    let path = legacy_path();
-    std::fs::write(&path, content)
+    let content = serialize(level, score, lives, "", snapshot);
+    write_save(&legacy_path(), &content)
         .map_err(|e| format!("Save failed: {}", e))
 }
 
-pub fn load_save() -> Option<SaveData> {
-    let candidates = [
-        legacy_path(),
-        PathBuf::from(LEGACY_SAVE),
-    ];
-    for path in &candidates {
-        if let Ok(content) = std::fs::read_to_string(path) {
-            return parse_save(&content);
-        }
-    }
-    None
+fn legacy_candidates() -> [PathBuf; 2] {
+    [legacy_path(), PathBuf::from(LEGACY_SAVE)]
+}
+
+pub fn load_save() -> Result<SaveData, LoadError> {
+    read_save(&legacy_candidates())
+}
+
+/// Load the Continue save from before the last one.
+pub fn load_save_backup() -> Result<SaveData, LoadError> {
+    read_backup(&legacy_candidates())
 }
 
 pub fn has_save() -> bool {
-    let candidates = [
-        legacy_path(),
-        PathBuf::from(LEGACY_SAVE),
-    ];
-    candidates.iter().any(|p| p.exists())
+    !served() && legacy_candidates().iter().any(|p| p.exists())
 }
 
 pub fn delete_save() {
-    let _ = std::fs::remove_file(legacy_path());
-    let _ = std::fs::remove_file(LEGACY_SAVE);
+    if served() { return; }
+    for path in legacy_candidates() {
+        let _ = std::fs::remove_file(backup_path(&path));
+        let _ = std::fs::remove_file(path);
+    }
+}
+
+// ══════════════════════════════════════════════════════════════
+// Suspend save (quit during play, resumed once)
+// ══════════════════════════════════════════════════════════════
+
+fn suspend_path() -> PathBuf {
+    save_dir().join(SUSPEND_SAVE)
+}
+
+pub fn save_suspend(level: usize, score: u32, lives: u32,
+                    snapshot: Option<&Snapshot>) -> Result<(), String> {
+    let content = serialize(level, score, lives, "", snapshot);
+    write_save(&suspend_path(), &content)
+        .map_err(|e| format!("Suspend save failed: {}", e))
+}
+
+pub fn load_suspend() -> Result<SaveData, LoadError> {
+    read_save(&[suspend_path()])
+}
+
+/// Drop the suspend save: it's been resumed, or can't be.
+pub fn delete_suspend() {
+    if served() { return; }
+    let path = suspend_path();
+    let _ = std::fs::remove_file(backup_path(&path));
+    let _ = std::fs::remove_file(path);
+}
+
+// ══════════════════════════════════════════════════════════════
+// Migration (--migrate-saves)
+// ══════════════════════════════════════════════════════════════
+
+/// What `migrate_saves` did with one file.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Migration {
+    Upgraded,
+    Current,
+    Failed(String),
+}
+
+/// Rewrite every version 1 save (slots and Continue, shared and in each
+/// profile) in the current format, keeping the original as its backup.
+pub fn migrate_saves() -> Vec<(PathBuf, Migration)> {
+    let root = data_dir();
+    let mut dirs = vec![root.clone()];
+    dirs.extend(profile_names().iter().map(|n| root.join(PROFILES_DIR).join(n)));
+    let files: Vec<String> = (1..=4).map(slot_filename).chain([LEGACY_SAVE.to_string()]).collect();
+
+    let mut done = vec![];
+    for dir in &dirs {
+        for file in &files {
+            let path = dir.join(file);
+            let Ok(content) = std::fs::read_to_string(&path) else { continue };
+            let outcome = match decode(&content) {
+                Ok(_) if save_version(&content) == SAVE_VERSION => Migration::Current,
+                Ok(data) => match write_save(&path, &serialize_data(&data)) {
+                    Ok(()) => Migration::Upgraded,
+                    Err(e) => Migration::Failed(e.to_string()),
+                },
+                Err(LoadError::Newer) => Migration::Failed("saved by a newer version".into()),
+                Err(_) => Migration::Failed("damaged".into()),
+            };
+            done.push((path, outcome));
+        }
+    }
+    done
+}
+
+// ══════════════════════════════════════════════════════════════
+// Per-pack progress
+// ══════════════════════════════════════════════════════════════
+
+fn read_progress() -> Vec<(String, Vec<usize>)> {
+    let content = std::fs::read_to_string(save_dir().join(PROGRESS_FILE)).unwrap_or_default();
+    content.lines()
+        .filter_map(|line| line.split_once('\t'))
+        .map(|(pack, levels)| {
+            let levels = levels.split(',').filter_map(|l| l.trim().parse().ok()).collect();
+            (pack.to_string(), levels)
+        })
+        .collect()
+}
+
+/// Levels of `pack` (its `active_pack_path`) cleared so far.
+pub fn load_cleared(pack: &str) -> Vec<usize> {
+    read_progress().into_iter()
+        .find(|(p, _)| p == pack)
+        .map(|(_, levels)| levels)
+        .unwrap_or_default()
+}
+
+/// Whether all `total` levels of `pack` have been cleared.
+pub fn pack_cleared(pack: &str, total: usize) -> bool {
+    let cleared = load_cleared(pack);
+    total > 0 && (0..total).all(|l| cleared.contains(&l))
+}
+
+/// Record `level` of `pack` as cleared. Already-cleared levels are a no-op.
+pub fn mark_cleared(pack: &str, level: usize) -> Result<(), String> {
+    let mut progress = read_progress();
+    let idx = match progress.iter().position(|(p, _)| p == pack) {
+        Some(i) => i,
+        None => {
+            progress.push((pack.to_string(), vec![]));
+            progress.len() - 1
+        }
+    };
+    let levels = &mut progress[idx].1;
+    if levels.contains(&level) || served() { return Ok(()); }
+    levels.push(level);
+    levels.sort_unstable();
+
+    let mut out = String::new();
+    for (p, levels) in &progress {
+        let list: Vec<String> = levels.iter().map(|l| l.to_string()).collect();
+        out.push_str(&format!("{}\t{}\n", p, list.join(",")));
+    }
+    std::fs::write(save_dir().join(PROGRESS_FILE), out)
+        .map_err(|e| format!("Progress save failed: {}", e))
+}
+
+// ══════════════════════════════════════════════════════════════
+// Time attack ranking
+// ══════════════════════════════════════════════════════════════
+
+/// One finished time attack run.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeAttackRun {
+    pub cleared: u32,
+    pub secs_left: u64,
+}
+
+fn read_time_attack() -> Vec<(String, TimeAttackRun)> {
+    let content = std::fs::read_to_string(save_dir().join(TIME_ATTACK_FILE)).unwrap_or_default();
+    content.lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            let pack = parts.next()?.to_string();
+            let cleared = parts.next()?.trim().parse().ok()?;
+            let secs_left = parts.next()?.trim().parse().ok()?;
+            Some((pack, TimeAttackRun { cleared, secs_left }))
+        })
+        .collect()
+}
+
+/// Best runs for `pack`, best first: most nodes cleared, then most time left.
+pub fn time_attack_ranking(pack: &str) -> Vec<TimeAttackRun> {
+    let mut runs: Vec<TimeAttackRun> = read_time_attack().into_iter()
+        .filter(|(p, _)| p == pack)
+        .map(|(_, r)| r)
+        .collect();
+    runs.sort_by(|a, b| b.cleared.cmp(&a.cleared).then(b.secs_left.cmp(&a.secs_left)));
+    runs
+}
+
+/// Add a finished run to the ranking. Returns its 1-based place, or None
+/// if it didn't make the kept top runs.
+pub fn record_time_attack(pack: &str, run: TimeAttackRun) -> Option<usize> {
+    let mut ranking = time_attack_ranking(pack);
+    // Ties go below existing runs
+    let place = ranking.iter()
+        .position(|r| (run.cleared, run.secs_left) > (r.cleared, r.secs_left))
+        .unwrap_or(ranking.len());
+    if place >= TIME_ATTACK_KEEP { return None; }
+    ranking.insert(place, run);
+    ranking.truncate(TIME_ATTACK_KEEP);
+
+    let mut out = String::new();
+    for (p, r) in read_time_attack().iter().filter(|(p, _)| p != pack) {
+        out.push_str(&format!("{}\t{}\t{}\n", p, r.cleared, r.secs_left));
+    }
+    for r in &ranking {
+        out.push_str(&format!("{}\t{}\t{}\n", pack, r.cleared, r.secs_left));
+    }
+    if !served() {
+        std::fs::write(save_dir().join(TIME_ATTACK_FILE), out).ok()?;
+    }
+    Some(place + 1)
+}
+
+// ══════════════════════════════════════════════════════════════
+// Run bests (normal mode, per pack)
+// ══════════════════════════════════════════════════════════════
+
+fn read_run_bests() -> Vec<(String, RunBests)> {
+    let content = std::fs::read_to_string(save_dir().join(RUN_BESTS_FILE)).unwrap_or_default();
+    content.lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            let pack = parts.next()?.to_string();
+            let mut n = || parts.next()?.trim().parse().ok();
+            Some((pack, RunBests { score: n()?, cleared: n()?, tokens: n()?, trapped: n()? }))
+        })
+        .collect()
+}
+
+/// The best of each column over `pack`'s finished runs; zeros if none.
+pub fn run_bests(pack: &str) -> RunBests {
+    read_run_bests().into_iter()
+        .find(|(p, _)| p == pack)
+        .map_or_else(RunBests::default, |(_, b)| b)
+}
+
+/// Fold a finished run into `pack`'s bests. Returns the bests from
+/// before it, to compare the run with.
+pub fn record_run_bests(pack: &str, run: RunBests) -> RunBests {
+    let before = run_bests(pack);
+    let b = RunBests {
+        score: before.score.max(run.score),
+        cleared: before.cleared.max(run.cleared),
+        tokens: before.tokens.max(run.tokens),
+        trapped: before.trapped.max(run.trapped),
+    };
+    let mut out = String::new();
+    for (p, r) in read_run_bests().iter().filter(|(p, _)| p != pack) {
+        out.push_str(&format!("{}\t{}\t{}\t{}\t{}\n", p, r.score, r.cleared, r.tokens, r.trapped));
+    }
+    out.push_str(&format!("{}\t{}\t{}\t{}\t{}\n", pack, b.score, b.cleared, b.tokens, b.trapped));
+    if !served() {
+        let _ = std::fs::write(save_dir().join(RUN_BESTS_FILE), out);
+    }
+    before
+}
+
+// ══════════════════════════════════════════════════════════════
+// Per-node leaderboard
+// ══════════════════════════════════════════════════════════════
+
+/// (pack, level, kind, value) rows; kind is "score" or "time".
+fn read_board() -> Vec<(String, usize, String, u64)> {
+    let content = std::fs::read_to_string(save_dir().join(LEADERBOARD_FILE)).unwrap_or_default();
+    content.lines()
+        .filter_map(|line| {
+            let mut parts = line.split('\t');
+            let pack = parts.next()?.to_string();
+            let level = parts.next()?.trim().parse().ok()?;
+            let kind = parts.next()?.to_string();
+            let value = parts.next()?.trim().parse().ok()?;
+            Some((pack, level, kind, value))
+        })
+        .collect()
+}
+
+/// Best scores and times for one node.
+pub fn load_board(pack: &str, level: usize) -> LevelBoard {
+    let rows: Vec<_> = read_board().into_iter()
+        .filter(|(p, l, _, _)| p == pack && *l == level)
+        .collect();
+    let mut scores: Vec<u32> = rows.iter()
+        .filter(|r| r.2 == "score")
+        .map(|r| r.3 as u32)
+        .collect();
+    let mut times_ms: Vec<u64> = rows.iter()
+        .filter(|r| r.2 == "time")
+        .map(|r| r.3)
+        .collect();
+    scores.sort_unstable_by(|a, b| b.cmp(a));
+    times_ms.sort_unstable();
+    LevelBoard { scores, times_ms, new_score: None, new_time: None }
+}
+
+/// Add a clear to the node's board and return the updated board, with
+/// the places the new results took. `score` is None when the mode has
+/// no score (time attack).
+pub fn record_board(pack: &str, level: usize, score: Option<u32>, time_ms: u64) -> LevelBoard {
+    let mut board = load_board(pack, level);
+    if let Some(score) = score {
+        let place = board.scores.iter().position(|&s| score > s).unwrap_or(board.scores.len());
+        if place < BOARD_KEEP {
+            board.scores.insert(place, score);
+            board.scores.truncate(BOARD_KEEP);
+            board.new_score = Some(place);
+        }
+    }
+    let place = board.times_ms.iter().position(|&t| time_ms < t).unwrap_or(board.times_ms.len());
+    if place < BOARD_KEEP {
+        board.times_ms.insert(place, time_ms);
+        board.times_ms.truncate(BOARD_KEEP);
+        board.new_time = Some(place);
+    }
+
+    let mut out = String::new();
+    for (p, l, kind, value) in read_board() {
+        if p == pack && l == level { continue; }
+        out.push_str(&format!("{}\t{}\t{}\t{}\n", p, l, kind, value));
+    }
+    for s in &board.scores {
+        out.push_str(&format!("{}\t{}\tscore\t{}\n", pack, level, s));
+    }
+    for t in &board.times_ms {
+        out.push_str(&format!("{}\t{}\ttime\t{}\n", pack, level, t));
+    }
+    if !served() {
+        let _ = std::fs::write(save_dir().join(LEADERBOARD_FILE), out);
+    }
+    board
 }
 
 // ══════════════════════════════════════════════════════════════
 // Serialization
 // ══════════════════════════════════════════════════════════════
 
-fn tile_to_char(t: Tile) -> char {
+/// Custom tiles are saved by registry index, as private-use chars from
+/// here, so a save parses before `load_level` has set up its pack's tiles.
+const CUSTOM_TILE_BASE: u32 = 0xE000;
+
+pub(crate) fn tile_to_char(t: Tile) -> char {
     match t {
         Tile::Empty        => ' ',
         Tile::Brick        => '#',
         Tile::Concrete     => '=',
         Tile::Ladder       => 'H',
         Tile::Rope         => '-',
-        Tile::Gold         => '$',
+        Tile::Gold(tier)   => TOKENS.get(tier as usize).map_or('$', |k| k.ch),
         Tile::HiddenLadder => '~',
         Tile::TrapBrick    => 'T',
+        Tile::FakeBrick    => 'S',
+        Tile::Laser        => '!',
+        Tile::Spawner      => 'G',
+        Tile::Lantern      => 'L',
+        Tile::Door         => 'D',
+        Tile::Custom(id)   => char::from_u32(CUSTOM_TILE_BASE + id as u32).unwrap_or(' '),
     }
 }
 
@@ -345,10 +885,18 @@ fn char_to_tile(c: char) -> Tile {
         '=' => Tile::Concrete,
         'H' => Tile::Ladder,
         '-' => Tile::Rope,
-        '$' => Tile::Gold,
+        '$' | '%' | '&' => token_for(c).unwrap_or(Tile::Gold(0)),
         '~' => Tile::HiddenLadder,
         'T' => Tile::TrapBrick,
-        _   => Tile::Empty,
+        'S' => Tile::FakeBrick,
+        '!' => Tile::Laser,
+        'G' => Tile::Spawner,
+        'L' => Tile::Lantern,
+        'D' => Tile::Door,
+        _   => match (c as u32).checked_sub(CUSTOM_TILE_BASE) {
+            Some(id) if id < 256 => Tile::Custom(id as u8),
+            _ => Tile::Empty,
+        },
     }
 }
 
@@ -383,11 +931,32 @@ fn parse_state(s: &str) -> ActorState {
     }
 }
 
-fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>) -> String {
+fn serialize_data(data: &SaveData) -> String {
+    serialize(data.level, data.score, data.lives, &data.name, data.snapshot.as_ref())
+}
+
+/// The level in play as snapshot save text, without a checksum: what a
+/// spectator frame carries (`sim::spectate`).
+pub(crate) fn snapshot_text(w: &WorldState) -> String {
+    serialize(w.current_level, w.score, w.lives, "", Some(&capture_snapshot(w)))
+}
+
+/// Read text `snapshot_text` wrote. None unless it holds a snapshot
+/// that fits its map.
+pub(crate) fn parse_snapshot_text(content: &str) -> Option<SaveData> {
+    parse_save(content).filter(|data| data.snapshot.is_some())
+}
+
+fn serialize(level: usize, score: u32, lives: u32, name: &str, snapshot: Option<&Snapshot>) -> String {
     let mut out = String::with_capacity(4096);
+    out.push_str(&format!("{VERSION}{SAVE_VERSION}\n"));
     out.push_str(&format!("level={}\n", level));
     out.push_str(&format!("score={}\n", score));
     out.push_str(&format!("lives={}\n", lives));
+    let name: String = name.chars().filter(|c| !c.is_control()).collect();
+    if !name.is_empty() {
+        out.push_str(&format!("name={}\n", name));
+    }
 
     if let Some(snap) = snapshot {
         out.push_str("has_snapshot=1\n");
@@ -397,6 +966,13 @@ fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>)
         out.push_str(&format!("gold_remaining={}\n", snap.gold_remaining));
         out.push_str(&format!("gold_total={}\n", snap.gold_total));
         out.push_str(&format!("exit_enabled={}\n", if snap.exit_enabled { 1 } else { 0 }));
+        out.push_str(&format!("digs_made={}\n", snap.digs_made));
+        if let Some(n) = snap.digs_left {
+            out.push_str(&format!("digs_left={}\n", n));
+        }
+        if let Some(n) = snap.light_radius {
+            out.push_str(&format!("light={}\n", n));
+        }
         out.push_str(&format!("player_spawn={},{}\n", snap.player_spawn.0, snap.player_spawn.1));
 
         let p = &snap.player;
@@ -406,7 +982,7 @@ fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>)
         for g in &snap.guards {
             out.push_str(&format!("guard={},{},{},{},{},{},{},{},{},{},{},{},{}\n",
                 g.id, g.x, g.y, facing_str(g.facing), state_str(g.state),
-                if g.carry_gold { 1 } else { 0 }, g.carry_gold_timer,
+                if g.carry_gold { 1 + g.carry_tier as u32 } else { 0 }, g.carry_gold_timer,
                 g.stuck_timer, g.move_cooldown,
                 g.spawn_x, g.spawn_y, g.respawn_timer, g.separation_timer));
         }
@@ -421,6 +997,10 @@ fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>)
                 d.x, d.y, d.ticks_remaining, d.total_ticks));
         }
 
+        for s in &snap.spawners {
+            out.push_str(&format!("spawner={},{},{},{}\n", s.x, s.y, s.timer, s.spawned));
+        }
+
         if !snap.exit_columns.is_empty() {
             let cols: Vec<String> = snap.exit_columns.iter().map(|c| c.to_string()).collect();
             out.push_str(&format!("exit_cols={}\n", cols.join(",")));
@@ -430,7 +1010,7 @@ fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>)
             out.push_str(&format!("hidden_ladder={},{}\n", x, y));
         }
 
-        for row in &snap.tiles {
+        for row in snap.tiles.rows() {
             let s: String = row.iter().map(|t| tile_to_char(*t)).collect();
             out.push_str(&format!("tile_row={}\n", s));
         }
@@ -439,6 +1019,53 @@ fn serialize(level: usize, score: u32, lives: u32, snapshot: Option<&Snapshot>)
     out
 }
 
+// ══════════════════════════════════════════════════════════════
+// Checksums
+// ══════════════════════════════════════════════════════════════
+
+const CHECKSUM: &str = "checksum=";
+const VERSION: &str = "version=";
+/// The save format written. 1: the original, unversioned and unchecked;
+/// 2: a version line first and a checksum line last.
+const SAVE_VERSION: u32 = 2;
+
+/// The format `content` is in, from its first line.
+fn save_version(content: &str) -> u32 {
+    match content.lines().next().and_then(|l| l.strip_prefix(VERSION)) {
+        Some(v) => v.trim().parse().unwrap_or(u32::MAX),
+        None => 1,
+    }
+}
+
+fn with_checksum(content: &str) -> String {
+    format!("{content}{CHECKSUM}{:08x}\n", crc32(content.as_bytes()))
+}
+
+/// `content` up to its checksum line, if the checksum matches what's
+/// before it. A version 1 save has no checksum, and is taken as it is.
+fn verified(content: &str) -> Option<&str> {
+    let at = content.match_indices(CHECKSUM).map(|(i, _)| i)
+        .find(|&i| i == 0 || content.as_bytes()[i - 1] == b'\n');
+    let Some(at) = at else {
+        return (save_version(content) == 1).then_some(content);
+    };
+    let (body, line) = content.split_at(at);
+    let sum = u32::from_str_radix(line[CHECKSUM.len()..].trim_end(), 16).ok()?;
+    (sum == crc32(body.as_bytes())).then_some(body)
+}
+
+/// CRC-32 (IEEE), a bit at a time: saves are a few KB.
+pub(crate) fn crc32(bytes: &[u8]) -> u32 {
+    let mut crc = !0u32;
+    for &b in bytes {
+        crc ^= b as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 { (crc >> 1) ^ 0xEDB8_8320 } else { crc >> 1 };
+        }
+    }
+    !crc
+}
+
 // ══════════════════════════════════════════════════════════════
 // Parsing
 // ══════════════════════════════════════════════════════════════
@@ -447,6 +1074,7 @@ fn parse_save(content: &str) -> Option<SaveData> {
     let mut level = None;
     let mut score = None;
     let mut lives = None;
+    let mut name = String::new();
     let mut has_snapshot = false;
     let mut tick: u64 = 0;
     let mut width: usize = 0;
@@ -454,11 +1082,15 @@ fn parse_save(content: &str) -> Option<SaveData> {
     let mut gold_remaining: usize = 0;
     let mut gold_total: usize = 0;
     let mut exit_enabled = false;
+    let mut digs_made: u32 = 0;
+    let mut digs_left: Option<u32> = None;
+    let mut light_radius: Option<u32> = None;
     let mut player_spawn = (0usize, 0usize);
     let mut player: Option<SnapshotPlayer> = None;
     let mut guards: Vec<SnapshotGuard> = vec![];
     let mut holes: Vec<SnapshotHole> = vec![];
     let mut digs: Vec<SnapshotDig> = vec![];
+    let mut spawners: Vec<SnapshotSpawner> = vec![];
     let mut exit_columns: Vec<usize> = vec![];
     let mut hidden_ladders: Vec<(usize, usize)> = vec![];
     let mut tile_rows: Vec<Vec<Tile>> = vec![];
@@ -472,6 +1104,8 @@ fn parse_save(content: &str) -> Option<SaveData> {
             score = val.trim().parse().ok();
         } else if let Some(val) = line.strip_prefix("lives=") {
             lives = val.trim().parse().ok();
+        } else if let Some(val) = line.strip_prefix("name=") {
+            name = val.to_string();
         } else if line.trim() == "has_snapshot=1" {
             has_snapshot = true;
         } else if let Some(val) = line.strip_prefix("tick=") {
@@ -486,6 +1120,12 @@ fn parse_save(content: &str) -> Option<SaveData> {
             gold_total = val.trim().parse().unwrap_or(0);
         } else if let Some(val) = line.strip_prefix("exit_enabled=") {
             exit_enabled = val.trim() == "1";
+        } else if let Some(val) = line.strip_prefix("digs_made=") {
+            digs_made = val.trim().parse().unwrap_or(0);
+        } else if let Some(val) = line.strip_prefix("digs_left=") {
+            digs_left = val.trim().parse().ok();
+        } else if let Some(val) = line.strip_prefix("light=") {
+            light_radius = val.trim().parse().ok();
         } else if let Some(val) = line.strip_prefix("player_spawn=") {
             let parts: Vec<&str> = val.split(',').collect();
             if parts.len() == 2 {
@@ -508,6 +1148,10 @@ fn parse_save(content: &str) -> Option<SaveData> {
             if let Some(d) = parse_dig(val) {
                 digs.push(d);
             }
+        } else if let Some(val) = line.strip_prefix("spawner=") {
+            if let Some(s) = parse_spawner(val) {
+                spawners.push(s);
+            }
         } else if let Some(val) = line.strip_prefix("exit_cols=") {
             exit_columns = val.split(',')
                 .filter_map(|s| s.trim().parse().ok())
@@ -524,23 +1168,34 @@ fn parse_save(content: &str) -> Option<SaveData> {
         }
     }
 
+    // Rows lose their trailing empty cells to the trim above
+    let cols = tile_rows.iter().map(Vec::len).max().unwrap_or(0).max(width);
+    for row in &mut tile_rows {
+        row.resize(cols, Tile::Empty);
+    }
+    // A snapshot that doesn't fit its map is dropped, so a save claiming
+    // one is refused as corrupt
     let snapshot = if has_snapshot && player.is_some() && !tile_rows.is_empty() {
         Some(Snapshot {
             tick,
             width,
             height,
-            tiles: tile_rows,
+            tiles: Grid::from_rows(tile_rows),
             player: player.unwrap(),
             guards,
             holes,
             digs,
+            spawners,
             gold_remaining,
             gold_total,
             exit_enabled,
+            digs_made,
+            digs_left,
+            light_radius,
             exit_columns,
             hidden_ladder_positions: hidden_ladders,
             player_spawn,
-        })
+        }).filter(Snapshot::fits)
     } else {
         None
     };
@@ -549,6 +1204,7 @@ fn parse_save(content: &str) -> Option<SaveData> {
         level: level?,
         score: score?,
         lives: lives?,
+        name,
         snapshot,
     })
 }
@@ -568,13 +1224,16 @@ fn parse_player(val: &str) -> Option<SnapshotPlayer> {
 fn parse_guard(val: &str) -> Option<SnapshotGuard> {
     let p: Vec<&str> = val.split(',').collect();
     if p.len() < 13 { return None; }
+    // What it carries: 0 nothing, else 1 + the token's tier
+    let carried: u8 = p[5].trim().parse().ok()?;
     Some(SnapshotGuard {
         id: p[0].trim().parse().ok()?,
         x: p[1].trim().parse().ok()?,
         y: p[2].trim().parse().ok()?,
         facing: parse_facing(p[3].trim()),
         state: parse_state(p[4].trim()),
-        carry_gold: p[5].trim() == "1",
+        carry_gold: carried > 0,
+        carry_tier: carried.saturating_sub(1),
         carry_gold_timer: p[6].trim().parse().ok()?,
         stuck_timer: p[7].trim().parse().ok()?,
         move_cooldown: p[8].trim().parse().ok()?,
@@ -606,3 +1265,80 @@ fn parse_dig(val: &str) -> Option<SnapshotDig> {
         total_ticks: p[3].trim().parse().ok()?,
     })
 }
+
+fn parse_spawner(val: &str) -> Option<SnapshotSpawner> {
+    let p: Vec<&str> = val.split(',').collect();
+    if p.len() < 4 { return None; }
+    Some(SnapshotSpawner {
+        x: p[0].trim().parse().ok()?,
+        y: p[1].trim().parse().ok()?,
+        timer: p[2].trim().parse().ok()?,
+        spawned: p[3].trim().parse().ok()?,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn checksum_catches_edits_and_passes_old_saves() {
+        assert_eq!(crc32(b"123456789"), 0xCBF4_3926);
+        let content = serialize(3, 1200, 2, "", None);
+        let saved = with_checksum(&content);
+        assert_eq!(verified(&saved), Some(content.as_str()));
+        assert_eq!(parse_save(verified(&saved).unwrap()).map(|d| d.score), Some(1200));
+
+        let edited = saved.replace("score=1200", "score=9999");
+        assert_eq!(verified(&edited), None);
+        assert_eq!(verified(&format!("{saved}lives=99\n")), None);
+        assert_eq!(verified(&content), None);
+    }
+
+    #[test]
+    fn version_1_saves_decode_and_upgrade_with_their_snapshot() {
+        let (world, _) = crate::sim::step::tests::world_from("# t\n P$ E H\n######\n");
+        let snap = capture_snapshot(&world);
+        let current = serialize(2, 500, 3, "", Some(&snap));
+        let v1 = current.split_once('\n').unwrap().1;
+        assert_eq!(save_version(v1), 1);
+
+        let data = decode(v1).expect("v1 save loads");
+        assert_eq!((data.level, data.score, data.lives), (2, 500, 3));
+        let upgraded = with_checksum(&serialize_data(&data));
+        assert_eq!(upgraded, with_checksum(&current));
+        assert!(decode(&upgraded).is_ok_and(|d| d.snapshot.is_some()));
+
+        let broken = v1.replace("player=", "plyer=");
+        assert_eq!(decode(&broken).err(), Some(LoadError::Corrupt { backup: false }));
+        assert_eq!(decode(&format!("version=9\n{v1}")).err(), Some(LoadError::Newer));
+    }
+
+    #[test]
+    fn snapshots_off_their_map_are_refused() {
+        let (world, _) = crate::sim::step::tests::world_from("# t\n P$ E H\n######\n");
+        let text = snapshot_text(&world);
+        assert!(parse_snapshot_text(&text).is_some());
+        let v1 = text.split_once('\n').unwrap().1;
+        let (w, h) = (world.width, world.height);
+        for broken in [
+            v1.replace(&format!("height={h}"), &format!("height={}", h + 1)),
+            v1.replace(&format!("width={w}"), "width=3"),
+            v1.replace("player=1,0,", &format!("player=1,{h},")),
+            format!("{v1}hole={w},0,10,10\n"),
+            format!("{v1}exit_cols={w}\n"),
+        ] {
+            assert!(parse_snapshot_text(&broken).is_none(), "{broken}");
+            assert_eq!(decode(&broken).err(), Some(LoadError::Corrupt { backup: false }), "{broken}");
+        }
+    }
+
+    #[test]
+    fn slot_names_survive_a_save() {
+        let named = with_checksum(&serialize(36, 12400, 4, "before the nasty bit\n", None));
+        let data = decode(&named).expect("named save loads");
+        assert_eq!((data.level, data.name.as_str()), (36, "before the nasty bit"));
+        assert_eq!(with_checksum(&serialize_data(&data)), named);
+        assert_eq!(decode(&with_checksum(&serialize(0, 0, 5, "", None))).map(|d| d.name), Ok(String::new()));
+    }
+}
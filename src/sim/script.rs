@@ -0,0 +1,207 @@
+//! Level scripts: Rhai snippets a level attaches to triggers.
+//!
+//!   `@on gold_collected <code>` — after each token the player mines
+//!   `@on tick <code>`           — every tick of play
+//!   `@on enter X,Y <code>`      — when the player steps onto cell (X, Y)
+//!
+//! Scripts read `tick`, `player_x`, `player_y`, `remaining` (tokens left)
+//! and, for `gold_collected` / `enter`, the cell as `x`, `y`. They act by
+//! calling `spawn_guard(x, y)`, `set_tile(x, y, "#")` (level-file tile
+//! chars, including the pack's custom tiles) and `message("text")`; the
+//! calls are queued and `step` applies them once the script returns, so
+//! scripts never touch the world directly.
+//!
+//! Built without the `scripting` feature, `@on` lines are kept but not run.
+
+use crate::domain::tile::{self, Tile};
+
+/// Operations a script may run per call before it's stopped (runaway loops).
+#[cfg(feature = "scripting")]
+const MAX_OPERATIONS: u64 = 50_000;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ScriptTrigger {
+    GoldCollected,
+    Tick,
+    Enter { x: usize, y: usize },
+}
+
+/// One `@on` line.
+#[derive(Clone, Debug)]
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub struct ScriptDef {
+    pub trigger: ScriptTrigger,
+    pub source: String,
+}
+
+/// What a script asked for, applied by `step` after it returns.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub enum ScriptAction {
+    SpawnGuard { x: usize, y: usize },
+    SetTile { x: usize, y: usize, tile: Tile },
+    Message(String),
+}
+
+/// What a script can read about the world.
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+pub struct ScriptVars {
+    pub tick: u64,
+    pub player: (usize, usize),
+    pub remaining: usize,
+    pub at: Option<(usize, usize)>,  // the token / cell the trigger is about
+}
+
+/// Parse the rest of an `@on` line: `tick code`, `enter 3,4 code`.
+pub fn parse_on(rest: &str) -> Option<ScriptDef> {
+    let (name, rest) = rest.trim().split_once(' ')?;
+    let (trigger, source) = match name {
+        "gold_collected" => (ScriptTrigger::GoldCollected, rest),
+        "tick" => (ScriptTrigger::Tick, rest),
+        "enter" => {
+            let (pos, source) = rest.trim().split_once(' ')?;
+            let (x, y) = pos.split_once(',')?;
+            (ScriptTrigger::Enter { x: x.parse().ok()?, y: y.parse().ok()? }, source)
+        }
+        _ => return None,
+    };
+    let source = source.trim();
+    if source.is_empty() { return None; }
+    Some(ScriptDef { trigger, source: source.to_string() })
+}
+
+/// Tiles a script may place, by their level-file char.
+#[cfg_attr(not(feature = "scripting"), allow(dead_code))]
+fn tile_from_char(c: char) -> Option<Tile> {
+    Some(match c {
+        ' ' | '.' => Tile::Empty,
+        '#' => Tile::Brick,
+        '=' => Tile::Concrete,
+        'H' => Tile::Ladder,
+        '-' => Tile::Rope,
+        '$' | '%' | '&' => return tile::token_for(c),
+        'T' => Tile::TrapBrick,
+        'S' => Tile::FakeBrick,
+        '!' => Tile::Laser,
+        'L' => Tile::Lantern,
+        _ => return tile::custom_tile_for(c),
+    })
+}
+
+#[cfg(feature = "scripting")]
+mod inner {
+    use std::cell::RefCell;
+    use std::rc::Rc;
+
+    use rhai::{Engine, Scope, AST};
+
+    use crate::i18n::t;
+    use super::{tile_from_char, ScriptAction, ScriptDef, ScriptTrigger, ScriptVars, MAX_OPERATIONS};
+
+    /// A level's compiled scripts and the engine that runs them.
+    #[derive(Default)]
+    pub struct Scripts {
+        engine: Option<Engine>,  // only built for levels that have scripts
+        compiled: Vec<(ScriptTrigger, AST)>,
+        queue: Rc<RefCell<Vec<ScriptAction>>>,
+    }
+
+    impl Scripts {
+        /// Compile a level's `@on` lines. Lines that don't compile are
+        /// dropped and described in the returned messages.
+        pub fn load(defs: &[ScriptDef]) -> (Self, Vec<String>) {
+            let mut scripts = Scripts::default();
+            if defs.is_empty() { return (scripts, vec![]); }
+
+            let engine = engine(&scripts.queue);
+            let mut errors = vec![];
+            for def in defs {
+                match engine.compile(&def.source) {
+                    Ok(ast) => scripts.compiled.push((def.trigger, ast)),
+                    Err(e) => errors.push(t!("msg.script_error", error = e)),
+                }
+            }
+            scripts.engine = Some(engine);
+            (scripts, errors)
+        }
+
+        pub fn is_empty(&self) -> bool {
+            self.compiled.is_empty()
+        }
+
+        /// Run every script on `trigger`. Returns what they asked for, and
+        /// the first runtime error as a message (actions queued before it
+        /// are kept).
+        pub fn run(&self, trigger: ScriptTrigger, vars: &ScriptVars) -> (Vec<ScriptAction>, Option<String>) {
+            let Some(engine) = &self.engine else { return (vec![], None) };
+            let mut error = None;
+            for (_, ast) in self.compiled.iter().filter(|(t, _)| *t == trigger) {
+                let mut scope = Scope::new();
+                scope.push_constant("tick", vars.tick as i64);
+                scope.push_constant("player_x", vars.player.0 as i64);
+                scope.push_constant("player_y", vars.player.1 as i64);
+                scope.push_constant("remaining", vars.remaining as i64);
+                if let Some((x, y)) = vars.at {
+                    scope.push_constant("x", x as i64);
+                    scope.push_constant("y", y as i64);
+                }
+                if let Err(e) = engine.run_ast_with_scope(&mut scope, ast) {
+                    error.get_or_insert_with(|| t!("msg.script_error", error = e));
+                }
+            }
+            (self.queue.borrow_mut().drain(..).collect(), error)
+        }
+    }
+
+    /// An engine whose script API pushes onto `queue`.
+    fn engine(queue: &Rc<RefCell<Vec<ScriptAction>>>) -> Engine {
+        let mut engine = Engine::new();
+        engine.set_max_operations(MAX_OPERATIONS);
+
+        let q = Rc::clone(queue);
+        engine.register_fn("spawn_guard", move |x: i64, y: i64| {
+            if let (Ok(x), Ok(y)) = (usize::try_from(x), usize::try_from(y)) {
+                q.borrow_mut().push(ScriptAction::SpawnGuard { x, y });
+            }
+        });
+        let q = Rc::clone(queue);
+        engine.register_fn("set_tile", move |x: i64, y: i64, tile: &str| {
+            let tile = tile.chars().next().and_then(tile_from_char);
+            if let (Ok(x), Ok(y), Some(tile)) = (usize::try_from(x), usize::try_from(y), tile) {
+                q.borrow_mut().push(ScriptAction::SetTile { x, y, tile });
+            }
+        });
+        let q = Rc::clone(queue);
+        engine.register_fn("message", move |text: &str| {
+            q.borrow_mut().push(ScriptAction::Message(text.to_string()));
+        });
+        engine
+    }
+}
+
+#[cfg(feature = "scripting")]
+pub use inner::Scripts;
+
+#[cfg(not(feature = "scripting"))]
+#[derive(Default)]
+pub struct Scripts {}
+
+#[cfg(not(feature = "scripting"))]
+impl Scripts {
+    pub fn load(defs: &[ScriptDef]) -> (Self, Vec<String>) {
+        let errors = if defs.is_empty() {
+            vec![]
+        } else {
+            vec![crate::i18n::t!("msg.no_scripting").to_string()]
+        };
+        (Scripts {}, errors)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        true
+    }
+
+    pub fn run(&self, _trigger: ScriptTrigger, _vars: &ScriptVars) -> (Vec<ScriptAction>, Option<String>) {
+        (vec![], None)
+    }
+}
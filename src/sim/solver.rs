@@ -0,0 +1,447 @@
+//! Level solver: an input sequence that clears a level.
+//!
+//! A best-first search over where the player stands, which tokens are
+//! left, the holes open around them and how many hacks have been used, one
+//! move or hack per step, costed in ticks. Holes close on the same
+//! schedule as in play, so the search can hack two side by side and dig on
+//! down through the second. Sentinels aren't planned around, and a trap
+//! brick gives way under the player every time they stand on it. The
+//! plan is then replayed through `step` on a copy of the level without
+//! its sentinels, and only inputs that clear the level are returned, so a
+//! `Some` is always a real solution — though not always the shortest.
+//!
+//! Single-room levels only: hubs and levels with doors give `None`.
+
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashMap};
+
+use crate::config::GameConfig;
+use crate::domain::entity::{ActorState, Facing, FrameInput, MoveDir};
+use crate::domain::grid::Grid;
+use crate::domain::rules::{self, MapView};
+use crate::domain::tile::Tile;
+use super::level::{self, LevelDef};
+use super::step;
+use super::world::{Phase, WorldState};
+
+/// Search nodes expanded before giving up: up to a few tenths of a
+/// second for `solve`, and a tenth of that for `reachable`, which the
+/// randomizer runs on the game thread for every deal.
+const SEARCH_LIMIT: usize = 100_000;
+const REACH_LIMIT: usize = 10_000;
+
+/// Tokens tracked per node (one bit each).
+const MAX_TOKENS: usize = 128;
+
+/// Open holes that tell nodes apart: the ones within `HOLE_REACH` cells
+/// (either axis) of the player, nearest first. Holes further off still
+/// open and close as the search goes, but two ways to the same cell that
+/// differ only in those count as one.
+const NEAR_HOLES: usize = 3;
+const HOLE_REACH: usize = 2;
+
+/// Ticks early a hole counts as closing on a player in it: one that
+/// starts to close holds them there until it fills.
+const HOLE_MARGIN: u32 = 10;
+
+/// How far the estimate is trusted over the cost so far, and what each
+/// token left adds to it, in moves. Both steer the search at tokens
+/// rather than proving a route shortest: a solution is wanted, and soon.
+const GREED: u32 = 3;
+const TOKEN_MOVES: u32 = 20;
+
+type Cell = (usize, usize);
+
+/// Holes not yet filled in, with the tick each starts to close.
+type Holes = Vec<(Cell, u32)>;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Action {
+    Move(MoveDir),
+    Dig(Facing),
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct Node {
+    x: usize,
+    y: usize,
+    left: u128,  // bit i: token i not yet mined
+    digs: u32,   // hacks used, when the level limits them
+    near: [Option<Cell>; NEAR_HOLES],
+}
+
+/// One step of a route: what to do, where it leads, and the tick (from
+/// the start) it's planned to begin on.
+#[derive(Clone, Copy, Debug)]
+struct Step {
+    action: Action,
+    to: Node,
+    at: u32,
+}
+
+/// A way the search found to a node: what it cost, the visit it came
+/// from and how, and every hole open on arrival. Kept even once a cheaper
+/// way turns up, so a route always reads back the holes it was planned
+/// with.
+struct Visit {
+    node: Node,
+    cost: u32,
+    from: Option<(usize, Action)>,
+    holes: Holes,
+}
+
+/// The level as the search sees it.
+struct Plan {
+    base: Grid<Tile>,  // tokens cleared
+    exit: Grid<Tile>,  // `base` with the exit ladders revealed
+    width: usize,
+    height: usize,
+    tokens: Vec<Cell>,
+    dig_limit: Option<u32>,
+    move_cost: u32,
+    dig_cost: u32,
+    hole_ticks: u32,
+    fill_ticks: u32,
+}
+
+impl Plan {
+    /// The map with `left` tokens still to mine and `holes` open.
+    fn tiles(&self, left: u128, holes: &[(Cell, u32)]) -> Grid<Tile> {
+        let mut tiles = if left == 0 { self.exit.clone() } else { self.base.clone() };
+        for &((x, y), _) in holes {
+            tiles[(x, y)] = Tile::Empty;
+        }
+        tiles
+    }
+
+    /// Mine the token at (x, y), if one is left there.
+    fn mine(&self, x: usize, y: usize, left: u128) -> u128 {
+        match self.tokens.iter().position(|&t| t == (x, y)) {
+            Some(i) => left & !(1 << i),
+            None => left,
+        }
+    }
+
+    fn deadly(&self, tiles: &Grid<Tile>, x: usize, y: usize) -> bool {
+        tiles[(x, y)].is_deadly() || (y + 1 < self.height && tiles[(x, y + 1)].is_deadly())
+    }
+
+    /// Arrive at (x, y) on tick `now` and fall until something holds the
+    /// player up. Returns where they come to rest, the tokens still left,
+    /// and the cells fallen; `None` if a hazard kills them on the way or a
+    /// closing hole catches them.
+    fn land(&self, holes: &[(Cell, u32)], now: u32, x: usize, mut y: usize, mut left: u128)
+        -> Option<(usize, u128, u32)>
+    {
+        let mut tiles = self.tiles(left, holes);
+        let mut fallen = 0;
+        loop {
+            if self.deadly(&tiles, x, y) { return None; }
+            let at = now + fallen;
+            if holes.iter().any(|&(h, closes)| h == (x, y) && at + HOLE_MARGIN > closes) { return None; }
+            if y + 1 < self.height && tiles[(x, y + 1)] == Tile::TrapBrick {
+                // Gives way under whoever's on it
+                tiles[(x, y + 1)] = Tile::Empty;
+            }
+            let map = MapView { tiles: &tiles, width: self.width, height: self.height };
+            let rests = map.has_support(x, y) || !map.is_passable(x, y + 1);
+            // `step` drops the player before picking up, so a token in a
+            // cell they step into with nothing under it stays put
+            if rests || fallen > 0 {
+                let mined = self.mine(x, y, left);
+                if mined == 0 && left != 0 {
+                    // The last token opens the exit
+                    tiles = self.tiles(0, holes);
+                }
+                left = mined;
+            }
+            if rests { return Some((y, left, fallen)); }
+            y += 1;
+            fallen += 1;
+        }
+    }
+
+    /// Every step from `node`, reached `cost` ticks in with `holes` open:
+    /// the next node, how, what it costs, and the holes open after it.
+    fn successors(&self, node: Node, cost: u32, holes: &[(Cell, u32)])
+        -> Vec<(Node, Action, u32, Holes)>
+    {
+        let mut out = vec![];
+        let tiles = self.tiles(node.left, holes);
+        let map = MapView { tiles: &tiles, width: self.width, height: self.height };
+        let state = rules::resolve_state(&map, node.x, node.y, ActorState::OnGround);
+        // Standing in a hole: out before it closes, or buried
+        let deadline = holes.iter()
+            .find(|&&(h, _)| h == (node.x, node.y))
+            .map_or(u32::MAX, |&(_, closes)| closes.saturating_sub(HOLE_MARGIN));
+
+        for dir in [MoveDir::Left, MoveDir::Right, MoveDir::Up, MoveDir::Down] {
+            let (ok, nx, ny) = match dir {
+                MoveDir::Left => (rules::can_move_left(&map, node.x, node.y, state), node.x.wrapping_sub(1), node.y),
+                MoveDir::Right => (rules::can_move_right(&map, node.x, node.y, state), node.x + 1, node.y),
+                MoveDir::Up => (rules::can_move_up(&map, node.x, node.y, state), node.x, node.y.wrapping_sub(1)),
+                MoveDir::Down => (rules::can_move_down(&map, node.x, node.y, state), node.x, node.y + 1),
+            };
+            if !ok || cost + self.move_cost > deadline { continue; }
+            let Some((y, left, fallen)) = self.land(holes, cost + self.move_cost, nx, ny, node.left) else { continue };
+            let step_cost = self.move_cost + fallen;
+            let open = self.unfilled(holes, cost + step_cost);
+            let next = Node { x: nx, y, left, near: near(&open, (nx, y)), ..node };
+            out.push((next, Action::Move(dir), step_cost, open));
+        }
+
+        let done = cost + self.dig_cost;
+        if self.dig_limit.is_none_or(|limit| node.digs < limit) && done <= deadline {
+            for dir in [Facing::Left, Facing::Right] {
+                let Some((hx, hy)) = rules::can_dig(&map, node.x, node.y, state, dir) else { continue };
+                // No hacking under a token
+                if self.mine(hx, node.y, node.left) != node.left { continue; }
+                let mut open = self.unfilled(holes, done);
+                open.push(((hx, hy), done + self.hole_ticks));
+                let digs = node.digs + self.dig_limit.is_some() as u32;
+                let next = Node { digs, near: near(&open, (node.x, node.y)), ..node };
+                out.push((next, Action::Dig(dir), self.dig_cost, open));
+            }
+        }
+        out
+    }
+
+    /// The holes not yet filled in at tick `now`.
+    fn unfilled(&self, holes: &[(Cell, u32)], now: u32) -> Holes {
+        holes.iter().copied().filter(|&(_, closes)| closes + self.fill_ticks > now).collect()
+    }
+
+    /// Ticks still needed, roughly: out to the furthest token left and up
+    /// from it to the top row, plus a charge per token, all weighted by
+    /// `GREED`.
+    fn estimate(&self, node: Node) -> u32 {
+        let far = self.tokens.iter().enumerate()
+            .filter(|&(i, _)| node.left & (1 << i) != 0)
+            .map(|(_, &(tx, ty))| node.x.abs_diff(tx) + node.y.abs_diff(ty) + ty)
+            .max()
+            .unwrap_or(node.y) as u32;
+        (far + node.left.count_ones() * TOKEN_MOVES) * self.move_cost * GREED
+    }
+}
+
+/// The open holes near `at` that tell nodes apart, nearest first.
+fn near(holes: &[(Cell, u32)], at: Cell) -> [Option<Cell>; NEAR_HOLES] {
+    let mut close: Vec<Cell> = holes.iter()
+        .map(|&(h, _)| h)
+        .filter(|&(hx, hy)| hx.abs_diff(at.0) <= HOLE_REACH && hy.abs_diff(at.1) <= HOLE_REACH)
+        .collect();
+    close.sort_by_key(|&(hx, hy)| (hx.abs_diff(at.0) + hy.abs_diff(at.1), hx, hy));
+    let mut out = [None; NEAR_HOLES];
+    for (slot, h) in out.iter_mut().zip(close) {
+        *slot = Some(h);
+    }
+    out
+}
+
+/// Inputs that clear `level` from its start, one per tick, or `None` if no
+/// solution was found.
+pub fn solve(level: &LevelDef, config: &GameConfig) -> Option<Vec<FrameInput>> {
+    if level.hub || !level.rooms.is_empty() { return None; }
+
+    let world = start(level, config);
+    let route = plan_route(&world, SEARCH_LIMIT)?;
+    replay(world, &route)
+}
+
+/// Whether the search finds a way through `world`'s current room as it
+/// stands, from the player's cell, sentinels aside. No replay and a
+/// shorter search: much cheaper than `solve`, and a check rather than a
+/// promise — it gives up on more levels that can be cleared.
+pub fn reachable(world: &WorldState) -> bool {
+    !world.is_multi_room() && plan_route(world, REACH_LIMIT).is_some()
+}
+
+/// The search's route from the player's cell to the top with every
+/// token mined, expanding at most `limit` nodes.
+fn plan_route(world: &WorldState, limit: usize) -> Option<Vec<Step>> {
+    let plan = plan_of(world)?;
+    let (x, y) = (world.player.x, world.player.y);
+    let all = u128::MAX >> (MAX_TOKENS - plan.tokens.len());
+    let (y, left, _) = plan.land(&[], 0, x, y, all)?;
+    search(&plan, Node { x, y, left, digs: 0, near: [None; NEAR_HOLES] }, limit)
+}
+
+/// The level, started, without its sentinels.
+fn start(level: &LevelDef, config: &GameConfig) -> WorldState {
+    let mut world = WorldState::new();
+    world.speed = config.speed.clone();
+    level::load_level_def(&mut world, level, config);
+    world.guards.clear();
+    world.rebuild_occupancy();
+    world.phase = Phase::Playing;
+    world
+}
+
+fn plan_of(world: &WorldState) -> Option<Plan> {
+    let mut tokens = vec![];
+    let mut base = world.tiles.clone();
+    for y in 0..base.height() {
+        for x in 0..base.width() {
+            if base[(x, y)].is_gold() {
+                tokens.push((x, y));
+                base[(x, y)] = Tile::Empty;
+            }
+        }
+    }
+    if tokens.is_empty() || tokens.len() > MAX_TOKENS { return None; }
+
+    // The exit as `step` opens it, on a throwaway copy of the tiles
+    let mut shown = WorldState::new();
+    shown.width = world.width;
+    shown.height = world.height;
+    shown.tiles = base.clone();
+    shown.exit_columns = world.exit_columns.clone();
+    shown.hidden_ladder_positions = world.hidden_ladder_positions.clone();
+    step::enable_exit(&mut shown);
+
+    Some(Plan {
+        base,
+        exit: shown.tiles,
+        width: world.width,
+        height: world.height,
+        tokens,
+        dig_limit: world.dig_limit,
+        move_cost: world.speed.player_move_rate + 1,
+        dig_cost: world.speed.dig_duration,
+        hole_ticks: world.speed.hole_open_ticks,
+        fill_ticks: world.speed.hole_close_ticks,
+    })
+}
+
+/// Best-first from `from` to the top row with every token mined.
+fn search(plan: &Plan, from: Node, limit: usize) -> Option<Vec<Step>> {
+    let mut visits = vec![Visit { node: from, cost: 0, from: None, holes: vec![] }];
+    let mut best: HashMap<Node, u32> = HashMap::from([(from, 0)]);
+    let mut open = BinaryHeap::new();
+    open.push(Reverse((plan.estimate(from), 0, 0)));
+
+    let mut expanded = 0;
+    while let Some(Reverse((_, cost, i))) = open.pop() {
+        let node = visits[i].node;
+        if best[&node] < cost { continue; }
+        if node.left == 0 && node.y == 0 {
+            let mut path = vec![];
+            let mut at = i;
+            while let Some((prev, action)) = visits[at].from {
+                path.push(Step { action, to: visits[at].node, at: visits[prev].cost });
+                at = prev;
+            }
+            path.reverse();
+            return Some(path);
+        }
+        expanded += 1;
+        if expanded > limit { return None; }
+
+        for (next, action, step_cost, holes) in plan.successors(node, cost, &visits[i].holes) {
+            let cost = cost + step_cost;
+            if best.get(&next).is_some_and(|&c| c <= cost) { continue; }
+            best.insert(next, cost);
+            visits.push(Visit { node: next, cost, from: Some((i, action)), holes });
+            open.push(Reverse((cost + plan.estimate(next), cost, visits.len() - 1)));
+        }
+    }
+    None
+}
+
+/// Play the plan through `step`, recording each tick's input. Each step
+/// waits for the tick it was planned on, so the holes keep the search's
+/// time: moves can run quicker than it costs them, never slower. `None`
+/// if the player doesn't end up where the plan says with the tokens it
+/// says, or dies.
+fn replay(mut world: WorldState, route: &[Step]) -> Option<Vec<FrameInput>> {
+    let idle = FrameInput { movement: None, dig: None };
+    let mut inputs = vec![];
+    let patience = world.speed.player_move_rate + world.speed.dig_duration + 4;
+    let fall = patience + world.height as u32;
+
+    // A spawn in mid-air falls first
+    settle(&mut world, &mut inputs, fall)?;
+    let start = world.tick;
+
+    for &Step { action, to: node, at } in route {
+        while world.tick < start + at as u64 && world.phase == Phase::Playing {
+            tick(&mut world, &mut inputs, idle)?;
+        }
+
+        match action {
+            Action::Dig(facing) => {
+                tick(&mut world, &mut inputs, FrameInput { movement: None, dig: Some(facing) })?;
+                let mut waited = 0;
+                while !world.digs.is_empty() && world.phase == Phase::Playing {
+                    waited += 1;
+                    if waited > patience { return None; }
+                    tick(&mut world, &mut inputs, idle)?;
+                }
+            }
+            Action::Move(dir) => {
+                let from = (world.player.x, world.player.y);
+                let mut waited = 0;
+                while (world.player.x, world.player.y) == from && world.phase == Phase::Playing {
+                    waited += 1;
+                    if waited > patience { return None; }
+                    tick(&mut world, &mut inputs, FrameInput { movement: Some(dir), dig: None })?;
+                }
+                settle(&mut world, &mut inputs, fall)?;
+            }
+        }
+
+        if world.phase == Phase::LevelOutro { return Some(inputs); }
+        let mined = world.gold_remaining == node.left.count_ones() as usize;
+        if (world.player.x, world.player.y) != (node.x, node.y) || !mined { return None; }
+    }
+    None
+}
+
+/// One tick of `input`; `None` once the level is lost.
+fn tick(world: &mut WorldState, inputs: &mut Vec<FrameInput>, input: FrameInput) -> Option<()> {
+    if step::run_ticks(world, [input], 1).ticks == 1 {
+        inputs.push(input);
+    }
+    matches!(world.phase, Phase::Playing | Phase::LevelOutro).then_some(())
+}
+
+/// Idle until the player stops falling.
+fn settle(world: &mut WorldState, inputs: &mut Vec<FrameInput>, patience: u32) -> Option<()> {
+    let idle = FrameInput { movement: None, dig: None };
+    let mut waited = 0;
+    while world.phase == Phase::Playing && falling(world) {
+        waited += 1;
+        if waited > patience { return None; }
+        tick(world, inputs, idle)?;
+    }
+    Some(())
+}
+
+fn falling(world: &WorldState) -> bool {
+    world.player.state == ActorState::Falling
+        || !world.has_support_for_player(world.player.x, world.player.y)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::path::Path;
+
+    use super::*;
+
+    #[test]
+    fn solutions_clear_bundled_levels() {
+        let config = GameConfig::builtin();
+        let levels = Path::new(env!("CARGO_MANIFEST_DIR")).join("levels");
+        // Trap bricks under tokens (13), hacking down through holes on
+        // the clock (15, 20), a hole left for later (119)
+        for name in ["001_level1", "013_level13", "015_level15", "018_level18", "020_level20", "119_level119"] {
+            let text = std::fs::read_to_string(levels.join(format!("{name}.txt"))).unwrap();
+            let def = level::parse_level_file(&text).unwrap();
+            let inputs = solve(&def, &config).unwrap_or_else(|| panic!("{name}: no solution found"));
+
+            let mut world = start(&def, &config);
+            step::run_ticks(&mut world, inputs.iter().copied(), inputs.len());
+            assert_eq!(world.phase, Phase::LevelOutro, "{name}: not cleared");
+        }
+    }
+}
@@ -0,0 +1,276 @@
+//! Live spectating: a game started with `--spectate-host` streams what's
+//! on screen to viewers started with `--spectate <addr>`, over plain TCP.
+//!
+//! The stream is lines of text:
+//!   - `node=<level code>` (see `level_code`) when the host starts a level
+//!     or changes room, and first thing to a viewer that joins mid-level
+//!   - a frame every tick: the phase and animation tick, the room, then
+//!     the level's state as snapshot save text (`save::snapshot_text`),
+//!     ending with `end`; away from a level, just `phase=waiting`
+//!
+//! Viewers build the level from its code and lay each frame's state over
+//! it. They run no sim of their own and send nothing back. Sockets are
+//! served from background threads (as `online` does its requests), so a
+//! slow or vanished viewer never stalls the host's game loop.
+
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread;
+use std::time::Duration;
+
+use crate::config::GameConfig;
+use crate::i18n::t;
+use crate::sim::level::{self, LevelDef};
+use crate::sim::level_code;
+use crate::sim::save;
+use crate::sim::story::StoryPage;
+use crate::sim::world::{Phase, WorldState};
+
+/// Port used when an address doesn't name one.
+pub const DEFAULT_PORT: u16 = 7777;
+/// A viewer that can't take a frame for this long is dropped.
+const WRITE_TIMEOUT: Duration = Duration::from_secs(2);
+const END: &str = "end";
+const WAITING: &str = "waiting";
+
+/// The phases a viewer is shown; the rest (menus, story, end screens)
+/// are the host's own business and show as waiting.
+const PHASES: [(Phase, &str); 6] = [
+    (Phase::LevelIntro, "intro"),
+    (Phase::LevelReady, "ready"),
+    (Phase::Playing, "playing"),
+    (Phase::Dying, "dying"),
+    (Phase::LevelOutro, "outro"),
+    (Phase::LevelComplete, "complete"),
+];
+
+/// `addr`, with the default port if it has none.
+pub fn with_port(addr: &str) -> String {
+    if addr.rsplit_once(':').is_some_and(|(_, port)| port.parse::<u16>().is_ok()) {
+        addr.to_string()
+    } else {
+        format!("{addr}:{DEFAULT_PORT}")
+    }
+}
+
+enum Out {
+    Viewer(TcpStream),
+    Node(String),
+    Frame(String),
+}
+
+/// The hosting side: accepts viewers and sends them each tick.
+pub struct Host {
+    tx: Sender<Out>,
+    pub addr: SocketAddr,
+    /// The level and room viewers were last sent: pack, level, room.
+    node: Option<(String, usize, usize)>,
+}
+
+impl Host {
+    pub fn bind(addr: &str) -> io::Result<Host> {
+        let listener = TcpListener::bind(with_port(addr))?;
+        let addr = listener.local_addr()?;
+        let (tx, rx) = mpsc::channel();
+        let accepted = tx.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                let _ = stream.set_nodelay(true);
+                let _ = stream.set_write_timeout(Some(WRITE_TIMEOUT));
+                if accepted.send(Out::Viewer(stream)).is_err() { break; }
+            }
+        });
+        thread::spawn(move || broadcast(rx));
+        Ok(Host { tx, addr, node: None })
+    }
+
+    /// Send viewers the world as it is after this tick.
+    pub fn send(&mut self, world: &WorldState, config: &GameConfig) {
+        let Some(phase) = phase_name(world.phase) else {
+            self.node = None;
+            let _ = self.tx.send(Out::Frame(format!("phase={WAITING}\n{END}\n")));
+            return;
+        };
+        let node = (world.active_pack_path.clone(), world.current_level, world.current_room);
+        if self.node.as_ref() != Some(&node) {
+            if let Some(def) = level::active_level(world, world.current_level, config) {
+                let _ = self.tx.send(Out::Node(level_code::encode(&level::share_text(&def))));
+            }
+            self.node = Some(node);
+        }
+        let _ = self.tx.send(Out::Frame(frame_text(world, phase)));
+    }
+}
+
+/// Write everything the game sends to every viewer, dropping the ones
+/// that fail. A viewer joining gets the level in play first.
+fn broadcast(rx: Receiver<Out>) {
+    let mut viewers: Vec<TcpStream> = vec![];
+    let mut node = String::new();
+    for msg in rx {
+        match msg {
+            Out::Viewer(mut viewer) => {
+                if viewer.write_all(node.as_bytes()).is_ok() {
+                    viewers.push(viewer);
+                }
+            }
+            Out::Node(code) => {
+                node = format!("node={code}\n");
+                viewers.retain_mut(|v| v.write_all(node.as_bytes()).is_ok());
+            }
+            Out::Frame(text) => viewers.retain_mut(|v| v.write_all(text.as_bytes()).is_ok()),
+        }
+    }
+}
+
+fn phase_name(phase: Phase) -> Option<&'static str> {
+    PHASES.iter().find(|(p, _)| *p == phase).map(|(_, name)| *name)
+}
+
+fn frame_text(w: &WorldState, phase: &str) -> String {
+    let mut out = format!("phase={phase}\nanim={}\nroom={}\ntotal={}\npack={}\n",
+                          w.anim_tick, w.current_room, w.total_levels, w.active_pack);
+    out.push_str(&save::snapshot_text(w));
+    out.push_str(END);
+    out.push('\n');
+    out
+}
+
+enum In {
+    Node(String),
+    Frame(String),
+    Closed,
+}
+
+/// The watching side: shows the host's game in a world of its own.
+pub struct Viewer {
+    rx: Receiver<In>,
+    host: String,
+    /// The host's level, from its last `node=` line.
+    level: Option<LevelDef>,
+    /// The room of `level` loaded into the world, if it's loaded.
+    loaded: Option<usize>,
+}
+
+impl Viewer {
+    pub fn connect(addr: &str) -> io::Result<Viewer> {
+        let host = with_port(addr);
+        let stream = TcpStream::connect(&host)?;
+        let (tx, rx) = mpsc::channel();
+        thread::spawn(move || {
+            let mut frame = String::new();
+            for line in BufReader::new(stream).lines() {
+                let Ok(line) = line else { break };
+                let msg = if let Some(code) = line.strip_prefix("node=") {
+                    In::Node(code.to_string())
+                } else if line == END {
+                    In::Frame(std::mem::take(&mut frame))
+                } else {
+                    frame.push_str(&line);
+                    frame.push('\n');
+                    continue;
+                };
+                if tx.send(msg).is_err() { return; }
+            }
+            let _ = tx.send(In::Closed);
+        });
+        Ok(Viewer { rx, host, level: None, loaded: None })
+    }
+
+    /// Show the waiting page until the host's first frame arrives.
+    pub fn start(&self, world: &mut WorldState) {
+        world.spectating = Some(self.host.clone());
+        self.notice(world, t!("spectate.waiting").to_string());
+    }
+
+    /// Apply whatever the host has sent. True if the screen changed.
+    pub fn poll(&mut self, world: &mut WorldState, config: &GameConfig) -> bool {
+        let mut changed = false;
+        while let Ok(msg) = self.rx.try_recv() {
+            changed = true;
+            match msg {
+                In::Node(code) => {
+                    self.level = level_code::decode(&code).and_then(|text| level::parse_level_file(&text));
+                    self.loaded = None;
+                }
+                In::Frame(text) => self.apply(world, &text, config),
+                In::Closed => {
+                    self.level = None;
+                    self.notice(world, t!("spectate.ended").to_string());
+                }
+            }
+        }
+        changed
+    }
+
+    fn apply(&mut self, world: &mut WorldState, text: &str, config: &GameConfig) {
+        let field = |key: &str| text.lines().find_map(|l| l.strip_prefix(key)?.strip_prefix('='));
+        let phase = field("phase").and_then(|name| PHASES.iter().find(|(_, n)| *n == name));
+        let data = save::parse_snapshot_text(text);
+        let (Some(&(phase, _)), Some(level), Some(data)) = (phase, &self.level, data) else {
+            self.notice(world, t!("spectate.waiting").to_string());
+            return;
+        };
+        let Some(snap) = &data.snapshot else { return };
+        let room = field("room").and_then(|v| v.parse().ok()).unwrap_or(0);
+        if self.loaded != Some(room) {
+            level::load_level_def(world, level, config);
+            world.enter_room(room);
+            world.clear_messages();
+            self.loaded = Some(room);
+        }
+        save::restore_snapshot(world, snap);
+        world.phase = phase;
+        world.anim_tick = field("anim").and_then(|v| v.parse().ok()).unwrap_or(0);
+        world.current_level = data.level;
+        world.total_levels = field("total").and_then(|v| v.parse().ok()).unwrap_or(data.level + 1);
+        world.active_pack = field("pack").unwrap_or_default().to_string();
+        world.score = data.score;
+        world.lives = data.lives;
+        world.tick_messages();
+    }
+
+    /// Show a page in place of the game: waiting for it, or its end.
+    fn notice(&self, world: &mut WorldState, text: String) {
+        let page = StoryPage { title: t!("spectate.title", host = self.host).to_string(), lines: vec![text], delay_secs: 0 };
+        if world.phase == Phase::Cutscene && world.story.first() == Some(&page) { return; }
+        world.story = vec![page];
+        world.phase = Phase::Cutscene;
+        // Typed out in full: there's no sim ticking to type it
+        world.anim_tick = world.story[0].typing_ticks();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn viewers_see_the_hosts_level_as_it_is() {
+        let text = "# Watched\nP  $   E\n########\n========\n";
+        let (mut host, config) = crate::sim::step::tests::world_from(text);
+        let def = level::parse_level_file(text).unwrap();
+        host.phase = Phase::Playing;
+        host.player.x = 2;
+        host.score = 250;
+        host.tiles[(5, 1)] = crate::domain::tile::Tile::Empty;
+
+        let (tx, rx) = mpsc::channel();
+        let mut viewer = Viewer { rx, host: "host:7777".into(), level: None, loaded: None };
+        let mut world = WorldState::new();
+        viewer.start(&mut world);
+        tx.send(In::Node(level_code::encode(&level::share_text(&def)))).unwrap();
+        tx.send(In::Frame(frame_text(&host, "playing"))).unwrap();
+        assert!(viewer.poll(&mut world, &config));
+        assert_eq!((world.phase, world.player.x, world.score), (Phase::Playing, 2, 250));
+        assert_eq!(world.tiles, host.tiles);
+        assert_eq!(world.guards.len(), 1);
+
+        tx.send(In::Closed).unwrap();
+        viewer.poll(&mut world, &config);
+        assert_eq!(world.phase, Phase::Cutscene);
+        assert_eq!(with_port("192.168.1.5"), "192.168.1.5:7777");
+        assert_eq!(with_port("[::1]:80"), "[::1]:80");
+    }
+}
@@ -4,24 +4,42 @@
 ///   1. Dig resolution
 ///   2. Movement resolution (player → guards)
 ///   3. Trap brick collapse
+///      (secret passages are found after gravity)
 ///   4. Gravity resolution
 ///   5. Hole effects (trap guards)
 ///   6. Collision / contact events
-///   7. Timer updates (hole regen, guard escape)
-///   8. Win / lose check
+///   7. Timer updates (hole regen, guard escape, spawners)
+///   8. Dig budget check (puzzle mode)
+///   9. Door transitions (multi-room levels)
+///  10. Tutorial tips
+///  11. Win / lose check
+///
+/// Each stage is a `System` of `pipeline()`, which `step` runs from
+/// `WorldState::pipeline` (see `sim::system`).
 ///
 /// Physics queries use terrain (physics::terrain_at) + occupancy (physics::has_*).
 /// Terrain = what the cell IS.  Occupancy = who is there.
 /// Movement = terrain.passable && !occupied.
 /// Support = terrain support || trapped guard below.
-
-use crate::domain::entity::{ActorState, DigInProgress, Facing, FrameInput, Hole, MoveDir};
+///
+/// A tick depends on nothing but the world and its input: no clock, no
+/// random numbers. `run_ticks` plays many back to back, as fast as they
+/// go, for everything that plays without a screen (solver, run code
+/// checks, replays, the agent protocol).
+
+use crate::config::GuardRespawn;
+use crate::domain::entity::{ActorState, DigInProgress, Facing, FrameInput, Guard, Hole, MoveDir};
+use crate::domain::grid::Grid;
 use crate::domain::rules::{self, MapView};
 use crate::domain::physics;
 use crate::domain::ai;
 use crate::domain::tile::Tile;
+use crate::i18n::t;
 use super::event::GameEvent;
-use super::world::{Phase, WorldState};
+use super::randomize;
+use super::script::{ScriptAction, ScriptTrigger, ScriptVars};
+use super::system::{FnSystem, Flow, Pipeline, System};
+use super::world::{GameMode, Phase, Severity, TipTrigger, WorldState};
 
 // ══════════════════════════════════════════════════════════════
 // Main entry point
@@ -30,29 +48,87 @@ use super::world::{Phase, WorldState};
 pub fn step(world: &mut WorldState, input: FrameInput) -> Vec<GameEvent> {
     if world.phase != Phase::Playing { return vec![]; }
 
-    let mut events: Vec<GameEvent> = Vec::new();
-    world.tick += 1;
+    let state = world.player.state;
+    let score = world.score;
+    let mut pipeline = std::mem::take(&mut world.pipeline);
+    let mut events = pipeline.run(world, input);
+    world.pipeline = pipeline;
+    #[cfg(feature = "verify")]
+    super::verify(world);
+    if world.player.state != state {
+        events.push(GameEvent::PlayerStateChanged { from: state, to: world.player.state });
+    }
+    if world.score != score {
+        events.push(GameEvent::ScoreChanged { from: score, to: world.score });
+    }
+    events
+}
+
+/// What `run_ticks` played.
+#[derive(Clone, Debug, Default)]
+pub struct Ticks {
+    pub ticks: usize,
+    /// Every tick's events, in order.
+    pub events: Vec<GameEvent>,
+}
 
-    if world.message_timer > 0 {
-        world.message_timer -= 1;
-        if world.message_timer == 0 { world.message.clear(); }
+impl Ticks {
+    /// The node was cleared by the last tick played.
+    pub fn cleared(&self) -> bool {
+        self.events.iter().any(|e| matches!(e, GameEvent::StageCleared))
     }
+}
 
-    resolve_dig(world, input.dig, &mut events);
-    resolve_dig_progress(world, &mut events);
-    world.rebuild_hole_grid(); // holes may have been added by dig completion
-    resolve_player_movement(world, input.movement);
-    resolve_guard_movement(world);
-    resolve_trap_bricks(world, &mut events);
-    resolve_gravity(world, &mut events);
-    resolve_hole_traps(world, &mut events);
-    resolve_gold_pickup(world, &mut events);
-    resolve_guard_gold_drop(world, &mut events);
-    if resolve_enemy_collision(world, &mut events) { return events; }
-    resolve_timers(world, &mut events);
-    resolve_win(world, &mut events);
+/// Step up to `n` ticks, each with the next of `inputs`, with nothing
+/// drawn and no waiting between them. Stops early when `inputs` runs out
+/// or the level is no longer being played (cleared or lost).
+pub fn run_ticks(world: &mut WorldState, inputs: impl IntoIterator<Item = FrameInput>, n: usize) -> Ticks {
+    let mut played = Ticks::default();
+    for input in inputs.into_iter().take(n) {
+        if world.phase != Phase::Playing { break; }
+        played.events.extend(step(world, input));
+        played.ticks += 1;
+    }
+    played
+}
 
-    events
+/// The built-in systems, in the fixed order a tick resolves them.
+pub fn pipeline() -> Pipeline {
+    fn stop_if(ended: bool) -> Flow { if ended { Flow::Stop } else { Flow::Continue } }
+
+    let systems: Vec<FnSystem> = vec![
+        FnSystem("clock", |w, t| {
+            w.tick += 1;
+            if tick_clock(w, &mut t.events) { return Flow::Stop; }
+            w.tick_messages();
+            Flow::Continue
+        }),
+        FnSystem("dig", |w, t| {
+            resolve_dig(w, t.input.dig, &mut t.events);
+            resolve_dig_progress(w, &mut t.events);
+            w.rebuild_hole_grid(); // holes may have been added by dig completion
+            Flow::Continue
+        }),
+        FnSystem("player", |w, t| { resolve_player_movement(w, t.input.movement); Flow::Continue }),
+        FnSystem("guards", |w, t| { resolve_guard_movement(w, &mut t.events); Flow::Continue }),
+        FnSystem("trap_bricks", |w, t| { resolve_trap_bricks(w, &mut t.events); Flow::Continue }),
+        FnSystem("gravity", |w, t| { resolve_gravity(w, &mut t.events); Flow::Continue }),
+        FnSystem("hole_traps", |w, t| { resolve_hole_traps(w, &mut t.events); Flow::Continue }),
+        FnSystem("secrets", |w, t| { resolve_secrets(w, &mut t.events); Flow::Continue }),
+        FnSystem("gold_pickup", |w, t| { resolve_gold_pickup(w, &mut t.events); Flow::Continue }),
+        FnSystem("lanterns", |w, t| { resolve_lanterns(w, &mut t.events); Flow::Continue }),
+        FnSystem("guard_gold_drop", |w, t| { resolve_guard_gold_drop(w, &mut t.events); Flow::Continue }),
+        FnSystem("hazards", |w, t| stop_if(resolve_hazards(w, &mut t.events))),
+        FnSystem("collision", |w, t| stop_if(resolve_enemy_collision(w, &mut t.events))),
+        FnSystem("timers", |w, t| { resolve_timers(w, &mut t.events); Flow::Continue }),
+        FnSystem("spawners", |w, t| { resolve_spawners(w, &mut t.events); Flow::Continue }),
+        FnSystem("dig_budget", |w, t| stop_if(resolve_dig_budget(w, &mut t.events))),
+        FnSystem("doors", |w, t| { resolve_doors(w, &mut t.events); Flow::Continue }),
+        FnSystem("tips", |w, t| { resolve_tips(w, t.input.movement, &t.events); Flow::Continue }),
+        FnSystem("scripts", |w, t| { resolve_scripts(w, t.from, &mut t.events); Flow::Continue }),
+        FnSystem("win", |w, t| { resolve_win(w, &mut t.events); Flow::Continue }),
+    ];
+    Pipeline::new(systems.into_iter().map(|s| Box::new(s) as Box<dyn System>).collect())
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -84,10 +160,7 @@ fn can_drop_gold_at(world: &WorldState, x: usize, y: usize) -> bool {
     if y + 1 >= world.height { return true; }
     if world.terrain_at(x, y + 1).is_solid() { return true; }
     // Check if there's an active hole below
-    if y + 1 < world.hole_grid.len() && x < world.hole_grid[y + 1].len() {
-        return world.hole_grid[y + 1][x];
-    }
-    false
+    world.hole_at(x, y + 1)
 }
 
 // ══════════════════════════════════════════════════════════════
@@ -96,6 +169,7 @@ fn can_drop_gold_at(world: &WorldState, x: usize, y: usize) -> bool {
 
 fn resolve_dig(world: &mut WorldState, dig_dir: Option<Facing>, events: &mut Vec<GameEvent>) {
     let dir = match dig_dir { Some(d) => d, None => return };
+    if world.digs_left == Some(0) { return; }
     let map = MapView { tiles: &world.tiles, width: world.width, height: world.height };
     let p = &world.player;
 
@@ -103,8 +177,10 @@ fn resolve_dig(world: &mut WorldState, dig_dir: Option<Facing>, events: &mut Vec
         if world.digs.iter().any(|d| d.x == dx && d.y == dy) { return; }
         if world.holes.iter().any(|h| h.x == dx && h.y == dy) { return; }
         // Can't dig under gold (prevents gold falling into hole edge cases)
-        if dy > 0 && world.terrain_at(dx, dy - 1) == Tile::Gold { return; }
+        if dy > 0 && world.terrain_at(dx, dy - 1).is_gold() { return; }
         world.digs.push(DigInProgress::new(dx, dy, world.speed.dig_duration));
+        world.digs_made += 1;
+        if let Some(n) = &mut world.digs_left { *n -= 1; }
         events.push(GameEvent::HoleCreated { x: dx, y: dy });
     }
 }
@@ -173,7 +249,7 @@ fn resolve_player_movement(world: &mut WorldState, movement: Option<MoveDir>) {
         if world.player.state == ActorState::Falling {
             if physics::has_support_for_player(
                 &world.tiles, world.width, world.height,
-                &world.hole_grid, &world.guards,
+                &world.hole_grid, &world.occupancy,
                 world.player.x, world.player.y,
             ) {
                 world.player.state = ActorState::OnGround;
@@ -202,9 +278,32 @@ struct MoveIntent {
     dx: i32,
 }
 
-fn resolve_guard_movement(world: &mut WorldState) {
-    let px = world.player.x;
-    let py = world.player.y;
+/// Search the guards' flow field again if the player has changed cell
+/// (once `[ai] repath_ticks` have passed), holes or trapped guards have
+/// come or gone, or it's gone stale.
+fn refresh_flow(world: &mut WorldState) {
+    let target = (world.player.x, world.player.y);
+    let blockers: Vec<(usize, usize)> = world.holes.iter().map(|h| (h.x, h.y))
+        .chain(world.guards.iter().filter(|g| g.state == ActorState::InHole).map(|g| (g.x, g.y)))
+        .collect();
+    let flow = &world.flow;
+    let age = flow.built.filter(|&t| t <= world.tick).map(|t| world.tick - t);
+    let fresh = flow.fits(world.width, world.height)
+        && flow.blockers == blockers
+        && age.is_some_and(|age| age < world.ai.repath_ticks as u64
+            || (flow.target == target && age < ai::FLOW_REFRESH_TICKS));
+    if fresh { return; }
+
+    let ctx = ai::Ctx {
+        tiles: &world.tiles, width: world.width, height: world.height,
+        hole_grid: &world.hole_grid, guards: &world.guards,
+        occupancy: &world.occupancy,
+    };
+    world.flow = ai::FlowField::build(&ctx, target, world.tick, blockers, world.ai.max_depth);
+}
+
+fn resolve_guard_movement(world: &mut WorldState, events: &mut Vec<GameEvent>) {
+    refresh_flow(world);
 
     // ── Phase 0: Tick separation timers ──
     for g in world.guards.iter_mut() {
@@ -231,18 +330,16 @@ fn resolve_guard_movement(world: &mut WorldState) {
         let gy = world.guards[i].y;
 
         // Choose AI mode: separation or chase
+        let ctx = ai::Ctx {
+            tiles: &world.tiles, width: world.width, height: world.height,
+            hole_grid: &world.hole_grid, guards: &world.guards,
+        occupancy: &world.occupancy,
+        };
+        let (state, facing) = (world.guards[i].state, world.guards[i].facing);
         let (dx, dy) = if world.guards[i].separation_timer > 0 {
-            ai::find_separation_direction(
-                &world.tiles, world.width, world.height,
-                &world.hole_grid, &world.guards,
-                i, gx, gy, world.guards[i].state, px, py,
-            )
+            ai::find_separation_direction(&ctx, &world.flow, i, (gx, gy), state, facing)
         } else {
-            ai::find_direction(
-                &world.tiles, world.width, world.height,
-                &world.hole_grid, &world.guards,
-                gx, gy, world.guards[i].state, px, py,
-            )
+            world.flow.direction(&ctx, (gx, gy), state, facing)
         };
 
         if dx == 0 && dy == 0 { continue; }
@@ -273,7 +370,7 @@ fn resolve_guard_movement(world: &mut WorldState) {
 
         // Blocked by existing active guard at target?
         let blocked_by_guard = physics::has_active_guard_except(
-            &world.guards, tx, ty, intent.guard_idx,
+            &world.occupancy, tx, ty, intent.guard_idx,
         );
         if blocked_by_guard { continue; }
 
@@ -291,9 +388,13 @@ fn resolve_guard_movement(world: &mut WorldState) {
         let i = intent.guard_idx;
         world.guards[i].x = intent.target_x;
         world.guards[i].y = intent.target_y;
+        world.guard_moved(i);
         if intent.dx < 0 { world.guards[i].facing = Facing::Left; }
         if intent.dx > 0 { world.guards[i].facing = Facing::Right; }
         world.guards[i].move_cooldown = world.speed.guard_move_rate;
+        world.guards[i].stepped_at = world.tick;
+        let g = &world.guards[i];
+        events.push(GameEvent::GuardStepped { id: g.id, x: g.x, y: g.y });
     }
 
     // ── Phase 4: Update state for all movable guards ──
@@ -304,9 +405,10 @@ fn resolve_guard_movement(world: &mut WorldState) {
 
         world.guards[i].state = physics::resolve_state(
             &world.tiles, world.width, world.height,
-            &world.hole_grid, &world.guards,
+            &world.hole_grid, &world.occupancy,
             world.guards[i].x, world.guards[i].y, world.guards[i].state,
         );
+        world.guard_moved(i);
     }
 
     // ── Phase 5: Detect guard contact → activate separation ──
@@ -358,6 +460,26 @@ fn resolve_trap_bricks(world: &mut WorldState, events: &mut Vec<GameEvent>) {
     }
 }
 
+// ══════════════════════════════════════════════════════════════
+// Secret passages
+// ══════════════════════════════════════════════════════════════
+
+/// The player going into a fake brick gives its passage away: the whole
+/// run of fake bricks it's part of turns to empty cells. Guards go
+/// through them without telling.
+fn resolve_secrets(world: &mut WorldState, events: &mut Vec<GameEvent>) {
+    let (px, py) = (world.player.x, world.player.y);
+    if !world.player.alive || world.terrain_at(px, py) != Tile::FakeBrick { return; }
+    let mut todo = vec![(px, py)];
+    while let Some((x, y)) = todo.pop() {
+        if world.terrain_at(x, y) != Tile::FakeBrick { continue; }
+        world.set_tile(x, y, Tile::Empty);
+        todo.extend([(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)]);
+    }
+    events.push(GameEvent::SecretFound { x: px, y: py });
+    world.push_message(Severity::Achievement, t!("msg.secret"), 60);
+}
+
 // ══════════════════════════════════════════════════════════════
 // Gravity
 // ══════════════════════════════════════════════════════════════
@@ -376,7 +498,7 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
             // Player uses player-specific support (active guards = floor)
             let full_support = physics::has_support_for_player(
                 &world.tiles, world.width, world.height,
-                &world.hole_grid, &world.guards, px, py,
+                &world.hole_grid, &world.occupancy, px, py,
             );
             let map = MapView { tiles: &world.tiles, width: world.width, height: world.height };
 
@@ -399,6 +521,7 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
                     world.player.state = ActorState::OnGround;
                 }
                 world.player.move_cooldown = 0;
+                events.push(GameEvent::PlayerLanded { x: px, y: py });
             }
         }
     }
@@ -418,7 +541,7 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         );
         if here.hole {
             // Guard is in a hole cell. Trap if no one else already trapped here.
-            if !physics::has_trapped_guard_except(&world.guards, gx, gy, i) {
+            if !physics::has_trapped_guard_except(&world.occupancy, gx, gy, i) {
                 // Drop gold above hole (gy-1) if no gold already there
                 let drop_y = if gy > 0 { Some(gy - 1) } else { None };
                 guard_enter_hole(world, i, gx, drop_y);
@@ -427,6 +550,7 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
             // Another guard already trapped — this guard is ON TOP (bridge)
             if world.guards[i].state == ActorState::Falling {
                 world.guards[i].state = ActorState::OnGround;
+                world.guard_moved(i);
             }
             continue;
         }
@@ -434,12 +558,13 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         // SUPPORT: terrain + trapped guard below
         let supported = physics::has_support_for_guard(
             &world.tiles, world.width, world.height,
-            &world.hole_grid, &world.guards, gx, gy, i,
+            &world.hole_grid, &world.occupancy, gx, gy, i,
         );
 
         if supported {
             if world.guards[i].state == ActorState::Falling {
                 world.guards[i].state = ActorState::OnGround;
+                world.guard_moved(i);
             }
             continue;
         }
@@ -448,6 +573,7 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         let ny = gy + 1;
         if ny >= world.height {
             world.guards[i].state = ActorState::OnGround;
+            world.guard_moved(i);
             continue;
         }
 
@@ -457,17 +583,21 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
 
         if !below.passable {
             world.guards[i].state = ActorState::OnGround;
-        } else if below.hole && !physics::has_trapped_guard(&world.guards, gx, ny) {
+            world.guard_moved(i);
+        } else if below.hole && !physics::has_trapped_guard(&world.occupancy, gx, ny) {
             // Empty hole below — guard falls in, gold stays at current pos (above hole)
             world.guards[i].y = ny;
+            world.guard_moved(i);
             guard_enter_hole(world, i, gx, Some(gy));
-        } else if below.hole && physics::has_trapped_guard(&world.guards, gx, ny) {
+        } else if below.hole && physics::has_trapped_guard(&world.occupancy, gx, ny) {
             // Hole with trapped guard below — acts as floor (bridge)
             world.guards[i].state = ActorState::OnGround;
+            world.guard_moved(i);
         } else {
             // Normal fall through empty space
             world.guards[i].y = ny;
             world.guards[i].state = ActorState::Falling;
+            world.guard_moved(i);
         }
     }
 }
@@ -479,12 +609,14 @@ fn resolve_gravity(world: &mut WorldState, events: &mut Vec<GameEvent>) {
 /// If gold already exists at the drop position, guard keeps the gold.
 fn guard_enter_hole(world: &mut WorldState, idx: usize, hole_x: usize, drop_y: Option<usize>) {
     world.guards[idx].state = ActorState::InHole;
+    world.guard_moved(idx);
     world.guards[idx].stuck_timer = world.speed.trap_escape_ticks;
+    reward(world, Reward::GuardTrapped);
 
     if world.guards[idx].carry_gold {
         if let Some(dy) = drop_y {
             if can_drop_gold_at(world, hole_x, dy) {
-                world.set_tile(hole_x, dy, Tile::Gold);
+                world.set_tile(hole_x, dy, Tile::Gold(world.guards[idx].carry_tier));
                 world.guards[idx].carry_gold = false;
                 world.guards[idx].carry_gold_timer = 0;
             }
@@ -510,7 +642,7 @@ fn resolve_hole_traps(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         let here = physics::terrain_at(
             &world.tiles, world.width, world.height, &world.hole_grid, gx, gy,
         );
-        if here.hole && !physics::has_trapped_guard_except(&world.guards, gx, gy, i) {
+        if here.hole && !physics::has_trapped_guard_except(&world.occupancy, gx, gy, i) {
             events.push(GameEvent::GuardTrapped { id: world.guards[i].id, x: gx, y: gy });
             let drop_y = if gy > 0 { Some(gy - 1) } else { None };
             guard_enter_hole(world, i, gx, drop_y);
@@ -525,29 +657,40 @@ fn resolve_hole_traps(world: &mut WorldState, events: &mut Vec<GameEvent>) {
 fn resolve_gold_pickup(world: &mut WorldState, events: &mut Vec<GameEvent>) {
     let px = world.player.x;
     let py = world.player.y;
-    if world.terrain_at(px, py) == Tile::Gold {
+    if let Some(token) = world.terrain_at(px, py).token() {
         world.set_tile(px, py, Tile::Empty);
         world.gold_remaining -= 1;
-        world.score += 100;
+        reward(world, Reward::Token(token.points));
         events.push(GameEvent::GoldPicked { x: px, y: py });
-        if world.gold_remaining == 0 {
+        // A hub's exit depends on cleared levels, not its own tokens
+        if world.gold_remaining == 0 && !world.in_hub() {
             events.push(GameEvent::AllGoldCollected);
             enable_exit(world);
-            world.set_message("All tokens mined! Escape to the top!", 80);
+            world.push_message(Severity::Achievement, t!("msg.all_tokens"), 80);
         }
     }
     for i in 0..world.guards.len() {
         let g = &world.guards[i];
         if g.state == ActorState::Dead || g.state == ActorState::InHole { continue; }
         if g.carry_gold { continue; }
-        if world.terrain_at(g.x, g.y) == Tile::Gold {
+        if let Tile::Gold(tier) = world.terrain_at(g.x, g.y) {
             world.set_tile(g.x, g.y, Tile::Empty);
             world.guards[i].carry_gold = true;
+            world.guards[i].carry_tier = tier;
             world.guards[i].carry_gold_timer = 0;
         }
     }
 }
 
+/// The player picks up a lantern in their cell: more light, in darkness.
+fn resolve_lanterns(world: &mut WorldState, events: &mut Vec<GameEvent>) {
+    let (px, py) = (world.player.x, world.player.y);
+    if !world.player.alive || world.terrain_at(px, py) != Tile::Lantern { return; }
+    world.set_tile(px, py, Tile::Empty);
+    world.add_light();
+    events.push(GameEvent::LanternPicked { x: px, y: py });
+}
+
 /// Guards drop gold after carrying it for too long.
 /// Gold is placed at the guard's current position only on solid ground.
 fn resolve_guard_gold_drop(world: &mut WorldState, events: &mut Vec<GameEvent>) {
@@ -563,16 +706,44 @@ fn resolve_guard_gold_drop(world: &mut WorldState, events: &mut Vec<GameEvent>)
             let gx = world.guards[i].x;
             let gy = world.guards[i].y;
             if can_drop_gold_at(world, gx, gy) {
-                world.set_tile(gx, gy, Tile::Gold);
+                world.set_tile(gx, gy, Tile::Gold(world.guards[i].carry_tier));
                 world.guards[i].carry_gold = false;
                 world.guards[i].carry_gold_timer = 0;
-                events.push(GameEvent::GuardDroppedGold { x: gx, y: gy });
+                events.push(GameEvent::GuardDroppedGold { id: world.guards[i].id, x: gx, y: gy });
             }
             // Can't drop here (ladder, rope, no solid ground) → keep trying next tick
         }
     }
 }
 
+/// Lasers and deadly custom tiles kill the player in them or standing on
+/// them, and sentinels too when the rules say so.
+fn resolve_hazards(world: &mut WorldState, events: &mut Vec<GameEvent>) -> bool {
+    if world.hazards_kill_guards {
+        for i in 0..world.guards.len() {
+            let (gx, gy) = (world.guards[i].x, world.guards[i].y);
+            if world.guards[i].state == ActorState::Dead || !deadly_at(world, gx, gy) { continue; }
+            // Killed with its token, it keeps it and comes back with it
+            world.guards[i].state = ActorState::Dead;
+            world.guard_moved(i);
+            world.guards[i].respawn_timer = 0;
+            events.push(GameEvent::GuardKilled { id: world.guards[i].id, x: gx, y: gy });
+        }
+    }
+
+    if !world.player.alive { return false; }
+    if !deadly_at(world, world.player.x, world.player.y) { return false; }
+
+    events.push(GameEvent::PlayerKilled);
+    player_die(world);
+    true
+}
+
+/// Is (x, y) in a deadly tile, or standing on one?
+fn deadly_at(world: &WorldState, x: usize, y: usize) -> bool {
+    world.terrain_at(x, y).is_deadly() || (y + 1 < world.height && world.terrain_at(x, y + 1).is_deadly())
+}
+
 fn resolve_enemy_collision(world: &mut WorldState, events: &mut Vec<GameEvent>) -> bool {
     if !world.player.alive { return false; }
     let px = world.player.x;
@@ -616,20 +787,17 @@ fn resolve_timers(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         if world.guards[i].state == ActorState::Dead {
             world.guards[i].respawn_timer += 1;
             if world.guards[i].respawn_timer >= world.speed.guard_respawn_ticks {
-                let rx = world.guards[i].spawn_x;
-                let ry = 1usize;
-                let occupied = world.guards.iter().enumerate().any(|(j, other)| {
-                    j != i && other.state != ActorState::Dead
-                    && other.x == rx && other.y == ry
-                });
+                let Some((rx, ry)) = respawn_cell(world, i) else { continue };
+                let occupied = world.occupancy.at(rx, ry).any(|(j, _)| j != i);
                 if !occupied {
                     world.guards[i].x = rx;
                     world.guards[i].y = ry;
                     world.guards[i].state = ActorState::OnGround;
+                    world.guard_moved(i);
                     world.guards[i].respawn_timer = 0;
-                    world.guards[i].carry_gold = false;
                     world.guards[i].carry_gold_timer = 0;
                     world.guards[i].separation_timer = 0;
+                    world.respawns += 1;
                     events.push(GameEvent::GuardRespawned { id: world.guards[i].id });
                 }
             }
@@ -661,20 +829,22 @@ fn resolve_timers(world: &mut WorldState, events: &mut Vec<GameEvent>) {
             if world.guards[i].x != hx || world.guards[i].y != hy { continue; }
             if world.guards[i].state == ActorState::InHole {
                 world.guards[i].state = ActorState::Dead;
+                world.guard_moved(i);
                 world.guards[i].respawn_timer = 0;
-                world.score += 50;
+                reward(world, Reward::GuardKilled);
                 events.push(GameEvent::GuardKilled { id: world.guards[i].id, x: hx, y: hy });
                 // Guard dies with gold → place above sealed brick
                 if world.guards[i].carry_gold {
                     world.guards[i].carry_gold = false;
                     world.guards[i].carry_gold_timer = 0;
                     if hy > 0 && can_drop_gold_at(world, hx, hy - 1) {
-                        world.set_tile(hx, hy - 1, Tile::Gold);
+                        world.set_tile(hx, hy - 1, Tile::Gold(world.guards[i].carry_tier));
                     }
                 }
             } else if world.guards[i].state != ActorState::Dead {
                 if hy > 0 && world.terrain_at(hx, hy - 1).is_passable() {
                     world.guards[i].y -= 1;
+                    world.guard_moved(i);
                 }
             }
         }
@@ -687,6 +857,21 @@ fn resolve_timers(world: &mut WorldState, events: &mut Vec<GameEvent>) {
     }
 }
 
+/// Spawners that are due send out a sentinel, once their cell is clear
+/// of sentinels and the player.
+fn resolve_spawners(world: &mut WorldState, events: &mut Vec<GameEvent>) {
+    for s in 0..world.spawners.len() {
+        if !world.spawners[s].tick() { continue; }
+        let (x, y) = (world.spawners[s].x, world.spawners[s].y);
+        let player_there = world.player.alive && (world.player.x, world.player.y) == (x, y);
+        if player_there || world.occupancy.at(x, y).next().is_some() { continue; }
+        let i = world.spawn_guard(x, y);
+        world.spawners[s].timer = 0;
+        world.spawners[s].spawned += 1;
+        events.push(GameEvent::GuardSpawned { id: world.guards[i].id, x, y });
+    }
+}
+
 /// Guard escapes hole: diagonal (x±1, y-1) toward player.
 fn try_escape(world: &mut WorldState, i: usize) {
     let gx = world.guards[i].x;
@@ -709,20 +894,18 @@ fn try_escape(world: &mut WorldState, i: usize) {
         // SUPPORT: must have support at destination
         let supported = physics::has_support(
             &world.tiles, world.width, world.height,
-            &world.hole_grid, &world.guards, ex, ey,
+            &world.hole_grid, &world.occupancy, ex, ey,
         );
         if !supported { continue; }
 
         // OCCUPANCY: no other guard at target
-        let blocked = world.guards.iter().enumerate().any(|(j, other)| {
-            j != i && other.state != ActorState::Dead
-            && other.x == ex && other.y == ey
-        });
+        let blocked = world.occupancy.at(ex, ey).any(|(j, _)| j != i);
         if blocked { continue; }
 
         world.guards[i].x = ex;
         world.guards[i].y = ey;
         world.guards[i].state = ActorState::OnGround;
+        world.guard_moved(i);
         if dx < 0 { world.guards[i].facing = Facing::Left; }
         if dx > 0 { world.guards[i].facing = Facing::Right; }
 
@@ -730,7 +913,7 @@ fn try_escape(world: &mut WorldState, i: usize) {
         // Guard escaped to (ex, ey) = (gx±1, gy-1), so it won't stand on the gold.
         if world.guards[i].carry_gold && gy > 0 {
             if can_drop_gold_at(world, gx, gy - 1) {
-                world.set_tile(gx, gy - 1, Tile::Gold);
+                world.set_tile(gx, gy - 1, Tile::Gold(world.guards[i].carry_tier));
                 world.guards[i].carry_gold = false;
                 world.guards[i].carry_gold_timer = 0;
             }
@@ -739,9 +922,10 @@ fn try_escape(world: &mut WorldState, i: usize) {
 
         world.guards[i].state = physics::resolve_state(
             &world.tiles, world.width, world.height,
-            &world.hole_grid, &world.guards,
+            &world.hole_grid, &world.occupancy,
             ex, ey, world.guards[i].state,
         );
+        world.guard_moved(i);
         return;
     }
 }
@@ -750,6 +934,113 @@ fn try_escape(world: &mut WorldState, i: usize) {
 // Win check
 // ══════════════════════════════════════════════════════════════
 
+/// Puzzle mode: out of digs with tokens left fails the attempt, once the
+/// last hole has refilled (a trapped guard may still drop what's needed).
+fn resolve_dig_budget(world: &mut WorldState, events: &mut Vec<GameEvent>) -> bool {
+    if world.digs_left != Some(0) || world.gold_remaining == 0 { return false; }
+    if !world.digs.is_empty() || !world.holes.is_empty() { return false; }
+    events.push(GameEvent::PlayerKilled);
+    world.push_message(Severity::Warning, t!("msg.out_of_hacks"), 60);
+    player_die(world);
+    true
+}
+
+/// Retire tag tips the player has acted on, then pick the tip to show.
+fn resolve_tips(world: &mut WorldState, movement: Option<MoveDir>, events: &[GameEvent]) {
+    if world.tips.is_empty() { return; }
+    let here = world.terrain_at(world.player.x, world.player.y);
+    let dug = events.iter().any(|e| matches!(e, GameEvent::HoleCreated { .. }));
+    let trapped = events.iter().any(|e| matches!(e, GameEvent::GuardTrapped { .. }));
+    let climbing = matches!(movement, Some(MoveDir::Up | MoveDir::Down));
+    for tip in &mut world.tips {
+        tip.done |= match tip.trigger {
+            TipTrigger::Start => movement.is_some(),
+            TipTrigger::Hack => dug,
+            TipTrigger::Ladder => here.is_climbable() && climbing,
+            TipTrigger::Rope => here.is_hangable() && matches!(movement, Some(MoveDir::Down)),
+            TipTrigger::Guard => trapped,
+            TipTrigger::Exit | TipTrigger::At { .. } => false,
+        };
+    }
+    refresh_tip(world);
+}
+
+/// Show the first live tip whose situation currently applies.
+pub fn refresh_tip(world: &mut WorldState) {
+    let (px, py) = (world.player.x, world.player.y);
+    let here = world.terrain_at(px, py);
+    let map = MapView { tiles: &world.tiles, width: world.width, height: world.height };
+    let p = &world.player;
+    let can_hack = world.digs_left != Some(0)
+        && [Facing::Left, Facing::Right].into_iter()
+            .any(|dir| rules::can_dig(&map, p.x, p.y, p.state, dir).is_some());
+    let near = |x: usize, y: usize, r: usize| px.abs_diff(x) <= r && py.abs_diff(y) <= r;
+    let guard_near = world.guards.iter()
+        .any(|g| g.state != ActorState::Dead && near(g.x, g.y, 6));
+
+    world.tip = world.tips.iter().position(|tip| !tip.done && match tip.trigger {
+        TipTrigger::Start => true,
+        TipTrigger::Hack => can_hack,
+        TipTrigger::Ladder => here.is_climbable(),
+        TipTrigger::Rope => here.is_hangable(),
+        TipTrigger::Guard => guard_near,
+        TipTrigger::Exit => world.exit_enabled,
+        TipTrigger::At { x, y } => near(x, y, 2),
+    });
+}
+
+/// Open the hub's exit once every door's level has been cleared.
+pub fn refresh_hub_exit(world: &mut WorldState) {
+    if world.in_hub() && !world.exit_enabled && world.hub_complete() {
+        enable_exit(world);
+    }
+}
+
+/// Standing on a door moves the player to the door with the same id in
+/// another room. The room left behind is parked as-is: its guards, holes
+/// and timers stay frozen until the player comes back.
+fn resolve_doors(world: &mut WorldState, events: &mut Vec<GameEvent>) {
+    if !world.player.alive || !(world.is_multi_room() || world.in_hub()) { return; }
+    let (px, py) = (world.player.x, world.player.y);
+    let Some(door) = world.doors().iter().find(|d| d.x == px && d.y == py).copied() else {
+        world.door_armed = true;
+        return;
+    };
+    if !world.door_armed || world.player.state == ActorState::Falling { return; }
+
+    // Hub doors launch the pack level with the door's number
+    if world.in_hub() {
+        let level = door.id as usize;
+        if level < world.total_levels {
+            world.hub.return_door = Some(door.id);
+            world.door_armed = false;
+            events.push(GameEvent::HubDoorEntered { level });
+        }
+        return;
+    }
+
+    let here = world.current_room;
+    let Some((room, to)) = world.rooms.iter().enumerate()
+        .filter(|&(i, _)| i != here)
+        .find_map(|(i, r)| r.doors.iter().find(|d| d.id == door.id).map(|d| (i, *d)))
+    else { return };
+
+    world.park_room();
+    world.enter_room(room);
+    world.player.x = to.x;
+    world.player.y = to.y;
+    world.player.state = world.resolve_actor_state(to.x, to.y, ActorState::OnGround);
+    world.player.move_cooldown = 0;
+    world.door_armed = false;
+    if world.exit_enabled {
+        enable_exit(world);
+    }
+    world.camera.center_on(to.x, to.y, world.width, world.height);
+    let name = world.room_name().to_string();
+    world.push_message(Severity::Info, &name, 40);
+    events.push(GameEvent::RoomEntered { room });
+}
+
 fn resolve_win(world: &mut WorldState, events: &mut Vec<GameEvent>) {
     if !world.player.alive { return; }
     if world.exit_enabled && world.player.y == 0 {
@@ -757,17 +1048,152 @@ fn resolve_win(world: &mut WorldState, events: &mut Vec<GameEvent>) {
         world.phase = Phase::LevelOutro;
         world.anim_tick = 0;
         world.anim_player_y = 0;  // start at row 0, will go negative
-        world.score += 500;
+        reward(world, Reward::NodeCleared);
         events.push(GameEvent::StageCleared);
-        world.set_message(&format!("Node {} Complete! +500", world.current_level + 1), 80);
+        let msg = match world.mode {
+            GameMode::Normal => t!("msg.node_complete_bonus", n = world.current_level + 1),
+            GameMode::TimeAttack => t!("msg.node_complete", n = world.current_level + 1),
+        };
+        world.push_message(Severity::Achievement, &msg, 80);
+    }
+}
+
+// ══════════════════════════════════════════════════════════════
+// Level scripts (`@on` lines, see `sim::script`)
+// ══════════════════════════════════════════════════════════════
+
+/// Run the scripts this tick triggers: one `gold_collected` per token the
+/// player mined, `enter` if the player moved onto a new cell, then `tick`.
+fn resolve_scripts(world: &mut WorldState, from: (usize, usize), events: &mut Vec<GameEvent>) {
+    if world.scripts.is_empty() { return; }
+
+    // Scripts keep the level's own columns: in mirror mode, cells are
+    // flipped on the way in and out
+    let (width, mirror) = (world.width, world.mirror);
+    let flip = |x: usize| if mirror { width.saturating_sub(1 + x) } else { x };
+    let player = (flip(world.player.x), world.player.y);
+    let mut fired: Vec<(ScriptTrigger, Option<(usize, usize)>)> = events.iter()
+        .filter_map(|e| match *e {
+            GameEvent::GoldPicked { x, y } => Some((ScriptTrigger::GoldCollected, Some((flip(x), y)))),
+            _ => None,
+        })
+        .collect();
+    if (world.player.x, world.player.y) != from {
+        fired.push((ScriptTrigger::Enter { x: player.0, y: player.1 }, Some(player)));
+    }
+    fired.push((ScriptTrigger::Tick, None));
+
+    for (trigger, at) in fired {
+        let vars = ScriptVars { tick: world.tick, player, remaining: world.gold_remaining, at };
+        let (actions, error) = world.scripts.run(trigger, &vars);
+        for action in actions {
+            let action = match action {
+                ScriptAction::SpawnGuard { x, y } => ScriptAction::SpawnGuard { x: flip(x), y },
+                ScriptAction::SetTile { x, y, tile } => ScriptAction::SetTile { x: flip(x), y, tile },
+                other => other,
+            };
+            apply_script_action(world, action, events);
+        }
+        if let Some(error) = error {
+            world.push_message(Severity::Warning, &error, 120);
+        }
+    }
+}
+
+fn apply_script_action(world: &mut WorldState, action: ScriptAction, events: &mut Vec<GameEvent>) {
+    match action {
+        ScriptAction::SpawnGuard { x, y } => {
+            // Up to the spawners' cap, and like them only into a free cell
+            if world.script_spawned >= world.script_spawn_cap { return; }
+            if x >= world.width || y >= world.height || !world.terrain_at(x, y).is_passable() { return; }
+            let player_there = world.player.alive && (world.player.x, world.player.y) == (x, y);
+            if player_there || world.occupancy.at(x, y).next().is_some() { return; }
+            let i = world.spawn_guard(x, y);
+            world.script_spawned += 1;
+            events.push(GameEvent::GuardSpawned { id: world.guards[i].id, x, y });
+        }
+        ScriptAction::SetTile { x, y, tile } => {
+            if x >= world.width || y >= world.height { return; }
+            // Doors, holes (open or being dug) and the player's own cell
+            // stay as they are, and a sentinel or spawner isn't walled in
+            let old = world.terrain_at(x, y);
+            if old == Tile::Door || (x, y) == (world.player.x, world.player.y) { return; }
+            if world.holes.iter().any(|h| (h.x, h.y) == (x, y)) || world.digs.iter().any(|d| (d.x, d.y) == (x, y)) {
+                return;
+            }
+            let taken = world.occupancy.at(x, y).next().is_some()
+                || world.spawners.iter().any(|s| (s.x, s.y) == (x, y));
+            if taken && !tile.is_passable() { return; }
+            world.set_tile(x, y, tile);
+            if old.is_gold() && !tile.is_gold() {
+                world.gold_remaining -= 1;
+                world.gold_total -= 1;
+            } else if tile.is_gold() && !old.is_gold() {
+                world.gold_remaining += 1;
+                world.gold_total += 1;
+            }
+            if old.is_gold() && world.gold_remaining == 0 && !world.exit_enabled && !world.in_hub() {
+                events.push(GameEvent::AllGoldCollected);
+                enable_exit(world);
+                world.push_message(Severity::Achievement, t!("msg.all_tokens"), 80);
+            }
+        }
+        ScriptAction::Message(text) => world.push_message(Severity::Info, &text, 80),
     }
 }
 
+// ══════════════════════════════════════════════════════════════
+// Scoring
+// ══════════════════════════════════════════════════════════════
+
+/// Something the player earned; what it's worth depends on the mode.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum Reward {
+    Token(u32),  // its points
+    GuardTrapped,
+    GuardKilled,
+    NodeCleared,
+}
+
+fn reward(world: &mut WorldState, r: Reward) {
+    match world.mode {
+        // Each New Game+ loop is worth one more of everything
+        GameMode::Normal => world.score += (1 + world.ng_loop) * match r {
+            Reward::Token(points) => points,
+            Reward::GuardTrapped => 0,
+            Reward::GuardKilled => 50,
+            Reward::NodeCleared => 500,
+        },
+        GameMode::TimeAttack => {
+            let ta = &mut world.time_attack;
+            ta.clock += match r {
+                Reward::Token(_) => ta.token_bonus,
+                Reward::GuardTrapped => ta.trap_bonus,
+                Reward::GuardKilled => 0,
+                Reward::NodeCleared => { ta.cleared += 1; 0 }
+            };
+        }
+    }
+}
+
+/// Run the time attack clock down; true once it has run out.
+fn tick_clock(world: &mut WorldState, events: &mut Vec<GameEvent>) -> bool {
+    if world.mode != GameMode::TimeAttack { return false; }
+    world.time_attack.clock = world.time_attack.clock.saturating_sub(1);
+    if world.time_attack.clock > 0 { return false; }
+    world.player.alive = false;
+    world.phase = Phase::GameOver;
+    world.push_message(Severity::Warning, t!("msg.time_up"), 120);
+    events.push(GameEvent::TimeUp);
+    true
+}
+
 // ══════════════════════════════════════════════════════════════
 // Helpers
 // ══════════════════════════════════════════════════════════════
 
-fn enable_exit(world: &mut WorldState) {
+/// Open the exit: reveal the hidden ladders.
+pub fn enable_exit(world: &mut WorldState) {
     world.exit_enabled = true;
 
     // Method 1: Exact hidden ladder positions (from binary level data / ~ markers)
@@ -830,6 +1256,39 @@ fn enable_exit(world: &mut WorldState) {
     }
 }
 
+/// Where dead guard `i` comes back: the room's `R` markers in turn if it
+/// has any, else by `world.guard_respawn`. `None` if it doesn't (yet).
+fn respawn_cell(world: &WorldState, i: usize) -> Option<(usize, usize)> {
+    let g = &world.guards[i];
+    let points = &world.respawn_points;
+    if !points.is_empty() && world.guard_respawn != GuardRespawn::Never {
+        return Some(points[world.respawns % points.len()]);
+    }
+    match world.guard_respawn {
+        GuardRespawn::Top => Some((g.spawn_x, 1)),
+        GuardRespawn::Spawn => Some((g.spawn_x, g.spawn_y)),
+        GuardRespawn::Never => None,
+        GuardRespawn::RandomTop => {
+            // No RNG in the sim: "random" is a hash of the tick and the
+            // guard, so a run plays out the same every time
+            let open: Vec<usize> = (0..world.width)
+                .filter(|&x| world.terrain_at(x, 1) == Tile::Empty)
+                .collect();
+            let h = (world.tick ^ (g.id as u64).wrapping_mul(0x9e37_79b9_7f4a_7c15)).wrapping_mul(0xff51_afd7_ed55_8ccd);
+            open.get((h >> 33) as usize % open.len().max(1)).map(|&x| (x, 1))
+        }
+    }
+}
+
+fn reset_guard(g: &mut Guard, move_rate: u32) {
+    g.x = g.spawn_x; g.y = g.spawn_y;
+    g.state = ActorState::OnGround;
+    g.carry_gold = false; g.carry_gold_timer = 0; g.stuck_timer = 0;
+    g.move_cooldown = move_rate;
+    g.respawn_timer = 0;
+    g.separation_timer = 0;
+}
+
 fn player_die(world: &mut WorldState) {
     world.player.alive = false;
     world.phase = Phase::Dying;
@@ -837,6 +1296,29 @@ fn player_die(world: &mut WorldState) {
 }
 
 pub fn restart_level(world: &mut WorldState) {
+    // Play from here on doesn't start from the level's start
+    world.recording = None;
+    world.restart_confirm = 0;
+    // Guards spawned by scripts go; the level's own are reset below
+    let level_guards = world.level_guards;
+    world.guards.retain(|g| g.id < level_guards);
+    for room in &mut world.rooms {
+        room.guards.retain(|g| g.id < level_guards);
+    }
+    if world.is_multi_room() {
+        // Every room goes back to its loaded state; the player starts in the first
+        world.park_room();
+        let rate = world.speed.guard_move_rate;
+        for room in &mut world.rooms {
+            room.tiles = room.base_tiles.clone();
+            room.holes.clear();
+            room.digs.clear();
+            for g in &mut room.guards { reset_guard(g, rate); }
+            for s in &mut room.spawners { s.reset(); }
+        }
+        world.enter_room(0);
+        world.door_armed = true;
+    }
     world.reset_tiles(); // restore tiles from base_tiles
     world.player.x = world.player_spawn.0;
     world.player.y = world.player_spawn.1;
@@ -847,18 +1329,25 @@ pub fn restart_level(world: &mut WorldState) {
     world.digs.clear();
     world.rebuild_hole_grid();
     world.exit_enabled = false;
-    world.gold_remaining = 0;
-    for row in &world.tiles {
-        for tile in row { if *tile == Tile::Gold { world.gold_remaining += 1; } }
-    }
+    world.digs_left = world.dig_limit;
+    world.light_radius = world.darkness;
+    world.respawns = 0;
+    let count = |tiles: &Grid<Tile>| tiles.iter().filter(|t| t.is_gold()).count();
+    world.gold_remaining = count(&world.tiles)
+        + world.rooms.iter().enumerate()
+            .filter(|&(i, _)| i != world.current_room)
+            .map(|(_, r)| count(&r.tiles))
+            .sum::<usize>();
     world.gold_total = world.gold_remaining;
-    for g in &mut world.guards {
-        g.x = g.spawn_x; g.y = g.spawn_y;
-        g.state = ActorState::OnGround;
-        g.carry_gold = false; g.carry_gold_timer = 0; g.stuck_timer = 0;
-        g.move_cooldown = world.speed.guard_move_rate;
-        g.respawn_timer = 0;
-        g.separation_timer = 0;
+    let rate = world.speed.guard_move_rate;
+    for g in &mut world.guards { reset_guard(g, rate); }
+    for s in &mut world.spawners { s.reset(); }
+    world.script_spawned = 0;
+    world.rebuild_occupancy();
+    refresh_hub_exit(world);
+    if let Some(r) = &mut world.randomizer {
+        r.attempt += 1;
+        randomize::deal(world);
     }
 
     // Re-center camera on player
@@ -867,3 +1356,338 @@ pub fn restart_level(world: &mut WorldState) {
         world.width, world.height,
     );
 }
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+    use crate::config::{AiConfig, GameConfig};
+    use crate::sim::level;
+    use crate::sim::randomize::Randomizer;
+    use crate::sim::solver;
+
+    /// `text` (a level file) loaded on the built-in settings, and those
+    /// settings. Never `GameConfig::load`: a config.toml lying about must
+    /// not change what a test sees.
+    pub(crate) fn world_from(text: &str) -> (WorldState, GameConfig) {
+        let config = GameConfig::builtin();
+        let def = level::parse_level_file(text).unwrap();
+        let mut world = WorldState::new();
+        level::load_level_def(&mut world, &def, &config);
+        (world, config)
+    }
+
+    #[test]
+    fn dead_guards_respawn_by_policy() {
+        let respawn = |policy, tick| {
+            let (mut world, _) = world_from("# Respawn\n       H\n       H\n P   E H\n########\n");
+            world.guard_respawn = policy;
+            world.tick = tick;
+            world.guards[0].state = ActorState::Dead;
+            world.guard_moved(0);
+            world.guards[0].respawn_timer = world.speed.guard_respawn_ticks;
+            resolve_timers(&mut world, &mut vec![]);
+            (world.guards[0].state, world.guards[0].x, world.guards[0].y)
+        };
+        assert_eq!(respawn(GuardRespawn::Top, 0), (ActorState::OnGround, 5, 1));
+        assert_eq!(respawn(GuardRespawn::Spawn, 0), (ActorState::OnGround, 5, 2));
+        assert_eq!(respawn(GuardRespawn::Never, 0).0, ActorState::Dead);
+        let columns: Vec<usize> = (0..40).map(|t| respawn(GuardRespawn::RandomTop, t).1).collect();
+        assert!(columns.iter().all(|&x| x < 7) && columns.iter().any(|&x| x != columns[0]));
+        assert_eq!(respawn(GuardRespawn::RandomTop, 7), respawn(GuardRespawn::RandomTop, 7));
+    }
+
+    #[test]
+    fn respawn_markers_take_turns() {
+        let (mut world, _) = world_from("# Markers\nR     R\n       \n P  E  \n#######\n");
+        world.guard_respawn = GuardRespawn::Spawn;
+        let mut cells = vec![];
+        for _ in 0..3 {
+            world.guards[0].state = ActorState::Dead;
+            world.guard_moved(0);
+            world.guards[0].respawn_timer = world.speed.guard_respawn_ticks;
+            resolve_timers(&mut world, &mut vec![]);
+            cells.push((world.guards[0].x, world.guards[0].y));
+        }
+        assert_eq!(cells, [(0, 0), (6, 0), (0, 0)]);
+        assert_eq!(world.terrain_at(0, 0), Tile::Empty);
+    }
+
+    #[test]
+    fn sealing_holes_push_guards_up_in_the_index_too() {
+        let (mut world, _) = world_from("# Seal\nP   E\n#####\n");
+        world.holes.push(Hole::new(4, 1, 0, 1));
+        world.rebuild_hole_grid();
+        world.guards[0].y = 1;
+        world.guard_moved(0);
+        resolve_timers(&mut world, &mut vec![]);
+        assert_eq!((world.guards[0].x, world.guards[0].y), (4, 0));
+        assert_eq!(world.occupancy.at(4, 0).map(|(i, _)| i).collect::<Vec<_>>(), [0]);
+        assert_eq!(world.occupancy.at(4, 1).count(), 0);
+    }
+
+    #[test]
+    fn script_spawns_are_capped_and_walls_spare_guards_and_holes() {
+        let (mut world, _) = world_from("# Script\n@spawn 100 2\nP G  E\n######\n");
+        let spawned = |w: &mut WorldState, x| {
+            let mut events = vec![];
+            apply_script_action(w, ScriptAction::SpawnGuard { x, y: 0 }, &mut events);
+            !events.is_empty()
+        };
+        // Not onto the player or a sentinel
+        assert!(!spawned(&mut world, 0) && !spawned(&mut world, 5));
+        assert!(spawned(&mut world, 3) && !spawned(&mut world, 3));
+        assert!(spawned(&mut world, 4) && !spawned(&mut world, 1), "past the cap");
+        restart_level(&mut world);
+        assert!(spawned(&mut world, 1));
+
+        let set = |w: &mut WorldState, x, y| {
+            apply_script_action(w, ScriptAction::SetTile { x, y, tile: Tile::Concrete }, &mut vec![]);
+            w.terrain_at(x, y) == Tile::Concrete
+        };
+        world.holes.push(Hole::new(4, 1, 10, 10));
+        world.rebuild_hole_grid();
+        assert!(!set(&mut world, 1, 0), "a sentinel's cell");
+        assert!(!set(&mut world, 2, 0), "a spawner's cell");
+        assert!(!set(&mut world, 4, 1), "an open hole");
+        assert!(set(&mut world, 3, 0));
+    }
+
+    #[test]
+    fn tokens_score_by_kind_and_guards_keep_theirs() {
+        let (mut world, _) = world_from("# Tokens
+P$%&   E
+########
+");
+        let mut scores = vec![];
+        for x in 1..4 {
+            world.player.x = x;
+            resolve_gold_pickup(&mut world, &mut vec![]);
+            scores.push(world.score);
+        }
+        assert_eq!(scores, [100, 350, 850]);
+
+        world.set_tile(7, 0, Tile::Gold(2));
+        world.gold_remaining += 1;
+        resolve_gold_pickup(&mut world, &mut vec![]);
+        assert_eq!((world.guards[0].carry_gold, world.terrain_at(7, 0)), (true, Tile::Empty));
+        world.guards[0].carry_gold_timer = world.speed.gold_carry_ticks;
+        resolve_guard_gold_drop(&mut world, &mut vec![]);
+        assert_eq!(world.terrain_at(7, 0), Tile::Gold(2));
+    }
+
+    #[test]
+    fn fake_bricks_give_way_to_the_player() {
+        let (mut world, _) = world_from("# Secret\nP SS$=\n######\n");
+        let secret = |w: &WorldState| (0..w.width).filter(|&x| w.secret_cells().get(x, 0)).collect::<Vec<_>>();
+        assert_eq!(secret(&world), [2, 3, 4]);
+
+        let mut events = vec![];
+        world.player.x = 2;
+        resolve_secrets(&mut world, &mut events);
+        assert!(matches!(events[..], [GameEvent::SecretFound { x: 2, y: 0 }]));
+        assert_eq!((world.terrain_at(3, 0), world.terrain_at(4, 0)), (Tile::Empty, Tile::Gold(0)));
+        assert!(secret(&world).is_empty());
+    }
+
+    #[test]
+    fn lasers_kill_the_player_and_guards_by_the_rules() {
+        let (mut world, _) = world_from("# Lasers\nP  E  \n#!!###\n");
+        world.guards[0].x = 2;
+        world.guards[0].carry_gold = true;
+        world.guard_moved(0);
+        let mut events = vec![];
+        assert!(!resolve_hazards(&mut world, &mut events));
+        assert!(events.is_empty());
+
+        world.hazards_kill_guards = true;
+        world.player.x = 1;
+        assert!(resolve_hazards(&mut world, &mut events));
+        assert!(matches!(events[..], [GameEvent::GuardKilled { id: 0, x: 2, y: 0 }, GameEvent::PlayerKilled]));
+        assert_eq!((world.guards[0].state, world.guards[0].carry_gold), (ActorState::Dead, true));
+    }
+
+    #[test]
+    fn custom_tiles_hold_up_and_kill_by_their_flags() {
+        let (mut world, _) = world_from("# Spikes\n## Tile: X glyph=▲ solid deadly\n  P $\nXX####\n");
+        world.phase = Phase::Playing;
+        let idle = FrameInput { movement: None, dig: None };
+        step(&mut world, idle);
+        assert_eq!((world.phase, world.player.alive), (Phase::Playing, true));
+
+        // Onto the spikes: held up by them, and killed
+        let events = step(&mut world, FrameInput { movement: Some(MoveDir::Left), dig: None });
+        assert_eq!((world.player.x, world.player.y), (1, 0));
+        assert!(world.has_support_for_player(1, 0));
+        assert!(events.iter().any(|e| matches!(e, GameEvent::PlayerKilled)));
+        assert_eq!((world.phase, world.player.alive), (Phase::Dying, false));
+    }
+
+    #[test]
+    fn spawners_send_out_sentinels_up_to_their_cap() {
+        let (mut world, _) = world_from("# Horde\n@spawn 2 2\nP  E G \n#######\n");
+        let mut spawned = vec![];
+        for _ in 0..8 {
+            let mut events = vec![];
+            resolve_spawners(&mut world, &mut events);
+            for e in events {
+                let GameEvent::GuardSpawned { id, .. } = e else { continue };
+                spawned.push((world.tick, id));
+                // Off the spawner, so it isn't blocked for the next one
+                let i = world.guards.len() - 1;
+                world.guards[i].x = 6;
+                world.guard_moved(i);
+            }
+            world.tick += 1;
+        }
+        assert_eq!(spawned, [(1, 1), (3, 2)]);
+        assert_eq!(world.guards.len(), 3);
+
+        restart_level(&mut world);
+        assert_eq!((world.guards.len(), world.spawners[0].spawned), (1, 0));
+    }
+
+    #[test]
+    fn nightmare_hides_sentinels_between_steps() {
+        let (mut world, _) = world_from("# Dread\nP         E\n###########\n");
+        world.phase = Phase::Playing;
+        world.invisible_guards = true;
+        let idle = FrameInput { movement: None, dig: None };
+
+        let stepped = |events: &[GameEvent]| events.iter().any(|e| matches!(e, GameEvent::GuardStepped { id: 0, .. }));
+        while !stepped(&step(&mut world, idle)) {}
+        assert!(world.guard_seen(&world.guards[0]));
+        step(&mut world, idle);
+        step(&mut world, idle);
+        assert!(!world.guard_seen(&world.guards[0]));
+
+        // Close by, or with the modifier off, it's always in view
+        world.guards[0].x = 3;
+        assert!(world.guard_seen(&world.guards[0]));
+        world.guards[0].x = 8;
+        world.invisible_guards = false;
+        assert!(world.guard_seen(&world.guards[0]));
+    }
+
+    #[test]
+    fn mirror_mode_flips_levels_as_they_load() {
+        let config = GameConfig::builtin();
+        let def = level::parse_level_file("# Glass\n@ 1,0\nP $  E\n######\n=\n").unwrap();
+        let mut world = WorldState::new();
+        world.mirror = true;
+        level::load_level_def(&mut world, &def, &config);
+        assert_eq!((world.player.x, world.guards[0].x), (5, 0));
+        assert_eq!(world.terrain_at(3, 0), Tile::Gold(0));
+        // Short rows are padded before the flip
+        assert_eq!((world.terrain_at(0, 2), world.terrain_at(5, 2)), (Tile::Empty, Tile::Concrete));
+        assert_eq!(world.hidden_ladder_positions, [(4, 0)]);
+    }
+
+    #[test]
+    fn randomizer_deals_each_attempt_from_its_seed() {
+        let config = GameConfig::builtin();
+        let def = level::parse_level_file("# Deck\nP $      $    E\n###H#########H\n   H    $    H\n##############\n").unwrap();
+        let layout = |w: &WorldState| {
+            let tokens: Vec<(usize, usize)> = (0..w.height)
+                .flat_map(|y| (0..w.width).map(move |x| (x, y)))
+                .filter(|&(x, y)| w.tiles[(x, y)].is_gold())
+                .collect();
+            (tokens, (w.guards[0].x, w.guards[0].y))
+        };
+        let load = |seed| {
+            let mut world = WorldState::new();
+            world.randomizer = Some(Randomizer { seed, attempt: 0 });
+            level::load_level_def(&mut world, &def, &config);
+            world
+        };
+
+        let mut world = load(7);
+        let first = layout(&world);
+        assert_eq!(first, layout(&load(7)));
+        assert_eq!((first.0.len(), world.gold_remaining), (3, 3));
+        assert!(first.1.0 + first.1.1 >= 6);
+        assert!(solver::reachable(&world));
+
+        let dealt: Vec<_> = (0..4).map(|_| { restart_level(&mut world); layout(&world) }).collect();
+        assert_eq!(world.randomizer.unwrap().attempt, 4);
+        assert!(dealt.iter().any(|l| *l != first));
+    }
+
+    #[test]
+    fn new_game_plus_loops_speed_sentinels_and_multiply_scoring() {
+        let config = GameConfig::builtin();
+        let (base, sped) = (&config.speed, config.speed.for_loop(2));
+        assert!(sped.guard_move_rate < base.guard_move_rate && sped.guard_move_rate > base.player_move_rate);
+        assert!(sped.trap_escape_ticks < base.trap_escape_ticks);
+        assert!(sped.guard_respawn_ticks < base.guard_respawn_ticks);
+        assert_eq!(sped.hole_open_ticks, base.hole_open_ticks);
+        assert_eq!(base.for_loop(40).trap_escape_ticks, 20);
+        assert_eq!(base.for_loop(0).guard_respawn_ticks, base.guard_respawn_ticks);
+
+        let mut world = WorldState::new();
+        world.ng_loop = 2;
+        reward(&mut world, Reward::NodeCleared);
+        assert_eq!(world.score, 1500);
+    }
+
+    #[test]
+    fn lanterns_light_the_dark_further() {
+        let (mut world, _) = world_from("# Cellar\n@dark 2\nPL  $    E\n##########\n");
+        assert_eq!(world.light_radius, Some(2));
+        assert_eq!([world.light_at(1, 0), world.light_at(2, 0), world.light_at(3, 0)], [1.0, 0.5, 0.0]);
+
+        world.player.x = 1;
+        let mut events = vec![];
+        resolve_lanterns(&mut world, &mut events);
+        assert!(matches!(events[..], [GameEvent::LanternPicked { x: 1, y: 0 }]));
+        assert_eq!((world.light_radius, world.terrain_at(1, 0)), (Some(4), Tile::Empty));
+        assert_eq!(world.light_at(4, 0), 1.0);
+
+        restart_level(&mut world);
+        assert_eq!((world.light_radius, world.terrain_at(1, 0)), (Some(2), Tile::Lantern));
+    }
+
+    #[test]
+    fn walled_off_sentinels_patrol_their_platform() {
+        let (mut world, _) = world_from("# Pens\n           \nP$ =  E   =\n===========\n");
+        world.phase = Phase::Playing;
+        let idle = FrameInput { movement: None, dig: None };
+
+        let mut seen = vec![];
+        for _ in 0..400 {
+            step(&mut world, idle);
+            seen.push(world.guards[0].x);
+        }
+        assert!(seen.iter().all(|x| (4..=9).contains(x)));
+        assert!(seen.contains(&4) && seen.contains(&9));
+        assert_eq!(world.guards[0].y, 1);
+    }
+
+    #[test]
+    fn ai_config_limits_the_search_and_how_often_it_runs() {
+        let (mut world, _) = world_from("# Hall\nP         E\n###########\n");
+        world.ai = AiConfig { max_depth: 3, repath_ticks: 10 };
+        refresh_flow(&mut world);
+        assert_eq!((world.flow.dist(3, 0), world.flow.dist(4, 0)), (Some(3), None));
+
+        // The player moves on, the sentinels keep the old search a while
+        world.player.x = 1;
+        world.tick = 9;
+        refresh_flow(&mut world);
+        assert_eq!(world.flow.target, (0, 0));
+        world.tick = 10;
+        refresh_flow(&mut world);
+        assert_eq!(world.flow.target, (1, 0));
+    }
+
+    #[test]
+    fn node_totals_count_every_attempt_and_par_is_read() {
+        let (mut world, _) = world_from("# Sprint\n@par 45\nP $  E\n######\n");
+        assert_eq!(world.par_secs, Some(45));
+
+        world.count_run(&[GameEvent::HoleCreated { x: 1, y: 1 }, GameEvent::PlayerKilled]);
+        restart_level(&mut world);
+        world.count_run(&[GameEvent::HoleCreated { x: 2, y: 1 }]);
+        assert_eq!((world.node_run.hacks, world.node_run.deaths, world.node_run.play_ticks), (2, 1, 2));
+        assert_eq!(world.run, world.node_run);
+    }
+}
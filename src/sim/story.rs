@@ -0,0 +1,117 @@
+//! Story pages: text screens a pack shows between its levels.
+//!
+//! A pack section whose first line is `@story [Title]` is a page, not a
+//! level. It's shown before the next level (or, after the last level,
+//! before the completion screen), typed out a few characters per tick.
+//!   `@delay N` — move on by itself N seconds after the text is typed
+//!                (without it, the page waits for ENTER)
+//! Every other line is page text, kept as written so ASCII art lines up.
+
+use crate::sim::world::{Phase, WorldState};
+
+/// Characters typed per tick. Spaces are free, so art doesn't crawl.
+const CHARS_PER_TICK: usize = 3;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StoryPage {
+    pub title: String,
+    pub lines: Vec<String>,
+    pub delay_secs: u32,  // 0 = wait for a key
+}
+
+impl StoryPage {
+    /// Ticks until the whole page is typed.
+    pub fn typing_ticks(&self) -> u32 {
+        let chars: usize = self.lines.iter()
+            .map(|l| l.chars().filter(|c| !c.is_whitespace()).count())
+            .sum();
+        chars.div_ceil(CHARS_PER_TICK) as u32
+    }
+
+    /// How many chars of each line are shown `ticks` into the page.
+    pub fn typed(&self, ticks: u32) -> Vec<usize> {
+        let mut budget = ticks as usize * CHARS_PER_TICK;
+        self.lines.iter().map(|line| {
+            let mut shown = 0;
+            for c in line.chars() {
+                if !c.is_whitespace() {
+                    if budget == 0 { break; }
+                    budget -= 1;
+                }
+                shown += 1;
+            }
+            shown
+        }).collect()
+    }
+}
+
+/// Parse a pack section, if it's a story page.
+pub fn parse_page(section: &str) -> Option<StoryPage> {
+    let mut lines = section.lines();
+    let first = lines.next()?.trim();
+    let title = first.strip_prefix("@story")?;
+    if !title.is_empty() && !title.starts_with(' ') { return None; }
+
+    let mut page = StoryPage { title: title.trim().to_string(), lines: vec![], delay_secs: 0 };
+    for line in lines {
+        if let Some(n) = line.strip_prefix("@delay ") {
+            page.delay_secs = n.trim().parse().unwrap_or(0);
+        } else {
+            page.lines.push(line.trim_end().to_string());
+        }
+    }
+    while page.lines.last().is_some_and(|l| l.is_empty()) {
+        page.lines.pop();
+    }
+    Some(page)
+}
+
+/// Show `pages`, then go on to `next`.
+pub fn start(world: &mut WorldState, pages: &[StoryPage], next: Phase) {
+    if pages.is_empty() {
+        world.phase = next;
+        return;
+    }
+    world.story = pages.to_vec();
+    world.story_next = next;
+    world.phase = Phase::Cutscene;
+    world.anim_tick = 0;
+}
+
+/// A cutscene tick: type, and turn the page once its delay has passed.
+pub fn tick(world: &mut WorldState) {
+    world.anim_tick += 1;
+    let Some(page) = world.story.first() else { return };
+    if page.delay_secs > 0
+        && world.anim_tick >= page.typing_ticks() + world.secs_to_ticks(page.delay_secs)
+    {
+        next_page(world);
+    }
+}
+
+/// ENTER: finish typing the page, or go to the next one.
+pub fn confirm(world: &mut WorldState) {
+    let Some(page) = world.story.first() else { return };
+    let typed = page.typing_ticks();
+    if world.anim_tick < typed {
+        world.anim_tick = typed;
+    } else {
+        next_page(world);
+    }
+}
+
+/// ESC: skip the rest of the story.
+pub fn skip(world: &mut WorldState) {
+    world.story.clear();
+    next_page(world);
+}
+
+fn next_page(world: &mut WorldState) {
+    if !world.story.is_empty() {
+        world.story.remove(0);
+    }
+    world.anim_tick = 0;
+    if world.story.is_empty() {
+        world.phase = world.story_next;
+    }
+}
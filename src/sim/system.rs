@@ -0,0 +1,137 @@
+//! The tick as a pipeline of systems.
+//!
+//! `step` runs `WorldState::pipeline` in order: each `System` updates the
+//! world and adds to the tick's events, and any of them can end the tick
+//! early (time up, caught, out of hacks). The built-in mechanics are the
+//! systems of `step::pipeline()`; a new one is another `System` put in
+//! its place with `insert_before` / `insert_after`.
+//!
+//! Every system is timed under its name for the perf overlay.
+
+use crate::domain::entity::FrameInput;
+
+use super::event::GameEvent;
+use super::timing;
+use super::world::WorldState;
+
+/// What the systems of one tick share.
+pub struct Tick {
+    pub input: FrameInput,
+    /// The player's cell when the tick began.
+    pub from: (usize, usize),
+    /// Events so far this tick, in the order they happened.
+    pub events: Vec<GameEvent>,
+}
+
+/// Whether the tick goes on after a system.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Flow {
+    Continue,
+    /// The tick ended the run; later systems don't run.
+    Stop,
+}
+
+pub trait System {
+    /// Unique within a pipeline; also the perf overlay's label.
+    fn name(&self) -> &'static str;
+    fn run(&mut self, world: &mut WorldState, tick: &mut Tick) -> Flow;
+}
+
+/// A system that's just a function.
+pub struct FnSystem(pub &'static str, pub fn(&mut WorldState, &mut Tick) -> Flow);
+
+impl System for FnSystem {
+    fn name(&self) -> &'static str {
+        self.0
+    }
+
+    fn run(&mut self, world: &mut WorldState, tick: &mut Tick) -> Flow {
+        (self.1)(world, tick)
+    }
+}
+
+#[derive(Default)]
+pub struct Pipeline {
+    systems: Vec<Box<dyn System>>,
+}
+
+impl Pipeline {
+    pub fn new(systems: Vec<Box<dyn System>>) -> Self {
+        Pipeline { systems }
+    }
+
+    /// The systems' names, in running order.
+    pub fn names(&self) -> Vec<&'static str> {
+        self.systems.iter().map(|s| s.name()).collect()
+    }
+
+    pub fn push(&mut self, system: Box<dyn System>) {
+        self.systems.push(system);
+    }
+
+    /// Put `system` just before the one called `name`; false (and not
+    /// added) if there's none.
+    pub fn insert_before(&mut self, name: &str, system: Box<dyn System>) -> bool {
+        let Some(i) = self.position(name) else { return false };
+        self.systems.insert(i, system);
+        true
+    }
+
+    /// Put `system` just after the one called `name`; false (and not
+    /// added) if there's none.
+    pub fn insert_after(&mut self, name: &str, system: Box<dyn System>) -> bool {
+        let Some(i) = self.position(name) else { return false };
+        self.systems.insert(i + 1, system);
+        true
+    }
+
+    pub fn remove(&mut self, name: &str) -> Option<Box<dyn System>> {
+        self.position(name).map(|i| self.systems.remove(i))
+    }
+
+    fn position(&self, name: &str) -> Option<usize> {
+        self.systems.iter().position(|s| s.name() == name)
+    }
+
+    /// Run one tick through every system, until one stops it.
+    pub fn run(&mut self, world: &mut WorldState, input: FrameInput) -> Vec<GameEvent> {
+        let mut tick = Tick { input, from: (world.player.x, world.player.y), events: vec![] };
+        for system in &mut self.systems {
+            let flow = timing::time(system.name(), || system.run(world, &mut tick));
+            if flow == Flow::Stop { break; }
+        }
+        tick.events
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sim::step;
+    use crate::sim::world::Phase;
+
+    fn idle() -> FrameInput {
+        FrameInput { movement: None, dig: None }
+    }
+
+    #[test]
+    fn added_systems_run_in_place_and_can_stop_the_tick() {
+        let mut pipeline = step::pipeline();
+        assert!(pipeline.insert_after("clock", Box::new(FnSystem("halt", |_, tick| {
+            tick.events.push(GameEvent::TimeUp);
+            Flow::Stop
+        }))));
+        assert!(!pipeline.insert_before("no such system", Box::new(FnSystem("x", |_, _| Flow::Continue))));
+        let names = pipeline.names();
+        assert_eq!(&names[..2], ["clock", "halt"]);
+
+        let mut world = WorldState::new();
+        world.phase = Phase::Playing;
+        let events = pipeline.run(&mut world, idle());
+        assert_eq!(world.tick, 1);
+        assert!(matches!(events[..], [GameEvent::TimeUp]));
+
+        assert!(pipeline.remove("halt").is_some());
+        assert_eq!(pipeline.names(), step::pipeline().names());
+    }
+}
@@ -0,0 +1,67 @@
+//! Where a tick's time goes: wall time per named section of the step
+//! pipeline and the renderer, summed until the `perf_overlay` reads it.
+//!
+//! Off unless `enable`d, when `time` is just the call it wraps. Totals
+//! are per thread, which is the game loop's.
+
+use std::cell::{Cell, RefCell};
+use std::time::{Duration, Instant};
+
+thread_local! {
+    static ENABLED: Cell<bool> = const { Cell::new(false) };
+    static TOTALS: RefCell<Vec<Section>> = const { RefCell::new(Vec::new()) };
+}
+
+/// A section's time since the last `take`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Section {
+    pub name: &'static str,
+    pub total: Duration,
+    pub calls: u32,
+}
+
+impl Section {
+    pub fn avg(&self) -> Duration {
+        self.total / self.calls.max(1)
+    }
+}
+
+pub fn enable(on: bool) {
+    ENABLED.with(|e| e.set(on));
+}
+
+pub fn enabled() -> bool {
+    ENABLED.with(|e| e.get())
+}
+
+/// Run `f`, adding its wall time to `name`.
+pub fn time<R>(name: &'static str, f: impl FnOnce() -> R) -> R {
+    if !enabled() { return f(); }
+    let started = Instant::now();
+    let out = f();
+    record(name, started.elapsed());
+    out
+}
+
+/// Add `took` to `name`.
+pub fn record(name: &'static str, took: Duration) {
+    if !enabled() { return; }
+    TOTALS.with(|totals| {
+        let mut totals = totals.borrow_mut();
+        match totals.iter_mut().find(|s| s.name == name) {
+            Some(s) => {
+                s.total += took;
+                s.calls += 1;
+            }
+            None => totals.push(Section { name, total: took, calls: 1 }),
+        }
+    });
+}
+
+/// Every section's totals since the last call, slowest first, and start
+/// over.
+pub fn take() -> Vec<Section> {
+    let mut sections = TOTALS.with(|totals| std::mem::take(&mut *totals.borrow_mut()));
+    sections.sort_by_key(|s| std::cmp::Reverse(s.avg()));
+    sections
+}
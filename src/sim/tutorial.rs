@@ -0,0 +1,113 @@
+//! Built-in tutorial pack and the tutorial tip triggers.
+//!
+//! Tips come from two level-file lines, usable in any pack:
+//!   `@tag <name> [text]` — a tip for a game situation (`start`, `hack`,
+//!   `ladder`, `rope`, `guard`, `exit`), with built-in text unless given.
+//!   `@tip X,Y <text>`    — shown while the player is near cell (X, Y).
+//! `step` decides which tip is on screen; tag tips retire once the player
+//! has done what they ask.
+
+use crate::i18n::t;
+use crate::sim::world::{Tip, TipTrigger};
+
+pub const PACK_NAME: &str = "Tutorial";
+
+/// The tutorial, in `.nlp` pack format.
+pub const PACK: &str = "\
+## Tutorial
+## Author: NodeRunner
+## Description: Learn to run, climb, hack and dodge in four short nodes
+---
+# Tutorial 1 - First Steps
+@tag start
+@tag ladder
+@tip 20,6 That $ is a token. Mine every token on the node to open the exit.
+@tag exit
+          ^
+
+          H
+    $     H          $
+##########H############
+          H
+  P       H         $
+############################
+---
+# Tutorial 2 - Hacking
+@tag hack
+@tip 3,4 Hacked holes fill back in after a while. Don't be inside when they do!
+@tag exit
+                        ^
+                        H
+  P                     H
+##########==============H===
+                        H
+       $         $      H
+============================
+---
+# Tutorial 3 - Data Lines
+@tag start
+@tag rope
+@tip 12,4 Dropped into the pit? Ladders lead back up.
+@tag exit
+                        ^
+                        H
+ P  ----------------  $ H
+###H                ########
+   H        $
+   H     #######
+============================
+---
+# Tutorial 4 - Sentinels
+@tag hack Hack a hole in the sentinel's path with Z or X to trap it.
+@tag guard
+@tip 13,2 A trapped sentinel can be walked over. It climbs out after a few seconds.
+@tag exit
+  ^
+  H
+  H  P       $        E
+##########################H#
+                          H
+============================
+";
+
+/// Parse the rest of an `@tag` line: `hack` or `hack Custom text`.
+pub fn parse_tag(rest: &str) -> Option<Tip> {
+    let rest = rest.trim();
+    let (name, text) = rest.split_once(' ').unwrap_or((rest, ""));
+    let trigger = match name.to_ascii_lowercase().as_str() {
+        "start" => TipTrigger::Start,
+        "hack" => TipTrigger::Hack,
+        "ladder" => TipTrigger::Ladder,
+        "rope" => TipTrigger::Rope,
+        "guard" => TipTrigger::Guard,
+        "exit" => TipTrigger::Exit,
+        _ => return None,
+    };
+    let text = match text.trim() {
+        "" => default_text(trigger).to_string(),
+        t => t.to_string(),
+    };
+    Some(Tip { trigger, text, done: false })
+}
+
+/// Parse the rest of an `@tip` line: `X,Y text`.
+pub fn parse_tip(rest: &str) -> Option<Tip> {
+    let (pos, text) = rest.trim().split_once(' ')?;
+    let (x, y) = pos.split_once(',')?;
+    let (x, y) = (x.parse().ok()?, y.parse().ok()?);
+    let text = text.trim();
+    if text.is_empty() { return None; }
+    Some(Tip { trigger: TipTrigger::At { x, y }, text: text.to_string(), done: false })
+}
+
+fn default_text(trigger: TipTrigger) -> &'static str {
+    match trigger {
+        TipTrigger::Start => t!("tip.start"),
+        TipTrigger::Hack => t!("tip.hack"),
+        TipTrigger::Ladder => t!("tip.ladder"),
+        TipTrigger::Rope => t!("tip.rope"),
+        TipTrigger::Guard => t!("tip.guard"),
+        TipTrigger::Exit => t!("tip.exit"),
+        TipTrigger::At { .. } => "",
+    }
+}
@@ -0,0 +1,160 @@
+//! Invariant checker for the step pipeline (`verify` feature).
+//!
+//! `step` calls `verify` after every tick. It checks that:
+//!   - the player, every sentinel and every hole are inside the map
+//!   - `hole_grid` is the map's size and marks exactly the open holes
+//!   - no two trapped sentinels share a cell
+//!   - `occupancy` lists every live sentinel where it is, as it is
+//!   - the tokens on the maps and in sentinels' hands are no more than
+//!     `gold_remaining` (fewer is allowed: a sentinel that dies holding a
+//!     token where it can't be dropped takes it with it)
+//!
+//! A broken invariant panics, or with `NODERUNNER_VERIFY=log` is written
+//! to the in-game log and play goes on. Nothing goes to stderr, which the
+//! terminal in raw mode would scribble over the screen with.
+
+use std::sync::OnceLock;
+
+use crate::domain::entity::{ActorState, Guard};
+use crate::domain::grid::Grid;
+use crate::domain::occupancy::Occupancy;
+use crate::domain::tile::Tile;
+use crate::sim::world::{Severity, WorldState};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VerifyMode {
+    Panic,
+    Log,
+}
+
+/// The mode, from `NODERUNNER_VERIFY` (`log`, else panic).
+pub fn mode() -> VerifyMode {
+    static MODE: OnceLock<VerifyMode> = OnceLock::new();
+    *MODE.get_or_init(|| match std::env::var("NODERUNNER_VERIFY").as_deref() {
+        Ok("log") => VerifyMode::Log,
+        _ => VerifyMode::Panic,
+    })
+}
+
+/// Check the world after a tick, and report what's broken.
+pub fn verify(world: &mut WorldState) {
+    let broken = violations(world);
+    if broken.is_empty() { return; }
+    match mode() {
+        VerifyMode::Panic => panic!("tick {}: {}", world.tick, broken.join("; ")),
+        VerifyMode::Log => {
+            for text in broken {
+                world.log(Severity::Warning, &format!("verify: {text}"));
+            }
+        }
+    }
+}
+
+/// Every invariant `world` breaks, described.
+pub fn violations(world: &WorldState) -> Vec<String> {
+    let mut broken = vec![];
+    let (w, h) = (world.width, world.height);
+    let inside = |x: usize, y: usize| x < w && y < h;
+
+    let p = &world.player;
+    if !inside(p.x, p.y) {
+        broken.push(format!("player at ({}, {}) is off the {w}x{h} map", p.x, p.y));
+    }
+    for g in &world.guards {
+        if !inside(g.x, g.y) {
+            broken.push(format!("sentinel {} at ({}, {}) is off the map", g.id, g.x, g.y));
+        }
+    }
+    for hole in &world.holes {
+        if !inside(hole.x, hole.y) {
+            broken.push(format!("hole at ({}, {}) is off the map", hole.x, hole.y));
+        }
+    }
+
+    let grid = &world.hole_grid;
+    if grid.width() != w || grid.height() != h {
+        broken.push(format!("hole_grid isn't {w}x{h}"));
+    } else {
+        for y in 0..h {
+            for x in 0..w {
+                let marked = grid.get(x, y);
+                let open = world.holes.iter().any(|hole| (hole.x, hole.y) == (x, y));
+                if marked != open {
+                    broken.push(format!("hole_grid at ({x}, {y}) says {marked}, holes say {open}"));
+                }
+            }
+        }
+    }
+
+    let trapped: Vec<_> = world.guards.iter().filter(|g| g.state == ActorState::InHole).collect();
+    for (i, a) in trapped.iter().enumerate() {
+        if let Some(b) = trapped[i + 1..].iter().find(|b| (b.x, b.y) == (a.x, a.y)) {
+            broken.push(format!("sentinels {} and {} are both trapped at ({}, {})", a.id, b.id, a.x, a.y));
+        }
+    }
+
+    let fresh = Occupancy::new(&world.guards, w, h);
+    'cells: for y in 0..h {
+        for x in 0..w {
+            let mut kept: Vec<_> = world.occupancy.at(x, y).collect();
+            let mut want: Vec<_> = fresh.at(x, y).collect();
+            kept.sort_by_key(|&(i, _)| i);
+            want.sort_by_key(|&(i, _)| i);
+            if kept != want {
+                broken.push(format!("occupancy at ({x}, {y}) lists {kept:?}, sentinels say {want:?}"));
+                break 'cells;
+            }
+        }
+    }
+
+    let on_map = |tiles: &Grid<Tile>| tiles.iter().filter(|t| t.is_gold()).count();
+    let carried = |guards: &[Guard]| guards.iter().filter(|g| g.carry_gold).count();
+    let parked = world.rooms.iter().enumerate().filter(|&(i, _)| i != world.current_room);
+    let tokens = on_map(&world.tiles) + carried(&world.guards)
+        + parked.map(|(_, r)| on_map(&r.tiles) + carried(&r.guards)).sum::<usize>();
+    if tokens > world.gold_remaining {
+        broken.push(format!("{tokens} tokens in play but gold_remaining is {}", world.gold_remaining));
+    }
+    broken
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::domain::entity::Hole;
+    use crate::domain::grid::BitGrid;
+    use crate::sim::step::tests::world_from;
+
+    type Break = fn(&mut WorldState);
+
+    #[test]
+    fn each_broken_invariant_is_reported() {
+        let broken = |breaks: Break| {
+            let (mut world, _) = world_from("# Verify\n P E E $\n########\n");
+            breaks(&mut world);
+            violations(&world)
+        };
+        assert_eq!(broken(|_| {}), Vec::<String>::new());
+
+        let cases: [(Break, &str); 8] = [
+            (|w| w.player.x = w.width, "player at"),
+            (|w| { w.guards[0].y = w.height; }, "sentinel 0 at"),
+            (|w| w.holes.push(Hole::new(w.width, 0, 10, 10)), "hole at"),
+            (|w| w.hole_grid = BitGrid::new(1, 1), "hole_grid isn't"),
+            (|w| w.holes.push(Hole::new(3, 1, 10, 10)), "hole_grid at (3, 1)"),
+            (|w| {
+                for i in 0..2 {
+                    w.guards[i].state = ActorState::InHole;
+                    w.guards[i].x = 3;
+                    w.guard_moved(i);
+                }
+            }, "both trapped at (3, 0)"),
+            (|w| w.guards[1].x = 6, "occupancy at"),
+            (|w| w.gold_remaining = 0, "1 tokens in play"),
+        ];
+        for (breaks, says) in cases {
+            let found = broken(breaks);
+            assert!(found.iter().any(|text| text.contains(says)), "{says:?} not in {found:?}");
+        }
+    }
+}
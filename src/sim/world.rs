@@ -10,6 +10,14 @@
 /// `terrain_at()` reads from the effective `tiles`.
 /// `restart_level` resets `tiles = base_tiles.clone()`.
 ///
+/// ## Rooms
+///
+/// A multi-room level keeps every room in `rooms`. The room the player is
+/// in is swapped into the top-level fields (`tiles`, `guards`, `holes`, …)
+/// so the sim and renderers only ever see one map; `enter_room` parks the
+/// current room and swaps another in. Score, lives and `gold_remaining`
+/// are level-wide and never swapped.
+///
 /// ## Camera / Viewport
 ///
 /// World coordinates and screen coordinates are separate:
@@ -18,10 +26,23 @@
 ///   - Camera follows the player with a dead-zone approach
 ///   - Maps smaller than the viewport are centered
 
-use crate::config::SpeedConfig;
-use crate::domain::entity::{DigInProgress, Guard, Hole, Player};
+use std::collections::VecDeque;
+
+use crate::config::{AiConfig, GuardRespawn, SpeedConfig, MAX_LIGHT};
+use crate::domain::ai::FlowField;
+use crate::domain::entity::{DigInProgress, Guard, Hole, Player, Spawner};
+use crate::domain::grid::{BitGrid, Grid};
+use crate::domain::occupancy::Occupancy;
 use crate::domain::physics::{self, TerrainCell};
 use crate::domain::tile::Tile;
+use crate::i18n::t;
+use crate::sim::event::GameEvent;
+use crate::sim::randomize::Randomizer;
+use crate::sim::run_code::{Playback, Recording};
+use crate::sim::script::Scripts;
+use crate::sim::step;
+use crate::sim::story::StoryPage;
+use crate::sim::system::Pipeline;
 
 /// Info about a level pack, displayed in the pack selector.
 #[derive(Clone, Debug)]
@@ -31,6 +52,181 @@ pub struct PackInfo {
     pub description: String,
     pub level_count: usize,
     pub path: String,        // filesystem path, or "__levels__" / "__embedded__"
+    pub credits: Vec<String>,  // `## Credit:` lines, for the ending credits
+}
+
+/// A pack selector dialog about the highlighted pack.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PackDialog {
+    Delete,                  // "delete this pack?"
+    Rename { name: String }, // editing the new file name (without `.nlp`)
+    Path { path: String },   // showing where the pack file is (absolute)
+}
+
+/// A level code dialog, over level select or the pack builder.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ShareDialog {
+    Export { name: String, code: String },  // the highlighted level's code
+    Import { text: String },                // pasting a code
+}
+
+/// An entry on the pause menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseItem {
+    Resume,
+    Restart,
+    Settings,
+    Save,
+    Load,
+    Quit,
+}
+
+/// An entry on the pause menu's settings page: the view toggles, then
+/// back to the menu.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PauseSetting {
+    Minimap,
+    EdgeMarkers,
+    GuardPaths,
+    CompactView,
+    Back,
+}
+
+/// The game a save slot holds, as the pause menu shows it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SlotInfo {
+    pub name: String,  // "" if it wasn't given one
+    pub level: usize,
+    pub score: u32,
+}
+
+/// A save waiting on "overwrite it?": the slot, the game it holds, and
+/// the name the new save goes under.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct OverwriteSlot {
+    pub slot: u8,
+    pub held: SlotInfo,
+    pub name: String,
+}
+
+/// The pause menu: the highlighted entry, the slot Save and Load use,
+/// whether the settings page is showing, and a save being named or
+/// waiting on "overwrite it?".
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct PauseMenu {
+    pub cursor: usize,
+    pub slot: u8,  // index into `slots`; see `slot_number`
+    pub settings: bool,
+    /// What each slot holds, read as the menu opens and after a save:
+    /// slots 1–4, then the Continue save.
+    pub slots: [Option<SlotInfo>; 5],
+    pub naming: Option<String>,
+    pub overwrite: Option<OverwriteSlot>,
+}
+
+impl PauseMenu {
+    pub const ITEMS: [PauseItem; 6] = [
+        PauseItem::Resume, PauseItem::Restart, PauseItem::Settings,
+        PauseItem::Save, PauseItem::Load, PauseItem::Quit,
+    ];
+    pub const SETTINGS: [PauseSetting; 5] = [
+        PauseSetting::Minimap, PauseSetting::EdgeMarkers, PauseSetting::GuardPaths,
+        PauseSetting::CompactView, PauseSetting::Back,
+    ];
+
+    /// Back to the top of the menu, as it opens. The slot is kept.
+    pub fn open(&mut self) {
+        self.cursor = 0;
+        self.settings = false;
+        self.naming = None;
+        self.overwrite = None;
+    }
+
+    /// What the slot Save and Load use holds.
+    pub fn slot_info(&self) -> Option<&SlotInfo> {
+        self.slots[self.slot as usize].as_ref()
+    }
+
+    fn len(&self) -> usize {
+        if self.settings { Self::SETTINGS.len() } else { Self::ITEMS.len() }
+    }
+
+    /// Move the highlight by `delta` entries, wrapping round.
+    pub fn move_cursor(&mut self, delta: isize) {
+        let len = self.len() as isize;
+        self.cursor = (self.cursor as isize + delta).rem_euclid(len) as usize;
+    }
+
+    /// Step the Save/Load slot by `delta`, wrapping round.
+    pub fn move_slot(&mut self, delta: i8) {
+        self.slot = (self.slot as i8 + delta).rem_euclid(self.slots.len() as i8) as u8;
+    }
+
+    /// The save slot Save and Load use: 1–4, or 0 for the Continue save
+    /// (the autosave), which Load offers and Save doesn't.
+    pub fn slot_number(&self) -> u8 {
+        if self.slot as usize + 1 == self.slots.len() { 0 } else { self.slot + 1 }
+    }
+
+    /// Open or leave the settings page, highlighting its first entry or
+    /// Settings again.
+    pub fn toggle_settings(&mut self) {
+        self.settings = !self.settings;
+        self.cursor = if self.settings {
+            0
+        } else {
+            Self::ITEMS.iter().position(|&i| i == PauseItem::Settings).unwrap_or(0)
+        };
+    }
+
+    /// The highlighted entry, on the main page.
+    pub fn item(&self) -> Option<PauseItem> {
+        (!self.settings).then(|| Self::ITEMS[self.cursor])
+    }
+
+    /// The highlighted entry, on the settings page.
+    pub fn setting(&self) -> Option<PauseSetting> {
+        self.settings.then(|| Self::SETTINGS[self.cursor])
+    }
+}
+
+/// Problems found in config.toml at startup, shown over the first screen
+/// until a key is pressed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfigReport {
+    pub path: String,           // the file, as shown
+    pub problems: Vec<String>,  // each with the default used instead
+}
+
+/// Pack builder state: the `levels/` files to choose from, the chosen
+/// ones in pack order, and the pack's metadata.
+#[derive(Clone, Debug, Default)]
+pub struct PackBuilder {
+    pub files: Vec<LevelFile>,         // sorted by file name
+    pub order: Vec<usize>,             // indices into `files`, in pack order
+    pub cursor: usize,
+    pub scroll: usize,
+    pub fields: [String; 3],           // name, author, description
+    pub editing: Option<usize>,        // the field being typed into
+    /// W was pressed once with warnings on the picked levels; again writes.
+    pub confirm_write: bool,
+}
+
+/// A `levels/` file as the pack builder lists it.
+#[derive(Clone, Debug, Default)]
+pub struct LevelFile {
+    pub file: String,
+    pub name: String,
+    pub warnings: Vec<String>,  // from `level::validate_level`
+    pub solver_tried: bool,     // `level::check_solvable` has run on it
+}
+
+/// What the pack selector shows of the highlighted pack.
+#[derive(Clone, Debug, Default)]
+pub struct PackPreview {
+    pub path: String,          // the `PackInfo::path` this was loaded for
+    pub names: Vec<String>,    // the first few level names
+    pub rows: Vec<String>,     // the first level's map rows (first room)
 }
 
 #[derive(Clone, Copy, PartialEq, Eq, Debug)]
@@ -38,6 +234,8 @@ pub enum Phase {
     Title,
     LevelSelect,
     PackSelect,
+    PackBuilder,   // assembling a pack from levels/ files
+    Cutscene,      // story pages between levels (see `sim::story`)
     LevelIntro,
     LevelReady,
     Playing,
@@ -63,18 +261,43 @@ pub struct Camera {
     pub view_w: usize,
     /// Number of world rows visible
     pub view_h: usize,
+    /// Peek offset in cells, eased towards the held peek direction by `update_peek`
+    pub peek: (i32, i32),
+    /// Part of `peek` actually applied to (x, y) after clamping to the world
+    peek_applied: (i32, i32),
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Camera {
     pub fn new() -> Self {
-        Camera { x: 0, y: 0, view_w: 0, view_h: 0 }
+        Camera { x: 0, y: 0, view_w: 0, view_h: 0, peek: (0, 0), peek_applied: (0, 0) }
+    }
+
+    /// Ease the peek offset one cell per axis towards `dir` × half a
+    /// viewport, or back to zero when `dir` is (0, 0). Call once per tick.
+    pub fn update_peek(&mut self, dir: (i32, i32)) {
+        let target = (dir.0 * self.view_w as i32 / 2, dir.1 * self.view_h as i32 / 2);
+        self.peek.0 += (target.0 - self.peek.0).signum();
+        self.peek.1 += (target.1 - self.peek.1).signum();
     }
 
     /// Update camera to follow a target position within the given world bounds.
     /// Uses a dead-zone approach: only scroll when the target is near the edge
     /// of the viewport. This gives a smooth, non-jerky Lode Runner feel.
+    ///
+    /// The peek offset is layered on top: the dead zone tracks the
+    /// un-peeked position, so releasing a peek glides back to where the
+    /// camera would have been.
     pub fn follow(&mut self, target_x: usize, target_y: usize, world_w: usize, world_h: usize) {
         if self.view_w == 0 || self.view_h == 0 { return; }
+        self.x -= self.peek_applied.0;
+        self.y -= self.peek_applied.1;
+        self.peek_applied = (0, 0);
 
         // If map fits entirely in viewport, center it
         if world_w <= self.view_w {
@@ -112,11 +335,28 @@ impl Camera {
 
             self.y = self.y.max(0).min((world_h as i32 - self.view_h as i32).max(0));
         }
+
+        // Peek only along axes that scroll
+        if world_w > self.view_w {
+            let x = (self.x + self.peek.0).clamp(0, world_w as i32 - self.view_w as i32);
+            self.peek_applied.0 = x - self.x;
+            self.x = x;
+        }
+        if world_h > self.view_h {
+            let y = (self.y + self.peek.1).clamp(0, world_h as i32 - self.view_h as i32);
+            self.peek_applied.1 = y - self.y;
+            self.y = y;
+        }
+        // Don't build up offset past the world edge, or release would lag
+        self.peek = self.peek_applied;
     }
 
     /// Snap camera directly to center on a position (no dead zone).
     /// Used on level load / restart.
+    /// Clears any peek.
     pub fn center_on(&mut self, target_x: usize, target_y: usize, world_w: usize, world_h: usize) {
+        self.peek = (0, 0);
+        self.peek_applied = (0, 0);
         if self.view_w == 0 || self.view_h == 0 { return; }
 
         if world_w <= self.view_w {
@@ -147,13 +387,203 @@ impl Camera {
     }
 }
 
+/// A door cell; door `id` links to the cell with the same id in another room.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Door {
+    pub id: u8,
+    pub x: usize,
+    pub y: usize,
+}
+
+/// What brings a tutorial tip on screen.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TipTrigger {
+    Start,                      // from the start until the player moves
+    Hack,                       // a dig is possible, until the first dig
+    Ladder,                     // on a ladder, until the player climbs
+    Rope,                       // on a rope, until the player lets go
+    Guard,                      // a sentinel is close, until one is trapped
+    Exit,                       // the exit is open
+    At { x: usize, y: usize },  // the player is within 2 cells of (x, y)
+}
+
+/// A tutorial prompt from an `@tag` / `@tip` line (see `sim::tutorial`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tip {
+    pub trigger: TipTrigger,
+    pub text: String,
+    pub done: bool,  // retired: the player has done what it asks
+}
+
+/// Totals for the current run, shown in the ending credits (and for
+/// the current node, shown when it's cleared).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunStats {
+    pub play_ticks: u64,
+    pub cleared: u32,
+    pub tokens: u32,
+    pub hacks: u32,
+    pub guards_trapped: u32,
+    pub secrets: u32,
+    pub deaths: u32,
+}
+
+impl RunStats {
+    /// Add a tick of play and its events.
+    pub fn count(&mut self, events: &[GameEvent]) {
+        self.play_ticks += 1;
+        for event in events {
+            match event {
+                GameEvent::StageCleared => self.cleared += 1,
+                GameEvent::GoldPicked { .. } => self.tokens += 1,
+                GameEvent::HoleCreated { .. } => self.hacks += 1,
+                GameEvent::GuardTrapped { .. } => self.guards_trapped += 1,
+                GameEvent::SecretFound { .. } => self.secrets += 1,
+                GameEvent::PlayerKilled => self.deaths += 1,
+                _ => {}
+            }
+        }
+    }
+}
+
+/// How a message-bar line is coloured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Severity {
+    Info,         // saves, loads, level names
+    Warning,      // refused actions, failures, running out
+    Achievement,  // tokens mined, node cleared
+}
+
+/// A queued message-bar line. `timer` counts down in ticks; `None` stays
+/// until cleared (the pause notice).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub text: String,
+    pub severity: Severity,
+    pub timer: Option<u32>,
+}
+
+/// A message or game event kept for the pause-menu event log.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct LogEntry {
+    pub node: usize,  // 1-based
+    pub secs: u64,    // into the level
+    pub severity: Severity,
+    pub text: String,
+}
+
+/// Entries the event log keeps; older ones drop off.
+const LOG_LEN: usize = 50;
+
+/// How much further a lantern lights, in cells.
+const LANTERN_LIGHT: u32 = 2;
+
+/// Nightmare: how near (in cells, any direction) a sentinel shows, and
+/// for how many ticks one flickers into view after a step.
+const GUARD_SIGHT: usize = 3;
+const GUARD_GLIMPSE_TICKS: u64 = 2;
+
+/// Messages on screen at once; the oldest drops off beyond this.
+const MESSAGE_STACK: usize = 3;
+/// Ticks a message lingers once a newer one covers it.
+const MESSAGE_LINGER: u32 = 30;
+
+/// One room of a multi-room level, parked while the player is elsewhere.
+#[derive(Clone, Debug, Default)]
+pub struct Room {
+    pub name: String,
+    pub base_tiles: Grid<Tile>,
+    pub tiles: Grid<Tile>,
+    pub width: usize,
+    pub height: usize,
+    pub guards: Vec<Guard>,
+    pub holes: Vec<Hole>,
+    pub digs: Vec<DigInProgress>,
+    pub exit_columns: Vec<usize>,
+    pub hidden_ladder_positions: Vec<(usize, usize)>,
+    pub respawn_points: Vec<(usize, usize)>,
+    pub spawners: Vec<Spawner>,
+    pub doors: Vec<Door>,
+}
+
+/// How a run is scored.
+#[derive(Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum GameMode {
+    /// Points for tokens, guards and cleared nodes; lives run out.
+    #[default]
+    Normal,
+    /// A countdown clock replaces the score; rewards add time instead.
+    TimeAttack,
+}
+
+/// Clock and bonuses for a time attack run. All times are in ticks.
+#[derive(Clone, Debug, Default)]
+pub struct TimeAttack {
+    pub clock: u32,
+    pub token_bonus: u32,
+    pub trap_bonus: u32,
+    /// Nodes cleared this run (the primary ranking key).
+    pub cleared: u32,
+    /// Position in the pack ranking, once the finished run is recorded.
+    pub rank: Option<usize>,
+    pub recorded: bool,
+}
+
+/// Best results on the current node from the local leaderboard.
+#[derive(Clone, Debug, Default)]
+pub struct LevelBoard {
+    /// Highest points earned on the node, best first.
+    pub scores: Vec<u32>,
+    /// Fastest clears in milliseconds, best first.
+    pub times_ms: Vec<u64>,
+    /// Places of the clear just recorded, for highlighting.
+    pub new_score: Option<usize>,
+    pub new_time: Option<usize>,
+}
+
+/// A pack's best finished normal runs, one best per column (not one run).
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct RunBests {
+    pub score: u32,
+    pub cleared: u32,
+    pub tokens: u32,
+    pub trapped: u32,
+}
+
+impl RunBests {
+    /// A finished run's own results, as a row to compare with the bests.
+    pub fn of(score: u32, run: &RunStats) -> Self {
+        RunBests { score, cleared: run.cleared, tokens: run.tokens, trapped: run.guards_trapped }
+    }
+}
+
+/// One row of the global (online) leaderboard for the current node.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct GlobalEntry {
+    pub name: String,
+    pub score: Option<u32>,  // None on time attack boards
+    pub time_ms: u64,
+}
+
+/// Campaign state for packs that open on a hub level (`## Hub`).
+/// Hub door `N` launches pack level `N`; the hub is always level 0.
+#[derive(Clone, Debug, Default)]
+pub struct HubState {
+    /// Active pack has a hub.
+    pub enabled: bool,
+    /// Pack levels cleared so far (persisted per pack).
+    pub cleared: Vec<usize>,
+    /// Door the player left the hub through, to return them there.
+    pub return_door: Option<u8>,
+}
+
 pub struct WorldState {
     // ── Tile layers ──
     /// Original level data. Never mutated after `load_level`.
-    pub base_tiles: Vec<Vec<Tile>>,
+    pub base_tiles: Grid<Tile>,
     /// Effective terrain = base + runtime changes (holes, gold pickup, etc).
     /// Always kept in sync via `set_tile()` / `clear_tile()`.
-    pub tiles: Vec<Vec<Tile>>,
+    pub tiles: Grid<Tile>,
     pub width: usize,
     pub height: usize,
 
@@ -164,21 +594,107 @@ pub struct WorldState {
     pub digs: Vec<DigInProgress>,
 
     // ── Derived: O(1) hole lookup grid ──
-    /// `hole_grid[y][x] == true` ↔ active hole at (x, y).
+    /// `hole_grid.get(x, y)` ↔ active hole at (x, y).
     /// Rebuilt automatically by `rebuild_hole_grid()`.
-    pub hole_grid: Vec<Vec<bool>>,
+    pub hole_grid: BitGrid,
+    /// Which guards are in each cell. Rebuilt by `rebuild_occupancy()`,
+    /// and kept up to date by `guard_moved()` as `step` moves them.
+    pub occupancy: Occupancy,
+    /// Guards' way to the player; refreshed by `step` as the player moves.
+    pub flow: FlowField,
 
     // ── Game tracking ──
     pub gold_remaining: usize,
     pub gold_total: usize,
     pub exit_enabled: bool,
+    pub digs_made: u32,  // holes hacked this level (HUD stat; kept across retries)
+    /// Puzzle mode dig budget per attempt (`@digs N` / `dig_limit`); None = unlimited.
+    pub dig_limit: Option<u32>,
+    pub digs_left: Option<u32>,
+    /// Light radius the level starts with (`@dark N` / `darkness`); `None` = lit.
+    pub darkness: Option<u32>,
+    /// Par time in seconds (`@par N`), for the clear summary.
+    pub par_secs: Option<u32>,
+    /// Light radius now, lanterns included.
+    pub light_radius: Option<u32>,
+    /// Where killed sentinels come back (`[rules] guard_respawn`).
+    pub guard_respawn: GuardRespawn,
+    /// Lasers and deadly tiles kill sentinels too (`[rules] hazards_kill_guards`).
+    pub hazards_kill_guards: bool,
+    /// How hard sentinels search for the player (`[ai]`).
+    pub ai: AiConfig,
+
+    // ── Rooms ──
+    /// Every room of the level; a single entry for ordinary one-map levels.
+    /// The slot at `current_room` is stale while that room is swapped in.
+    pub rooms: Vec<Room>,
+    pub current_room: usize,
+    /// Cleared on arrival so the player isn't bounced straight back;
+    /// re-armed once they step off the door.
+    pub door_armed: bool,
+    pub hub: HubState,
 
     // ── Speed config ──
     pub speed: SpeedConfig,
 
     // ── Meta ──
     pub phase: Phase,
+    pub mode: GameMode,
+    pub time_attack: TimeAttack,
+    /// The pack's run bests from before this run, once a lost normal run
+    /// has been recorded (for the Game Over comparison).
+    pub run_bests: Option<RunBests>,
+    /// Every node of the active pack cleared: New Game+ is open.
+    pub ng_plus: bool,
+    /// New Game+ nightmare: sentinels are only seen near the player or
+    /// just after a step; the rest is footsteps.
+    pub invisible_guards: bool,
+    /// Mirror mode: levels load flipped left to right.
+    pub mirror: bool,
+    /// Randomizer mode: tokens and sentinels dealt anew each attempt.
+    pub randomizer: Option<Randomizer>,
+    /// New Game+ loops played through the pack on this run (0 the first
+    /// time through). Speeds up sentinels and multiplies scoring.
+    pub ng_loop: u32,
     pub score: u32,
+    /// Score when the current node was loaded (for its leaderboard entry).
+    pub level_start_score: u32,
+    pub board: LevelBoard,
+    /// Global top 10 for the current node; empty until fetched (or offline).
+    pub global_board: Vec<GlobalEntry>,
+    /// Tutorial tips of the current level, and the one on screen.
+    pub tips: Vec<Tip>,
+    pub tip: Option<usize>,
+    /// `@on` scripts of the current level (see `sim::script`).
+    pub scripts: Scripts,
+    /// The systems `step` runs each tick, in order (`step::pipeline()`
+    /// plus any a level adds).
+    pub pipeline: Pipeline,
+    /// Story pages still to show (the first is on screen), and the phase
+    /// that follows them.
+    pub story: Vec<StoryPage>,
+    pub story_next: Phase,
+    pub run: RunStats,
+    /// Totals for the current node over all its attempts, for the
+    /// summary when it's cleared.
+    pub node_run: RunStats,
+    /// The attempt so far, while it can still become a run code.
+    pub recording: Option<Recording>,
+    /// A run code being watched; its input replaces the player's.
+    pub playback: Option<Playback>,
+    /// The host being watched (`--spectate`); this world only shows its game.
+    pub spectating: Option<String>,
+    /// Played over `--serve-telnet`: the host's files are off limits, so
+    /// no pack management, pack builder, level imports or saving.
+    pub served: bool,
+    /// The pack just finished, for the ending credits.
+    pub completed_pack: Option<PackInfo>,
+    /// Guards the level file places; ids from here up were spawned by scripts.
+    pub level_guards: usize,
+    /// Sentinels the level's scripts may add per attempt (its spawners'
+    /// cap), and how many they have so far.
+    pub script_spawn_cap: u32,
+    pub script_spawned: u32,
     pub lives: u32,
     pub current_level: usize,
     pub total_levels: usize,
@@ -187,13 +703,30 @@ pub struct WorldState {
     pub tick: u64,
 
     // ── UI ──
-    pub message: String,
-    pub message_timer: u32,
+    pub messages: VecDeque<Message>,  // oldest first; the newest takes the message bar
+    pub log: VecDeque<LogEntry>,  // oldest first, up to LOG_LEN
+    pub log_open: bool,  // event log overlay, over the pause menu
+    pub log_scroll: usize,  // entries scrolled back from the newest
+    pub pause_menu: PauseMenu,
+    pub config_report: Option<ConfigReport>,
+    pub code_prompt: Option<String>,  // the title screen's "Play code" prompt and what's pasted so far
+    pub backup_offer: Option<u8>,  // a damaged save whose load key, pressed next, loads its backup (0 = Continue)
+    pub show_minimap: bool,
+    pub show_edge_markers: bool,  // off-screen guard / token markers on the viewport edge
+    pub show_guard_paths: bool,  // each sentinel's chase path to the player
+    pub compact_view: bool,  // one terminal column per cell, for wide levels
+    pub show_overview: bool,  // whole-level braille view; freezes the sim while open
 
     // ── Spawn / exit ──
     pub player_spawn: (usize, usize),
     pub exit_columns: Vec<usize>,
     pub hidden_ladder_positions: Vec<(usize, usize)>,
+    /// The room's `R` markers: where killed sentinels come back, in turn.
+    pub respawn_points: Vec<(usize, usize)>,
+    /// Respawns so far this attempt; picks the next of `respawn_points`.
+    pub respawns: usize,
+    /// The room's `G` spawners and their timers.
+    pub spawners: Vec<Spawner>,
 
     // ── Animation ──
     pub anim_tick: u32,
@@ -201,6 +734,8 @@ pub struct WorldState {
 
     // ── Pause ──
     pub paused: bool,
+    pub auto_paused: bool,  // paused by the game (resize, focus loss, suspend); any key resumes
+    pub restart_confirm: u32,  // ticks of play left to press R again and restart (0: not asked)
 
     // ── Camera / Viewport ──
     pub camera: Camera,
@@ -210,11 +745,18 @@ pub struct WorldState {
     pub select_scroll: usize,
     pub level_names: Vec<String>,
     pub has_save: bool,
+    /// Node of the game suspended by quitting, offered on the title screen.
+    pub suspended: Option<usize>,
 
     // ── Pack select (F3 filer) ──
     pub pack_list: Vec<PackInfo>,
     pub pack_cursor: usize,
     pub pack_scroll: usize,
+    pub pack_preview: Option<PackPreview>,
+    pub pack_dialog: Option<PackDialog>,
+    pub share: Option<ShareDialog>,
+    pub clipboard: Option<String>,  // text for the renderer to put on the clipboard with the next frame
+    pub builder: PackBuilder,
     pub active_pack: String,       // display name of active pack
     pub active_pack_path: String,  // path or "__levels__" or "__embedded__"
 }
@@ -225,18 +767,20 @@ impl WorldState {
     /// Query effective terrain at (x, y).
     #[inline]
     pub fn terrain_at(&self, x: usize, y: usize) -> Tile {
-        if x < self.width && y < self.height {
-            self.tiles[y][x]
-        } else {
-            Tile::Concrete // out of bounds = wall
-        }
+        self.tiles.get(x, y).unwrap_or(Tile::Concrete) // out of bounds = wall
+    }
+
+    /// Is there an active hole at (x, y)?
+    #[inline]
+    pub fn hole_at(&self, x: usize, y: usize) -> bool {
+        self.hole_grid.get(x, y)
     }
 
     /// Set a tile in the effective layer (runtime change).
     #[inline]
     pub fn set_tile(&mut self, x: usize, y: usize, tile: Tile) {
         if x < self.width && y < self.height {
-            self.tiles[y][x] = tile;
+            self.tiles[(x, y)] = tile;
         }
     }
 
@@ -244,7 +788,7 @@ impl WorldState {
     #[inline]
     pub fn clear_tile(&mut self, x: usize, y: usize) {
         if x < self.width && y < self.height {
-            self.tiles[y][x] = self.base_tiles[y][x];
+            self.tiles[(x, y)] = self.base_tiles[(x, y)];
         }
     }
 
@@ -252,6 +796,145 @@ impl WorldState {
     pub fn reset_tiles(&mut self) {
         self.tiles = self.base_tiles.clone();
     }
+
+    /// How lit (x, y) is in darkness: 1.0 within `light_radius` - 1 of
+    /// the player, fading to 0.0 at `light_radius` + 1. Always 1.0 in a
+    /// lit level.
+    pub fn light_at(&self, x: usize, y: usize) -> f32 {
+        let Some(r) = self.light_radius else { return 1.0 };
+        let dx = x as f32 - self.player.x as f32;
+        let dy = y as f32 - self.player.y as f32;
+        ((r as f32 + 1.0 - (dx * dx + dy * dy).sqrt()) / 2.0).clamp(0.0, 1.0)
+    }
+
+    /// Whether sentinel `g` is drawn: always, unless nightmare is on and
+    /// the level is being played.
+    pub fn guard_seen(&self, g: &Guard) -> bool {
+        if !self.invisible_guards || self.phase != Phase::Playing { return true; }
+        let p = &self.player;
+        g.x.abs_diff(p.x).max(g.y.abs_diff(p.y)) <= GUARD_SIGHT
+            || self.tick.saturating_sub(g.stepped_at) < GUARD_GLIMPSE_TICKS
+    }
+
+    /// A lantern's worth more light, up to `MAX_LIGHT`.
+    pub fn add_light(&mut self) {
+        if let Some(r) = &mut self.light_radius {
+            *r = (*r + LANTERN_LIGHT).min(MAX_LIGHT);
+        }
+    }
+
+    /// Cells not yet found: fake bricks, and the open cells only reached
+    /// through them. Open means passable, flooding four ways from the
+    /// player's cell (gravity aside).
+    pub fn secret_cells(&self) -> BitGrid {
+        let mut secret = BitGrid::new(self.width, self.height);
+        let fakes: Vec<(usize, usize)> = (0..self.height)
+            .flat_map(|y| (0..self.width).map(move |x| (x, y)))
+            .filter(|&(x, y)| self.tiles[(x, y)] == Tile::FakeBrick)
+            .collect();
+        if fakes.is_empty() { return secret; }
+
+        let flood = |from: Vec<(usize, usize)>, into: &mut BitGrid, enter: &dyn Fn(usize, usize) -> bool| {
+            let mut todo = from;
+            while let Some((x, y)) = todo.pop() {
+                if into.get(x, y) || !enter(x, y) { continue; }
+                into.set(x, y, true);
+                todo.extend([(x.wrapping_sub(1), y), (x + 1, y), (x, y.wrapping_sub(1)), (x, y + 1)]);
+            }
+        };
+        let mut open = BitGrid::new(self.width, self.height);
+        flood(vec![(self.player.x, self.player.y)], &mut open, &|x, y| {
+            let t = self.terrain_at(x, y);
+            t.is_passable() && t != Tile::FakeBrick
+        });
+        flood(fakes, &mut secret, &|x, y| self.terrain_at(x, y).is_passable() && !open.get(x, y));
+        secret
+    }
+}
+
+// ── Rooms ──
+
+impl WorldState {
+    pub fn is_multi_room(&self) -> bool {
+        self.rooms.len() > 1
+    }
+
+    pub fn room_name(&self) -> &str {
+        self.rooms.get(self.current_room).map_or(&self.level_name, |r| &r.name)
+    }
+
+    /// Doors of the room that is currently swapped in.
+    pub fn doors(&self) -> &[Door] {
+        self.rooms.get(self.current_room).map_or(&[], |r| &r.doors)
+    }
+
+    /// Add a sentinel at (x, y) to the current room, under an id no
+    /// sentinel of the level has, and return its index in `guards`.
+    pub fn spawn_guard(&mut self, x: usize, y: usize) -> usize {
+        let id = self.guards.iter()
+            .chain(self.rooms.iter().flat_map(|r| &r.guards))
+            .map(|g| g.id + 1)
+            .fold(self.level_guards, usize::max);
+        let mut g = Guard::new(id, x, y);
+        g.move_cooldown = self.speed.guard_move_rate;
+        self.guards.push(g);
+        self.guard_moved(self.guards.len() - 1);
+        self.guards.len() - 1
+    }
+
+    pub fn in_hub(&self) -> bool {
+        self.hub.enabled && self.current_level == 0
+    }
+
+    /// Has the level behind the hub door at (x, y) been cleared?
+    pub fn hub_door_cleared(&self, x: usize, y: usize) -> bool {
+        self.in_hub() && self.doors().iter()
+            .any(|d| d.x == x && d.y == y && self.hub.cleared.contains(&(d.id as usize)))
+    }
+
+    /// Every level reachable from a hub door has been cleared.
+    pub fn hub_complete(&self) -> bool {
+        self.doors().iter()
+            .map(|d| d.id as usize)
+            .filter(|&level| level < self.total_levels)
+            .all(|level| self.hub.cleared.contains(&level))
+    }
+
+    /// Move the live map state back into its `rooms` slot.
+    pub fn park_room(&mut self) {
+        let Some(room) = self.rooms.get_mut(self.current_room) else { return };
+        room.base_tiles = std::mem::take(&mut self.base_tiles);
+        room.tiles = std::mem::take(&mut self.tiles);
+        room.width = self.width;
+        room.height = self.height;
+        room.guards = std::mem::take(&mut self.guards);
+        room.holes = std::mem::take(&mut self.holes);
+        room.digs = std::mem::take(&mut self.digs);
+        room.exit_columns = std::mem::take(&mut self.exit_columns);
+        room.hidden_ladder_positions = std::mem::take(&mut self.hidden_ladder_positions);
+        room.respawn_points = std::mem::take(&mut self.respawn_points);
+        room.spawners = std::mem::take(&mut self.spawners);
+    }
+
+    /// Swap room `idx` into the live fields. The current room must have
+    /// been parked first, or its state is lost.
+    pub fn enter_room(&mut self, idx: usize) {
+        let Some(room) = self.rooms.get_mut(idx) else { return };
+        self.base_tiles = std::mem::take(&mut room.base_tiles);
+        self.tiles = std::mem::take(&mut room.tiles);
+        self.width = room.width;
+        self.height = room.height;
+        self.guards = std::mem::take(&mut room.guards);
+        self.holes = std::mem::take(&mut room.holes);
+        self.digs = std::mem::take(&mut room.digs);
+        self.exit_columns = std::mem::take(&mut room.exit_columns);
+        self.hidden_ladder_positions = std::mem::take(&mut room.hidden_ladder_positions);
+        self.respawn_points = std::mem::take(&mut room.respawn_points);
+        self.spawners = std::mem::take(&mut room.spawners);
+        self.current_room = idx;
+        self.rebuild_hole_grid();
+        self.rebuild_occupancy();
+    }
 }
 
 // ── Hole grid maintenance ──
@@ -265,6 +948,22 @@ impl WorldState {
     }
 }
 
+// ── Occupancy maintenance ──
+
+impl WorldState {
+    /// Index every guard afresh. Call after guards are added, removed or
+    /// replaced wholesale.
+    pub fn rebuild_occupancy(&mut self) {
+        self.occupancy.rebuild(&self.guards, self.width, self.height);
+    }
+
+    /// Re-index guard `i` after its cell or state changed.
+    #[inline]
+    pub fn guard_moved(&mut self, i: usize) {
+        self.occupancy.update(i, &self.guards[i]);
+    }
+}
+
 // ── Unified physics queries (single source of truth) ──
 //
 // All systems (step, AI, renderer) should prefer these methods
@@ -289,7 +988,7 @@ impl WorldState {
     pub fn has_support(&self, x: usize, y: usize) -> bool {
         physics::has_support(
             &self.tiles, self.width, self.height,
-            &self.hole_grid, &self.guards, x, y,
+            &self.hole_grid, &self.occupancy, x, y,
         )
     }
 
@@ -298,7 +997,7 @@ impl WorldState {
     pub fn has_support_for_player(&self, x: usize, y: usize) -> bool {
         physics::has_support_for_player(
             &self.tiles, self.width, self.height,
-            &self.hole_grid, &self.guards, x, y,
+            &self.hole_grid, &self.occupancy, x, y,
         )
     }
 
@@ -307,7 +1006,7 @@ impl WorldState {
     pub fn has_support_for_guard(&self, x: usize, y: usize, guard_idx: usize) -> bool {
         physics::has_support_for_guard(
             &self.tiles, self.width, self.height,
-            &self.hole_grid, &self.guards, x, y, guard_idx,
+            &self.hole_grid, &self.occupancy, x, y, guard_idx,
         )
     }
 
@@ -316,7 +1015,7 @@ impl WorldState {
     pub fn resolve_actor_state(&self, x: usize, y: usize, current: crate::domain::entity::ActorState) -> crate::domain::entity::ActorState {
         physics::resolve_state(
             &self.tiles, self.width, self.height,
-            &self.hole_grid, &self.guards, x, y, current,
+            &self.hole_grid, &self.occupancy, x, y, current,
         )
     }
 
@@ -329,21 +1028,42 @@ impl WorldState {
 
 // ── Construction ──
 
+impl Default for WorldState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl WorldState {
     pub fn new() -> Self {
         WorldState {
-            base_tiles: vec![],
-            tiles: vec![],
+            base_tiles: Grid::default(),
+            tiles: Grid::default(),
             width: 0,
             height: 0,
             player: Player::new(0, 0),
             guards: vec![],
             holes: vec![],
             digs: vec![],
-            hole_grid: vec![],
+            hole_grid: BitGrid::default(),
+            occupancy: Occupancy::default(),
+            flow: FlowField::default(),
             gold_remaining: 0,
             gold_total: 0,
             exit_enabled: false,
+            digs_made: 0,
+            dig_limit: None,
+            par_secs: None,
+            digs_left: None,
+            darkness: None,
+            light_radius: None,
+            guard_respawn: GuardRespawn::Top,
+            hazards_kill_guards: false,
+            ai: AiConfig::default(),
+            rooms: vec![],
+            current_room: 0,
+            door_armed: true,
+            hub: HubState::default(),
             speed: SpeedConfig {
                 tick_rate_ms: 75,
                 player_move_rate: 2,
@@ -356,35 +1076,238 @@ impl WorldState {
                 gold_carry_ticks: 150,
             },
             phase: Phase::Title,
+            mode: GameMode::Normal,
+            time_attack: TimeAttack::default(),
+            run_bests: None,
+            ng_plus: false,
+            invisible_guards: false,
+            mirror: false,
+            randomizer: None,
+            ng_loop: 0,
             score: 0,
+            level_start_score: 0,
+            board: LevelBoard::default(),
+            global_board: vec![],
+            tips: vec![],
+            tip: None,
+            scripts: Scripts::default(),
+            pipeline: step::pipeline(),
+            story: vec![],
+            story_next: Phase::LevelIntro,
+            run: RunStats::default(),
+            node_run: RunStats::default(),
+            recording: None,
+            playback: None,
+            spectating: None,
+            served: false,
+            completed_pack: None,
+            level_guards: 0,
+            script_spawn_cap: 0,
+            script_spawned: 0,
             lives: 5,
             current_level: 0,
             total_levels: 0,
             level_name: String::new(),
             tick: 0,
-            message: String::new(),
-            message_timer: 0,
+            messages: VecDeque::new(),
+            log: VecDeque::new(),
+            log_open: false,
+            config_report: None,
+            code_prompt: None,
+            backup_offer: None,
+            log_scroll: 0,
+            pause_menu: PauseMenu::default(),
+            restart_confirm: 0,
+            show_minimap: true,
+            show_edge_markers: true,
+            show_guard_paths: false,
+            compact_view: false,
+            show_overview: false,
             player_spawn: (0, 0),
             exit_columns: vec![],
             hidden_ladder_positions: vec![],
+            respawn_points: vec![],
+            respawns: 0,
+            spawners: vec![],
             anim_tick: 0,
             anim_player_y: 0,
             paused: false,
+            auto_paused: false,
             camera: Camera::new(),
             select_cursor: 0,
             select_scroll: 0,
             level_names: vec![],
             has_save: false,
+            suspended: None,
             pack_list: vec![],
             pack_cursor: 0,
+            pack_preview: None,
+            pack_dialog: None,
+            share: None,
+            clipboard: None,
+            builder: PackBuilder::default(),
             pack_scroll: 0,
             active_pack: String::from("Built-in Levels"),
             active_pack_path: String::from("__embedded__"),
         }
     }
 
-    pub fn set_message(&mut self, msg: &str, duration: u32) {
-        self.message = msg.to_string();
-        self.message_timer = duration;
+    /// Convert seconds to sim ticks at the current tick rate.
+    pub fn secs_to_ticks(&self, secs: u32) -> u32 {
+        (secs as u64 * 1000 / self.speed.tick_rate_ms.max(1)) as u32
+    }
+
+    /// FNV-1a hash of every room's map as loaded, so a server can tell
+    /// apart different levels that share a pack name and number.
+    pub fn level_hash(&self) -> u64 {
+        let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+        let mut feed = |tiles: &Grid<Tile>| {
+            for row in tiles.rows() {
+                for &t in row {
+                    h ^= t.code();
+                    h = h.wrapping_mul(0x0100_0000_01b3);
+                }
+                h ^= 0xff;
+                h = h.wrapping_mul(0x0100_0000_01b3);
+            }
+        };
+        feed(&self.base_tiles);
+        for (i, room) in self.rooms.iter().enumerate() {
+            if i != self.current_room { feed(&room.base_tiles); }
+        }
+        h
+    }
+
+    /// Text of the tutorial tip on screen, if any.
+    pub fn tip_text(&self) -> Option<&str> {
+        self.tip.and_then(|i| self.tips.get(i)).map(|t| t.text.as_str())
+    }
+
+    /// Whole seconds left on the time attack clock (rounded up).
+    pub fn clock_secs(&self) -> u64 {
+        (self.time_attack.clock as u64 * self.speed.tick_rate_ms).div_ceil(1000)
+    }
+
+    /// Queue a message for `duration` ticks (0: until cleared). Older
+    /// messages stay stacked briefly instead of being overwritten; the same
+    /// text again just moves to the front.
+    pub fn push_message(&mut self, severity: Severity, text: &str, duration: u32) {
+        let repeat = self.messages.iter().any(|m| m.text == text);
+        self.messages.retain(|m| m.text != text);
+        for m in &mut self.messages {
+            if let Some(t) = &mut m.timer {
+                *t = (*t).min(MESSAGE_LINGER);
+            }
+        }
+        let timer = (duration > 0).then_some(duration);
+        // A message still on screen isn't logged again
+        if timer.is_some() && !repeat {
+            self.log(severity, text);
+        }
+        self.messages.push_back(Message { text: text.to_string(), severity, timer });
+        while self.messages.len() > MESSAGE_STACK {
+            self.messages.pop_front();
+        }
+    }
+
+    /// Count message timers down one tick and drop the expired ones.
+    pub fn tick_messages(&mut self) {
+        for m in &mut self.messages {
+            if let Some(t) = &mut m.timer {
+                *t = t.saturating_sub(1);
+            }
+        }
+        self.messages.retain(|m| m.timer != Some(0));
+    }
+
+    pub fn clear_messages(&mut self) {
+        self.messages.clear();
+    }
+
+    /// Add a line to the event log, stamped with the node and level time.
+    pub fn log(&mut self, severity: Severity, text: &str) {
+        self.log.push_back(LogEntry {
+            node: self.current_level + 1,
+            secs: self.tick * self.speed.tick_rate_ms / 1000,
+            severity,
+            text: text.to_string(),
+        });
+        while self.log.len() > LOG_LEN {
+            self.log.pop_front();
+        }
+    }
+
+    /// Add a tick of play and its events to the run's and the node's
+    /// totals.
+    pub fn count_run(&mut self, events: &[GameEvent]) {
+        self.run.count(events);
+        self.node_run.count(events);
+    }
+
+    /// Log the events worth reviewing later: what sentinels did and what
+    /// happened to the player. Routine ones (holes, falls, pickups) and
+    /// those already announced by a message are left out.
+    pub fn log_events(&mut self, events: &[GameEvent]) {
+        for event in events {
+            let (severity, text) = match *event {
+                GameEvent::GuardTrapped { id, x, y } =>
+                    (Severity::Achievement, t!("log.guard_trapped", id = id + 1, x = x, y = y)),
+                GameEvent::GuardKilled { id, x, y } =>
+                    (Severity::Achievement, t!("log.guard_killed", id = id + 1, x = x, y = y)),
+                GameEvent::GuardRespawned { id } =>
+                    (Severity::Info, t!("log.guard_respawned", id = id + 1)),
+                GameEvent::GuardDroppedGold { id, x, y } =>
+                    (Severity::Info, t!("log.guard_dropped", id = id + 1, x = x, y = y)),
+                GameEvent::PlayerKilled =>
+                    (Severity::Warning, t!("log.player_killed").to_string()),
+                GameEvent::TrapCollapsed { x, y } =>
+                    (Severity::Warning, t!("log.trap", x = x, y = y)),
+                _ => continue,
+            };
+            self.log(severity, &text);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts(world: &WorldState) -> Vec<&str> {
+        world.messages.iter().map(|m| m.text.as_str()).collect()
+    }
+
+    #[test]
+    fn message_queue_dedupes_lingers_caps_and_keeps_sticky() {
+        let mut world = WorldState::new();
+
+        // A repeat moves to the back without a second log line
+        world.push_message(Severity::Info, "a", 100);
+        world.push_message(Severity::Info, "b", 100);
+        world.push_message(Severity::Info, "a", 100);
+        assert_eq!(texts(&world), ["b", "a"]);
+        assert_eq!(world.log.len(), 2);
+
+        // A newer message clamps the covered ones to the linger time
+        assert_eq!(world.messages[0].timer, Some(MESSAGE_LINGER));
+        assert_eq!(world.messages[1].timer, Some(100));
+
+        // Beyond the stack, the oldest drops off
+        world.push_message(Severity::Warning, "c", 100);
+        world.push_message(Severity::Warning, "d", 100);
+        assert_eq!(world.messages.len(), MESSAGE_STACK);
+        assert_eq!(texts(&world), ["a", "c", "d"]);
+
+        // Duration 0 stays until cleared, and isn't logged
+        world.clear_messages();
+        let logged = world.log.len();
+        world.push_message(Severity::Info, "sticky", 0);
+        world.push_message(Severity::Info, "timed", 5);
+        assert_eq!(world.messages[0].timer, None);
+        assert_eq!(world.log.len(), logged + 1);
+        for _ in 0..1000 {
+            world.tick_messages();
+        }
+        assert_eq!(texts(&world), ["sticky"]);
     }
 }
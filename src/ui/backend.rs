@@ -0,0 +1,157 @@
+//! What the UI needs from the platform it runs on.
+//!
+//! Frames are written as ANSI escape sequences, which native terminals and
+//! browser ones (xterm.js) both understand, so the renderer and input
+//! handling don't care where they run. What differs is around that:
+//!   - `Screen`: taking the display over and giving it back, and its size
+//!   - `EventSource`: where key, focus and resize events come from
+//!
+//! `Crossterm` is both for a native terminal, and the default. `Headless`
+//! is a screen of a fixed size and `EventQueue` events pushed in by hand:
+//! benches and tests use them, and they're what a browser frontend builds
+//! on (its page sets the size and forwards xterm.js key events).
+
+use std::cell::RefCell;
+use std::collections::VecDeque;
+use std::io;
+use std::rc::Rc;
+use std::time::Duration;
+
+use crossterm::event::{self, Event};
+use crossterm::terminal;
+
+use crate::ui::terminal as ui_terminal;
+
+pub trait Screen {
+    /// Take the display over: raw input, no echo.
+    fn enter(&mut self) -> io::Result<()>;
+    /// Give it back as it was.
+    fn leave(&mut self) -> io::Result<()>;
+    /// Columns and rows.
+    fn size(&self) -> (u16, u16);
+    /// Stop the process for the shell (Ctrl+Z), and return once it's
+    /// continued. False (and nothing done) where that means nothing.
+    fn suspend(&mut self) -> bool {
+        false
+    }
+}
+
+pub trait EventSource {
+    /// Wait up to `timeout` for an event; true if one is pending.
+    fn poll(&mut self, timeout: Duration) -> bool;
+    /// The next pending event, without waiting.
+    fn read(&mut self) -> Option<Event>;
+}
+
+/// The native terminal, through crossterm.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct Crossterm;
+
+impl Screen for Crossterm {
+    fn enter(&mut self) -> io::Result<()> {
+        terminal::enable_raw_mode()?;
+        ui_terminal::set_active(true);
+        Ok(())
+    }
+
+    fn leave(&mut self) -> io::Result<()> {
+        ui_terminal::set_active(false);
+        terminal::disable_raw_mode()
+    }
+
+    fn size(&self) -> (u16, u16) {
+        terminal::size().unwrap_or((80, 24))
+    }
+
+    fn suspend(&mut self) -> bool {
+        ui_terminal::suspend()
+    }
+}
+
+impl EventSource for Crossterm {
+    fn poll(&mut self, timeout: Duration) -> bool {
+        event::poll(timeout).unwrap_or(false)
+    }
+
+    fn read(&mut self) -> Option<Event> {
+        event::read().ok()
+    }
+}
+
+/// A screen of a set size that needs no taking over.
+#[derive(Clone, Copy, Debug)]
+pub struct Headless {
+    pub width: u16,
+    pub height: u16,
+}
+
+impl Screen for Headless {
+    fn enter(&mut self) -> io::Result<()> { Ok(()) }
+    fn leave(&mut self) -> io::Result<()> { Ok(()) }
+    fn size(&self) -> (u16, u16) { (self.width, self.height) }
+}
+
+/// Events pushed in from elsewhere. Clones share the queue: one feeds it,
+/// the `InputState` holding another drains it.
+#[derive(Clone, Debug, Default)]
+pub struct EventQueue(Rc<RefCell<VecDeque<Event>>>);
+
+impl EventQueue {
+    pub fn push(&self, event: Event) {
+        self.0.borrow_mut().push_back(event);
+    }
+}
+
+impl EventSource for EventQueue {
+    /// Never waits: whoever pushes events runs the loop.
+    fn poll(&mut self, _timeout: Duration) -> bool {
+        !self.0.borrow().is_empty()
+    }
+
+    fn read(&mut self) -> Option<Event> {
+        self.0.borrow_mut().pop_front()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+
+    use crate::config::GameConfig;
+    use crate::sim::world::WorldState;
+    use crate::ui::input::InputState;
+    use crate::ui::renderer::Renderer;
+
+    #[test]
+    fn headless_backends_drive_input_and_frames() {
+        let queue = EventQueue::default();
+        let mut kb = InputState::with_source(Box::new(queue.clone()));
+        queue.push(Event::Key(KeyEvent::new(KeyCode::Enter, KeyModifiers::NONE)));
+        kb.drain_events();
+        assert!(kb.was_pressed(KeyCode::Enter));
+        kb.drain_events();
+        assert!(!kb.was_pressed(KeyCode::Enter));
+
+        let config = GameConfig::builtin();
+        let screen = Headless { width: 100, height: 40 };
+        let out = Rc::new(RefCell::new(Vec::new()));
+        let mut renderer = Renderer::with_screen(&config.display, Box::new(SharedBuf(Rc::clone(&out))), Box::new(screen));
+        renderer.init().unwrap();
+        renderer.render(&mut WorldState::new()).unwrap();
+        renderer.cleanup().unwrap();
+        let text = String::from_utf8_lossy(&out.borrow()).into_owned();
+        assert!(text.contains("\x1b[?1049h") && text.contains("\x1b[?1049l"));
+        assert!(!renderer.too_small());
+    }
+
+    struct SharedBuf(Rc<RefCell<Vec<u8>>>);
+
+    impl io::Write for SharedBuf {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.0.borrow_mut().write(buf)
+        }
+
+        fn flush(&mut self) -> io::Result<()> { Ok(()) }
+    }
+}
@@ -125,24 +125,34 @@ fn btn_index(btn: Btn) -> usize {
     btn as usize
 }
 
+impl Default for GamepadState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl GamepadState {
+    #[cfg(feature = "gamepad")]
     pub fn new() -> Self {
-        #[cfg(feature = "gamepad")]
-        let (gilrs_opt, connected) = {
-            match Gilrs::new() {
-                Ok(g) => {
-                    let has_pad = g.gamepads().next().is_some();
-                    (Some(g), has_pad)
-                }
-                Err(_) => (None, false),
-            }
-        };
-        #[cfg(not(feature = "gamepad"))]
-        let connected = false;
+        let mut state = Self::absent();
+        if let Ok(g) = Gilrs::new() {
+            state.connected = g.gamepads().next().is_some();
+            state.gilrs = Some(g);
+        }
+        state
+    }
 
+    #[cfg(not(feature = "gamepad"))]
+    pub fn new() -> Self {
+        Self::absent()
+    }
+
+    /// No pad, ever: for a game played from elsewhere (`--serve-telnet`),
+    /// which pads on the host mustn't reach.
+    pub fn absent() -> Self {
         GamepadState {
             #[cfg(feature = "gamepad")]
-            gilrs: gilrs_opt,
+            gilrs: None,
             buttons: [BtnState::default(); 10],
             dpad_up: BtnState::default(),
             dpad_down: BtnState::default(),
@@ -155,26 +165,37 @@ impl GamepadState {
             stick_x: 0.0,
             stick_y: 0.0,
             action_map: ActionMap::default(),
-            connected,
+            connected: false,
         }
     }
 
-    /// Load button mapping from config.
-    pub fn load_button_config(&mut self, cfg: &GamepadConfig) {
-        fn parse_list(names: &[String]) -> Vec<Btn> {
-            names.iter().filter_map(|s| Btn::from_name(s)).collect()
-        }
+    /// Load button mapping from config. Returns what was wrong with it:
+    /// unknown button names are skipped, and an action left with no
+    /// buttons keeps its default.
+    pub fn load_button_config(&mut self, cfg: &GamepadConfig) -> Vec<String> {
+        let mut problems = vec![];
         let map = &mut self.action_map;
-        let hl = parse_list(&cfg.hack_left);
-        if !hl.is_empty() { map.hack_left = hl; }
-        let hr = parse_list(&cfg.hack_right);
-        if !hr.is_empty() { map.hack_right = hr; }
-        let cf = parse_list(&cfg.confirm);
-        if !cf.is_empty() { map.confirm = cf; }
-        let ca = parse_list(&cfg.cancel);
-        if !ca.is_empty() { map.cancel = ca; }
-        let rs = parse_list(&cfg.restart);
-        if !rs.is_empty() { map.restart = rs; }
+        for (action, names, buttons) in [
+            ("hack_left", &cfg.hack_left, &mut map.hack_left),
+            ("hack_right", &cfg.hack_right, &mut map.hack_right),
+            ("confirm", &cfg.confirm, &mut map.confirm),
+            ("cancel", &cfg.cancel, &mut map.cancel),
+            ("restart", &cfg.restart, &mut map.restart),
+        ] {
+            let mut parsed = vec![];
+            for name in names {
+                match Btn::from_name(name) {
+                    Some(b) => parsed.push(b),
+                    None => problems.push(format!("gamepad.{action}: unknown button \"{name}\", ignored")),
+                }
+            }
+            if !parsed.is_empty() {
+                *buttons = parsed;
+            } else if !names.is_empty() {
+                problems.push(format!("gamepad.{action}: no usable buttons, keeping the default"));
+            }
+        }
+        problems
     }
 
     pub fn update(&mut self) {
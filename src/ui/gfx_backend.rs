@@ -0,0 +1,541 @@
+//! Image-based map rendering over the kitty graphics protocol or sixel
+//! (`render_mode = "graphics"`).
+//!
+//! The text renderer still owns the screen: HUD, message bar, overlays and
+//! every non-playing phase are drawn as cells. While playing, the map area
+//! is left blank in the cell buffer and this backend paints the camera
+//! viewport on top of it as one bitmap built from 8×8 sprites.
+//!
+//! The image is only re-sent when its pixels (or placement) change, which
+//! with cell-based movement is a few times per second at most.
+
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::io::{self, Write};
+
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use crossterm::{cursor::MoveTo, queue, style::Color, terminal};
+
+use crate::domain::entity::{ActorState, Facing};
+use crate::domain::tile::Tile;
+use crate::sim::world::{Phase, WorldState};
+use crate::ui::halfblock;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Protocol {
+    Kitty,
+    Sixel,
+}
+
+/// Guess the terminal's graphics support from the environment.
+///
+/// Querying the terminal (DA1 / kitty `a=q`) would be more reliable, but the
+/// reply arrives on stdin and would race the input loop, so we stick to the
+/// variables the common emulators set.
+pub fn detect() -> Option<Protocol> {
+    let var = |k: &str| std::env::var(k).unwrap_or_default();
+    let term = var("TERM");
+    let program = var("TERM_PROGRAM");
+
+    if std::env::var_os("KITTY_WINDOW_ID").is_some()
+        || term.contains("kitty")
+        || term.contains("ghostty")
+        || matches!(program.as_str(), "WezTerm" | "ghostty")
+    {
+        return Some(Protocol::Kitty);
+    }
+    if term.contains("sixel")
+        || term.starts_with("foot")
+        || term.starts_with("mlterm")
+        || matches!(program.as_str(), "mintty" | "iTerm.app")
+    {
+        return Some(Protocol::Sixel);
+    }
+    None
+}
+
+// ── Sprites (8×8, one byte per pixel, '.' = transparent) ──
+
+const SPRITE: usize = 8;
+type Bitmap = [&'static str; SPRITE];
+
+const BRICK: Bitmap = [
+    "bbbmbbbb",
+    "bbbmbbbb",
+    "bbbmbbbb",
+    "mmmmmmmm",
+    "bbbbbbbm",
+    "bbbbbbbm",
+    "bbbbbbbm",
+    "mmmmmmmm",
+];
+
+const CONCRETE: Bitmap = [
+    "hhhhhhhs",
+    "hggggggs",
+    "hggggggs",
+    "hggggggs",
+    "hggggggs",
+    "hggggggs",
+    "hggggggs",
+    "ssssssss",
+];
+
+const LADDER: Bitmap = [
+    "l......l",
+    "llllllll",
+    "l......l",
+    "l......l",
+    "l......l",
+    "llllllll",
+    "l......l",
+    "l......l",
+];
+
+const HIDDEN_LADDER: Bitmap = [
+    "t......t",
+    "tttttttt",
+    "t......t",
+    "t......t",
+    "t......t",
+    "tttttttt",
+    "t......t",
+    "t......t",
+];
+
+const ROPE: Bitmap = [
+    "........",
+    "pppppppp",
+    "........",
+    "........",
+    "........",
+    "........",
+    "........",
+    "........",
+];
+
+const GOLD: Bitmap = [
+    "........",
+    "........",
+    "........",
+    "..yyyy..",
+    ".yYYYYy.",
+    ".yYyyYy.",
+    ".yYYYYy.",
+    "..yyyy..",
+];
+
+const LASER: Bitmap = [
+    "........",
+    "........",
+    "qqqqqqqq",
+    "zzzzzzzz",
+    "zzzzzzzz",
+    "qqqqqqqq",
+    "........",
+    "........",
+];
+
+const LANTERN: Bitmap = [
+    "...nn...",
+    "..n..n..",
+    "..nnnn..",
+    "..nood..",
+    "..nood..",
+    "..nood..",
+    "..nnnn..",
+    "........",
+];
+
+const SPAWNER: Bitmap = [
+    "..DDDD..",
+    ".D....D.",
+    "D..RR..D",
+    "D.R..R.D",
+    "D.R..R.D",
+    "D..RR..D",
+    ".D....D.",
+    "..DDDD..",
+];
+
+const DOOR: Bitmap = [
+    "dddddddd",
+    "dk....kd",
+    "dk....kd",
+    "dk....kd",
+    "dk..d.kd",
+    "dk....kd",
+    "dk....kd",
+    "dk....kd",
+];
+
+/// Facing right; mirrored for left.
+const PLAYER: Bitmap = [
+    "..PPPP..",
+    "..PPPw..",
+    "..BBBB..",
+    ".B.BB.B.",
+    "B..BB..B",
+    "...LL...",
+    "..L..L..",
+    ".L....L.",
+];
+
+/// Facing right; mirrored for left. Body 'D' turns gold when carrying.
+const GUARD: Bitmap = [
+    "..RRRR..",
+    "..RRRw..",
+    ".DDDDDD.",
+    "D.DDDD.D",
+    "..DDDD..",
+    "..D..D..",
+    "..D..D..",
+    ".DD..DD.",
+];
+
+const BG: [u8; 3] = [22, 22, 35];  // same as the cell renderer's BASE_BG
+
+fn palette(ch: u8) -> Option<[u8; 3]> {
+    Some(match ch {
+        b'b' => [180, 120, 60],   // brick
+        b'm' => [100, 65, 30],    // mortar
+        b'h' => [160, 160, 160],  // concrete highlight
+        b'g' => [120, 120, 120],  // concrete face
+        b's' => [70, 70, 70],     // concrete shadow
+        b'l' => [100, 200, 255],  // ladder
+        b't' => [0, 180, 180],    // escape ladder
+        b'p' => [180, 100, 200],  // rope
+        b'y' => [200, 150, 20],   // token rim
+        b'Y' => [255, 220, 60],   // token face
+        b'c' => [80, 220, 255],   // 250 token face
+        b'v' => [230, 110, 255],  // 500 token face
+        b'z' => [255, 60, 60],    // laser beam
+        b'q' => [90, 10, 10],     // laser glow
+        b'o' => [255, 170, 60],   // lantern flame
+        b'n' => [120, 80, 30],    // lantern frame
+        b'f' => [60, 45, 0],      // refilling pit
+        b'd' => [60, 200, 160],   // door frame
+        b'k' => [10, 50, 40],     // door shadow
+        b'P' => [80, 220, 80],    // player head
+        b'B' => [40, 140, 200],   // player body
+        b'L' => [30, 90, 140],    // player legs
+        b'R' => [255, 80, 80],    // guard head
+        b'D' => [160, 30, 30],    // guard body
+        b'w' => [255, 255, 255],  // eye
+        _ => return None,
+    })
+}
+
+// ── Image ──
+
+struct Image {
+    w: usize,
+    h: usize,
+    px: Vec<[u8; 3]>,
+}
+
+impl Image {
+    fn new(w: usize, h: usize) -> Self {
+        Image { w, h, px: vec![BG; w * h] }
+    }
+
+    fn fill(&mut self, x0: usize, y0: usize, w: usize, h: usize, c: [u8; 3]) {
+        for y in y0..(y0 + h).min(self.h) {
+            for x in x0..(x0 + w).min(self.w) {
+                self.px[y * self.w + x] = c;
+            }
+        }
+    }
+
+    /// Scale an 8×8 bitmap (nearest neighbour) into the `cw`×`ch` box at
+    /// (x0, y0). Rows above `first_row` are skipped (dig animation).
+    #[allow(clippy::too_many_arguments)]
+    fn blit(&mut self, x0: usize, y0: usize, cw: usize, ch: usize,
+            bmp: &Bitmap, mirror: bool, first_row: usize, swap: Option<(u8, u8)>) {
+        for py in 0..ch {
+            let sy = py * SPRITE / ch;
+            if sy < first_row { continue; }
+            let row = bmp[sy].as_bytes();
+            for px in 0..cw {
+                let sx = px * SPRITE / cw;
+                let mut b = row[if mirror { SPRITE - 1 - sx } else { sx }];
+                if let Some((from, to)) = swap {
+                    if b == from { b = to; }
+                }
+                let (x, y) = (x0 + px, y0 + py);
+                if x >= self.w || y >= self.h { continue; }
+                if let Some(c) = palette(b) {
+                    self.px[y * self.w + x] = c;
+                }
+            }
+        }
+    }
+
+    /// Fade the box at (x0, y0) toward the background, keeping `light`
+    /// (0..=1) of each pixel's distance from it.
+    fn dim(&mut self, x0: usize, y0: usize, w: usize, h: usize, light: f32) {
+        for y in y0..(y0 + h).min(self.h) {
+            for x in x0..(x0 + w).min(self.w) {
+                let p = &mut self.px[y * self.w + x];
+                for (v, base) in p.iter_mut().zip(BG) {
+                    *v = (base as f32 + (*v as f32 - base as f32) * light).round() as u8;
+                }
+            }
+        }
+    }
+
+    fn digest(&self) -> u64 {
+        let mut h = DefaultHasher::new();
+        self.w.hash(&mut h);
+        self.h.hash(&mut h);
+        self.px.hash(&mut h);
+        h.finish()
+    }
+}
+
+/// Draw the camera viewport at `cw`×`ch` image pixels per game cell.
+fn rasterize(w: &WorldState, cw: usize, ch: usize) -> Image {
+    let cam = &w.camera;
+    let mut img = Image::new(cam.view_w * cw, cam.view_h * ch);
+
+    for vy in 0..cam.view_h {
+        for vx in 0..cam.view_w {
+            let wx = cam.x + vx as i32;
+            let wy = cam.y + vy as i32;
+            if wx < 0 || wy < 0 || wx >= w.width as i32 || wy >= w.height as i32 { continue; }
+            let (gx, gy) = (wx as usize, wy as usize);
+            let (x0, y0) = (vx * cw, vy * ch);
+
+            if let Some(dig) = w.digs.iter().find(|d| d.x == gx && d.y == gy) {
+                // Brick erodes from the top, two sprite rows per stage
+                img.blit(x0, y0, cw, ch, &BRICK, false, dig.stage() as usize * 2, None);
+                continue;
+            }
+            if let Some(hole) = w.holes.iter().find(|h| h.x == gx && h.y == gy) {
+                let pit = match halfblock::pit_color(hole.open_progress(w.speed.hole_open_ticks)) {
+                    Color::Rgb { r, g, b } => [r, g, b],
+                    _ => BG,
+                };
+                img.fill(x0, y0, cw, ch, pit);
+                if hole.is_closing() {
+                    let pct = hole.close_progress(w.speed.hole_close_ticks);
+                    let filled = ((ch as f32) * pct).ceil() as usize;
+                    img.fill(x0, y0 + ch - filled.min(ch), cw, filled, palette(b'f').unwrap_or(BG));
+                }
+                continue;
+            }
+
+            let bmp = match w.tiles[(gx, gy)] {
+                Tile::Empty => None,
+                Tile::Brick | Tile::TrapBrick | Tile::FakeBrick => Some(&BRICK),
+                Tile::Concrete => Some(&CONCRETE),
+                Tile::Ladder => Some(&LADDER),
+                Tile::HiddenLadder => Some(&HIDDEN_LADDER),
+                Tile::Rope => Some(&ROPE),
+                Tile::Gold(_) => Some(&GOLD),
+                Tile::Laser => Some(&LASER),
+                Tile::Spawner => Some(&SPAWNER),
+                Tile::Lantern => Some(&LANTERN),
+                Tile::Door => Some(&DOOR),
+                t @ Tile::Custom(_) => {
+                    if let Some(def) = t.custom_def() {
+                        let (r, g, b) = def.color;
+                        let h = if def.solid { ch } else { ch / 2 };
+                        img.fill(x0, y0, cw, h, [r, g, b]);
+                    }
+                    None
+                }
+            };
+            if let Some(bmp) = bmp {
+                // The richer tokens have faces of their own colour
+                let swap = match w.tiles[(gx, gy)] {
+                    Tile::Gold(1) => Some((b'Y', b'c')),
+                    Tile::Gold(2) => Some((b'Y', b'v')),
+                    _ => None,
+                };
+                img.blit(x0, y0, cw, ch, bmp, false, 0, swap);
+            }
+        }
+    }
+
+    // Actors on top: guards first so the player stays visible on contact
+    let mut actor = |x: usize, y: usize, bmp: &Bitmap, facing: Facing, swap| {
+        let vx = x as i32 - cam.x;
+        let vy = y as i32 - cam.y;
+        if vx < 0 || vy < 0 || vx >= cam.view_w as i32 || vy >= cam.view_h as i32 { return; }
+        img.blit(vx as usize * cw, vy as usize * ch, cw, ch, bmp,
+                 facing == Facing::Left, 0, swap);
+    };
+    for g in &w.guards {
+        if g.state == ActorState::Dead || !w.guard_seen(g) { continue; }
+        let swap = if g.carry_gold { Some((b'D', b'Y')) } else { None };
+        actor(g.x, g.y, &GUARD, g.facing, swap);
+    }
+    if w.player.alive {
+        actor(w.player.x, w.player.y, &PLAYER, w.player.facing, None);
+    }
+
+    // Darkness mode: everything fades with its distance from the player
+    if w.light_radius.is_some() && w.phase == Phase::Playing {
+        for vy in 0..cam.view_h {
+            for vx in 0..cam.view_w {
+                let (wx, wy) = (cam.x + vx as i32, cam.y + vy as i32);
+                let light = if wx < 0 || wy < 0 { 0.0 } else { w.light_at(wx as usize, wy as usize) };
+                if light < 1.0 { img.dim(vx * cw, vy * ch, cw, ch, light); }
+            }
+        }
+    }
+
+    img
+}
+
+// ── Encoders ──
+
+/// Kitty: transmit raw RGB and place it in one command, chunked at 4 KiB.
+/// `cols`×`rows` makes the terminal scale the image to the map area, so we
+/// can send it at sprite resolution regardless of font size.
+fn write_kitty(out: &mut impl Write, img: &Image, cols: usize, rows: usize) -> io::Result<()> {
+    let raw: Vec<u8> = img.px.iter().flatten().copied().collect();
+    let data = STANDARD.encode(&raw);
+    let chunks: Vec<&[u8]> = data.as_bytes().chunks(4096).collect();
+    for (i, chunk) in chunks.iter().enumerate() {
+        let more = u8::from(i + 1 < chunks.len());
+        if i == 0 {
+            write!(out, "\x1b_Ga=T,f=24,s={},v={},c={},r={},i=1,p=1,q=2,C=1,m={};",
+                   img.w, img.h, cols, rows, more)?;
+        } else {
+            write!(out, "\x1b_Gm={};", more)?;
+        }
+        out.write_all(chunk)?;
+        out.write_all(b"\x1b\\")?;
+    }
+    Ok(())
+}
+
+fn kitty_delete(out: &mut impl Write) -> io::Result<()> {
+    out.write_all(b"\x1b_Ga=d,d=I,i=1,q=2\x1b\\")
+}
+
+/// Sixel: palette of the (few) distinct colours, then 6-pixel bands with
+/// run-length encoding.
+fn write_sixel(out: &mut impl Write, img: &Image) -> io::Result<()> {
+    let mut colors: Vec<[u8; 3]> = vec![];
+    let idx: Vec<usize> = img.px.iter().map(|c| {
+        colors.iter().position(|k| k == c).unwrap_or_else(|| {
+            colors.push(*c);
+            colors.len() - 1
+        })
+    }).collect();
+
+    write!(out, "\x1bP0;1;0q\"1;1;{};{}", img.w, img.h)?;
+    for (i, c) in colors.iter().enumerate() {
+        let pct = |v: u8| v as u32 * 100 / 255;
+        write!(out, "#{};2;{};{};{}", i, pct(c[0]), pct(c[1]), pct(c[2]))?;
+    }
+
+    for band in (0..img.h).step_by(6) {
+        for ci in 0..colors.len() {
+            let mut line = Vec::with_capacity(img.w);
+            let mut any = false;
+            for x in 0..img.w {
+                let mut bits = 0u8;
+                for dy in 0..6 {
+                    let y = band + dy;
+                    if y < img.h && idx[y * img.w + x] == ci {
+                        bits |= 1 << dy;
+                    }
+                }
+                any |= bits != 0;
+                line.push(63 + bits);
+            }
+            if !any { continue; }
+
+            write!(out, "#{}", ci)?;
+            let mut x = 0;
+            while x < line.len() {
+                let run = line[x..].iter().take_while(|&&b| b == line[x]).count();
+                if run > 3 {
+                    write!(out, "!{}", run)?;
+                    out.write_all(&[line[x]])?;
+                } else {
+                    out.write_all(&line[x..x + run])?;
+                }
+                x += run;
+            }
+            out.write_all(b"$")?;
+        }
+        out.write_all(b"-")?;
+    }
+    out.write_all(b"\x1b\\")
+}
+
+// ── Backend ──
+
+pub struct GfxBackend {
+    protocol: Protocol,
+    last: Option<u64>,  // digest of the image currently on screen
+}
+
+impl GfxBackend {
+    pub fn new(protocol: Protocol) -> Self {
+        GfxBackend { protocol, last: None }
+    }
+
+    /// Forget what's on screen (after a clear or resize) so the next
+    /// `draw` re-sends the image.
+    pub fn invalidate(&mut self) {
+        self.last = None;
+    }
+
+    /// Paint the camera viewport with its top-left at terminal (col, row).
+    /// `cell_w` is the number of terminal columns per game cell.
+    pub fn draw(&mut self, out: &mut impl Write, w: &WorldState,
+                col: u16, row: u16, cell_w: usize) -> io::Result<()> {
+        let cam = &w.camera;
+        let (cols, rows) = (cam.view_w * cell_w, cam.view_h);
+        if cols == 0 || rows == 0 { return Ok(()); }
+
+        let img = match self.protocol {
+            Protocol::Kitty => rasterize(w, SPRITE, SPRITE),
+            Protocol::Sixel => {
+                // Sixel has no scaling: render at the real pixel size of the
+                // map area, falling back to a typical 8×16 font cell.
+                let (fw, fh) = match terminal::window_size() {
+                    Ok(s) if s.width > 0 && s.columns > 0 && s.rows > 0 => {
+                        ((s.width / s.columns) as usize, (s.height / s.rows) as usize)
+                    }
+                    _ => (8, 16),
+                };
+                rasterize(w, fw * cell_w, fh)
+            }
+        };
+
+        let mut h = DefaultHasher::new();
+        (img.digest(), col, row, cols, rows).hash(&mut h);
+        let digest = h.finish();
+        if self.last == Some(digest) { return Ok(()); }
+
+        queue!(out, MoveTo(col, row))?;
+        match self.protocol {
+            Protocol::Kitty => write_kitty(out, &img, cols, rows)?,
+            Protocol::Sixel => write_sixel(out, &img)?,
+        }
+        self.last = Some(digest);
+        out.flush()
+    }
+
+    /// Remove the image. Returns true if the cells underneath need a
+    /// repaint (sixel pixels stay until overwritten).
+    pub fn hide(&mut self, out: &mut impl Write) -> io::Result<bool> {
+        if self.last.take().is_none() { return Ok(false); }
+        match self.protocol {
+            Protocol::Kitty => {
+                kitty_delete(out)?;
+                out.flush()?;
+                Ok(false)
+            }
+            Protocol::Sixel => Ok(true),
+        }
+    }
+}
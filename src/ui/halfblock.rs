@@ -0,0 +1,233 @@
+//! Half-block pixel art for the map (`render_mode = "halfblock"`).
+//!
+//! A game cell is still 2 terminal columns × 1 row, but every column is
+//! drawn as `▀` with fg = top pixel and bg = bottom pixel. That gives a
+//! 2×2 grid of roughly square pseudo-pixels per cell — twice the vertical
+//! resolution of the glyph renderer — and replaces emoji with flat-colour
+//! sprites.
+//!
+//! Everything here is pure: the renderer asks for pixels and owns the
+//! frame buffer. `Color::Reset` means "map background".
+
+use crossterm::style::Color;
+
+use crate::domain::entity::{ActorState, Facing, Guard};
+use crate::domain::tile::Tile;
+use crate::sim::world::WorldState;
+
+/// Opaque 2×2 pixels, `[row][col]`.
+pub type Pixels = [[Color; 2]; 2];
+
+/// Actor sprite, `[row][col]`. `None` lets the terrain show through.
+pub type Sprite = [[Option<Color>; 2]; 2];
+
+const BG: Color = Color::Reset;
+
+// ── Terrain ──
+
+/// Tile pixels only (no digs, holes, or actors).
+/// `reveal_hidden` draws the escape ladder before it has appeared.
+pub fn tile(t: Tile, gx: usize, gy: usize, reveal_hidden: bool) -> Pixels {
+    let brick = Color::Rgb{r:180,g:120,b:60};
+    let mortar = Color::Rgb{r:100,g:65,b:30};
+    match t {
+        Tile::Empty => [[BG, BG], [BG, BG]],
+        Tile::Brick | Tile::TrapBrick | Tile::FakeBrick => {
+            // Stagger the mortar so walls read as a brick pattern
+            if (gx + gy).is_multiple_of(2) {
+                [[brick, brick], [brick, mortar]]
+            } else {
+                [[brick, brick], [mortar, brick]]
+            }
+        }
+        Tile::Concrete => {
+            let hi = Color::Rgb{r:140,g:140,b:140};
+            let lo = Color::Rgb{r:90,g:90,b:90};
+            [[hi, hi], [lo, lo]]
+        }
+        Tile::Ladder => {
+            let rung = Color::Rgb{r:100,g:200,b:255};
+            let rail = Color::Rgb{r:50,g:110,b:150};
+            [[rung, rung], [rail, rail]]
+        }
+        Tile::HiddenLadder if reveal_hidden => {
+            let rung = Color::Rgb{r:0,g:180,b:180};
+            let rail = Color::Rgb{r:0,g:90,b:90};
+            [[rung, rung], [rail, rail]]
+        }
+        Tile::HiddenLadder => [[BG, BG], [BG, BG]],
+        Tile::Rope => {
+            let rope = Color::Rgb{r:180,g:100,b:200};
+            [[rope, rope], [BG, BG]]
+        }
+        Tile::Gold(_) => {
+            // Token sits on the floor of its cell, its own colour
+            // over a darker shade of it
+            let (r, g, b) = t.token().map_or((255, 210, 60), |k| k.color);
+            let hi = Color::Rgb{r, g, b};
+            let lo = Color::Rgb{r: r / 4 * 3, g: g / 4 * 3, b: b / 4 * 3};
+            [[BG, BG], [hi, lo]]
+        }
+        Tile::Laser => {
+            // A beam across the middle of the cell
+            let beam = Color::Rgb{r:255,g:60,b:60};
+            let glow = Color::Rgb{r:90,g:10,b:10};
+            [[glow, glow], [beam, beam]]
+        }
+        Tile::Lantern => {
+            // A flame in a frame, on the floor of its cell
+            let flame = Color::Rgb{r:255,g:170,b:60};
+            let frame = Color::Rgb{r:120,g:80,b:30};
+            [[BG, BG], [flame, frame]]
+        }
+        Tile::Spawner => {
+            // Sentinel colours, in a checker
+            let rim = Color::Rgb{r:160,g:30,b:30};
+            let core = Color::Rgb{r:255,g:80,b:80};
+            [[rim, core], [core, rim]]
+        }
+        Tile::Door => {
+            let frame = Color::Rgb{r:60,g:200,b:160};
+            let dark = Color::Rgb{r:10,g:50,b:40};
+            [[frame, frame], [dark, dark]]
+        }
+        // Solid custom tiles fill the cell, the rest sit in its top half
+        Tile::Custom(_) => match custom_colors(t) {
+            Some((fg, bg)) if t.is_solid() => [[fg, fg], [bg, bg]],
+            Some((fg, _)) => [[fg, fg], [BG, BG]],
+            None => [[BG, BG], [BG, BG]],
+        },
+    }
+}
+
+/// Foreground and background of a custom tile: its colour, on a dimmed
+/// copy of it if solid. `None` for built-in tiles.
+pub fn custom_colors(t: Tile) -> Option<(Color, Color)> {
+    let def = t.custom_def()?;
+    let (r, g, b) = def.color;
+    let bg = if def.solid { Color::Rgb{r: r / 3, g: g / 3, b: b / 3} } else { BG };
+    Some((Color::Rgb{r, g, b}, bg))
+}
+
+/// Dig animation, open/closing hole, or tile — the half-block equivalent
+/// of `Renderer::compose_terrain`.
+pub fn terrain(w: &WorldState, gx: usize, gy: usize, reveal_hidden: bool) -> Pixels {
+    if let Some(dig) = w.digs.iter().find(|d| d.x == gx && d.y == gy) {
+        // Brick crumbles from the top down
+        let brick = Color::Rgb{r:150,g:100,b:40};
+        let crumb = Color::Rgb{r:90,g:60,b:20};
+        return match dig.stage() {
+            0 => [[brick, brick], [brick, brick]],
+            1 => [[crumb, brick], [brick, brick]],
+            2 => [[BG, BG], [brick, crumb]],
+            _ => [[BG, BG], [crumb, BG]],
+        };
+    }
+
+    if let Some(hole) = w.holes.iter().find(|h| h.x == gx && h.y == gy) {
+        if !hole.is_closing() {
+            let pit = pit_color(hole.open_progress(w.speed.hole_open_ticks));
+            return [[pit, pit], [pit, pit]];
+        }
+        // Fills from the bottom up
+        let pit = pit_color(1.0);
+        let fill = Color::Rgb{r:60,g:45,b:0};
+        return if hole.close_progress(w.speed.hole_close_ticks) < 0.5 {
+            [[pit, pit], [fill, fill]]
+        } else {
+            [[fill, fill], [fill, fill]]
+        };
+    }
+
+    if w.hub_door_cleared(gx, gy) {
+        let frame = Color::Rgb{r:255,g:210,b:60};
+        let dark = Color::Rgb{r:60,g:45,b:0};
+        return [[frame, frame], [dark, dark]];
+    }
+
+    tile(w.tiles[(gx, gy)], gx, gy, reveal_hidden)
+}
+
+// ── Actors ──
+
+/// Head on top, body below. The pixel on the facing side is lighter,
+/// so direction is visible even at 2×2.
+fn figure(facing: Facing, head: Color, face: Color, body: Color) -> Sprite {
+    let top = match facing {
+        Facing::Left  => [Some(face), Some(head)],
+        Facing::Right => [Some(head), Some(face)],
+    };
+    [top, [Some(body), Some(body)]]
+}
+
+pub fn player(facing: Facing) -> Sprite {
+    figure(
+        facing,
+        Color::Rgb{r:80,g:220,b:80},
+        Color::Rgb{r:200,g:255,b:200},
+        Color::Rgb{r:40,g:140,b:200},
+    )
+}
+
+pub fn guard(g: &Guard, escape_ticks: u32) -> Sprite {
+    let body = if g.state == ActorState::InHole {
+        urgency_color(g.escape_progress(escape_ticks))
+    } else if g.carry_gold {
+        Color::Rgb{r:255,g:200,b:40}
+    } else {
+        Color::Rgb{r:160,g:30,b:30}
+    };
+    let mut s = figure(
+        g.facing,
+        Color::Rgb{r:255,g:80,b:80},
+        Color::Rgb{r:255,g:190,b:190},
+        body,
+    );
+    if g.state == ActorState::InHole {
+        // Sunk into the pit: only the head pokes out, tinted by escape time
+        s = [[None, None], [Some(body), Some(body)]];
+    }
+    s
+}
+
+/// Actor occupying (gx, gy), if any. Player wins over guards, matching
+/// the glyph renderer.
+pub fn actor_at(w: &WorldState, gx: usize, gy: usize, include_player: bool) -> Option<Sprite> {
+    if include_player && w.player.alive && w.player.x == gx && w.player.y == gy {
+        return Some(player(w.player.facing));
+    }
+    w.occupancy.first(gx, gy)
+        .and_then(|i| w.guards.get(i))
+        .filter(|g| w.guard_seen(g))
+        .map(|g| guard(g, w.speed.trap_escape_ticks))
+}
+
+/// Dark green (plenty of time) → amber → red (imminent) for progress 0..1.
+pub fn urgency_color(p: f32) -> Color {
+    let p = p.clamp(0.0, 1.0);
+    let (r, g) = if p < 0.5 {
+        (p * 2.0 * 160.0, 110.0)
+    } else {
+        (160.0, (1.0 - p) * 2.0 * 110.0)
+    };
+    Color::Rgb{r: r as u8, g: g as u8, b: 0}
+}
+
+/// Open-pit colour: near black when freshly dug, warming to a deep ember
+/// as the hole approaches its closing phase.
+pub fn pit_color(open_progress: f32) -> Color {
+    let p = open_progress.clamp(0.0, 1.0);
+    Color::Rgb{r: (10.0 + 90.0 * p) as u8, g: (8.0 + 22.0 * p) as u8, b: 0}
+}
+
+/// Draw `sprite` over `base`, keeping base pixels where the sprite is transparent.
+pub fn overlay(mut base: Pixels, sprite: &Sprite) -> Pixels {
+    for (row, srow) in base.iter_mut().zip(sprite) {
+        for (px, s) in row.iter_mut().zip(srow) {
+            if let Some(c) = s {
+                *px = *c;
+            }
+        }
+    }
+    base
+}
@@ -8,16 +8,21 @@
 /// Uses crossterm's keyboard enhancement for Release events when available.
 /// Falls back to timeout-based release detection on terminals that don't support it.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::time::{Duration, Instant};
 
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, poll};
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+
+use crate::ui::backend::{Crossterm, EventSource};
 
 /// After this duration without a Press/Repeat event, consider the key released.
 /// Only used when the terminal doesn't report Release events.
 const HOLD_TIMEOUT: Duration = Duration::from_millis(160);
 
 pub struct InputState {
+    /// Where events come from (the terminal, unless `with_source`).
+    source: Box<dyn EventSource>,
+
     /// Timestamp of last Press/Repeat event for each key.
     last_active: HashMap<KeyCode, Instant>,
 
@@ -25,20 +30,40 @@ pub struct InputState {
     /// most recent drain_events() call. Used for edge-triggered actions (dig).
     fresh_presses: Vec<KeyCode>,
 
+    /// Keys whose latest Press/Repeat came with Shift held (camera peek).
+    shifted: HashSet<KeyCode>,
+
     /// Raw key events collected during drain, for meta-key handling.
     pub raw_events: Vec<KeyEvent>,
 
+    /// The terminal lost focus during the most recent drain_events().
+    pub focus_lost: bool,
+
     /// Whether to honor Release events. Only true when keyboard
     /// enhancement is confirmed working.
     pub honor_release: bool,
 }
 
+impl Default for InputState {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl InputState {
     pub fn new() -> Self {
+        Self::with_source(Box::new(Crossterm))
+    }
+
+    /// Input read from `source` instead of the terminal (see `ui::backend`).
+    pub fn with_source(source: Box<dyn EventSource>) -> Self {
         InputState {
+            source,
             last_active: HashMap::with_capacity(16),
             fresh_presses: Vec::with_capacity(8),
+            shifted: HashSet::with_capacity(4),
             raw_events: Vec::with_capacity(8),
+            focus_lost: false,
             honor_release: false,
         }
     }
@@ -48,11 +73,12 @@ impl InputState {
     pub fn drain_events(&mut self) {
         self.fresh_presses.clear();
         self.raw_events.clear();
+        self.focus_lost = false;
 
         // Read all available events without blocking
-        while poll(Duration::ZERO).unwrap_or(false) {
-            match event::read() {
-                Ok(Event::Key(key)) => {
+        while self.source.poll(Duration::ZERO) {
+            match self.source.read() {
+                Some(Event::Key(key)) => {
                     self.raw_events.push(key);
 
                     match key.kind {
@@ -69,12 +95,22 @@ impl InputState {
                             // treat as active key input
                             let was_held = self.is_held_inner(key.code);
                             self.last_active.insert(key.code, Instant::now());
+                            if key.modifiers.contains(KeyModifiers::SHIFT) {
+                                self.shifted.insert(key.code);
+                            } else {
+                                self.shifted.remove(&key.code);
+                            }
                             if !was_held {
                                 self.fresh_presses.push(key.code);
                             }
                         }
                     }
                 }
+                Some(Event::FocusLost) => {
+                    // Releases go to the other window: nothing stays held
+                    self.focus_lost = true;
+                    self.last_active.clear();
+                }
                 _ => {}
             }
         }
@@ -82,6 +118,14 @@ impl InputState {
         // Expire keys that have timed out (fallback for terminals without Release)
         let now = Instant::now();
         self.last_active.retain(|_, t| now.duration_since(*t) < HOLD_TIMEOUT);
+        let last_active = &self.last_active;
+        self.shifted.retain(|k| last_active.contains_key(k));
+    }
+
+    /// Block until a terminal event is pending or `timeout` passes.
+    /// The event itself is left for the next `drain_events()`.
+    pub fn wait(&mut self, timeout: Duration) {
+        self.source.poll(timeout);
     }
 
     /// Is this key currently held down?
@@ -95,12 +139,22 @@ impl InputState {
         codes.iter().any(|c| self.is_held(*c))
     }
 
+    /// Is this key held, with Shift down when it was pressed?
+    pub fn is_held_shifted(&self, code: KeyCode) -> bool {
+        self.is_held(code) && self.shifted.contains(&code)
+    }
+
     /// Was this key freshly pressed this frame? (edge trigger)
     /// Used for one-shot actions (dig, confirm).
     pub fn was_pressed(&self, code: KeyCode) -> bool {
         self.fresh_presses.contains(&code)
     }
 
+    /// Was any key pressed this frame? (not a repeat or release)
+    pub fn any_key_pressed(&self) -> bool {
+        self.raw_events.iter().any(|k| k.kind == KeyEventKind::Press)
+    }
+
     /// Convenience: was any of these keys freshly pressed?
     pub fn any_pressed(&self, codes: &[KeyCode]) -> bool {
         codes.iter().any(|c| self.was_pressed(*c))
@@ -108,15 +162,23 @@ impl InputState {
 
     /// Check if any raw event this frame has Ctrl+C
     pub fn ctrl_c_pressed(&self) -> bool {
-        use crossterm::event::KeyModifiers;
+        self.ctrl_pressed('c')
+    }
+
+    /// Check if any raw event this frame has Ctrl+Z (suspend)
+    pub fn ctrl_z_pressed(&self) -> bool {
+        self.ctrl_pressed('z')
+    }
+
+    // ── Internal ──
+
+    fn ctrl_pressed(&self, c: char) -> bool {
         self.raw_events.iter().any(|k| {
             k.modifiers.contains(KeyModifiers::CONTROL)
-                && (k.code == KeyCode::Char('c') || k.code == KeyCode::Char('C'))
+                && (k.code == KeyCode::Char(c) || k.code == KeyCode::Char(c.to_ascii_uppercase()))
         })
     }
 
-    // ── Internal ──
-
     fn is_held_inner(&self, code: KeyCode) -> bool {
         self.last_active.get(&code)
             .map(|t| t.elapsed() < HOLD_TIMEOUT)
@@ -1,4 +1,12 @@
+pub mod backend;
 pub mod gamepad;
+pub mod gfx_backend;
+pub mod halfblock;
 pub mod input;
+pub mod particles;
+pub mod perf;
 pub mod renderer;
 pub mod sound;
+pub mod telnet;
+pub mod terminal;
+pub mod transition;
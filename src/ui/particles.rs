@@ -0,0 +1,137 @@
+//! Particle effects: gold sparkle, dig debris, landing dust.
+//!
+//! Purely cosmetic. Particles are spawned from the `GameEvent`s a step
+//! returns, live in world coordinates for a fraction of a second, and are
+//! composited by the renderer after the map. The simulation never sees them.
+
+use std::time::Instant;
+
+use crossterm::style::Color;
+
+use crate::sim::event::GameEvent;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Kind {
+    Sparkle,
+    Debris,
+    Dust,
+}
+
+struct Particle {
+    kind: Kind,
+    x: f32,       // world cells (fractional)
+    y: f32,
+    vx: f32,      // cells / second
+    vy: f32,
+    age: f32,     // seconds
+    life: f32,
+}
+
+pub struct Particles {
+    list: Vec<Particle>,
+    last_update: Instant,
+    seed: u32,
+}
+
+/// Hard cap so a burst of events can't grow the list without bound.
+const MAX_PARTICLES: usize = 256;
+
+impl Default for Particles {
+    fn default() -> Self {
+        Particles { list: vec![], last_update: Instant::now(), seed: 0x9e37_79b9 }
+    }
+}
+
+impl Particles {
+    /// xorshift32 → [0, 1)
+    fn rand(&mut self) -> f32 {
+        let mut s = self.seed;
+        s ^= s << 13;
+        s ^= s >> 17;
+        s ^= s << 5;
+        self.seed = s;
+        (s >> 8) as f32 / (1u32 << 24) as f32
+    }
+
+    fn burst(&mut self, kind: Kind, x: usize, y: usize, n: usize) {
+        for _ in 0..n {
+            if self.list.len() >= MAX_PARTICLES { return; }
+            let (r1, r2, r3) = (self.rand(), self.rand(), self.rand());
+            let (vx, vy, life) = match kind {
+                // Rise and drift outwards
+                Kind::Sparkle => ((r1 - 0.5) * 4.0, -1.5 - r2 * 2.0, 0.4 + r3 * 0.3),
+                // Kicked up and sideways, then fall under gravity
+                Kind::Debris => ((r1 - 0.5) * 6.0, -2.0 - r2 * 2.0, 0.35 + r3 * 0.25),
+                // Low puff along the floor
+                Kind::Dust => ((r1 - 0.5) * 5.0, -0.3 - r2 * 0.5, 0.25 + r3 * 0.2),
+            };
+            self.list.push(Particle {
+                kind,
+                x: x as f32 + 0.5,
+                y: y as f32 + if kind == Kind::Dust { 0.4 } else { 0.0 },
+                vx, vy,
+                age: 0.0,
+                life,
+            });
+        }
+    }
+
+    /// Spawn particles for the events of one step.
+    pub fn spawn(&mut self, events: &[GameEvent]) {
+        for e in events {
+            match *e {
+                GameEvent::GoldPicked { x, y } => self.burst(Kind::Sparkle, x, y, 6),
+                GameEvent::HoleCreated { x, y }
+                | GameEvent::TrapCollapsed { x, y } => self.burst(Kind::Debris, x, y, 5),
+                GameEvent::PlayerLanded { x, y } => self.burst(Kind::Dust, x, y, 4),
+                // Old room's particles would hang over the new map
+                GameEvent::RoomEntered { .. } => self.list.clear(),
+                _ => {}
+            }
+        }
+    }
+
+    /// Advance by wall-clock time since the last call and drop dead particles.
+    pub fn update(&mut self) {
+        let now = Instant::now();
+        // Clamp so a long stall (pause, resize) doesn't teleport everything
+        let dt = now.duration_since(self.last_update).as_secs_f32().min(0.1);
+        self.last_update = now;
+
+        for p in &mut self.list {
+            p.age += dt;
+            p.x += p.vx * dt;
+            p.y += p.vy * dt;
+            if p.kind == Kind::Debris {
+                p.vy += 18.0 * dt;
+            }
+        }
+        self.list.retain(|p| p.age < p.life);
+    }
+
+    pub fn clear(&mut self) {
+        self.list.clear();
+    }
+
+    /// Live particles as (world x, world y, glyph, colour), fading with age.
+    pub fn iter(&self) -> impl Iterator<Item = (f32, f32, char, Color)> + '_ {
+        self.list.iter().map(|p| {
+            let t = p.age / p.life;
+            let (ch, color) = match p.kind {
+                Kind::Sparkle => (
+                    if t < 0.4 { '*' } else if t < 0.75 { '+' } else { '·' },
+                    Color::Rgb{r:255,g:230,b:(100.0 + 155.0 * t) as u8},
+                ),
+                Kind::Debris => (
+                    if t < 0.5 { '▘' } else { '·' },
+                    Color::Rgb{r:180,g:120,b:60},
+                ),
+                Kind::Dust => (
+                    if t < 0.5 { '░' } else { '·' },
+                    Color::Rgb{r:150,g:150,b:160},
+                ),
+            };
+            (p.x, p.y, ch, color)
+        })
+    }
+}
@@ -0,0 +1,71 @@
+//! Frame rate and sim step timing for the `perf_overlay` HUD readout.
+//!
+//! The game loop records every rendered frame and the wall time of every
+//! `step()` call; once a second the totals, and the slowest sections from
+//! `sim::timing`, are folded into a readout the renderer draws in the HUD
+//! corner.
+
+use std::time::{Duration, Instant};
+
+use crate::sim::timing::{self, Section};
+
+/// Averages over the last complete one-second window.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PerfReadout {
+    pub fps: f32,
+    /// Mean `step()` duration, or `None` if the sim didn't run (menus, pause).
+    pub step_avg: Option<Duration>,
+    /// Timed sections of the step and the renderer, slowest first.
+    pub sections: Vec<Section>,
+}
+
+pub struct PerfMeter {
+    window_start: Instant,
+    frames: u32,
+    steps: u32,
+    step_total: Duration,
+    readout: Option<PerfReadout>,
+}
+
+const WINDOW: Duration = Duration::from_secs(1);
+
+impl Default for PerfMeter {
+    fn default() -> Self {
+        PerfMeter {
+            window_start: Instant::now(),
+            frames: 0,
+            steps: 0,
+            step_total: Duration::ZERO,
+            readout: None,
+        }
+    }
+}
+
+impl PerfMeter {
+    pub fn record_step(&mut self, took: Duration) {
+        self.steps += 1;
+        self.step_total += took;
+    }
+
+    /// Count one rendered frame, closing the window if a second has passed.
+    pub fn record_frame(&mut self) {
+        self.frames += 1;
+        let elapsed = self.window_start.elapsed();
+        if elapsed < WINDOW { return; }
+
+        self.readout = Some(PerfReadout {
+            fps: self.frames as f32 / elapsed.as_secs_f32(),
+            step_avg: (self.steps > 0).then(|| self.step_total / self.steps),
+            sections: timing::take(),
+        });
+        self.window_start = Instant::now();
+        self.frames = 0;
+        self.steps = 0;
+        self.step_total = Duration::ZERO;
+    }
+
+    /// Latest readout; `None` until the first window completes.
+    pub fn readout(&self) -> Option<PerfReadout> {
+        self.readout.clone()
+    }
+}
@@ -8,19 +8,42 @@
 ///   5. Swap front/back
 ///
 /// This eliminates flicker caused by full-screen redraws.
+///
+/// The commands are plain ANSI escape sequences; the `Screen` it's given
+/// (`ui::backend`) only supplies the size and takes the display over.
 
 use std::io::{self, BufWriter, Write};
+use std::time::{Duration, Instant};
 
 use crossterm::{
     cursor::{self, MoveTo},
+    event::{DisableFocusChange, EnableFocusChange},
     execute, queue,
     style::{Color, Print, ResetColor, SetBackgroundColor, SetForegroundColor},
     terminal::{self, Clear, ClearType},
 };
 
-use crate::domain::entity::{ActorState, Facing};
-use crate::domain::tile::Tile;
-use crate::sim::world::{Phase, WorldState};
+use base64::engine::general_purpose::STANDARD;
+use base64::Engine as _;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::{UnicodeWidthChar, UnicodeWidthStr};
+
+use crate::config::{BarPosition, DisplayConfig, RenderMode, Transition};
+use crate::i18n::t;
+use crate::domain::ai;
+use crate::domain::entity::{ActorState, Facing, Guard};
+use crate::domain::tile::{Tile, TokenKind, TOKENS};
+use crate::sim::credits::{self, CreditStyle};
+use crate::sim::event::{EventSink, GameEvent};
+use crate::sim::timing;
+use crate::sim::world::{ConfigReport, GameMode, PackDialog, PackPreview, PauseItem, PauseMenu, PauseSetting, Phase, RunBests, Severity, ShareDialog,
+    SlotInfo, WorldState};
+use crate::ui::backend::{Crossterm, Headless, Screen};
+use crate::ui::gfx_backend::{self, GfxBackend, Protocol};
+use crate::ui::halfblock::{self, Pixels, Sprite};
+use crate::ui::particles::Particles;
+use crate::ui::perf::PerfReadout;
+use crate::ui::transition::{self, Reveal};
 
 // ── Cell: the unit of the back-buffer ──
 
@@ -105,17 +128,18 @@ impl Cell {
         cell
     }
 
-    /// Create a wide cell from a multi-codepoint string (e.g. ZWJ emoji).
-    #[allow(dead_code)]
-    fn from_str_wide(s: &str, fg: Color, bg: Color, _bold: bool) -> Self {
+    /// Create a cell from a multi-codepoint string (a grapheme cluster such
+    /// as a ZWJ emoji or a letter with combining marks). Clusters over 16
+    /// bytes are cut at the last whole char that fits.
+    fn from_str(s: &str, fg: Color, bg: Color, wide: bool) -> Self {
         let mut cell = Self::BLANK;
-        let bytes = s.as_bytes();
-        let len = bytes.len().min(16);
-        cell.ch[..len].copy_from_slice(&bytes[..len]);
+        let mut len = s.len().min(16);
+        while !s.is_char_boundary(len) { len -= 1; }
+        cell.ch[..len].copy_from_slice(&s.as_bytes()[..len]);
         cell.ch_len = len as u8;
         cell.fg = fg;
         cell.bg = Self::norm_bg(bg);
-        cell.wide = true;
+        cell.wide = wide;
         cell
     }
 
@@ -168,59 +192,560 @@ impl FrameBuffer {
         }
     }
 
-    /// Write a string at (x, y) with given colors. Each char occupies 1 column.
+    /// Write a string at (x, y) with given colors. CJK and other wide
+    /// chars take 2 columns; zero-width chars are dropped.
     fn put_str(&mut self, x: usize, y: usize, s: &str, fg: Color, bg: Color, _bold: bool) {
+        let mut cx = x;
+        for g in s.graphemes(true) {
+            match grapheme_width(g) {
+                0 => {}
+                1 => {
+                    if cx >= self.width { break; }
+                    self.set(cx, y, Cell::from_str(g, fg, bg, false));
+                    cx += 1;
+                }
+                _ => {
+                    // A wide cluster that doesn't fit whole isn't drawn at all
+                    if cx + 1 >= self.width { break; }
+                    self.set(cx, y, Cell::from_str(g, fg, bg, true));
+                    self.set(cx + 1, y, Cell { bg: Cell::norm_bg(bg), ..Cell::WIDE_CONT });
+                    cx += 2;
+                }
+            }
+        }
+    }
+
+    /// Like `put_str`, but every char takes 2 columns: ASCII as its
+    /// fullwidth form, other narrow chars followed by a space.
+    fn put_str_wide(&mut self, x: usize, y: usize, s: &str, fg: Color, bg: Color) {
         let mut cx = x;
         for ch in s.chars() {
-            if cx >= self.width { break; }
-            self.set(cx, y, Cell::from_char(ch, fg, bg, false));
-            cx += 1;
+            if cx + 1 >= self.width { break; }
+            let full = match ch {
+                '!'..='~' => char::from_u32(ch as u32 - 0x21 + 0xFF01).unwrap_or(ch),
+                _ => ch,
+            };
+            match full.width() {
+                Some(2) => {
+                    self.set(cx, y, Cell::from_char_wide(full, fg, bg, false));
+                    self.set(cx + 1, y, Cell { bg: Cell::norm_bg(bg), ..Cell::WIDE_CONT });
+                }
+                Some(0) | None => continue,
+                Some(_) => {
+                    self.set(cx, y, Cell::from_char(full, fg, bg, false));
+                    self.set(cx + 1, y, Cell::from_char(' ', fg, bg, false));
+                }
+            }
+            cx += 2;
         }
     }
 }
 
+/// Columns one grapheme cluster takes in a cell grid: 0, 1 or 2.
+fn grapheme_width(g: &str) -> usize {
+    g.width().min(2)
+}
+
+/// Message bar colours (fg, bg) per severity.
+fn severity_colors(severity: Severity) -> (Color, Color) {
+    match severity {
+        Severity::Info => (Color::Black, Color::Rgb{r:200,g:180,b:50}),
+        Severity::Warning => (Color::White, Color::Rgb{r:170,g:40,b:40}),
+        Severity::Achievement => (Color::Black, Color::Rgb{r:80,g:210,b:120}),
+    }
+}
+
+/// Terminal columns `s` takes up, as `put_str` lays it out.
+fn text_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
+
+/// Longest prefix of `s` that fits in `max` columns, never splitting a
+/// grapheme cluster or a wide char.
+fn fit_width(s: &str, max: usize) -> String {
+    let mut out = String::new();
+    let mut w = 0;
+    for g in s.graphemes(true) {
+        w += grapheme_width(g);
+        if w > max { break; }
+        out.push_str(g);
+    }
+    out
+}
+
+/// A save slot as the pause menu names it: "Slot 3", or "Auto" for the
+/// Continue save (slot 0).
+fn slot_label(slot: u8) -> String {
+    match slot {
+        0 => t!("pause.slot_auto").to_string(),
+        n => t!("pause.slot", n = n),
+    }
+}
+
+/// `s` if it fits in `max` columns, else cut short with "...".
+fn ellipsize(s: &str, max: usize) -> String {
+    if text_width(s) <= max {
+        s.to_string()
+    } else {
+        format!("{}...", fit_width(s, max.saturating_sub(3)))
+    }
+}
+
+/// The end of `s` in at most `max` columns, for a text field whose
+/// cursor is at the end.
+fn tail(s: &str, max: usize) -> String {
+    if text_width(s) <= max { return s.to_string(); }
+    let skip = s.chars().count().saturating_sub(max.saturating_sub(1));
+    format!("…{}", s.chars().skip(skip).collect::<String>())
+}
+
+/// `s` broken at spaces into lines of at most `max` columns (a longer
+/// word is cut).
+fn wrap_words(s: &str, max: usize) -> Vec<String> {
+    let mut lines = vec![];
+    let mut line = String::new();
+    for word in s.split(' ') {
+        if !line.is_empty() && text_width(&line) + 1 + text_width(word) > max {
+            lines.push(std::mem::take(&mut line));
+        }
+        if !line.is_empty() {
+            line.push(' ');
+        }
+        line.push_str(&fit_width(word, max));
+    }
+    lines.push(line);
+    lines
+}
+
+/// `s` cut or space-padded to exactly `w` columns.
+fn pad_width(s: &str, w: usize) -> String {
+    let mut out = fit_width(s, w);
+    let used = text_width(&out);
+    out.extend(std::iter::repeat_n(' ', w - used));
+    out
+}
+
+/// `ms` as m:ss.t.
+fn clear_time(ms: u64) -> String {
+    let s = ms / 1000;
+    format!("{}:{:02}.{}", s / 60, s % 60, ms % 1000 / 100)
+}
+
+/// The node's best score and time from its leaderboard, as one line;
+/// None before its first clear.
+fn best_line(w: &WorldState) -> Option<String> {
+    let time = clear_time(*w.board.times_ms.first()?);
+    Some(match w.board.scores.first() {
+        Some(&score) => t!("intro.best", score = score, time = time),
+        None => t!("intro.best_time", time = time),
+    })
+}
+
+/// The summary of a cleared node: how long it took against par (or the
+/// best clear before this one), what happened on the way, over all its
+/// attempts, and the points it earned.
+fn clear_stats(w: &WorldState) -> Vec<String> {
+    let time_ms = w.tick * w.speed.tick_rate_ms;
+    let mut lines = vec![t!("clear.time", time = clear_time(time_ms))];
+    let diff = |target: u64| {
+        let sign = if time_ms <= target { '-' } else { '+' };
+        format!("{sign}{}", clear_time(time_ms.abs_diff(target)))
+    };
+    let best = w.board.times_ms.iter().enumerate()
+        .find(|&(i, _)| w.board.new_time != Some(i))
+        .map(|(_, &ms)| ms);
+    match (w.par_secs, best) {
+        (Some(par), _) => {
+            let par = par as u64 * 1000;
+            lines.push(t!("clear.par", time = clear_time(par), diff = diff(par)));
+        }
+        (None, Some(best)) => lines.push(t!("clear.best", time = clear_time(best), diff = diff(best))),
+        (None, None) => {}
+    }
+    let n = &w.node_run;
+    lines.push(t!("clear.hacks", n = n.hacks));
+    lines.push(t!("clear.trapped", n = n.guards_trapped));
+    lines.push(t!("clear.deaths", n = n.deaths));
+    if w.mode == GameMode::Normal {
+        lines.push(t!("clear.score", n = w.score - w.level_start_score));
+    }
+    lines
+}
+
+/// `text` centred in a double-line box `inner` columns wide (at least
+/// wide enough for the text): top, middle and bottom rows.
+fn boxed(text: &str, inner: usize) -> [String; 3] {
+    let inner = inner.max(text_width(text) + 2);
+    let pad = inner - text_width(text);
+    let left = pad / 2;
+    [
+        format!("╔{}╗", "═".repeat(inner)),
+        format!("║{}{}{}║", " ".repeat(left), text, " ".repeat(pad - left)),
+        format!("╚{}╝", "═".repeat(inner)),
+    ]
+}
+
+// ── Tween: render-side interpolation between two cells ──
+
+/// Tracks an actor's last cell-to-cell move so the renderer can draw it
+/// part-way between cells. The simulation never sees this.
+#[derive(Clone, Copy)]
+struct Tween {
+    from: (usize, usize),
+    to: (usize, usize),
+    started: Instant,
+    duration: Duration,
+}
+
+impl Tween {
+    fn snap(x: usize, y: usize, now: Instant) -> Self {
+        Tween { from: (x, y), to: (x, y), started: now, duration: Duration::ZERO }
+    }
+
+    /// Point the tween at the actor's current cell. A one-cell step
+    /// animates from the previous target; anything else (respawn, restart,
+    /// diagonal hole escape) snaps.
+    fn retarget(slot: &mut Option<Tween>, x: usize, y: usize, duration: Duration, now: Instant) {
+        match slot {
+            Some(t) if t.to == (x, y) => {}
+            Some(t) if t.to.0.abs_diff(x) + t.to.1.abs_diff(y) == 1 => {
+                *t = Tween { from: t.to, to: (x, y), started: now, duration };
+            }
+            _ => *slot = Some(Tween::snap(x, y, now)),
+        }
+    }
+
+    /// Interpolated world position at `now`.
+    fn pos(&self, now: Instant) -> (f32, f32) {
+        let t = if self.duration.is_zero() {
+            1.0
+        } else {
+            (now.duration_since(self.started).as_secs_f32() / self.duration.as_secs_f32()).min(1.0)
+        };
+        let lerp = |a: usize, b: usize| a as f32 + (b as f32 - a as f32) * t;
+        (lerp(self.from.0, self.to.0), lerp(self.from.1, self.to.1))
+    }
+}
+
+/// Width of the leaderboard strip on the intro and clear screens.
+const BOARD_W: usize = 24;
+
 // ── Renderer ──
 
 /// Total terminal columns needed = map_width * 2 (each game cell = 2 terminal cols)
 /// We use a 1:1 terminal-column buffer, so game cell (gx) maps to columns (gx*2, gx*2+1).
 const CELL_W: usize = 2;
 
-/// Vertical offsets
-const HUD_ROW: usize = 0;
-const MAP_ROW: usize = 2;
+/// Smallest playable viewport, in game cells. Below this (plus the bars)
+/// the renderer shows a "please enlarge" screen and main.rs holds the sim.
+const MIN_VIEW_W: usize = 20;
+/// Pack selector: column of the preview panel, and the least room it needs.
+const PREVIEW_COL: usize = 60;
+const PREVIEW_MIN_W: usize = 24;
+const MIN_VIEW_H: usize = 8;
+
+/// Slowest the adaptive pacing will draw: ~5 fps.
+const MAX_FRAME_INTERVAL: Duration = Duration::from_millis(200);
+
+/// Part of the terminal the frame buffer covers. The whole terminal,
+/// except in the streaming layout, where it sits inside the frame.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+struct Area {
+    x: usize,
+    y: usize,
+    w: usize,
+    h: usize,
+}
+
+/// Screen rows for the map and the bars around it. Bars placed on top
+/// stack downwards above the map, each followed by a blank row; bars on
+/// the bottom stack below it, each preceded by one. Recomputed every frame
+/// once the viewport height is known.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct Layout {
+    hud: Option<usize>,
+    map: usize,
+    message: Option<usize>,
+    help: Option<usize>,
+    status: usize,  // screen-reader line, directly after the last bottom bar
+}
+
+impl Layout {
+    const BARS: usize = 3;
+
+    /// Rows used by bars (and their blank separators), excluding the map.
+    fn reserved_rows(display: &DisplayConfig) -> usize {
+        let bars = [display.hud_position, display.message_position, display.help_position];
+        let mut rows = 2 * bars.iter().filter(|&&p| p != BarPosition::Hidden).count();
+        if display.screen_reader {
+            rows += 1;
+        }
+        rows
+    }
+
+    fn compute(display: &DisplayConfig, view_h: usize) -> Self {
+        let bars = [display.hud_position, display.message_position, display.help_position];
+        let mut rows = [None; Self::BARS];
+
+        let mut y = 0;
+        for (row, &pos) in rows.iter_mut().zip(&bars) {
+            if pos == BarPosition::Top {
+                *row = Some(y);
+                y += 2;
+            }
+        }
+        let map = y;
+        y += view_h;
+        for (row, &pos) in rows.iter_mut().zip(&bars) {
+            if pos == BarPosition::Bottom {
+                *row = Some(y + 1);
+                y += 2;
+            }
+        }
+
+        Layout { hud: rows[0], map, message: rows[1], help: rows[2], status: y }
+    }
+
+    /// Row for prompts that normally sit in the message bar ("PRESS ANY
+    /// KEY", intro hints). Falls back to just below the map when the
+    /// message bar is hidden.
+    fn prompt(&self, view_h: usize) -> usize {
+        self.message.unwrap_or(self.map + view_h)
+    }
+}
 
 pub struct Renderer {
-    writer: BufWriter<io::Stdout>,
+    writer: BufWriter<Box<dyn Write>>,
+    screen: Box<dyn Screen>,
     front: FrameBuffer,
     back: FrameBuffer,
     term_w: usize,
     term_h: usize,
     last_phase: Option<Phase>,
+    display: DisplayConfig,
+    last_status: String,
+    player_tween: Option<Tween>,
+    guard_tweens: Vec<Option<Tween>>,
+    gfx: Option<GfxBackend>,  // image backend for the map, if enabled and supported
+    particles: Particles,
+    shake: f32,               // 0..1, decays over time; camera jolts while > 0
+    shake_frame: u32,
+    shake_last: Instant,
+    perf: Option<PerfReadout>,
+    layout: Layout,
+    area: Area,
+    frame_dirty: bool,  // screen was cleared: redraw the streaming frame
+    compact: bool,  // one terminal column per game cell (world.compact_view)
+    too_small: bool,
+    flush_avg: Duration,  // smoothed time to write + flush a frame
 }
 
 impl Renderer {
-    pub fn new() -> Self {
+    pub fn new(display: &DisplayConfig) -> Self {
+        Renderer::with_screen(display, Box::new(io::stdout()), Box::new(Crossterm))
+    }
+
+    /// A renderer writing its frames to `out`, for `screen` (see
+    /// `ui::backend`).
+    pub fn with_screen(display: &DisplayConfig, out: Box<dyn Write>, screen: Box<dyn Screen>) -> Self {
+        let protocol = match display.render_mode {
+            RenderMode::Graphics => gfx_backend::detect(),
+            RenderMode::Kitty => Some(Protocol::Kitty),
+            RenderMode::Sixel => Some(Protocol::Sixel),
+            RenderMode::Cells | RenderMode::HalfBlock => None,
+        };
         Renderer {
-            writer: BufWriter::with_capacity(16384, io::stdout()),
+            writer: BufWriter::with_capacity(16384, out),
+            screen,
             front: FrameBuffer::new(0, 0),
             back: FrameBuffer::new(0, 0),
             term_w: 0,
             term_h: 0,
             last_phase: None,
+            display: display.clone(),
+            last_status: String::new(),
+            player_tween: None,
+            guard_tweens: vec![],
+            gfx: protocol.map(GfxBackend::new),
+            particles: Particles::default(),
+            shake: 0.0,
+            shake_frame: 0,
+            shake_last: Instant::now(),
+            perf: None,
+            layout: Layout::compute(display, 0),
+            area: Area::default(),
+            frame_dirty: true,
+            compact: false,
+            too_small: false,
+            flush_avg: Duration::ZERO,
         }
     }
 
+    /// A `w`×`h` renderer writing to `out` instead of the terminal, for
+    /// driving `flush_lines` without one (benches).
+    pub fn offscreen(display: &DisplayConfig, out: Box<dyn Write>, w: usize, h: usize) -> Self {
+        let mut r = Renderer::with_screen(display, out, Box::new(Headless { width: w as u16, height: h as u16 }));
+        r.gfx = None;
+        r.term_w = w;
+        r.term_h = h;
+        r.area = Area { x: 0, y: 0, w, h };
+        r.front.resize(w, h);
+        r.back.resize(w, h);
+        r.back.cells.fill(Cell::INVALID);
+        r
+    }
+
+    /// Draw `lines` (text, colour) as the next frame and write out what
+    /// changed since the last one, the way `render` does a game frame.
+    pub fn flush_lines(&mut self, lines: &[(String, Color)]) -> io::Result<()> {
+        self.front.clear();
+        for (y, (text, fg)) in lines.iter().enumerate() {
+            self.front.put_str(0, y, text, *fg, Cell::BASE_BG, false);
+        }
+        self.flush_diff()?;
+        std::mem::swap(&mut self.front, &mut self.back);
+        Ok(())
+    }
+
+    /// Latest frame / step timing from the game loop (`perf_overlay`).
+    pub fn set_perf(&mut self, readout: Option<PerfReadout>) {
+        self.perf = readout;
+    }
+
+    /// Camera offset for this frame: ±1 cell, cycling direction each frame.
+    /// Once the shake has decayed below half, only every other frame jolts.
+    fn shake_offset(&mut self, phase: Phase) -> (i32, i32) {
+        const DECAY_PER_SEC: f32 = 3.0;
+        const PATTERN: [(i32, i32); 4] = [(1, 0), (0, 1), (-1, 0), (0, -1)];
+
+        if self.shake <= 0.0 { return (0, 0); }
+        let now = Instant::now();
+        self.shake -= now.duration_since(self.shake_last).as_secs_f32() * DECAY_PER_SEC;
+        self.shake_last = now;
+        if self.shake <= 0.0 || !matches!(phase, Phase::Playing | Phase::Dying) {
+            self.shake = 0.0;
+            return (0, 0);
+        }
+
+        self.shake_frame = self.shake_frame.wrapping_add(1);
+        if self.shake < 0.5 && !self.shake_frame.is_multiple_of(2) {
+            return (0, 0);
+        }
+        PATTERN[(self.shake_frame / 2) as usize % PATTERN.len()]
+    }
+
+    fn half_block(&self) -> bool {
+        // Half-block needs both columns of a cell for its 2×2 pixels
+        self.display.render_mode == RenderMode::HalfBlock && !self.compact
+    }
+
+    /// Shortest frame interval the terminal keeps up with. Output should
+    /// take at most half of each frame; past that, a saturated stdout (slow
+    /// SSH link) queues frames and input lag builds up behind them.
+    pub fn min_frame_interval(&self) -> Duration {
+        (self.flush_avg * 2).min(MAX_FRAME_INTERVAL)
+    }
+
+    /// Whether the last frame found the terminal below the minimum size.
+    /// The game loop treats this like a pause.
+    pub fn too_small(&self) -> bool {
+        self.too_small
+    }
+
+    /// Minimum terminal (columns, rows) for the current view and layout.
+    fn min_size(&self) -> (usize, usize) {
+        let (w, h) = (MIN_VIEW_W * self.cell_w(), MIN_VIEW_H + Layout::reserved_rows(&self.display));
+        if self.display.stream_layout {
+            (w + 2 + self.display.stream_panel_width, h + 2)
+        } else {
+            (w, h)
+        }
+    }
+
+    /// Area the game draws into for the current terminal size.
+    fn content_area(&self) -> Area {
+        if !self.display.stream_layout || self.too_small {
+            return Area { x: 0, y: 0, w: self.term_w, h: self.term_h };
+        }
+        Area {
+            x: 1,
+            y: 1,
+            w: self.term_w.saturating_sub(2 + self.display.stream_panel_width),
+            h: self.term_h.saturating_sub(2),
+        }
+    }
+
+    fn clear_screen(&mut self) -> io::Result<()> {
+        self.frame_dirty = true;
+        queue!(self.writer, SetBackgroundColor(Cell::BASE_BG), Clear(ClearType::All))
+    }
+
+    /// Accent border around the game area; the side panel stays blank.
+    fn draw_stream_frame(&mut self) -> io::Result<()> {
+        let a = self.area;
+        let (r, g, b) = self.display.stream_accent;
+        let horiz = "─".repeat(a.w);
+        queue!(self.writer,
+            SetForegroundColor(Color::Rgb{r, g, b}),
+            SetBackgroundColor(Cell::BASE_BG),
+            MoveTo(0, 0),
+            Print(format!("╭{horiz}╮")),
+            MoveTo(0, (a.y + a.h) as u16),
+            Print(format!("╰{horiz}╯")),
+        )?;
+        for y in a.y..a.y + a.h {
+            queue!(self.writer,
+                MoveTo(0, y as u16), Print('│'),
+                MoveTo((a.x + a.w) as u16, y as u16), Print('│'),
+            )?;
+        }
+        Ok(())
+    }
+
+    /// Terminal columns per game cell.
+    fn cell_w(&self) -> usize {
+        if self.compact { 1 } else { CELL_W }
+    }
+
+    /// Write a wide glyph (actor / token) filling a game cell. Compact view
+    /// swaps it for its single-column stand-in.
+    fn put_wide(&mut self, col: usize, row: usize, cell: Cell) {
+        if self.compact {
+            self.front.set(col, row, narrow_cell(cell));
+        } else {
+            self.front.set(col, row, cell);
+            self.front.set(col + 1, row, Cell::WIDE_CONT);
+        }
+    }
+
+    /// Write the two glyphs of a game cell. Compact view merges them into one.
+    fn put_pair(&mut self, col: usize, row: usize, c0: char, c1: char, fg: Color, bg: Color) {
+        if self.compact {
+            self.front.set(col, row, Cell::from_char(merge_glyphs(c0, c1), fg, bg, false));
+        } else {
+            self.front.set(col, row, Cell::from_char(c0, fg, bg, false));
+            self.front.set(col + 1, row, Cell::from_char(c1, fg, bg, false));
+        }
+    }
+
+    /// Blink phase for prompts and cursors: on for `period` ticks, off for `period`.
+    /// Always on in reduced-motion mode.
+    fn blink_on(&self, tick: u32, period: u32) -> bool {
+        self.display.reduced_motion || (tick / period).is_multiple_of(2)
+    }
+
     pub fn init(&mut self) -> io::Result<()> {
-        terminal::enable_raw_mode()?;
+        self.screen.enter()?;
         execute!(
             self.writer,
             terminal::EnterAlternateScreen,
+            EnableFocusChange,
             cursor::Hide,
             SetBackgroundColor(Cell::BASE_BG),
             Clear(ClearType::All)
         )?;
 
-        let (tw, th) = terminal::size().unwrap_or((80, 24));
+        let (tw, th) = self.screen.size();
         self.term_w = tw as usize;
         self.term_h = th as usize;
         self.front.resize(self.term_w, self.term_h);
@@ -231,35 +756,71 @@ impl Renderer {
         Ok(())
     }
 
+    /// Hand the screen back for a Ctrl+Z, if it has a shell to go back
+    /// to; true once the game is continued, for `resume`.
+    pub fn suspend(&mut self) -> bool {
+        self.screen.suspend()
+    }
+
+    /// Take the terminal back after a suspend, and repaint everything.
+    pub fn resume(&mut self) -> io::Result<()> {
+        self.init()?;
+        if let Some(gfx) = &mut self.gfx { gfx.invalidate(); }
+        Ok(())
+    }
+
     pub fn cleanup(&mut self) -> io::Result<()> {
+        if let Some(gfx) = &mut self.gfx {
+            gfx.hide(&mut self.writer)?;
+        }
         execute!(
             self.writer,
             ResetColor,
             cursor::Show,
+            DisableFocusChange,
             terminal::LeaveAlternateScreen
         )?;
-        terminal::disable_raw_mode()
+        self.screen.leave()
     }
 
     pub fn render(&mut self, world: &mut WorldState) -> io::Result<()> {
+        // Compact view changes every column: start from a clean screen
+        if world.compact_view != self.compact {
+            self.compact = world.compact_view;
+            self.back.cells.fill(Cell::INVALID);
+            if let Some(gfx) = &mut self.gfx { gfx.invalidate(); }
+            self.player_tween = None;
+            self.guard_tweens.clear();
+            self.clear_screen()?;
+        }
+
         // Detect terminal resize
-        let (tw, th) = terminal::size().unwrap_or((80, 24));
-        if tw as usize != self.term_w || th as usize != self.term_h {
-            self.term_w = tw as usize;
-            self.term_h = th as usize;
-            self.front.resize(self.term_w, self.term_h);
-            self.back.resize(self.term_w, self.term_h);
+        let (tw, th) = self.screen.size();
+        self.term_w = tw as usize;
+        self.term_h = th as usize;
+        let (min_w, min_h) = self.min_size();
+        self.too_small = self.term_w < min_w || self.term_h < min_h;
+        let area = self.content_area();
+        if area != self.area {
+            self.area = area;
+            self.front.resize(area.w, area.h);
+            self.back.resize(area.w, area.h);
             // Force full repaint after resize.
             self.back.cells.fill(Cell::INVALID);
-            queue!(self.writer, SetBackgroundColor(Cell::BASE_BG), Clear(ClearType::All))?;
+            if let Some(gfx) = &mut self.gfx { gfx.invalidate(); }
+            self.clear_screen()?;
+        }
+
+        if self.too_small {
+            return self.render_too_small(min_w, min_h);
         }
 
         // Update camera viewport dimensions from terminal size
-        // viewport = terminal cols / CELL_W wide, terminal rows - reserved rows high
-        let reserved_rows = MAP_ROW + 4; // HUD + gap + msg + help
-        world.camera.view_w = self.term_w / CELL_W;
-        let max_view_h = if self.term_h > reserved_rows {
-            self.term_h - reserved_rows
+        // viewport = terminal cols / cell_w wide, terminal rows - reserved rows high
+        let reserved_rows = Layout::reserved_rows(&self.display);
+        world.camera.view_w = self.area.w / self.cell_w();
+        let max_view_h = if self.area.h > reserved_rows {
+            self.area.h - reserved_rows
         } else {
             1
         };
@@ -272,13 +833,16 @@ impl Renderer {
         } else {
             max_view_h
         };
+        self.layout = Layout::compute(&self.display, world.camera.view_h);
 
         // Detect phase change → clear for clean transition
         let phase_changed = self.last_phase != Some(world.phase);
         if phase_changed {
             self.back.cells.fill(Cell::INVALID);
-            queue!(self.writer, SetBackgroundColor(Cell::BASE_BG), Clear(ClearType::All))?;
+            self.clear_screen()?;
             self.last_phase = Some(world.phase);
+            if let Some(gfx) = &mut self.gfx { gfx.invalidate(); }
+            self.particles.clear();
         }
 
         // Re-center camera now that view_w/view_h are up to date.
@@ -299,6 +863,11 @@ impl Renderer {
             _ => {}
         }
 
+        // Screen shake: nudge the camera for this frame only
+        let (shake_x, shake_y) = self.shake_offset(world.phase);
+        world.camera.x += shake_x;
+        world.camera.y += shake_y;
+
         // Build front buffer
         self.front.clear();
 
@@ -306,6 +875,8 @@ impl Renderer {
             Phase::Title => self.compose_title(world),
             Phase::LevelSelect => self.compose_level_select(world),
             Phase::PackSelect => self.compose_pack_select(world),
+            Phase::PackBuilder => self.compose_pack_builder(world),
+            Phase::Cutscene => self.compose_cutscene(world),
             Phase::LevelIntro => self.compose_level_intro(world),
             Phase::LevelReady => self.compose_level_ready(world),
             Phase::LevelOutro | Phase::LevelComplete => self.compose_game_animated(world),
@@ -315,28 +886,103 @@ impl Renderer {
             Phase::Playing => self.compose_game(world),
         }
 
-        // Pause overlay (drawn on top of game)
-        if world.paused {
+        // Whole-level overview (drawn over the map, under the pause box)
+        if world.show_overview && world.phase == Phase::Playing {
+            self.compose_overview(world);
+        }
+
+        // Pause overlay (drawn on top of game), or the event log over it
+        if world.paused && world.log_open {
+            self.compose_log_overlay(world);
+        } else if world.paused {
             self.compose_pause_overlay(world);
         }
 
+        // Startup config problems, over everything until dismissed
+        if let Some(report) = &world.config_report {
+            self.compose_config_report(report);
+        }
+
+        if self.display.screen_reader {
+            self.compose_status_line(world);
+        }
+
+        if self.display.perf_overlay {
+            self.compose_perf();
+        }
+
+        if let Some(host) = &world.spectating {
+            if world.phase != Phase::Cutscene {
+                self.compose_spectating(host);
+            }
+        }
+
+        // Text for the clipboard, by OSC 52 (terminals that don't allow it ignore it)
+        if let Some(text) = world.clipboard.take() {
+            queue!(self.writer, Print(format!("\x1b]52;c;{}\x07", STANDARD.encode(text))))?;
+        }
+
         // Diff and emit
         self.flush_diff()?;
 
+        // Map image on top of the (blank) map cells while playing
+        let mut repaint = false;
+        let cell_w = self.cell_w();
+        if let Some(gfx) = &mut self.gfx {
+            if world.phase == Phase::Playing && !world.paused && !world.show_overview {
+                let (col, row) = (self.area.x as u16, (self.area.y + self.layout.map) as u16);
+                gfx.draw(&mut self.writer, world, col, row, cell_w)?;
+            } else {
+                repaint = gfx.hide(&mut self.writer)?;
+            }
+        }
+
+        world.camera.x -= shake_x;
+        world.camera.y -= shake_y;
+
         // Swap: current front becomes next back
         std::mem::swap(&mut self.front, &mut self.back);
+        if repaint {
+            self.back.cells.fill(Cell::INVALID);
+        }
+
+        Ok(())
+    }
 
+    /// Stand-in frame while the terminal is below the minimum size.
+    /// Forgets the last phase so the real screen repaints fully on return.
+    fn render_too_small(&mut self, min_w: usize, min_h: usize) -> io::Result<()> {
+        self.last_phase = None;
+        if let Some(gfx) = &mut self.gfx {
+            gfx.hide(&mut self.writer)?;
+        }
+
+        self.front.clear();
+        let lines = [
+            t!("small.title").to_string(),
+            t!("small.enlarge", w = min_w, h = min_h),
+            t!("small.current", w = self.term_w, h = self.term_h),
+        ];
+        let top = self.front.height.saturating_sub(lines.len()) / 2;
+        for (i, line) in lines.iter().enumerate() {
+            let col = self.front.width.saturating_sub(text_width(line)) / 2;
+            let fg = if i == 0 { Color::Rgb{r:255,g:200,b:60} } else { Color::White };
+            self.front.put_str(col, top + i, line, fg, Color::Reset, false);
+        }
+
+        self.flush_diff()?;
+        std::mem::swap(&mut self.front, &mut self.back);
         Ok(())
     }
 
     // ── Diff flush: only write changed cells ──
 
     fn flush_diff(&mut self) -> io::Result<()> {
+        let started = Instant::now();
         let mut last_fg = Color::White;
         let mut last_bg = Cell::BASE_BG;
-        let mut need_move = true;
-        let mut last_x: usize = 0;
-        let mut last_y: usize = 0;
+        // Changed cells with the same colours are batched into one Print
+        let mut run = String::with_capacity(256);
 
         // Set explicit base colors at start of frame.
         // IMPORTANT: Do NOT use ResetColor here — it resets to the terminal's
@@ -345,127 +991,446 @@ impl Renderer {
             SetForegroundColor(Color::White),
             SetBackgroundColor(Cell::BASE_BG),
         )?;
+        if self.frame_dirty {
+            self.frame_dirty = false;
+            if self.area.x > 0 {
+                self.draw_stream_frame()?;
+                queue!(self.writer, SetForegroundColor(last_fg), SetBackgroundColor(last_bg))?;
+            }
+        }
+        let (ox, oy) = (self.area.x, self.area.y);
 
         for y in 0..self.front.height {
+            // Column the terminal cursor is known to be at on this row
+            let mut cursor: Option<usize> = None;
             let mut x = 0;
             while x < self.front.width {
                 let cell = self.front.get(x, y);
-                let prev = self.back.get(x, y);
 
-                // Skip continuation cells (right half of wide emoji)
+                // Continuation cells (right half of wide emoji) are printed
+                // with their wide cell; it notices if they changed.
                 if cell.cont {
-                    if cell != prev { need_move = true; }
                     x += 1;
                     continue;
                 }
 
-                // For wide cells, also check if the continuation changed
                 let cont_changed = cell.wide
                     && x + 1 < self.front.width
                     && self.front.get(x + 1, y) != self.back.get(x + 1, y);
-
-                if cell == prev && !cont_changed {
-                    need_move = true;
+                if cell == self.back.get(x, y) && !cont_changed {
                     x += 1;
                     continue;
                 }
 
-                // Position cursor if needed
-                if need_move || x != last_x + 1 || y != last_y {
-                    queue!(self.writer, MoveTo(x as u16, y as u16))?;
-                    need_move = false;
+                // Get the cursor to x: reprint a short unchanged gap if that's
+                // cheaper than a move sequence, else move right or jump.
+                if cursor != Some(x) {
+                    match cursor {
+                        Some(c) if c < x && self.can_bridge(c, x, y, last_fg, last_bg) => {
+                            for gx in c..x {
+                                run.push_str(self.front.get(gx, y).as_str());
+                            }
+                        }
+                        Some(c) if c < x => {
+                            Self::flush_run(&mut self.writer, &mut run)?;
+                            queue!(self.writer, cursor::MoveRight((x - c) as u16))?;
+                        }
+                        _ => {
+                            Self::flush_run(&mut self.writer, &mut run)?;
+                            queue!(self.writer, MoveTo((ox + x) as u16, (oy + y) as u16))?;
+                        }
+                    }
                 }
 
                 // Set colors only if changed
-                if cell.fg != last_fg {
-                    queue!(self.writer, SetForegroundColor(cell.fg))?;
-                    last_fg = cell.fg;
-                }
-                if cell.bg != last_bg {
-                    queue!(self.writer, SetBackgroundColor(cell.bg))?;
-                    last_bg = cell.bg;
+                if cell.fg != last_fg || cell.bg != last_bg {
+                    Self::flush_run(&mut self.writer, &mut run)?;
+                    if cell.fg != last_fg {
+                        queue!(self.writer, SetForegroundColor(cell.fg))?;
+                        last_fg = cell.fg;
+                    }
+                    if cell.bg != last_bg {
+                        queue!(self.writer, SetBackgroundColor(cell.bg))?;
+                        last_bg = cell.bg;
+                    }
                 }
 
-                queue!(self.writer, Print(cell.as_str()))?;
+                run.push_str(cell.as_str());
 
-                if cell.wide {
-                    // Wide char printed: cursor advanced 2 columns
-                    last_x = x + 1;
-                    x += 2; // skip the continuation cell
-                } else {
-                    last_x = x;
-                    x += 1;
-                }
-                last_y = y;
+                // Wide char printed: cursor advanced 2 columns
+                x += if cell.wide { 2 } else { 1 };
+                cursor = Some(x);
             }
+            Self::flush_run(&mut self.writer, &mut run)?;
         }
 
-        self.writer.flush()
+        self.writer.flush()?;
+        // Blocks while the terminal drains output, so this tracks link speed
+        let took = started.elapsed();
+        self.flush_avg = self.flush_avg.mul_f32(0.8) + took.mul_f32(0.2);
+        timing::record("flush", took);
+        Ok(())
+    }
+
+    /// Whether unchanged cells `from..to` on row `y` can simply be printed
+    /// again to carry the cursor across: short, all narrow, and already in
+    /// the current colours (reprinting them is then invisible).
+    fn can_bridge(&self, from: usize, to: usize, y: usize, fg: Color, bg: Color) -> bool {
+        // A cursor move is ~4–8 bytes; beyond this, reprinting costs more
+        const MAX_BRIDGE: usize = 4;
+        to - from <= MAX_BRIDGE && (from..to).all(|gx| {
+            let c = self.front.get(gx, y);
+            !c.wide && !c.cont && c.fg == fg && c.bg == bg && c == self.back.get(gx, y)
+        })
+    }
+
+    fn flush_run(writer: &mut BufWriter<Box<dyn Write>>, run: &mut String) -> io::Result<()> {
+        if !run.is_empty() {
+            queue!(writer, Print(&*run))?;
+            run.clear();
+        }
+        Ok(())
     }
 
     // ── Compose: build front buffer content ──
 
+    /// HUD text: level, score, lives, gold, then the optional fields
+    /// enabled by `hud_fields`, then `status` (e.g. "ESCAPE!").
+    fn hud_line(&self, w: &WorldState, status: &str) -> String {
+        let node = format!("{:<2}", w.current_level + 1);
+        let got = w.gold_total - w.gold_remaining;
+        let mut hud = match w.mode {
+            GameMode::Normal => t!("hud.normal",
+                node = node, score = format!("{:<7}", w.score), lives = w.lives,
+                got = got, total = w.gold_total),
+            GameMode::TimeAttack => {
+                let secs = w.clock_secs();
+                t!("hud.time_attack",
+                    node = node, clock = format!("{}:{:02}", secs / 60, secs % 60),
+                    got = got, total = w.gold_total)
+            }
+        };
+        if w.ng_loop > 0 {
+            hud.push_str(&t!("hud.ng_loop", n = w.ng_loop));
+        }
+        let fields = self.display.hud_fields;
+        if fields.time {
+            let secs = w.tick * w.speed.tick_rate_ms / 1000;
+            hud.push_str(&t!("hud.time", time = format!("{}:{:02}", secs / 60, secs % 60)));
+        }
+        if fields.guards {
+            let active = w.guards.iter().filter(|g| g.state != ActorState::Dead).count();
+            hud.push_str(&t!("hud.guards", n = active));
+        }
+        if let (Some(left), Some(limit)) = (w.digs_left, w.dig_limit) {
+            // Puzzle mode: the budget matters more than the running total
+            hud.push_str(&t!("hud.hacks_left", left = left, limit = limit));
+        } else if fields.digs {
+            hud.push_str(&t!("hud.hacks", n = w.digs_made));
+        }
+        if !status.is_empty() {
+            hud.push_str(&format!("  {} ", status));
+        }
+        hud
+    }
+
+    /// Fill the HUD row (if shown) and write the HUD text.
+    fn compose_hud(&mut self, w: &WorldState, status: &str) {
+        let Some(row) = self.layout.hud else { return };
+        let hud_bg = Color::Rgb{r:20,g:20,b:60};
+        for x in 0..self.front.width {
+            self.front.set(x, row, Cell::from_char(' ', Color::White, hud_bg, false));
+        }
+        let hud = self.hud_line(w, status);
+        if self.display.stream_layout {
+            // Double-width text survives a scaled-down stream capture
+            self.front.put_str_wide(0, row, &hud, Color::White, hud_bg);
+        } else {
+            self.front.put_str(0, row, &hud, Color::White, hud_bg, false);
+        }
+    }
+
     fn compose_game(&mut self, w: &WorldState) {
         let buf_w = self.front.width;
         let cam = &w.camera;
 
         // ── HUD row ──
-        let gold_status = if w.exit_enabled {
-            "ESCAPE!"
-        } else {
-            ""
-        };
-        let hud = format!(
-            " Node.{:<2}  Score:{:<7}  ♥×{}  ${}/{}  {} ",
-            w.current_level + 1, w.score, w.lives,
-            w.gold_total - w.gold_remaining, w.gold_total,
-            gold_status,
-        );
-        // Fill entire HUD row with background
-        for x in 0..buf_w {
-            self.front.set(x, HUD_ROW, Cell::from_char(' ', Color::White, Color::Rgb{r:20,g:20,b:60}, false));
-        }
-        self.front.put_str(0, HUD_ROW, &hud, Color::White, Color::Rgb{r:20,g:20,b:60}, false);
+        let gold_status = if w.exit_enabled { t!("hud.escape") } else { "" };
+        self.compose_hud(w, gold_status);
 
         // ── Map (camera viewport) ──
         for vy in 0..cam.view_h {
             let wy = cam.y + vy as i32;
-            let row = MAP_ROW + vy;
+            let row = self.layout.map + vy;
             if row >= self.front.height { break; }
 
             for vx in 0..cam.view_w {
                 let wx = cam.x + vx as i32;
-                let col = vx * CELL_W;
-                if col + 1 >= buf_w { break; }
+                let col = vx * self.cell_w();
+                if col + self.cell_w() > buf_w { break; }
 
-                self.compose_cell_cam(w, wx, wy, col, row);
+                if self.gfx.is_some() {
+                    // Painted by the image backend after the flush
+                    self.compose_void(col, row);
+                } else if self.display.smooth_movement {
+                    self.compose_terrain_cam(w, wx, wy, col, row);
+                } else {
+                    self.compose_cell_cam(w, wx, wy, col, row);
+                }
             }
         }
+        if self.display.smooth_movement && self.gfx.is_none() {
+            self.compose_actors_smooth(w);
+        }
+        if self.gfx.is_none() {
+            self.compose_particles(w);
+        }
+        if w.show_guard_paths && self.gfx.is_none() {
+            self.compose_guard_paths(w);
+        }
+        if w.light_radius.is_some() && self.gfx.is_none() {
+            self.compose_darkness(w);
+        }
+        // Pointing at what the dark hides would give it away
+        if w.show_edge_markers && w.light_radius.is_none() && self.gfx.is_none() {
+            self.compose_edge_markers(w);
+        }
+        if w.exit_enabled && self.gfx.is_none() {
+            self.compose_exit_arrows(w);
+        }
+        self.compose_tip(w);
 
         // ── Message bar ──
-        let msg_row = MAP_ROW + cam.view_h + 1;
-        if msg_row < self.front.height {
-            if !w.message.is_empty() {
-                let msg = format!(" ◈ {} ", w.message);
-                for x in 0..buf_w {
-                    self.front.set(x, msg_row, Cell::from_char(' ', Color::Black, Color::Rgb{r:200,g:180,b:50}, false));
-                }
-                self.front.put_str(0, msg_row, &msg, Color::Black, Color::Rgb{r:200,g:180,b:50}, false);
-            }
+        if let Some(msg_row) = self.layout.message {
+            let room = self.stack_room(cam.view_h);
+            self.compose_messages(w, msg_row, room);
+        }
+
+        // ── Minimap (top-right corner, large levels only) ──
+        if w.show_minimap && w.light_radius.is_none() && self.gfx.is_none() {
+            self.compose_minimap(w);
         }
 
         // ── Help bar ──
-        let help_row = MAP_ROW + cam.view_h + 3;
-        if help_row < self.front.height {
-            let help = " Z/Q:HackL  X/E:HackR  F1:Pause  │  Pad: B/Y/L1:L  A/X/R1:R";
+        if let Some(help_row) = self.layout.help.filter(|&r| r < self.front.height) {
+            let help = t!("hud.help");
             self.front.put_str(0, help_row, help, Color::DarkGrey, Color::Reset, false);
         }
     }
 
+    /// Rows older messages may stack into: the map, unless the image
+    /// backend paints over it.
+    fn stack_room(&self, view_h: usize) -> std::ops::Range<usize> {
+        if self.gfx.is_some() { return 0..0; }
+        self.layout.map..self.layout.map + view_h
+    }
+
+    /// The message queue: the newest fills the bar at `row`; older ones
+    /// still on their timers stack away from it, one row each, within
+    /// `room` (upwards when the bar is below it).
+    fn compose_messages(&mut self, w: &WorldState, row: usize, room: std::ops::Range<usize>) {
+        if row >= self.front.height { return; }
+        let up = row >= room.end;
+        for (i, m) in w.messages.iter().rev().enumerate() {
+            let (fg, bg) = severity_colors(m.severity);
+            let text = format!(" ◈ {} ", m.text);
+            if i == 0 {
+                for x in 0..self.front.width {
+                    self.front.set(x, row, Cell::from_char(' ', fg, bg, false));
+                }
+                self.front.put_str(0, row, &text, fg, bg, false);
+                continue;
+            }
+            let stacked = if up { row.checked_sub(i) } else { Some(row + i) };
+            let Some(r) = stacked.filter(|r| room.contains(r) && *r < self.front.height) else { break };
+            self.front.put_str(0, r, &text, fg, bg, false);
+        }
+    }
+
+    /// Tutorial tip in a box across the top of the map. The image backend
+    /// paints over map cells, so there it takes the help bar instead.
+    fn compose_tip(&mut self, w: &WorldState) {
+        let Some(text) = w.tip_text() else { return };
+        let line = t!("tip.label", text = text);
+        let fg = Color::Rgb{r:230,g:255,b:240};
+        let bg = Color::Rgb{r:20,g:90,b:70};
+        if self.gfx.is_some() {
+            if let Some(row) = self.layout.help.filter(|&r| r < self.front.height) {
+                for x in 0..self.front.width {
+                    self.front.set(x, row, Cell::from_char(' ', fg, bg, false));
+                }
+                self.front.put_str(0, row, &line, fg, bg, true);
+            }
+            return;
+        }
+        let view_cols = (w.camera.view_w * self.cell_w()).min(self.front.width);
+        let text = fit_width(&line, view_cols);
+        let col = (view_cols - text_width(&text)) / 2;
+        let row = self.layout.map + 1;
+        if row >= self.front.height { return; }
+        self.front.put_str(col, row, &text, fg, bg, true);
+    }
+
+    /// Minimap overlay: the whole level downsampled into a small block in
+    /// the top-right corner of the map area, with the camera rectangle
+    /// highlighted. Only drawn when the level doesn't fit the viewport.
+    fn compose_minimap(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        if w.width <= cam.view_w && w.height <= cam.view_h { return; }
+
+        let mini = downsample_minimap(w, MINIMAP_MAX_W, MINIMAP_MAX_H);
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width);
+        if mini.w + 2 > view_cols || mini.h + 2 > cam.view_h { return; }
+
+        let frame_bg = Color::Rgb{r:10,g:10,b:20};
+        let view_bg = Color::Rgb{r:40,g:40,b:90};
+        let x0 = view_cols - mini.w - 2;
+        let y0 = self.layout.map;
+
+        // 1-cell frame so the minimap reads as separate from the map
+        for y in y0..y0 + mini.h + 2 {
+            for x in x0..x0 + mini.w + 2 {
+                self.front.set(x, y, Cell::from_char(' ', Color::Reset, frame_bg, false));
+            }
+        }
+
+        for my in 0..mini.h {
+            for mx in 0..mini.w {
+                let cell = &mini.cells[my * mini.w + mx];
+
+                // Does this block overlap the camera rectangle?
+                let wx0 = (mx * mini.block_w) as i32;
+                let wy0 = (my * mini.block_h) as i32;
+                let in_view = wx0 + mini.block_w as i32 > cam.x
+                    && wx0 < cam.x + cam.view_w as i32
+                    && wy0 + mini.block_h as i32 > cam.y
+                    && wy0 < cam.y + cam.view_h as i32;
+                let bg = if in_view { view_bg } else { frame_bg };
+
+                let (ch, fg) = if cell.player {
+                    ('@', Color::Rgb{r:80,g:255,b:80})
+                } else if cell.guard {
+                    ('!', Color::Rgb{r:255,g:80,b:80})
+                } else if cell.gold {
+                    ('$', Color::Rgb{r:255,g:220,b:50})
+                } else {
+                    let ch = match cell.density {
+                        0 => ' ',
+                        1 => '░',
+                        2 => '▒',
+                        3 => '▓',
+                        _ => '█',
+                    };
+                    (ch, Color::Rgb{r:140,g:110,b:80})
+                };
+                self.front.set(x0 + 1 + mx, y0 + 1 + my, Cell::from_char(ch, fg, bg, false));
+            }
+        }
+    }
+
+    /// Whole-level overview in braille (2×4 dots per character), scaled
+    /// down until it fits the map area. The game is frozen while it's open.
+    fn compose_overview(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width);
+        let view_rows = cam.view_h.min(self.front.height.saturating_sub(self.layout.map));
+        // frame on each side + caption row
+        if view_cols < 4 || view_rows < 4 { return; }
+
+        let ov = braille_overview(w, view_cols - 2, view_rows - 3);
+        let box_w = ov.cols + 2;
+        let box_h = ov.rows + 3;
+        let x0 = (view_cols - box_w) / 2;
+        let y0 = self.layout.map + (view_rows - box_h) / 2;
+
+        let bg = Color::Rgb{r:10,g:10,b:20};
+        for y in y0..y0 + box_h {
+            for x in x0..x0 + box_w {
+                self.front.set(x, y, Cell::from_char(' ', Color::Reset, bg, false));
+            }
+        }
+        let caption = if ov.block > 1 {
+            t!("hud.overview_scaled", scale = ov.block)
+        } else {
+            t!("hud.overview").to_string()
+        };
+        let caption = fit_width(&caption, box_w);
+        self.front.put_str(x0, y0, &caption, Color::Rgb{r:255,g:220,b:50}, bg, false);
+
+        for oy in 0..ov.rows {
+            for ox in 0..ov.cols {
+                let (bits, mark) = ov.cells[oy * ov.cols + ox];
+                if bits == 0 { continue; }
+                let ch = char::from_u32(0x2800 + bits as u32).unwrap_or(' ');
+                let fg = match mark {
+                    Mark::Terrain => Color::Rgb{r:160,g:130,b:100},
+                    Mark::Exit    => Color::Rgb{r:0,g:220,b:220},
+                    Mark::Gold    => Color::Rgb{r:255,g:220,b:50},
+                    Mark::Guard   => Color::Rgb{r:255,g:80,b:80},
+                    Mark::Player  => Color::Rgb{r:80,g:255,b:80},
+                };
+                self.front.set(x0 + 1 + ox, y0 + 2 + oy, Cell::from_char(ch, fg, bg, false));
+            }
+        }
+    }
+
+    /// Accessibility status line (screen-reader mode).
+    ///
+    /// Written to a fixed row below the help bar. When the text changes,
+    /// the whole row is invalidated so the terminal receives the complete
+    /// line in one write instead of a few diffed characters.
+    fn compose_status_line(&mut self, w: &WorldState) {
+        let status = match w.phase {
+            Phase::Playing | Phase::LevelReady if w.paused => t!("status.paused").to_string(),
+            Phase::Playing | Phase::LevelReady => describe_status(w),
+            _ => return,
+        };
+        let row = self.layout.status;
+        if row >= self.front.height { return; }
+
+        if status != self.last_status {
+            for x in 0..self.back.width {
+                self.back.set(x, row, Cell::INVALID);
+            }
+            self.last_status = status.clone();
+        }
+        self.front.put_str(0, row, &status, Color::White, Color::Reset, false);
+    }
+
+    /// FPS, mean step() time and the slowest timed sections (ms per
+    /// call), right-aligned on the HUD row.
+    fn compose_perf(&mut self) {
+        /// Sections shown after the step time.
+        const SECTIONS: usize = 3;
+        let Some(p) = &self.perf else { return };
+        let step = match p.step_avg {
+            Some(d) => format!("{:.2}ms", d.as_secs_f64() * 1000.0),
+            None => "--".to_string(),
+        };
+        let mut text = format!(" {:.0}fps step:{} ", p.fps, step);
+        for s in p.sections.iter().take(SECTIONS) {
+            text.push_str(&format!("{}:{:.2} ", s.name, s.avg().as_secs_f64() * 1000.0));
+        }
+        let len = text_width(&text);
+        if len > self.front.width { return; }
+        let col = self.front.width - len;
+        // Top-right corner: the HUD row, or row 0 when the HUD is hidden
+        let row = self.layout.hud.unwrap_or(0);
+        self.front.put_str(col, row, &text, Color::Rgb{r:150,g:150,b:170}, Color::Rgb{r:20,g:20,b:60}, false);
+    }
+
+    /// Who's being watched, and how to stop, at the right of the bottom row.
+    fn compose_spectating(&mut self, host: &str) {
+        let text = format!(" {} ", t!("spectate.banner", host = host));
+        let len = text_width(&text);
+        if len > self.front.width || self.front.height == 0 { return; }
+        let (col, row) = (self.front.width - len, self.front.height - 1);
+        self.front.put_str(col, row, &text, Color::Rgb{r:255,g:255,b:255}, Color::Rgb{r:170,g:30,b:30}, true);
+    }
+
     /// Render an out-of-bounds / void cell (game background).
     fn compose_void(&mut self, col: usize, row: usize) {
-        self.front.set(col, row, Cell::from_char(' ', Color::White, Cell::BASE_BG, false));
-        self.front.set(col + 1, row, Cell::from_char(' ', Color::White, Cell::BASE_BG, false));
+        self.put_pair(col, row, ' ', ' ', Color::White, Cell::BASE_BG);
     }
 
     /// Render a world cell through the camera. If (wx, wy) is out of world bounds, void.
@@ -477,6 +1442,299 @@ impl Renderer {
         }
     }
 
+    /// Render terrain only (no actors) through the camera.
+    fn compose_terrain_cam(&mut self, w: &WorldState, wx: i32, wy: i32, col: usize, row: usize) {
+        if wx < 0 || wy < 0 || wx >= w.width as i32 || wy >= w.height as i32 {
+            self.compose_void(col, row);
+        } else {
+            self.compose_terrain(w, wx as usize, wy as usize, col, row);
+        }
+    }
+
+    /// Draw actors at interpolated positions on top of the terrain pass.
+    /// Horizontal motion resolves to half-cell steps (one terminal column);
+    /// vertical motion rounds to the nearer row.
+    fn compose_actors_smooth(&mut self, w: &WorldState) {
+        let now = Instant::now();
+        let tick = Duration::from_millis(w.speed.tick_rate_ms);
+
+        // A move happens every (rate + 1) ticks: the cooldown counts down
+        // `rate` idle ticks first. Falling moves one cell per tick.
+        let step_time = |state: ActorState, rate: u32| {
+            if state == ActorState::Falling { tick } else { tick * (rate + 1) }
+        };
+
+        self.guard_tweens.resize(w.guards.len(), None);
+        for (i, g) in w.guards.iter().enumerate() {
+            if g.state == ActorState::Dead || !w.guard_seen(g) {
+                self.guard_tweens[i] = None;
+                continue;
+            }
+            let d = step_time(g.state, w.speed.guard_move_rate);
+            Tween::retarget(&mut self.guard_tweens[i], g.x, g.y, d, now);
+            if let Some(t) = self.guard_tweens[i] {
+                let (fx, fy) = t.pos(now);
+                if self.half_block() {
+                    self.put_sprite(w, fx, fy, &halfblock::guard(g, w.speed.trap_escape_ticks));
+                } else {
+                    self.put_actor(w, fx, fy, guard_cell(g, w.speed.trap_escape_ticks));
+                }
+            }
+        }
+
+        let p = &w.player;
+        if p.alive {
+            let d = step_time(p.state, w.speed.player_move_rate);
+            Tween::retarget(&mut self.player_tween, p.x, p.y, d, now);
+            if let Some(t) = self.player_tween {
+                let (fx, fy) = t.pos(now);
+                if self.half_block() {
+                    self.put_sprite(w, fx, fy, &halfblock::player(p.facing));
+                } else {
+                    self.put_actor(w, fx, fy, Cell::from_char_wide('🧍', Color::Reset, Color::Reset, false));
+                }
+            }
+        } else {
+            self.player_tween = None;
+        }
+    }
+
+    /// Off-screen markers: a small arrow on the viewport edge in the
+    /// direction of each guard / token outside the camera. Guards win when
+    /// both land on the same edge cell. Only for levels that scroll.
+    fn compose_edge_markers(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        if w.width <= cam.view_w && w.height <= cam.view_h { return; }
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width);
+        if view_cols == 0 || cam.view_h == 0 { return; }
+
+        let guard_fg = Color::Rgb{r:255,g:80,b:80};
+
+        let mut targets: Vec<(usize, usize, Color)> = vec![];
+        for y in 0..w.height {
+            for x in 0..w.width {
+                if let Some(token) = w.tiles[(x, y)].token() {
+                    let (r, g, b) = token.color;
+                    targets.push((x, y, Color::Rgb{r, g, b}));
+                }
+            }
+        }
+        for g in &w.guards {
+            if g.state != ActorState::Dead && w.guard_seen(g) { targets.push((g.x, g.y, guard_fg)); }
+        }
+
+        for (x, y, fg) in targets {
+            if cam.world_to_view(x, y).is_some() { continue; }
+            let vx = x as i32 - cam.x;
+            let vy = y as i32 - cam.y;
+
+            // Vertical arrows win in the corners: height is usually the
+            // shorter axis, so up/down is the more useful hint.
+            let (ch, col, vrow) = if vy < 0 || vy >= cam.view_h as i32 {
+                let col = (vx.clamp(0, cam.view_w as i32 - 1) as usize) * self.cell_w();
+                if vy < 0 { ('▴', col, 0) } else { ('▾', col, cam.view_h - 1) }
+            } else if vx < 0 {
+                ('◂', 0, vy as usize)
+            } else {
+                ('▸', view_cols - 1, vy as usize)
+            };
+            let row = self.layout.map + vrow;
+            if fg != guard_fg && self.front.get(col, row).fg == guard_fg { continue; }
+            self.put_narrow(col, row, Cell::from_char(ch, fg, Color::Rgb{r:30,g:30,b:50}, true));
+        }
+    }
+
+    /// Darkness mode: each view cell fades toward the background with its
+    /// distance from the player, and is blank past the light's reach.
+    fn compose_darkness(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        let cw = self.cell_w();
+        for vy in 0..cam.view_h {
+            let row = self.layout.map + vy;
+            if row >= self.front.height { break; }
+            for vx in 0..cam.view_w {
+                let col = vx * cw;
+                if col + cw > self.front.width { break; }
+                let (gx, gy) = (cam.x + vx as i32, cam.y + vy as i32);
+                let light = if gx < 0 || gy < 0 { 0.0 } else { w.light_at(gx as usize, gy as usize) };
+                if light >= 1.0 { continue; }
+                for c in col..col + cw {
+                    let mut cell = self.front.get(c, row);
+                    if cell.cont { continue; }
+                    // Emoji can't be dimmed, only kept or put out
+                    let keep = if cell.wide { light >= 0.5 } else { light > 0.0 };
+                    if !keep {
+                        self.front.set(c, row, Cell::BLANK);
+                        if cell.wide { self.front.set(c + 1, row, Cell::BLANK); }
+                        continue;
+                    }
+                    cell.fg = dim_toward_bg(cell.fg, light);
+                    cell.bg = dim_toward_bg(cell.bg, light);
+                    self.front.set(c, row, cell);
+                }
+            }
+        }
+    }
+
+    /// Where each sentinel is headed: a dotted trail down the flow field
+    /// to the player, over empty-looking cells only.
+    fn compose_guard_paths(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width);
+        let trail_fg = Color::Rgb{r:255,g:110,b:110};
+        let ctx = ai::Ctx {
+            tiles: &w.tiles, width: w.width, height: w.height,
+            hole_grid: &w.hole_grid, guards: &w.guards,
+        occupancy: &w.occupancy,
+        };
+
+        for g in &w.guards {
+            if matches!(g.state, ActorState::Dead | ActorState::InHole) || !w.guard_seen(g) { continue; }
+            let path = w.flow.path(&ctx, (g.x, g.y));
+            // The last cell is the player
+            for &(x, y) in path.iter().take(path.len().saturating_sub(1)) {
+                let Some((vx, vy)) = cam.world_to_view(x, y) else { continue };
+                let (col, row) = (vx * self.cell_w(), self.layout.map + vy);
+                if col + self.cell_w() > view_cols { continue; }
+                let under = self.front.get(col, row);
+                if under.wide || under.cont || under.as_str() != " " { continue; }
+                self.front.set(col, row, Cell::from_char('·', trail_fg, under.bg, false));
+            }
+        }
+    }
+
+    /// Write a narrow cell, blanking the other half of any wide glyph it
+    /// lands on so the terminal isn't left with half an emoji.
+    fn put_narrow(&mut self, col: usize, row: usize, cell: Cell) {
+        let under = self.front.get(col, row);
+        let blank = Cell::from_char(' ', Color::Reset, Color::Reset, false);
+        if under.wide {
+            self.front.set(col + 1, row, blank);
+        } else if under.cont && col > 0 {
+            self.front.set(col - 1, row, blank);
+        }
+        self.front.set(col, row, cell);
+    }
+
+    /// Escape route: once the exit is open, up-arrows ripple up the revealed
+    /// ladder cells. If every exit column is off-camera, an edge marker on
+    /// the player's row points to the nearest one.
+    fn compose_exit_arrows(&mut self, w: &WorldState) {
+        let cam = &w.camera;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width);
+        let phase = if self.display.reduced_motion { 0 } else { (w.tick / 2) as usize };
+        let arrow_fg = Color::Rgb{r:120,g:255,b:255};
+
+        let mut nearest: Option<usize> = None;
+        let mut column_visible = false;
+        for y in 0..w.height {
+            for x in 0..w.width {
+                if w.tiles[(x, y)] != Tile::HiddenLadder { continue; }
+                let cur = nearest.map_or(usize::MAX, |n| n.abs_diff(w.player.x));
+                if x.abs_diff(w.player.x) < cur { nearest = Some(x); }
+
+                let Some((vx, vy)) = cam.world_to_view(x, y) else { continue };
+                column_visible = true;
+                // Every 4th cell lit, shifting upward over time
+                if !(y + phase).is_multiple_of(4) { continue; }
+                let (col, row) = (vx * self.cell_w(), self.layout.map + vy);
+                if col + self.cell_w() > view_cols { continue; }
+                let under = self.front.get(col, row);
+                if under.wide || under.cont { continue; }
+                self.front.set(col, row, Cell::from_char('▲', arrow_fg, under.bg, true));
+            }
+        }
+
+        let Some(ex) = nearest else { return };
+        if column_visible { return; }
+        let Some((_, pvy)) = cam.world_to_view(w.player.x, w.player.y) else { return };
+        let row = self.layout.map + pvy;
+        let bg = Color::Rgb{r:0,g:60,b:70};
+        let right = t!("hud.exit_right");
+        if (ex as i32) < cam.x {
+            self.front.put_str(0, row, t!("hud.exit_left"), arrow_fg, bg, true);
+        } else if view_cols >= text_width(right) {
+            self.front.put_str(view_cols - text_width(right), row, right, arrow_fg, bg, true);
+        }
+    }
+
+    /// Particles over the map. They never overwrite half of a wide glyph
+    /// (actor or token), so they pass behind emoji.
+    fn compose_particles(&mut self, w: &WorldState) {
+        if !w.paused && !w.show_overview {
+            self.particles.update();
+        }
+        let cam = &w.camera;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width) as i32;
+        for (x, y, ch, fg) in self.particles.iter() {
+            let col = ((x - cam.x as f32) * self.cell_w() as f32).floor() as i32;
+            let vy = (y - cam.y as f32).floor() as i32;
+            if col < 0 || col >= view_cols || vy < 0 || vy >= cam.view_h as i32 { continue; }
+
+            let (col, row) = (col as usize, self.layout.map + vy as usize);
+            let under = self.front.get(col, row);
+            if under.wide || under.cont { continue; }
+            self.front.set(col, row, Cell::from_char(ch, fg, under.bg, false));
+        }
+    }
+
+    /// Place a wide actor glyph at a fractional world position.
+    /// If it lands half-way across a neighbouring wide glyph (e.g. a token),
+    /// the broken half is blanked so the terminal doesn't mis-render it.
+    fn put_actor(&mut self, w: &WorldState, fx: f32, fy: f32, cell: Cell) {
+        let cam = &w.camera;
+        let col = ((fx - cam.x as f32) * self.cell_w() as f32).round() as i32;
+        let vy = (fy - cam.y as f32).round() as i32;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width) as i32;
+        if col < 0 || col + self.cell_w() as i32 > view_cols || vy < 0 || vy >= cam.view_h as i32 { return; }
+
+        let col = col as usize;
+        let row = self.layout.map + vy as usize;
+        let blank = Cell::from_char(' ', Color::Reset, Color::Reset, false);
+        if col > 0 && self.front.get(col - 1, row).wide {
+            self.front.set(col - 1, row, blank);
+        }
+        if self.front.get(col + 1, row).wide {
+            self.front.set(col + 2, row, blank);
+        }
+        self.put_wide(col, row, cell);
+    }
+
+    /// Half-block counterpart of `put_actor`: overlays a sprite at
+    /// pseudo-pixel precision, so vertical motion gets half-row steps too.
+    fn put_sprite(&mut self, w: &WorldState, fx: f32, fy: f32, sprite: &Sprite) {
+        let cam = &w.camera;
+        let px0 = ((fx - cam.x as f32) * 2.0).round() as i32;
+        let py0 = ((fy - cam.y as f32) * 2.0).round() as i32;
+        let view_cols = (cam.view_w * self.cell_w()).min(self.front.width) as i32;
+        let view_px_h = cam.view_h as i32 * 2;
+
+        for (dy, srow) in sprite.iter().enumerate() {
+            for (dx, s) in srow.iter().enumerate() {
+                let Some(color) = *s else { continue };
+                let (x, y) = (px0 + dx as i32, py0 + dy as i32);
+                if x < 0 || x >= view_cols || y < 0 || y >= view_px_h { continue; }
+
+                let (x, row) = (x as usize, self.layout.map + y as usize / 2);
+                let mut cell = self.front.get(x, row);
+                if y % 2 == 0 {
+                    cell.fg = Cell::norm_bg(color);
+                } else {
+                    cell.bg = Cell::norm_bg(color);
+                }
+                self.front.set(x, row, cell);
+            }
+        }
+    }
+
+    /// Write 2×2 half-block pixels into the two columns of a game cell.
+    /// `Color::Reset` pixels become the map background.
+    fn put_pixels(&mut self, col: usize, row: usize, px: Pixels) {
+        for (c, (&top, &bottom)) in px[0].iter().zip(&px[1]).enumerate() {
+            self.front.set(col + c, row, Cell::from_char('▀', Cell::norm_bg(top), bottom, false));
+        }
+    }
+
     /// Render a world cell (no player) through the camera.
     #[allow(dead_code)]
     fn compose_cell_no_player_cam(&mut self, w: &WorldState, wx: i32, wy: i32, col: usize, row: usize) {
@@ -500,25 +1758,40 @@ impl Renderer {
     /// Write the visual for game cell (gx, gy) into the front buffer at (col, row).
     /// Each game cell = 2 terminal columns.
     fn compose_cell(&mut self, w: &WorldState, gx: usize, gy: usize, col: usize, row: usize) {
+        if self.half_block() {
+            let mut px = halfblock::terrain(w, gx, gy, true);
+            if let Some(s) = halfblock::actor_at(w, gx, gy, true) {
+                px = halfblock::overlay(px, &s);
+            }
+            self.put_pixels(col, row, px);
+            return;
+        }
+
         // Player: direction-dependent emoji
         if w.player.alive && w.player.x == gx && w.player.y == gy {
             let ch = match w.player.facing {
                 Facing::Left  => '🧍',
                 Facing::Right => '🧍',
             };
-            self.front.set(col, row, Cell::from_char_wide(ch, Color::Reset, Color::Reset, false));
-            self.front.set(col + 1, row, Cell::WIDE_CONT);
+            self.put_wide(col, row, Cell::from_char_wide(ch, Color::Reset, Color::Reset, false));
             return;
         }
 
         // Sentinels
-        for g in &w.guards {
-            if g.state == ActorState::Dead { continue; }
-            if g.x == gx && g.y == gy {
-                self.front.set(col, row, Cell::from_char_wide('🤺', Color::Reset, Color::Reset, false));
-                self.front.set(col + 1, row, Cell::WIDE_CONT);
-                return;
-            }
+        if let Some(g) = w.occupancy.first(gx, gy).and_then(|i| w.guards.get(i)).filter(|g| w.guard_seen(g)) {
+            self.put_wide(col, row, guard_cell(g, w.speed.trap_escape_ticks));
+            return;
+        }
+
+        self.compose_terrain(w, gx, gy, col, row);
+    }
+
+    /// Terrain at a game cell: dig animation, open/closing hole, or tile.
+    /// Everything except actors.
+    fn compose_terrain(&mut self, w: &WorldState, gx: usize, gy: usize, col: usize, row: usize) {
+        if self.half_block() {
+            self.put_pixels(col, row, halfblock::terrain(w, gx, gy, true));
+            return;
         }
 
         // Dig in progress (cracking animation)
@@ -530,8 +1803,7 @@ impl Renderer {
                     2 => ('░', '░', Color::DarkYellow, Color::Reset),
                     _ => ('·', '·', Color::DarkYellow, Color::Reset),
                 };
-                self.front.set(col, row, Cell::from_char(c0, fg, bg, false));
-                self.front.set(col + 1, row, Cell::from_char(c1, fg, bg, false));
+                self.put_pair(col, row, c0, c1, fg, bg);
                 return;
             }
         }
@@ -549,35 +1821,49 @@ impl Renderer {
                     } else {
                         ('▅', Color::Rgb{r:60,g:45,b:0})
                     };
-                    self.front.set(col, row, Cell::from_char(ch, Color::DarkYellow, bg, false));
-                    self.front.set(col + 1, row, Cell::from_char(ch, Color::DarkYellow, bg, false));
+                    self.put_pair(col, row, ch, ch, Color::DarkYellow, bg);
                 } else {
                     // Phase 1: fully open pit
-                    self.front.set(col, row, Cell::from_char(' ', Color::Reset, Color::Rgb{r:10,g:8,b:0}, false));
-                    self.front.set(col + 1, row, Cell::from_char(' ', Color::Reset, Color::Rgb{r:10,g:8,b:0}, false));
+                    // Warms up as the seal approaches, so the next hole to close stands out
+                    let pit = halfblock::pit_color(hole.open_progress(w.speed.hole_open_ticks));
+                    self.put_pair(col, row, ' ', ' ', Color::Reset, pit);
                 }
                 return;
             }
         }
 
         // Tile
-        let (c0, c1, fg, bg) = match w.tiles[gy][gx] {
+        let (c0, c1, fg, bg) = match w.tiles[(gx, gy)] {
             Tile::Empty => (' ', ' ', Color::Reset, Color::Reset),
             Tile::Brick         => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
             Tile::TrapBrick     => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
+            Tile::FakeBrick     => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
             Tile::Concrete      => ('█', '█', Color::Rgb{r:120,g:120,b:120}, Color::Rgb{r:70,g:70,b:70}),
             Tile::Ladder        => ('╠', '╣', Color::Rgb{r:100,g:200,b:255}, Color::Reset),
             Tile::HiddenLadder  => ('╏', '╏', Color::Rgb{r:0,g:180,b:180}, Color::Rgb{r:0,g:40,b:40}),
             Tile::Rope          => ('━', '━', Color::Rgb{r:180,g:100,b:200}, Color::Reset),
-            Tile::Gold          => {
-                // Token: wide emoji 💰
-                self.front.set(col, row, Cell::from_char_wide('💰', Color::Reset, Color::Reset, false));
-                self.front.set(col + 1, row, Cell::WIDE_CONT);
+            // Lasers blink, so they read as live
+            Tile::Laser if self.blink_on(w.anim_tick, 3)
+                                => ('═', '═', Color::Rgb{r:255,g:60,b:60}, Color::Rgb{r:90,g:10,b:10}),
+            Tile::Laser         => ('─', '─', Color::Rgb{r:140,g:30,b:30}, Color::Reset),
+            Tile::Spawner       => ('▞', '▚', Color::Rgb{r:255,g:80,b:80}, Color::Rgb{r:60,g:10,b:10}),
+            // Hub door whose level is already cleared
+            Tile::Door if w.hub_door_cleared(gx, gy)
+                                => ('▐', '▌', Color::Rgb{r:255,g:210,b:60}, Color::Rgb{r:60,g:45,b:0}),
+            Tile::Door          => ('▐', '▌', Color::Rgb{r:60,g:200,b:160}, Color::Rgb{r:10,g:50,b:40}),
+            t @ Tile::Custom(_) => custom_tile_cell(t),
+            Tile::Lantern       => {
+                self.put_wide(col, row, Cell::from_char_wide('🏮', Color::Reset, Color::Reset, false));
+                return;
+            }
+            t @ Tile::Gold(_)   => {
+                // Token: wide emoji (💰 and the richer kinds)
+                let glyph = t.token().map_or('💰', |k| k.glyph);
+                self.put_wide(col, row, Cell::from_char_wide(glyph, Color::Reset, Color::Reset, false));
                 return;
             }
         };
-        self.front.set(col, row, Cell::from_char(c0, fg, bg, false));
-        self.front.set(col + 1, row, Cell::from_char(c1, fg, bg, false));
+        self.put_pair(col, row, c0, c1, fg, bg);
     }
 
     // ── Static screens (title, game over, etc.) ──
@@ -586,58 +1872,50 @@ impl Renderer {
     fn compose_level_intro(&mut self, w: &WorldState) {
         let buf_w = self.front.width;
         let cam = &w.camera;
-        let tick = w.anim_tick;
+        let reveal = transition::strategy(self.display.intro_transition);
 
-        // Constants matching main.rs
-        let intro_name_ticks: u32 = 8;
-        let intro_row_interval: u32 = 2;
-
-        // How many rows are visible (from bottom of WORLD)
-        let rows_visible = if tick <= intro_name_ticks {
-            0
-        } else {
-            ((tick - intro_name_ticks) / intro_row_interval).min(w.height as u32) as usize
-        };
-
-        // Show entities only when all rows revealed
-        let show_entities = rows_visible >= w.height;
+        // Ticks into the reveal (after the name card)
+        let t = w.anim_tick.saturating_sub(transition::NAME_CARD_TICKS);
+        let started = w.anim_tick > transition::NAME_CARD_TICKS;
+        // Show entities (and drop the name card) once everything is revealed
+        let done = started && t >= reveal.duration(w.width, w.height);
 
         // ── HUD ──
-        let hud_bg = Color::Rgb{r:20,g:20,b:60};
-        for x in 0..buf_w {
-            self.front.set(x, HUD_ROW, Cell::from_char(' ', Color::White, hud_bg, false));
-        }
-        let hud = format!(
-            " Node.{:<2}  Score:{:<7}  ♥×{}  ${}/{}",
-            w.current_level + 1, w.score, w.lives,
-            w.gold_total - w.gold_remaining, w.gold_total,
-        );
-        self.front.put_str(0, HUD_ROW, &hud, Color::White, hud_bg, false);
+        self.compose_hud(w, "");
 
         // ── Level name display (centered in viewport) ──
-        let name_row = MAP_ROW + cam.view_h / 2 - 1;
-        if name_row < self.front.height && rows_visible < w.height {
+        let name_row = self.layout.map + cam.view_h / 2 - 1;
+        if name_row < self.front.height && !done {
             let name = format!(" ◈ {} ◈ ", w.level_name);
-            let view_cols = cam.view_w * CELL_W;
-            let cx = view_cols.saturating_sub(name.len()) / 2;
+            let view_cols = cam.view_w * self.cell_w();
+            let cx = view_cols.saturating_sub(text_width(&name)) / 2;
             self.front.put_str(cx, name_row, &name, Color::Rgb{r:255,g:220,b:50}, Color::Reset, true);
 
+            // The node's records in a line, under its name
+            if let Some(best) = best_line(w) {
+                let bx = view_cols.saturating_sub(text_width(&best)) / 2;
+                self.front.put_str(bx, name_row + 1, &best, Color::Rgb{r:100,g:200,b:255}, Color::Reset, false);
+            }
+
             // "GET READY" below
-            let ready = "▸▸▸ GET READY ◂◂◂";
-            let rx = view_cols.saturating_sub(ready.len()) / 2;
+            let ready = t!("intro.ready");
+            let rx = view_cols.saturating_sub(text_width(ready)) / 2;
             self.front.put_str(rx, name_row + 2, ready, Color::Rgb{r:80,g:255,b:80}, Color::Reset, false);
+
+            // Targets to beat, while the map is still mostly hidden
+            self.compose_boards(w, view_cols, name_row + 4, Color::Reset);
         }
 
-        // ── Map reveal from bottom (camera viewport) ──
+        // ── Map reveal (camera viewport) ──
         for vy in 0..cam.view_h {
             let wy = cam.y + vy as i32;
-            let row = MAP_ROW + vy;
+            let row = self.layout.map + vy;
             if row >= self.front.height { break; }
 
             for vx in 0..cam.view_w {
                 let wx = cam.x + vx as i32;
-                let col = vx * CELL_W;
-                if col + 1 >= buf_w { break; }
+                let col = vx * self.cell_w();
+                if col + self.cell_w() > buf_w { break; }
 
                 // Out of world bounds → void
                 if wx < 0 || wy < 0 || wx >= w.width as i32 || wy >= w.height as i32 {
@@ -648,86 +1926,83 @@ impl Renderer {
                 let gx = wx as usize;
                 let gy = wy as usize;
 
-                // Row gy is visible if (height - 1 - gy) < rows_visible
-                let from_bottom = w.height - 1 - gy;
-                if from_bottom >= rows_visible {
-                    // Not yet revealed → void
-                    self.compose_void(col, row);
-                    continue;
-                }
-
-                // Reveal effect: the freshest row gets a highlight
-                let is_frontier = from_bottom + 1 == rows_visible;
-
-                if is_frontier {
-                    let tile = w.tiles[gy][gx];
-                    let (c0, c1) = match tile {
-                        Tile::Empty => (' ', ' '),
-                        Tile::Brick | Tile::TrapBrick => ('▓', '▓'),
-                        Tile::Concrete => ('█', '█'),
-                        Tile::Ladder => ('╠', '╣'),
-                        Tile::Rope => ('━', '━'),
-                        Tile::Gold => ('◆', '◆'),
-                        Tile::HiddenLadder => (' ', ' '),
-                    };
-                    let flash_fg = Color::Rgb{r:180,g:255,b:255};
-                    let flash_bg = Color::Rgb{r:0,g:40,b:60};
-                    self.front.set(col, row, Cell::from_char(c0, flash_fg, flash_bg, true));
-                    self.front.set(col + 1, row, Cell::from_char(c1, flash_fg, flash_bg, true));
-                } else if show_entities {
-                    self.compose_cell(w, gx, gy, col, row);
+                let state = if started {
+                    reveal.reveal(gx, gy, w.width, w.height, t)
                 } else {
-                    self.compose_tile_only(w, gx, gy, col, row);
+                    Reveal::Hidden
+                };
+                match state {
+                    Reveal::Hidden => self.compose_void(col, row),
+                    // Reveal effect: the freshest cells get a highlight
+                    Reveal::Frontier => self.compose_frontier(w, gx, gy, col, row),
+                    Reveal::Shown if done => self.compose_cell(w, gx, gy, col, row),
+                    Reveal::Shown => self.compose_tile_only(w, gx, gy, col, row),
                 }
             }
         }
 
         // ── "ENTER to skip" hint ──
-        let hint_row = MAP_ROW + cam.view_h + 1;
-        if hint_row < self.front.height && rows_visible < w.height {
-            let hint = " Press ENTER to skip ";
+        let hint_row = self.layout.prompt(cam.view_h);
+        if hint_row < self.front.height && !done {
+            let hint = t!("intro.skip");
             self.front.put_str(0, hint_row, hint, Color::DarkGrey, Color::Reset, false);
         }
     }
 
+    /// Highlighted glyph for a cell on the transition frontier.
+    fn compose_frontier(&mut self, w: &WorldState, gx: usize, gy: usize, col: usize, row: usize) {
+        let (c0, c1) = match w.tiles[(gx, gy)] {
+            Tile::Empty => (' ', ' '),
+            Tile::Brick | Tile::TrapBrick | Tile::FakeBrick => ('▓', '▓'),
+            Tile::Concrete => ('█', '█'),
+            Tile::Ladder => ('╠', '╣'),
+            Tile::Rope => ('━', '━'),
+            Tile::Laser => ('═', '═'),
+            Tile::Spawner => ('▞', '▚'),
+            Tile::Lantern => ('◇', '◇'),
+            Tile::Gold(_) => ('◆', '◆'),
+            Tile::Door => ('▐', '▌'),
+            Tile::HiddenLadder => (' ', ' '),
+            t @ Tile::Custom(_) => {
+                let (c0, c1, _, _) = custom_tile_cell(t);
+                (c0, c1)
+            }
+        };
+        let flash_fg = Color::Rgb{r:180,g:255,b:255};
+        let flash_bg = Color::Rgb{r:0,g:40,b:60};
+        self.put_pair(col, row, c0, c1, flash_fg, flash_bg);
+    }
+
     /// Level ready: full map visible with entities, blinking "PRESS ANY KEY" prompt
     fn compose_level_ready(&mut self, w: &WorldState) {
         let buf_w = self.front.width;
         let cam = &w.camera;
 
         // ── HUD ──
-        let hud_bg = Color::Rgb{r:20,g:20,b:60};
-        for x in 0..buf_w {
-            self.front.set(x, HUD_ROW, Cell::from_char(' ', Color::White, hud_bg, false));
-        }
-        let hud = format!(
-            " Node.{:<2}  Score:{:<7}  ♥×{}  ${}/{}",
-            w.current_level + 1, w.score, w.lives,
-            w.gold_total - w.gold_remaining, w.gold_total,
-        );
-        self.front.put_str(0, HUD_ROW, &hud, Color::White, hud_bg, false);
+        self.compose_hud(w, "");
 
         // ── Full map with all entities (camera viewport) ──
         for vy in 0..cam.view_h {
             let wy = cam.y + vy as i32;
-            let row = MAP_ROW + vy;
+            let row = self.layout.map + vy;
             if row >= self.front.height { break; }
             for vx in 0..cam.view_w {
                 let wx = cam.x + vx as i32;
-                let col = vx * CELL_W;
-                if col + 1 >= buf_w { break; }
+                let col = vx * self.cell_w();
+                if col + self.cell_w() > buf_w { break; }
                 self.compose_cell_cam(w, wx, wy, col, row);
             }
         }
+        self.compose_tip(w);
 
         // ── Blinking "PRESS ANY KEY" prompt ──
-        let blink = (w.anim_tick / 5) % 2 == 0;
-        let prompt_row = MAP_ROW + cam.view_h + 1;
+        let blink = self.blink_on(w.anim_tick, 5);
+        let prompt_row = self.layout.prompt(cam.view_h);
         if prompt_row < self.front.height {
             if blink {
-                let prompt = " ▸▸▸ PRESS ANY KEY TO START ◂◂◂ ";
-                let view_cols = cam.view_w * CELL_W;
-                let cx = view_cols.saturating_sub(prompt.len()) / 2;
+                let prompt = t!("intro.press_any_key");
+                let view_cols = cam.view_w * self.cell_w();
+                let cx = view_cols.saturating_sub(text_width(prompt)) / 2;
                 for x in 0..buf_w {
                     self.front.set(x, prompt_row, Cell::from_char(' ', Color::Black, Color::Rgb{r:200,g:180,b:50}, false));
                 }
@@ -738,22 +2013,35 @@ impl Renderer {
 
     /// Render a tile without entities (for intro animation)
     fn compose_tile_only(&mut self, w: &WorldState, gx: usize, gy: usize, col: usize, row: usize) {
-        let (c0, c1, fg, bg) = match w.tiles[gy][gx] {
+        if self.half_block() {
+            self.put_pixels(col, row, halfblock::tile(w.tiles[(gx, gy)], gx, gy, false));
+            return;
+        }
+
+        let (c0, c1, fg, bg) = match w.tiles[(gx, gy)] {
             Tile::Empty => (' ', ' ', Color::Reset, Color::Reset),
             Tile::Brick         => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
             Tile::TrapBrick     => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
+            Tile::FakeBrick     => ('░', '░', Color::Rgb{r:180,g:120,b:60}, Color::Rgb{r:100,g:65,b:30}),
             Tile::Concrete      => ('█', '█', Color::Rgb{r:120,g:120,b:120}, Color::Rgb{r:70,g:70,b:70}),
             Tile::Ladder        => ('╠', '╣', Color::Rgb{r:100,g:200,b:255}, Color::Reset),
             Tile::HiddenLadder  => (' ', ' ', Color::Reset, Color::Reset),
             Tile::Rope          => ('━', '━', Color::Rgb{r:180,g:100,b:200}, Color::Reset),
-            Tile::Gold          => {
-                self.front.set(col, row, Cell::from_char_wide('💰', Color::Reset, Color::Reset, false));
-                self.front.set(col + 1, row, Cell::WIDE_CONT);
+            Tile::Laser         => ('═', '═', Color::Rgb{r:255,g:60,b:60}, Color::Rgb{r:90,g:10,b:10}),
+            Tile::Spawner       => ('▞', '▚', Color::Rgb{r:255,g:80,b:80}, Color::Rgb{r:60,g:10,b:10}),
+            Tile::Door          => ('▐', '▌', Color::Rgb{r:60,g:200,b:160}, Color::Rgb{r:10,g:50,b:40}),
+            t @ Tile::Custom(_) => custom_tile_cell(t),
+            Tile::Lantern       => {
+                self.put_wide(col, row, Cell::from_char_wide('🏮', Color::Reset, Color::Reset, false));
+                return;
+            }
+            t @ Tile::Gold(_)   => {
+                let glyph = t.token().map_or('💰', |k| k.glyph);
+                self.put_wide(col, row, Cell::from_char_wide(glyph, Color::Reset, Color::Reset, false));
                 return;
             }
         };
-        self.front.set(col, row, Cell::from_char(c0, fg, bg, false));
-        self.front.set(col + 1, row, Cell::from_char(c1, fg, bg, false));
+        self.put_pair(col, row, c0, c1, fg, bg);
     }
 
     /// Animated game view: handles LevelOutro, LevelComplete, and Dying phases
@@ -762,27 +2050,24 @@ impl Renderer {
         let cam = &w.camera;
 
         // ── HUD ──
-        let gold_status = if w.exit_enabled { "ESCAPE!" } else { "" };
-        let hud = format!(
-            " Node.{:<2}  Score:{:<7}  ♥×{}  ${}/{}  {} ",
-            w.current_level + 1, w.score, w.lives,
-            w.gold_total - w.gold_remaining, w.gold_total, gold_status,
-        );
-        for x in 0..buf_w {
-            self.front.set(x, HUD_ROW, Cell::from_char(' ', Color::White, Color::Rgb{r:20,g:20,b:60}, false));
-        }
-        self.front.put_str(0, HUD_ROW, &hud, Color::White, Color::Rgb{r:20,g:20,b:60}, false);
+        let gold_status = if w.exit_enabled { t!("hud.escape") } else { "" };
+        self.compose_hud(w, gold_status);
 
         // ── Map (camera viewport, tiles + guards, player handled specially) ──
+        // "instant" means no wipe: the map stays up until the clear box
+        let wipe = (self.display.outro_transition != Transition::Instant)
+            .then(|| transition::strategy(self.display.outro_transition));
+        // Once the clear box is up the wipe is over, frontier included
+        let wipe_t = if w.phase == Phase::LevelComplete { u32::MAX / 2 } else { w.anim_tick };
         for vy in 0..cam.view_h {
             let wy = cam.y + vy as i32;
-            let row = MAP_ROW + vy;
+            let row = self.layout.map + vy;
             if row >= self.front.height { break; }
 
             for vx in 0..cam.view_w {
                 let wx = cam.x + vx as i32;
-                let col = vx * CELL_W;
-                if col + 1 >= buf_w { break; }
+                let col = vx * self.cell_w();
+                if col + self.cell_w() > buf_w { break; }
 
                 if wx < 0 || wy < 0 || wx >= w.width as i32 || wy >= w.height as i32 {
                     self.compose_void(col, row);
@@ -792,6 +2077,21 @@ impl Renderer {
                 let gx = wx as usize;
                 let gy = wy as usize;
 
+                // Outro wipe: cells disappear in the outro transition's order
+                if let (Some(wipe), Phase::LevelOutro | Phase::LevelComplete) = (wipe, w.phase) {
+                    match wipe.reveal(gx, gy, w.width, w.height, wipe_t) {
+                        Reveal::Hidden => {}
+                        Reveal::Frontier => {
+                            self.compose_frontier(w, gx, gy, col, row);
+                            continue;
+                        }
+                        Reveal::Shown => {
+                            self.compose_void(col, row);
+                            continue;
+                        }
+                    }
+                }
+
                 // Skip player position - handled below with animation
                 let is_player_pos = w.player.x == gx && w.player.y == gy;
 
@@ -813,28 +2113,26 @@ impl Renderer {
                 let vy = py_world - cam.y;
                 let vx = px_world - cam.x;
                 if vy >= 0 && vx >= 0 && (vx as usize) < cam.view_w {
-                    let row = MAP_ROW + vy as usize;
-                    let col = vx as usize * CELL_W;
-                    if row < self.front.height && col + 1 < buf_w {
-                        self.front.set(col, row, Cell::from_char_wide('🧗', Color::Reset, Color::Reset, false));
-                        self.front.set(col + 1, row, Cell::WIDE_CONT);
+                    let row = self.layout.map + vy as usize;
+                    let col = vx as usize * self.cell_w();
+                    if row < self.front.height && col + self.cell_w() <= buf_w {
+                        self.put_wide(col, row, Cell::from_char_wide('🧗', Color::Reset, Color::Reset, false));
                     }
                 }
             }
             Phase::Dying => {
-                let visible = (w.anim_tick / 2) % 2 == 0;
+                let visible = self.blink_on(w.anim_tick, 2);
                 if visible {
                     if let Some((vx, vy)) = cam.world_to_view(w.player.x, w.player.y) {
-                        let row = MAP_ROW + vy;
-                        let col = vx * CELL_W;
-                        if row < self.front.height && col + 1 < buf_w {
-                            let flash = if w.anim_tick < 6 {
+                        let row = self.layout.map + vy;
+                        let col = vx * self.cell_w();
+                        if row < self.front.height && col + self.cell_w() <= buf_w {
+                            let flash = if w.anim_tick < 6 && !self.display.reduced_motion {
                                 Color::Rgb{r:255,g:60,b:60}
                             } else {
                                 Color::Rgb{r:200,g:200,b:200}
                             };
-                            self.front.set(col, row, Cell::from_char_wide('🧍', flash, Color::Reset, false));
-                            self.front.set(col + 1, row, Cell::WIDE_CONT);
+                            self.put_wide(col, row, Cell::from_char_wide('🧍', flash, Color::Reset, false));
                         }
                     }
                 }
@@ -843,45 +2141,108 @@ impl Renderer {
         }
 
         // ── Message bar ──
-        let msg_row = MAP_ROW + cam.view_h + 1;
-        if msg_row < self.front.height && !w.message.is_empty() {
-            let msg = format!(" ◈ {} ", w.message);
-            for x in 0..buf_w {
-                self.front.set(x, msg_row, Cell::from_char(' ', Color::Black, Color::Rgb{r:200,g:180,b:50}, false));
-            }
-            self.front.put_str(0, msg_row, &msg, Color::Black, Color::Rgb{r:200,g:180,b:50}, false);
+        if let Some(msg_row) = self.layout.message {
+            let room = self.stack_room(cam.view_h);
+            self.compose_messages(w, msg_row, room);
         }
 
         // ── Level complete overlay (centered in viewport) ──
         if w.phase == Phase::LevelComplete {
-            let cy = MAP_ROW + cam.view_h / 2;
-            if cy < self.front.height {
-                let border = "╔══════════════════════════════╗";
-                let middle = "║   ★ NODE CLEARED ★           ║";
-                let prompt = "║  ENTER: Next  ESC: Title     ║";
-                let bottom = "╚══════════════════════════════╝";
-                let view_cols = cam.view_w * CELL_W;
-                let cx = view_cols.saturating_sub(border.len()) / 2;
+            let stats = clear_stats(w);
+            let top = (self.layout.map + cam.view_h / 2).saturating_sub(stats.len() / 2 + 1);
+            if top + stats.len() + 3 < self.front.height {
+                let inner = stats.iter().map(|s| text_width(s) + 4)
+                    .fold(30.max(text_width(t!("clear.title")) + 4).max(text_width(t!("clear.prompt")) + 4), usize::max);
+                let [border, middle, bottom] = boxed(t!("clear.title"), inner);
+                let [_, prompt, _] = boxed(t!("clear.prompt"), inner);
+                let view_cols = cam.view_w * self.cell_w();
+                let cx = view_cols.saturating_sub(text_width(&border)) / 2;
                 let fg = Color::Rgb{r:255,g:220,b:50};
                 let bg = Color::Rgb{r:20,g:60,b:20};
-                self.front.put_str(cx, cy - 1, border, fg, bg, true);
-                self.front.put_str(cx, cy,     middle, fg, bg, true);
-                self.front.put_str(cx, cy + 1, prompt, Color::Rgb{r:80,g:255,b:80}, bg, false);
-                self.front.put_str(cx, cy + 2, bottom, fg, bg, true);
+                self.front.put_str(cx, top,     &border, fg, bg, true);
+                self.front.put_str(cx, top + 1, &middle, fg, bg, true);
+                for (i, line) in stats.iter().enumerate() {
+                    let row = format!("║  {}║", pad_width(line, inner - 2));
+                    self.front.put_str(cx, top + 2 + i, &row, Color::White, bg, false);
+                }
+                let cy = top + 2 + stats.len();
+                self.front.put_str(cx, cy,     &prompt, Color::Rgb{r:80,g:255,b:80}, bg, false);
+                self.front.put_str(cx, cy + 1, &bottom, fg, bg, true);
+                self.compose_boards(w, view_cols, cy + 3, bg);
             }
         }
     }
 
+    /// Local board centred, or local and global side by side once the
+    /// online top 10 has arrived.
+    fn compose_boards(&mut self, w: &WorldState, view_cols: usize, row: usize, bg: Color) {
+        if w.global_board.is_empty() {
+            self.compose_board(w, view_cols.saturating_sub(BOARD_W) / 2, row, bg);
+            return;
+        }
+        let bx = view_cols.saturating_sub(BOARD_W * 2 + 2) / 2;
+        self.compose_board(w, bx, row, bg);
+        self.compose_global_board(w, bx + BOARD_W + 2, row, bg);
+    }
+
+    /// Online top 10: scores in normal play, clear times in time attack.
+    fn compose_global_board(&mut self, w: &WorldState, col: usize, row: usize, bg: Color) {
+        let head = Color::Rgb{r:100,g:200,b:255};
+        self.put_padded(col, row, t!("board.world"), head, bg);
+        for (i, e) in w.global_board.iter().enumerate() {
+            let value = match w.mode {
+                GameMode::Normal => e.score.map_or_else(String::new, |s| s.to_string()),
+                GameMode::TimeAttack => {
+                    let s = e.time_ms / 1000;
+                    format!("{}:{:02}.{}", s / 60, s % 60, e.time_ms % 1000 / 100)
+                }
+            };
+            let line = format!("{:>2}. {} {:>9}", i + 1, pad_width(&e.name, 10), value);
+            self.put_padded(col, row + 1 + i, &line, Color::White, bg);
+        }
+    }
+
+    /// This node's best scores and times, one place per row under a
+    /// heading. The clear just recorded is highlighted. Nothing if empty.
+    fn compose_board(&mut self, w: &WorldState, col: usize, row: usize, bg: Color) {
+        let b = &w.board;
+        let rows = b.scores.len().max(b.times_ms.len());
+        if rows == 0 { return; }
+        let head = Color::Rgb{r:255,g:200,b:50};
+        let hi = Color::Rgb{r:80,g:255,b:80};
+        self.put_padded(col, row, t!("board.best"), head, bg);
+        for i in 0..rows {
+            let r = row + 1 + i;
+            if r >= self.front.height { break; }
+            let score = b.scores.get(i).map_or("-".to_string(), |s| s.to_string());
+            let time = b.times_ms.get(i).map_or("-".to_string(), |&ms| clear_time(ms));
+            let line = format!("  {}.  {:>6}  {:>7}", i + 1, score, time);
+            let fresh = b.new_score == Some(i) || b.new_time == Some(i);
+            self.put_padded(col, r, &line, if fresh { hi } else { Color::White }, bg);
+        }
+    }
+
+    /// `text` left-aligned in a BOARD_W-wide strip.
+    fn put_padded(&mut self, col: usize, row: usize, text: &str, fg: Color, bg: Color) {
+        if row >= self.front.height { return; }
+        self.front.put_str(col, row, &pad_width(text, BOARD_W), fg, bg, false);
+    }
+
     /// Compose a game cell without rendering the player (for animated phases)
     fn compose_cell_no_player(&mut self, w: &WorldState, gx: usize, gy: usize, col: usize, row: usize) {
-        // Guards
-        for g in &w.guards {
-            if g.state == ActorState::Dead { continue; }
-            if g.x == gx && g.y == gy {
-                self.front.set(col, row, Cell::from_char_wide('🤺', Color::Reset, Color::Reset, false));
-                self.front.set(col + 1, row, Cell::WIDE_CONT);
-                return;
+        if self.half_block() {
+            let mut px = halfblock::terrain(w, gx, gy, false);
+            if let Some(s) = halfblock::actor_at(w, gx, gy, false) {
+                px = halfblock::overlay(px, &s);
             }
+            self.put_pixels(col, row, px);
+            return;
+        }
+
+        // Guards
+        if let Some(g) = w.occupancy.first(gx, gy).and_then(|i| w.guards.get(i)) {
+            self.put_wide(col, row, guard_cell(g, w.speed.trap_escape_ticks));
+            return;
         }
 
         // Dig in progress
@@ -893,8 +2254,7 @@ impl Renderer {
                     2 => ('░', '░', Color::DarkYellow, Color::Reset),
                     _ => ('·', '·', Color::DarkYellow, Color::Reset),
                 };
-                self.front.set(col, row, Cell::from_char(c0, fg, bg, false));
-                self.front.set(col + 1, row, Cell::from_char(c1, fg, bg, false));
+                self.put_pair(col, row, c0, c1, fg, bg);
                 return;
             }
         }
@@ -911,11 +2271,11 @@ impl Renderer {
                     } else {
                         ('▅', Color::Rgb{r:60,g:45,b:0})
                     };
-                    self.front.set(col, row, Cell::from_char(ch, Color::DarkYellow, bg, false));
-                    self.front.set(col + 1, row, Cell::from_char(ch, Color::DarkYellow, bg, false));
+                    self.put_pair(col, row, ch, ch, Color::DarkYellow, bg);
                 } else {
-                    self.front.set(col, row, Cell::from_char(' ', Color::Reset, Color::Rgb{r:10,g:8,b:0}, false));
-                    self.front.set(col + 1, row, Cell::from_char(' ', Color::Reset, Color::Rgb{r:10,g:8,b:0}, false));
+                    // Warms up as the seal approaches, so the next hole to close stands out
+                    let pit = halfblock::pit_color(hole.open_progress(w.speed.hole_open_ticks));
+                    self.put_pair(col, row, ' ', ' ', Color::Reset, pit);
                 }
                 return;
             }
@@ -937,12 +2297,12 @@ impl Renderer {
             self.front.put_str(2, 2 + i, line, Color::Rgb{r:255,g:200,b:50}, Color::Reset, true);
         }
 
-        let subtitle = "◈◈  Mainnet Protocol  ◈◈";
-        let sx = 2 + (title[1].len().saturating_sub(subtitle.len())) / 2;
+        let subtitle = t!("title.subtitle");
+        let sx = 2 + (title[1].len().saturating_sub(text_width(subtitle))) / 2;
         self.front.put_str(sx, 7, subtitle, Color::Rgb{r:80,g:255,b:80}, Color::Reset, true);
 
-        let tagline = "━━━ Terminal Edition (Rust) ━━━";
-        let tx = 2 + (title[1].len().saturating_sub(tagline.len())) / 2;
+        let tagline = t!("title.tagline");
+        let tx = 2 + (title[1].len().saturating_sub(text_width(tagline))) / 2;
         self.front.put_str(tx, 9, tagline, Color::Rgb{r:180,g:140,b:50}, Color::Reset, false);
 
         // Menu options
@@ -950,47 +2310,74 @@ impl Renderer {
         let hi = Color::Rgb{r:80,g:255,b:80};
         let dim = Color::DarkGrey;
 
-        self.front.put_str(8, menu_base,     "ENTER   New Game", hi, Color::Reset, true);
+        if let Some(level) = w.suspended {
+            let text = t!("title.resume", n = level + 1);
+            self.front.put_str(6, menu_base - 1, &text, Color::Rgb{r:255,g:220,b:50}, Color::Reset, true);
+        }
+        self.front.put_str(8, menu_base,     t!("title.new_game"), hi, Color::Reset, true);
         if w.has_save {
-            self.front.put_str(8, menu_base + 1, "  C     Continue", Color::Rgb{r:255,g:220,b:50}, Color::Reset, false);
+            self.front.put_str(8, menu_base + 1, t!("title.continue"), Color::Rgb{r:255,g:220,b:50}, Color::Reset, false);
+        } else {
+            self.front.put_str(8, menu_base + 1, t!("title.continue_none"), dim, Color::Reset, false);
+        }
+        self.front.put_str(8, menu_base + 2, t!("title.time_attack"), Color::Rgb{r:255,g:140,b:60}, Color::Reset, false);
+        self.front.put_str(8, menu_base + 3, t!("title.level_select"), Color::White, Color::Reset, false);
+        self.front.put_str(8, menu_base + 4, t!("title.packs"), Color::Rgb{r:100,g:200,b:255}, Color::Reset, false);
+        self.front.put_str(8, menu_base + 5, t!("title.play_code"), Color::White, Color::Reset, false);
+        self.front.put_str(8, menu_base + 6, t!("title.quit"), Color::White, Color::Reset, false);
+        let (text, fg) = if w.mirror {
+            (t!("title.mirror_on"), Color::Rgb{r:100,g:200,b:255})
         } else {
-            self.front.put_str(8, menu_base + 1, "  C     Continue  (no save)", dim, Color::Reset, false);
+            (t!("title.mirror_off"), dim)
+        };
+        self.front.put_str(8, menu_base + 7, text, fg, Color::Reset, false);
+        match w.randomizer {
+            Some(r) => {
+                let text = t!("title.randomizer_on", seed = format!("{:08x}", r.seed));
+                self.front.put_str(8, menu_base + 8, &text, Color::Rgb{r:255,g:200,b:50}, Color::Reset, false);
+            }
+            None => self.front.put_str(8, menu_base + 8, t!("title.randomizer_off"), dim, Color::Reset, false),
+        }
+        if w.ng_plus {
+            let (text, fg) = if w.invisible_guards {
+                (t!("title.nightmare_on"), Color::Rgb{r:255,g:80,b:80})
+            } else {
+                (t!("title.nightmare_off"), dim)
+            };
+            self.front.put_str(8, menu_base + 9, text, fg, Color::Reset, false);
         }
-        self.front.put_str(8, menu_base + 2, "  L     Level Select", Color::White, Color::Reset, false);
-        self.front.put_str(8, menu_base + 3, "  F3    Level Packs", Color::Rgb{r:100,g:200,b:255}, Color::Reset, false);
-        self.front.put_str(8, menu_base + 4, "  Q     Quit", Color::White, Color::Reset, false);
 
         // Pack and level info
-        let pack_info = format!("      📦 {}  ({} levels)", w.active_pack, w.total_levels);
-        self.front.put_str(8, menu_base + 6, &pack_info, dim, Color::Reset, false);
+        let pack_info = t!("title.pack_info", pack = w.active_pack, n = w.total_levels);
+        self.front.put_str(8, menu_base + 10, &pack_info, dim, Color::Reset, false);
 
         // Controls reference
         let help = [
-            "Controls",
-            "  ←→↑↓ / WASD   Move          Z/Q Hack L",
-            "  X/E            Hack R        ESC Title",
-            "  F1 Pause   F2 Restart   F3 Level Packs",
-            "  F4 Level Select              F5-F8 Save",
-            "  F9-F12 Load Slot 1-4",
+            t!("title.controls"),
+            t!("title.controls_1"),
+            t!("title.controls_2"),
+            t!("title.controls_3"),
+            t!("title.controls_4"),
+            t!("title.controls_5"),
         ];
 
-        let help_base = menu_base + 8;
+        let help_base = menu_base + 12;
         for (i, line) in help.iter().enumerate() {
             let color = if i == 0 { Color::Rgb{r:255,g:200,b:50} } else { Color::White };
             self.front.put_str(8, help_base + i, line, color, Color::Reset, false);
         }
 
         // Message bar (for pack switch confirmation, etc.)
-        if !w.message.is_empty() {
-            let msg_row = self.front.height.saturating_sub(1);
-            if msg_row > help_base + help.len() {
-                let msg = format!(" ◈ {} ", w.message);
-                let buf_w = self.front.width;
-                for x in 0..buf_w {
-                    self.front.set(x, msg_row, Cell::from_char(' ', Color::Black, Color::Rgb{r:200,g:180,b:50}, false));
-                }
-                self.front.put_str(0, msg_row, &msg, Color::Black, Color::Rgb{r:200,g:180,b:50}, false);
-            }
+        let msg_row = self.front.height.saturating_sub(1);
+        if msg_row > help_base + help.len() {
+            self.compose_messages(w, msg_row, help_base + help.len() + 1..msg_row);
+        }
+
+        if let Some(text) = &w.code_prompt {
+            let inner = 56_usize.min(self.front.width).saturating_sub(4);
+            let cursor = if self.blink_on(w.anim_tick, 4) { "▌" } else { " " };
+            let field = format!("{}{cursor}", tail(text, inner.saturating_sub(1)));
+            self.compose_dialog(t!("code.prompt_title"), &field, t!("code.prompt_keys"));
         }
     }
 
@@ -1001,9 +2388,9 @@ impl Renderer {
         let cursor_bg = Color::Rgb{r:30,g:60,b:30};
 
         // Header
-        self.front.put_str(2, 1, "╔═══════════════════════════════════════════╗", Color::Rgb{r:255,g:200,b:50}, Color::Reset, true);
-        self.front.put_str(2, 2, "║          LEVEL  SELECT                    ║", Color::Rgb{r:255,g:200,b:50}, Color::Reset, true);
-        self.front.put_str(2, 3, "╚═══════════════════════════════════════════╝", Color::Rgb{r:255,g:200,b:50}, Color::Reset, true);
+        for (i, line) in boxed(t!("select.header"), 43).iter().enumerate() {
+            self.front.put_str(2, 1 + i, line, Color::Rgb{r:255,g:200,b:50}, Color::Reset, true);
+        }
 
         // Active pack indicator
         let pack_str = format!("  📦 {}", w.active_pack);
@@ -1035,17 +2422,12 @@ impl Renderer {
                 "???"
             };
 
-            // Truncate name to fit
-            let max_name = 40;
-            let display_name: String = if name.len() > max_name {
-                format!("{}...", &name[..max_name - 3])
-            } else {
-                name.to_string()
-            };
+            // Truncate name to fit the highlight row
+            let display_name = ellipsize(name, 40);
 
             if is_selected {
                 // Blinking cursor indicator
-                let blink = (w.anim_tick / 5) % 2 == 0;
+                let blink = self.blink_on(w.anim_tick, 5);
                 let arrow = if blink { "▸" } else { " " };
 
                 // Fill row with highlight
@@ -1072,10 +2454,33 @@ impl Renderer {
         // Footer
         let footer_row = list_top + visible + 2;
         if footer_row < self.front.height {
-            self.front.put_str(2, footer_row, "  ENTER: Start   ↑↓: Select   PgUp/PgDn   F3: Packs   ESC: Back", dim, Color::Reset, false);
-            let count_str = format!("  {}/{} levels", w.select_cursor + 1, total);
+            self.front.put_str(2, footer_row, t!("select.footer"), dim, Color::Reset, false);
+            let count_str = t!("select.count", n = w.select_cursor + 1, total = total);
             if footer_row + 1 < self.front.height {
-                self.front.put_str(2, footer_row + 1, &count_str, dim, Color::Reset, false);
+                let share = if w.served { t!("select.share_served") } else { t!("select.share") };
+                let line = format!("{count_str}   {share}");
+                self.front.put_str(2, footer_row + 1, &line, dim, Color::Reset, false);
+            }
+            self.compose_messages(w, footer_row + 3, 0..0);
+        }
+
+        if let Some(dialog) = &w.share {
+            self.compose_share_dialog(w, dialog);
+        }
+    }
+
+    /// A level's code to copy, or the prompt to paste one.
+    fn compose_share_dialog(&mut self, w: &WorldState, dialog: &ShareDialog) {
+        match dialog {
+            ShareDialog::Export { name, code } => {
+                let title = t!("share.export_title", name = name);
+                self.compose_dialog(&title, code, t!("share.export_keys"));
+            }
+            ShareDialog::Import { text } => {
+                let inner = 56_usize.min(self.front.width).saturating_sub(4);
+                let cursor = if self.blink_on(w.anim_tick, 4) { "▌" } else { " " };
+                let field = format!("{}{cursor}", tail(text, inner.saturating_sub(1)));
+                self.compose_dialog(t!("share.import_title"), &field, t!("share.import_keys"));
             }
         }
     }
@@ -1090,12 +2495,12 @@ impl Renderer {
         let active_fg = Color::Rgb{r:255,g:180,b:80};
 
         // Header
-        self.front.put_str(2, 1, "╔═══════════════════════════════════════════════════╗", gold, Color::Reset, true);
-        self.front.put_str(2, 2, "║            📦 LEVEL PACK SELECT                   ║", gold, Color::Reset, true);
-        self.front.put_str(2, 3, "╚═══════════════════════════════════════════════════╝", gold, Color::Reset, true);
+        for (i, line) in boxed(t!("packs.header"), 51).iter().enumerate() {
+            self.front.put_str(2, 1 + i, line, gold, Color::Reset, true);
+        }
 
         // Active pack indicator
-        let active_str = format!("  Active: {}", w.active_pack);
+        let active_str = t!("packs.active", pack = w.active_pack);
         self.front.put_str(2, 5, &active_str, active_fg, Color::Reset, false);
 
         // Pack list
@@ -1120,11 +2525,12 @@ impl Renderer {
             let is_active = pack.path == w.active_pack_path;
 
             let marker = if is_active { "★" } else { " " };
-            let name_line = format!("{}  {}", marker, pack.name);
-            let count_str = format!("{} levels", pack.level_count);
+            // Keep clear of the level count at column 46
+            let name_line = format!("{}  {}", marker, ellipsize(&pack.name, 40));
+            let count_str = t!("packs.levels", n = pack.level_count);
 
             if is_selected {
-                let blink = (w.anim_tick / 5) % 2 == 0;
+                let blink = self.blink_on(w.anim_tick, 5);
                 let arrow = if blink { "▸" } else { " " };
 
                 // Highlight rows
@@ -1143,17 +2549,13 @@ impl Renderer {
 
                 // Row 2: author
                 if !pack.author.is_empty() {
-                    let author_str = format!("     by {}", pack.author);
+                    let author_str = ellipsize(&t!("packs.by", author = pack.author), 54);
                     self.front.put_str(2, row + 1, &author_str, normal, cursor_bg, false);
                 }
 
                 // Row 3: description
                 if !pack.description.is_empty() {
-                    let desc: String = if pack.description.len() > 50 {
-                        format!("     {}...", &pack.description[..47])
-                    } else {
-                        format!("     {}", pack.description)
-                    };
+                    let desc = format!("     {}", ellipsize(&pack.description, 49));
                     self.front.put_str(2, row + 2, &desc, dim, cursor_bg, false);
                 }
             } else {
@@ -1162,7 +2564,7 @@ impl Renderer {
                 self.front.put_str(46, row, &count_str, dim, Color::Reset, false);
 
                 if !pack.author.is_empty() {
-                    let author_str = format!("     by {}", pack.author);
+                    let author_str = ellipsize(&t!("packs.by", author = pack.author), 53);
                     self.front.put_str(3, row + 1, &author_str, dim, Color::Reset, false);
                 }
             }
@@ -1181,7 +2583,7 @@ impl Renderer {
         if detail_row + 2 < self.front.height && w.pack_cursor < total {
             let pack = &w.pack_list[w.pack_cursor];
             let path_display = if pack.path.starts_with("__") {
-                "(built-in)".to_string()
+                t!("packs.builtin").to_string()
             } else {
                 // Show just the filename
                 std::path::Path::new(&pack.path)
@@ -1190,66 +2592,348 @@ impl Renderer {
                     .to_string_lossy()
                     .to_string()
             };
-            let detail = format!("  Source: {}", path_display);
+            let detail = t!("packs.source", path = path_display);
             self.front.put_str(2, detail_row, &detail, dim, Color::Reset, false);
         }
 
+        // Preview of the highlighted pack, right of the list
+        if let Some(preview) = &w.pack_preview {
+            self.compose_pack_preview(w, preview, PREVIEW_COL, list_top);
+        }
+
+        // Results of deleting / renaming
+        let msg_row = self.front.height.saturating_sub(3);
+        if msg_row > list_top {
+            self.compose_messages(w, msg_row, 0..0);
+        }
+
+        if let Some(dialog) = &w.pack_dialog {
+            self.compose_pack_dialog(w, dialog);
+        }
+
         // Footer
         let footer_row = self.front.height.saturating_sub(2);
         if footer_row > list_top {
-            self.front.put_str(2, footer_row, "  ENTER: Select Pack   ↑↓: Browse   ESC: Back", dim, Color::Reset, false);
-            let hint = "  Place .nlp files in packs/ to add level packs";
-            if footer_row + 1 < self.front.height {
+            let footer = if w.served { t!("packs.footer_served") } else { t!("packs.footer") };
+            self.front.put_str(2, footer_row, footer, dim, Color::Reset, false);
+            let hint = t!("packs.hint");
+            if footer_row + 1 < self.front.height && !w.served {
                 self.front.put_str(2, footer_row + 1, hint, Color::Rgb{r:80,g:80,b:100}, Color::Reset, false);
             }
         }
     }
 
+    /// Pack builder: metadata fields, then the `levels/` files with their
+    /// place in the pack.
+    fn compose_pack_builder(&mut self, w: &WorldState) {
+        let b = &w.builder;
+        let gold = Color::Rgb{r:255,g:200,b:50};
+        let hi = Color::Rgb{r:80,g:255,b:80};
+        let cyan = Color::Rgb{r:100,g:200,b:255};
+        let dim = Color::DarkGrey;
+        let cursor_bg = Color::Rgb{r:20,g:50,b:60};
+        let width = self.front.width;
+
+        for (i, line) in boxed(t!("builder.header"), 51).iter().enumerate() {
+            self.front.put_str(2, 1 + i, line, gold, Color::Reset, true);
+        }
+
+        // Metadata fields
+        let labels = [t!("builder.name"), t!("builder.author"), t!("builder.description")];
+        for (i, label) in labels.iter().enumerate() {
+            let row = 5 + i;
+            let editing = b.editing == Some(i);
+            let cursor = if editing && self.blink_on(w.anim_tick, 4) { "▌" } else { "" };
+            let value = format!("{}{}", b.fields[i], cursor);
+            let (fg, bg) = if editing { (hi, cursor_bg) } else { (Color::White, Color::Reset) };
+            self.front.put_str(2, row, label, cyan, Color::Reset, false);
+            let x = 2 + text_width(label) + 1;
+            let value = ellipsize(&value, width.saturating_sub(x + 1));
+            self.front.put_str(x, row, &value, fg, bg, false);
+        }
+
+        // Level files: order number if picked
+        let list_top = 9;
+        let visible = 12_usize.min(self.front.height.saturating_sub(list_top + 4));
+        self.front.put_str(2, list_top, &t!("builder.count", n = b.order.len(), total = b.files.len()),
+                           dim, Color::Reset, false);
+        for i in 0..visible {
+            let idx = b.scroll + i;
+            let Some(level) = b.files.get(idx) else { break };
+            let row = list_top + 1 + i;
+            let place = b.order.iter().position(|&o| o == idx)
+                .map_or("    ".to_string(), |at| format!("{:>3}.", at + 1));
+            let flag = if level.warnings.is_empty() { "  " } else { "⚠ " };
+            let line = format!("{place} {flag}{}  {}", level.file, level.name);
+            let line = ellipsize(&line, width.saturating_sub(4));
+            let selected = idx == b.cursor && b.editing.is_none();
+            let fg = if place.trim().is_empty() { Color::White } else { hi };
+            let bg = if selected { cursor_bg } else { Color::Reset };
+            if selected {
+                self.front.put_str(1, row, "▸", hi, bg, true);
+            }
+            self.front.put_str(3, row, &line, fg, bg, selected);
+        }
+
+        // What's wrong with the highlighted level
+        let warn_row = list_top + 2 + visible;
+        if let Some(level) = b.files.get(b.cursor) {
+            for (i, warning) in level.warnings.iter().enumerate() {
+                let row = warn_row + i;
+                if row + 3 >= self.front.height { break; }
+                let text = ellipsize(&format!("⚠ {warning}"), width.saturating_sub(4));
+                self.front.put_str(3, row, &text, Color::Rgb{r:255,g:180,b:60}, Color::Reset, false);
+            }
+        }
+
+        // Footer, and write results / problems above it
+        let footer_row = self.front.height.saturating_sub(2);
+        if footer_row > list_top {
+            self.front.put_str(2, footer_row, t!("builder.footer_1"), dim, Color::Reset, false);
+            if footer_row + 1 < self.front.height {
+                self.front.put_str(2, footer_row + 1, t!("builder.footer_2"), dim, Color::Reset, false);
+            }
+            self.compose_messages(w, footer_row - 1, 0..0);
+        }
+
+        if let Some(dialog) = &w.share {
+            self.compose_share_dialog(w, dialog);
+        }
+    }
+
+    /// Delete / rename / path dialog, boxed in the middle of the screen.
+    fn compose_pack_dialog(&mut self, w: &WorldState, dialog: &PackDialog) {
+        let Some(pack) = w.pack_list.get(w.pack_cursor) else { return };
+        let inner = 56_usize.min(self.front.width).saturating_sub(4);
+        let (title, body, hint) = match dialog {
+            PackDialog::Delete => (
+                t!("packs.delete_title"),
+                t!("packs.delete_confirm", pack = pack.name),
+                t!("packs.delete_keys"),
+            ),
+            PackDialog::Rename { name } => {
+                let cursor = if self.blink_on(w.anim_tick, 4) { "▌" } else { " " };
+                let shown = tail(&format!("{name}.nlp"), inner.saturating_sub(1));
+                (t!("packs.rename_title"), format!("{shown}{cursor}"), t!("packs.rename_keys"))
+            }
+            PackDialog::Path { path } => (t!("packs.path_title"), path.clone(), t!("packs.close")),
+        };
+        self.compose_dialog(title, &body, hint);
+    }
+
+    /// A box in the middle of the screen: a title, the body (wrapped onto
+    /// as many rows as it needs) and a key hint.
+    fn compose_dialog(&mut self, title: &str, body: &str, hint: &str) {
+        let bg = Color::Rgb{r:30,g:30,b:45};
+        let title_c = Color::Rgb{r:255,g:200,b:50};
+        let hint_c = Color::Rgb{r:80,g:255,b:80};
+        let box_w = 56_usize.min(self.front.width);
+        let inner = box_w.saturating_sub(4);
+
+        // Long bodies (paths) wrap onto as many rows as they need
+        let mut lines = vec![];
+        let mut line = String::new();
+        for g in body.graphemes(true) {
+            if text_width(&line) + grapheme_width(g) > inner {
+                lines.push(std::mem::take(&mut line));
+            }
+            line.push_str(g);
+        }
+        lines.push(line);
+
+        let box_h = (lines.len() + 6).min(self.front.height);
+        let x0 = self.front.width.saturating_sub(box_w) / 2;
+        let y0 = self.front.height.saturating_sub(box_h) / 2;
+        for y in y0..y0 + box_h {
+            for x in x0..x0 + box_w {
+                self.front.set(x, y, Cell::from_char(' ', Color::Reset, bg, false));
+            }
+        }
+        self.front.put_str(x0 + 2, y0 + 1, &ellipsize(title, inner), title_c, bg, true);
+        for (i, l) in lines.iter().enumerate() {
+            if y0 + 3 + i >= y0 + box_h { break; }
+            self.front.put_str(x0 + 2, y0 + 3 + i, l, Color::White, bg, false);
+        }
+        if box_h >= 2 {
+            self.front.put_str(x0 + 2, y0 + box_h - 2, &ellipsize(hint, inner), hint_c, bg, false);
+        }
+    }
+
+    /// First-level thumbnail (two map rows per text row) and the first
+    /// few level names. Left out when the terminal is too narrow.
+    fn compose_pack_preview(&mut self, w: &WorldState, preview: &PackPreview, col: usize, row: usize) {
+        let room = self.front.width.saturating_sub(col + 1);
+        if room < PREVIEW_MIN_W || w.pack_list.get(w.pack_cursor).is_none_or(|p| p.path != preview.path) {
+            return;
+        }
+        let cyan = Color::Rgb{r:100,g:200,b:255};
+        self.front.put_str(col, row, t!("packs.preview"), cyan, Color::Reset, true);
+
+        let map_w = preview.rows.first().map_or(0, |r| r.chars().count()).min(room);
+        let map_rows: Vec<Vec<char>> = preview.rows.iter().map(|r| r.chars().collect()).collect();
+        let at = |x: usize, y: usize| map_rows.get(y).and_then(|r| r.get(x)).copied().unwrap_or(' ');
+        let mut y = row + 2;
+        for pair in (0..map_rows.len()).step_by(2) {
+            if y + 2 >= self.front.height { break; }
+            for x in 0..map_w {
+                let (top, bottom) = (preview_color(at(x, pair)), preview_color(at(x, pair + 1)));
+                self.front.set(col + x, y, Cell::from_char('▀', top, bottom, false));
+            }
+            y += 1;
+        }
+
+        y += 1;
+        for (i, name) in preview.names.iter().enumerate() {
+            if y >= self.front.height.saturating_sub(3) { break; }
+            let line = ellipsize(&format!("{}. {}", i + 1, name), room);
+            self.front.put_str(col, y, &line, Color::White, Color::Reset, false);
+            y += 1;
+        }
+    }
+
     fn compose_game_over(&mut self, w: &WorldState) {
-        let box_art = [
-            "╔════════════════════════════════╗",
-            "║     ✕ CONNECTION  LOST  ✕      ║",
-            "╚════════════════════════════════╝",
-        ];
-        for (i, l) in box_art.iter().enumerate() {
+        for (i, l) in boxed(t!("over.title"), 32).iter().enumerate() {
             self.front.put_str(6, 4 + i, l, Color::Rgb{r:255,g:60,b:60}, Color::Reset, true);
         }
-        let score = format!("◈ Final Score: {}", w.score);
-        let level = format!("◈ Reached Node: {}", w.current_level + 1);
-        self.front.put_str(8, 9, &score, Color::White, Color::Reset, false);
-        self.front.put_str(8, 10, &level, Color::White, Color::Reset, false);
-        self.front.put_str(8, 12, "▸ ENTER: Retry from Node 1", Color::Rgb{r:80,g:255,b:80}, Color::Reset, false);
-        self.front.put_str(8, 13, "▸ ESC:   Back to Title", Color::DarkGrey, Color::Reset, false);
+        let mut row = 9;
+        if w.mode == GameMode::TimeAttack {
+            self.compose_time_attack_result(w, 8, row);
+            row += 3;
+        } else {
+            row = self.compose_run_summary(w, 8, row) + 1;
+        }
+        self.front.put_str(8, row, t!("over.retry"), Color::Rgb{r:80,g:255,b:80}, Color::Reset, false);
+        self.front.put_str(8, row + 1, t!("over.back"), Color::DarkGrey, Color::Reset, false);
     }
 
-    fn compose_game_complete(&mut self, w: &WorldState) {
-        let box_art = [
-            "╔══════════════════════════════════════════╗",
-            "║  ★ MAINNET SECURED! PROTOCOL COMPLETE! ★ ║",
-            "╚══════════════════════════════════════════╝",
+    /// A lost normal run's totals, each against the pack's best before
+    /// it. Returns the row after the last line.
+    fn compose_run_summary(&mut self, w: &WorldState, col: usize, row: usize) -> usize {
+        let run = RunBests::of(w.score, &w.run);
+        let best = w.run_bests.unwrap_or_default();
+        let secs = w.run.play_ticks * w.speed.tick_rate_ms / 1000;
+        let lines = [
+            (t!("over.score", score = w.score), Some((run.score, best.score))),
+            (t!("over.reached", n = w.current_level + 1), None),
+            (t!("over.cleared", n = run.cleared), Some((run.cleared, best.cleared))),
+            (t!("over.tokens", n = run.tokens), Some((run.tokens, best.tokens))),
+            (t!("over.trapped", n = run.trapped), Some((run.trapped, best.trapped))),
+            (t!("over.time", time = format!("{}:{:02}:{:02}", secs / 3600, secs / 60 % 60, secs % 60)), None),
         ];
-        for (i, l) in box_art.iter().enumerate() {
-            self.front.put_str(4, 4 + i, l, Color::Rgb{r:255,g:220,b:50}, Color::Reset, true);
+        let width = lines.iter().map(|(l, _)| text_width(l)).max().unwrap_or(0) + 3;
+        for (i, (line, compare)) in lines.iter().enumerate() {
+            self.front.put_str(col, row + i, line, Color::White, Color::Reset, false);
+            let Some((now, before)) = *compare else { continue };
+            let (text, fg) = if now > before {
+                (t!("over.new_best").to_string(), Color::Rgb{r:255,g:220,b:50})
+            } else {
+                (t!("over.best", n = before), Color::DarkGrey)
+            };
+            self.front.put_str(col + width, row + i, &text, fg, Color::Reset, now > before);
+        }
+        row + lines.len()
+    }
+
+    /// Story page: title, then the text typed out as a centred block.
+    fn compose_cutscene(&mut self, w: &WorldState) {
+        let Some(page) = w.story.first() else { return };
+        let (buf_w, buf_h) = (self.front.width, self.front.height);
+        let typed = page.typed(w.anim_tick);
+        let typing = w.anim_tick < page.typing_ticks();
+
+        let block_w = page.lines.iter().map(|l| text_width(l)).max().unwrap_or(0).min(buf_w);
+        let title_rows = if page.title.is_empty() { 0 } else { 2 };
+        let block_h = title_rows + page.lines.len();
+        let x = buf_w.saturating_sub(block_w) / 2;
+        let mut row = buf_h.saturating_sub(block_h + 2) / 2;
+
+        if !page.title.is_empty() {
+            let title = ellipsize(&page.title, buf_w);
+            let tx = buf_w.saturating_sub(text_width(&title)) / 2;
+            self.front.put_str(tx, row, &title, Color::Rgb{r:255,g:220,b:50}, Color::Reset, true);
+            row += title_rows;
+        }
+
+        let text_c = Color::Rgb{r:200,g:230,b:200};
+        for (line, &shown) in page.lines.iter().zip(&typed) {
+            if row + 2 >= buf_h { break; }
+            let visible: String = line.chars().take(shown).collect();
+            let visible = fit_width(&visible, buf_w - x);
+            self.front.put_str(x, row, &visible, text_c, Color::Reset, false);
+            // Cursor after the last character typed so far
+            if typing && shown < line.chars().count() {
+                let cx = x + text_width(&visible);
+                if cx < buf_w && self.blink_on(w.anim_tick, 4) {
+                    self.front.set(cx, row, Cell::from_char('▌', text_c, Color::Reset, false));
+                }
+                break;
+            }
+            row += 1;
+        }
+
+        let hint = if w.spectating.is_some() {
+            t!("spectate.quit")
+        } else if typing {
+            t!("story.skip_typing")
+        } else {
+            t!("story.next")
+        };
+        let hx = buf_w.saturating_sub(text_width(hint)) / 2;
+        self.front.put_str(hx, buf_h.saturating_sub(1), hint, Color::DarkGrey, Color::Reset, false);
+    }
+
+    /// Ending credits, rolling up from the bottom of the screen. A roll
+    /// shorter than the screen comes to rest centred.
+    fn compose_game_complete(&mut self, w: &WorldState) {
+        let lines = credits::roll(w);
+        let (buf_w, buf_h) = (self.front.width, self.front.height);
+        let scrolled = credits::scrolled(w.anim_tick, lines.len());
+        let rest = if lines.len() < buf_h { (buf_h - lines.len()) / 2 } else { 0 };
+        let top = (buf_h as i64 - scrolled as i64).max(rest as i64);
+
+        for (i, line) in lines.iter().enumerate() {
+            let row = top + i as i64;
+            if row < 0 { continue; }
+            let row = row as usize;
+            if row >= buf_h { break; }
+            let (fg, bold) = match line.style {
+                CreditStyle::Title => (Color::Rgb{r:255,g:220,b:50}, true),
+                CreditStyle::Heading => (Color::Rgb{r:100,g:200,b:255}, true),
+                CreditStyle::Text => (Color::White, false),
+                CreditStyle::Hint => (Color::Rgb{r:80,g:255,b:80}, false),
+            };
+            let text = ellipsize(&line.text, buf_w);
+            let x = buf_w.saturating_sub(text_width(&text)) / 2;
+            self.front.put_str(x, row, &text, fg, Color::Reset, bold);
         }
-        let score = format!("◈ Final Score: {}", w.score);
-        let levels = format!("◈ All {} nodes cleared!", w.total_levels);
-        self.front.put_str(6, 9, &score, Color::White, Color::Reset, false);
-        self.front.put_str(6, 10, &levels, Color::Rgb{r:80,g:255,b:80}, Color::Reset, false);
-        self.front.put_str(6, 12, "▸ ENTER / ESC: Back to Title", Color::Rgb{r:80,g:255,b:80}, Color::Reset, false);
+    }
+
+    /// Time attack result and ranking place (two rows from `row`).
+    fn compose_time_attack_result(&mut self, w: &WorldState, col: usize, row: usize) {
+        let secs = w.clock_secs();
+        let result = t!("time_attack.result",
+                        n = w.time_attack.cleared, clock = format!("{}:{:02}", secs / 60, secs % 60));
+        let rank = match w.time_attack.rank {
+            Some(1) => t!("time_attack.best").to_string(),
+            Some(n) => t!("time_attack.ranked", n = n),
+            None => t!("time_attack.unranked").to_string(),
+        };
+        self.front.put_str(col, row, &result, Color::White, Color::Reset, false);
+        self.front.put_str(col, row + 1, &rank, Color::Rgb{r:255,g:140,b:60}, Color::Reset, false);
     }
 
     fn compose_pause_overlay(&mut self, w: &WorldState) {
         let dim = Color::Rgb{r:40,g:40,b:40};
-        let blink = (w.anim_tick / 8) % 2 == 0;
+        let blink = self.blink_on(w.anim_tick, 8);
         let cam = &w.camera;
 
         // Center the overlay in the viewport
-        let view_cols = cam.view_w * CELL_W;
+        let view_cols = cam.view_w * self.cell_w();
         let view_rows = cam.view_h;
         let box_w = 40_usize.min(view_cols);
-        let box_h = 16_usize.min(view_rows);
+        let box_h = 17_usize.min(view_rows);
         let box_x = (view_cols.saturating_sub(box_w)) / 2;
-        let box_y = MAP_ROW + (view_rows.saturating_sub(box_h)) / 2;
+        let box_y = self.layout.map + (view_rows.saturating_sub(box_h)) / 2;
 
         // Draw dark background box
         for y in box_y..box_y + box_h {
@@ -1264,23 +2948,472 @@ impl Renderer {
         let sep_c = Color::Rgb{r:80,g:80,b:80};
 
         // Title
-        let pause_label = if blink { "║  ▶  PAUSED  ◀  ║" } else { "║     PAUSED      ║" };
-        self.front.put_str(box_x + 11, box_y, "╔══════════════════╗", hdr, dim, true);
-        self.front.put_str(box_x + 11, box_y + 1, pause_label, hdr, dim, true);
-        self.front.put_str(box_x + 11, box_y + 2, "╚══════════════════╝", hdr, dim, true);
+        let label = if blink { format!("▶  {}  ◀", t!("pause.title")) } else { t!("pause.title").to_string() };
+        for (i, line) in boxed(&label, 18).iter().enumerate() {
+            let x = box_x + box_w.saturating_sub(text_width(line)) / 2;
+            self.front.put_str(x, box_y + i, line, hdr, dim, true);
+        }
 
+        // Entries: the highlighted one marked, shortcut keys to the right
+        let menu = &w.pause_menu;
+        let entries: Vec<(String, &str)> = if menu.settings {
+            let on_off = |on: bool| if on { t!("pause.on") } else { t!("pause.off") };
+            PauseMenu::SETTINGS.iter().map(|s| match s {
+                PauseSetting::Minimap => (t!("pause.minimap", state = on_off(w.show_minimap)), "M"),
+                PauseSetting::EdgeMarkers => (t!("pause.edge_markers", state = on_off(w.show_edge_markers)), "I"),
+                PauseSetting::GuardPaths => (t!("pause.guard_paths", state = on_off(w.show_guard_paths)), "G"),
+                PauseSetting::CompactView => (t!("pause.compact_view", state = on_off(w.compact_view)), "N"),
+                PauseSetting::Back => (t!("pause.settings_back").to_string(), "ESC"),
+            }).collect()
+        } else {
+            let slot = slot_label(menu.slot_number());
+            PauseMenu::ITEMS.iter().map(|i| match i {
+                PauseItem::Resume => (t!("pause.resume").to_string(), "F1"),
+                PauseItem::Restart => (t!("pause.restart").to_string(), "F2"),
+                PauseItem::Settings => (t!("pause.settings").to_string(), ""),
+                PauseItem::Save => (t!("pause.save", slot = slot), "F5-F8"),
+                PauseItem::Load => (t!("pause.load", slot = slot), "F9-F12"),
+                PauseItem::Quit => (t!("pause.quit").to_string(), "ESC"),
+            }).collect()
+        };
         let y0 = box_y + 4;
-        self.front.put_str(box_x + 2, y0,     "F1  Resume", key_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 1, "F2  Restart Level", key_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 2, "F3  Level Packs", key_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 3, "F4  Change Level", key_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 4, "────────────────────────", sep_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 5, "F5 Save 1  F6 Save 2", desc_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 6, "F7 Save 3  F8 Save 4", desc_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 7, "────────────────────────", sep_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 8, "F9 Load 1  F10 Load 2", desc_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 9, "F11 Load 3 F12 Load 4", desc_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 10, "────────────────────────", sep_c, dim, false);
-        self.front.put_str(box_x + 2, y0 + 11, "ESC Back to Title", key_c, dim, false);
+        for (i, (label, key)) in entries.iter().enumerate() {
+            let selected = i == menu.cursor;
+            let (mark, color) = if selected { ("▶ ", hdr) } else { ("  ", key_c) };
+            self.front.put_str(box_x + 2, y0 + i, &format!("{mark}{label}"), color, dim, selected);
+            let key_x = (box_x + box_w).saturating_sub(2 + text_width(key));
+            self.front.put_str(key_x, y0 + i, key, desc_c, dim, false);
+        }
+        let y1 = y0 + PauseMenu::ITEMS.len() + 1;
+        self.front.put_str(box_x + 2, y1, "────────────────────────", sep_c, dim, false);
+
+        // Below: what the slot holds, then the keys, or a save being
+        // named or waiting on "overwrite it?"
+        let warn_c = Color::Rgb{r:255,g:140,b:60};
+        let slot_line = |slot: u8, info: Option<&SlotInfo>| {
+            let slot = slot_label(slot);
+            match info {
+                Some(s) if !s.name.is_empty() =>
+                    t!("pause.slot_named", slot = slot, name = s.name, n = s.level + 1, score = s.score),
+                Some(s) => t!("pause.slot_held", slot = slot, n = s.level + 1, score = s.score),
+                None => t!("pause.slot_empty", slot = slot),
+            }
+        };
+        let lines = if let Some(name) = &menu.naming {
+            [
+                (t!("pause.name_prompt", slot = menu.slot_number()), hdr),
+                (format!("{name}_"), Color::White),
+                (t!("pause.name_keys").to_string(), desc_c),
+            ]
+        } else if let Some(over) = &menu.overwrite {
+            [
+                (slot_line(over.slot, Some(&over.held)), warn_c),
+                (t!("pause.overwrite_keys").to_string(), warn_c),
+                (t!("pause.more").to_string(), desc_c),
+            ]
+        } else {
+            let detail = match menu.item() {
+                Some(PauseItem::Save | PauseItem::Load) => slot_line(menu.slot_number(), menu.slot_info()),
+                _ => String::new(),
+            };
+            [
+                (detail, desc_c),
+                (t!("pause.keys").to_string(), desc_c),
+                (t!("pause.more").to_string(), desc_c),
+            ]
+        };
+        for (i, (text, color)) in lines.iter().enumerate() {
+            self.front.put_str(box_x + 2, y1 + 1 + i, &fit_width(text, box_w.saturating_sub(4)), *color, dim, i == 0);
+        }
+    }
+
+    /// Event log over the paused game: newest entry at the bottom,
+    /// `log_scroll` entries back from it.
+    fn compose_log_overlay(&mut self, w: &WorldState) {
+        let dim = Color::Rgb{r:40,g:40,b:40};
+        let cam = &w.camera;
+        let view_cols = cam.view_w * self.cell_w();
+        let view_rows = cam.view_h;
+        let box_w = 60_usize.min(view_cols);
+        let box_h = 20_usize.min(view_rows);
+        let box_x = view_cols.saturating_sub(box_w) / 2;
+        let box_y = self.layout.map + view_rows.saturating_sub(box_h) / 2;
+
+        for y in box_y..box_y + box_h {
+            for x in box_x..box_x + box_w {
+                self.front.set(x, y, Cell::from_char(' ', Color::Reset, dim, false));
+            }
+        }
+
+        let hdr = Color::Rgb{r:255,g:220,b:50};
+        let stamp_c = Color::Rgb{r:120,g:120,b:140};
+        let title = t!("log.title");
+        let tx = box_x + box_w.saturating_sub(text_width(title)) / 2;
+        self.front.put_str(tx, box_y + 1, title, hdr, dim, true);
+
+        // Rows 3 .. box_h - 2 hold entries; the footer takes the last row
+        let visible = box_h.saturating_sub(5);
+        let text_w = box_w.saturating_sub(14);
+        if w.log.is_empty() {
+            self.front.put_str(box_x + 2, box_y + 3, t!("log.empty"), stamp_c, dim, false);
+        }
+        let end = w.log.len().saturating_sub(w.log_scroll.min(w.log.len().saturating_sub(1)));
+        let start = end.saturating_sub(visible);
+        for (i, e) in w.log.range(start..end).enumerate() {
+            let row = box_y + 3 + i;
+            let stamp = format!("N{:<2} {:>2}:{:02}", e.node, e.secs / 60, e.secs % 60);
+            let fg = match e.severity {
+                Severity::Info => Color::Rgb{r:200,g:200,b:200},
+                Severity::Warning => Color::Rgb{r:255,g:110,b:110},
+                Severity::Achievement => Color::Rgb{r:110,g:230,b:140},
+            };
+            self.front.put_str(box_x + 2, row, &stamp, stamp_c, dim, false);
+            self.front.put_str(box_x + 12, row, &ellipsize(&e.text, text_w), fg, dim, false);
+        }
+
+        // Scroll hints beside the first and last entry rows
+        let arrow_c = Color::Rgb{r:100,g:200,b:255};
+        if start > 0 {
+            self.front.put_str(box_x + box_w - 2, box_y + 3, "▲", arrow_c, dim, false);
+        }
+        if end < w.log.len() {
+            self.front.put_str(box_x + box_w - 2, box_y + 2 + visible, "▼", arrow_c, dim, false);
+        }
+        self.front.put_str(box_x + 2, box_y + box_h - 1, t!("log.footer"), arrow_c, dim, false);
+    }
+
+    /// What was wrong in config.toml, each problem word-wrapped, in a box
+    /// centred on the screen.
+    fn compose_config_report(&mut self, report: &ConfigReport) {
+        let bg = Color::Rgb{r:60,g:20,b:20};
+        let box_w = 70_usize.min(self.area.w);
+        let text_w = box_w.saturating_sub(4);
+        let mut lines = vec![];
+        for problem in &report.problems {
+            for (i, line) in wrap_words(problem, text_w.saturating_sub(2)).into_iter().enumerate() {
+                lines.push(format!("{} {line}", if i == 0 { '•' } else { ' ' }));
+            }
+        }
+        let box_h = (lines.len() + 6).min(self.area.h);
+        let box_x = self.area.w.saturating_sub(box_w) / 2;
+        let box_y = self.area.h.saturating_sub(box_h) / 2;
+
+        for y in box_y..box_y + box_h {
+            for x in box_x..box_x + box_w {
+                self.front.set(x, y, Cell::from_char(' ', Color::Reset, bg, false));
+            }
+        }
+        let hdr = Color::Rgb{r:255,g:200,b:80};
+        let title = ellipsize(&t!("config_report.title", path = report.path), text_w);
+        self.front.put_str(box_x + 2, box_y + 1, &title, hdr, bg, true);
+        let visible = box_h.saturating_sub(6);
+        for (i, line) in lines.iter().take(visible).enumerate() {
+            self.front.put_str(box_x + 2, box_y + 3 + i, line, Color::White, bg, false);
+        }
+        let footer = Color::Rgb{r:100,g:200,b:255};
+        self.front.put_str(box_x + 2, box_y + box_h.saturating_sub(2), t!("config_report.footer"), footer, bg, false);
+    }
+}
+
+impl EventSink for Renderer {
+    /// Feed a step's events to the cosmetic layers (particles, shake).
+    fn on_events(&mut self, _world: &WorldState, events: &[GameEvent]) {
+        if events.iter().any(|e| matches!(e, GameEvent::RoomEntered { .. })) {
+            // New map: nothing should glide in from the old room's positions
+            self.player_tween = None;
+            self.guard_tweens.clear();
+        }
+        if self.display.reduced_motion { return; }
+        if self.display.particles {
+            self.particles.spawn(events);
+        }
+        for e in events {
+            let kick = match e {
+                GameEvent::PlayerKilled => 1.0,
+                GameEvent::GuardKilled { .. } => 0.6,
+                GameEvent::TrapCollapsed { .. } => 0.4,
+                _ => continue,
+            };
+            if self.shake <= 0.0 {
+                self.shake_last = Instant::now();
+            }
+            self.shake = self.shake.max(kick);
+        }
+    }
+}
+
+/// Guard glyph. A trapped guard's background shifts green → amber → red
+/// as it works its way out, so the player can judge whether it's still safe
+/// to walk over. A guard carrying a token gets a gold background so it
+/// stands out as worth trapping (the emoji itself can't be recoloured).
+fn guard_cell(g: &Guard, escape_ticks: u32) -> Cell {
+    let bg = if g.state == ActorState::InHole {
+        halfblock::urgency_color(g.escape_progress(escape_ticks))
+    } else if g.carry_gold {
+        Color::Rgb{r:150,g:110,b:0}
+    } else {
+        Color::Reset
+    };
+    Cell::from_char_wide('🤺', Color::Reset, bg, false)
+}
+
+/// Compact-view stand-in for a wide glyph: a letter in the emoji's colour,
+/// keeping the cell background (trapped / carrying tints still show).
+/// Glyph pair and colours for a custom tile. A glyph that isn't one
+/// column wide falls back to the tile's level-file char.
+fn custom_tile_cell(t: Tile) -> (char, char, Color, Color) {
+    let (Some(def), Some((fg, bg))) = (t.custom_def(), halfblock::custom_colors(t)) else {
+        return (' ', ' ', Color::Reset, Color::Reset);
+    };
+    let glyph = if def.glyph.width() == Some(1) { def.glyph } else { def.ch };
+    (glyph, glyph, fg, bg)
+}
+
+/// Pack preview thumbnail colour for a level-file map char.
+fn preview_color(ch: char) -> Color {
+    match ch {
+        '#' | 'T' | 'S' => Color::Rgb{r:180,g:120,b:60},
+        '=' => Color::Rgb{r:120,g:120,b:120},
+        'H' => Color::Rgb{r:100,g:200,b:255},
+        '-' => Color::Rgb{r:180,g:100,b:200},
+        'L' => Color::Rgb{r:255,g:170,b:60},
+        '!' => Color::Rgb{r:255,g:60,b:60},
+        '$' => Color::Rgb{r:255,g:210,b:60},
+        '%' => Color::Rgb{r:80,g:220,b:255},
+        '&' => Color::Rgb{r:230,g:110,b:255},
+        'P' => Color::Rgb{r:80,g:255,b:80},
+        'E' | 'R' | 'G' => Color::Rgb{r:255,g:60,b:60},
+        '1'..='9' => Color::Rgb{r:60,g:200,b:160},
+        _ => Color::Rgb{r:12,g:12,b:20},
+    }
+}
+
+/// `c` with only `light` (0..=1) of its distance from the background
+/// left. Named colours can't be mixed, so they stay or go out at half.
+fn dim_toward_bg(c: Color, light: f32) -> Color {
+    let Color::Rgb { r: br, g: bg, b: bb } = Cell::BASE_BG else { return c };
+    let mix = |v: u8, base: u8| (base as f32 + (v as f32 - base as f32) * light).round() as u8;
+    match c {
+        Color::Rgb { r, g, b } => Color::Rgb { r: mix(r, br), g: mix(g, bg), b: mix(b, bb) },
+        Color::Reset => c,
+        _ if light >= 0.5 => c,
+        _ => Cell::BASE_BG,
+    }
+}
+
+fn narrow_cell(cell: Cell) -> Cell {
+    if !cell.wide { return cell; }
+    let (ch, fg) = match cell.as_str() {
+        "🤺" => ('&', Color::Rgb{r:255,g:80,b:80}),
+        "🏮" => ('i', Color::Rgb{r:255,g:170,b:60}),
+        s => match TOKENS.iter().find(|k| s.starts_with(k.glyph)) {
+            Some(&TokenKind { narrow, color: (r, g, b), .. }) => (narrow, Color::Rgb{r, g, b}),
+            None => ('@', Color::Rgb{r:80,g:220,b:80}),  // runner (standing / climbing)
+        },
+    };
+    // Keep an explicit tint (e.g. the death flash) over the default colour
+    let fg = if cell.fg == Color::Reset { fg } else { cell.fg };
+    Cell::from_char(ch, fg, cell.bg, true)
+}
+
+/// One glyph for a two-glyph game cell in compact view.
+fn merge_glyphs(c0: char, c1: char) -> char {
+    match (c0, c1) {
+        ('╠', '╣') => '╫',
+        ('▓', '░') => '▒',
+        (' ', c) => c,
+        (c, _) => c,
+    }
+}
+
+// ── Minimap downsampling ──
+
+/// Upper bound on minimap size in terminal cells.
+const MINIMAP_MAX_W: usize = 24;
+const MINIMAP_MAX_H: usize = 8;
+
+/// One minimap cell: summary of a `block_w × block_h` block of world cells.
+struct MiniCell {
+    density: u8,   // 0..=4, share of solid tiles in the block
+    gold: bool,
+    guard: bool,
+    player: bool,
+}
+
+struct Minimap {
+    w: usize,
+    h: usize,
+    block_w: usize,
+    block_h: usize,
+    cells: Vec<MiniCell>,
+}
+
+/// Downsample the world into at most `max_w × max_h` cells.
+/// Each cell covers a fixed block of world cells; terrain density is the
+/// fraction of solid tiles, quantized to 5 levels.
+fn downsample_minimap(w: &WorldState, max_w: usize, max_h: usize) -> Minimap {
+    let block_w = w.width.div_ceil(max_w).max(1);
+    let block_h = w.height.div_ceil(max_h).max(1);
+    let mw = w.width.div_ceil(block_w);
+    let mh = w.height.div_ceil(block_h);
+
+    let mut solid = vec![0usize; mw * mh];
+    let mut cells: Vec<MiniCell> = (0..mw * mh)
+        .map(|_| MiniCell { density: 0, gold: false, guard: false, player: false })
+        .collect();
+
+    for (y, row) in w.tiles.rows().enumerate() {
+        for (x, tile) in row.iter().enumerate() {
+            let idx = (y / block_h) * mw + x / block_w;
+            if tile.is_solid() { solid[idx] += 1; }
+            if tile.is_gold() { cells[idx].gold = true; }
+        }
+    }
+    for g in &w.guards {
+        if g.state == ActorState::Dead || !w.guard_seen(g) { continue; }
+        if g.x < w.width && g.y < w.height {
+            cells[(g.y / block_h) * mw + g.x / block_w].guard = true;
+        }
+    }
+    if w.player.alive && w.player.x < w.width && w.player.y < w.height {
+        cells[(w.player.y / block_h) * mw + w.player.x / block_w].player = true;
+    }
+
+    let block_area = block_w * block_h;
+    for (cell, &count) in cells.iter_mut().zip(&solid) {
+        cell.density = ((count * 4 + block_area / 2) / block_area) as u8;
     }
+
+    Minimap { w: mw, h: mh, block_w, block_h, cells }
+}
+
+/// Concise plain-text description of the play state, for screen readers.
+/// e.g. "player at 12,8 on ladder, guard 3 right 1 up, 2 tokens left"
+fn describe_status(w: &WorldState) -> String {
+    let p = &w.player;
+    let where_ = match p.state {
+        ActorState::OnGround => t!("status.standing"),
+        ActorState::Falling  => t!("status.falling"),
+        ActorState::OnLadder => t!("status.on_ladder"),
+        ActorState::OnRope   => t!("status.on_rope"),
+        ActorState::InHole   => t!("status.in_hole"),
+        ActorState::Dead     => t!("status.down"),
+    };
+    let mut status = t!("status.player", x = p.x, y = p.y, state = where_);
+
+    // Nearest active guard (Manhattan distance), relative to the player
+    let nearest = w.guards.iter()
+        .filter(|g| g.state != ActorState::Dead && w.guard_seen(g))
+        .min_by_key(|g| g.x.abs_diff(p.x) + g.y.abs_diff(p.y));
+    match nearest {
+        Some(g) => {
+            let mut parts = vec![];
+            if g.x > p.x { parts.push(t!("status.right", n = g.x - p.x)); }
+            if g.x < p.x { parts.push(t!("status.left", n = p.x - g.x)); }
+            if g.y < p.y { parts.push(t!("status.up", n = p.y - g.y)); }
+            if g.y > p.y { parts.push(t!("status.below", n = g.y - p.y)); }
+            if parts.is_empty() { parts.push(t!("status.here").to_string()); }
+            let trapped = if g.state == ActorState::InHole { t!("status.trapped") } else { "" };
+            let carrying = if g.carry_gold { t!("status.carrying") } else { "" };
+            status.push_str(&t!("status.guard", where = parts.join(" "), trapped = trapped, carrying = carrying));
+        }
+        None => status.push_str(t!("status.no_guards")),
+    }
+
+    if w.exit_enabled {
+        status.push_str(t!("status.exit_open"));
+    } else {
+        status.push_str(&t!("status.tokens_left", n = w.gold_remaining));
+    }
+    if let Some(n) = w.digs_left {
+        status.push_str(&t!("status.hacks_left", n = n));
+    }
+    if w.mode == GameMode::TimeAttack {
+        status.push_str(&t!("status.seconds_left", n = w.clock_secs()));
+    }
+    if let Some(tip) = w.tip_text() {
+        status.push_str(&t!("status.tip", text = tip));
+    }
+    status
+}
+
+// ── Braille overview ──
+
+/// What a braille character is coloured by. Later variants win.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum Mark {
+    Terrain,
+    Exit,
+    Gold,
+    Guard,
+    Player,
+}
+
+struct Overview {
+    cols: usize,
+    rows: usize,
+    block: usize,              // world cells per dot, both axes
+    cells: Vec<(u8, Mark)>,    // braille dot bits + colour
+}
+
+/// Braille dot bit for dot (dx, dy) inside one 2×4 character.
+fn braille_bit(dx: usize, dy: usize) -> u8 {
+    match (dx, dy) {
+        (0, 3) => 0x40,
+        (1, 3) => 0x80,
+        (0, y) => 1 << y,
+        (_, y) => 1 << (y + 3),
+    }
+}
+
+/// Render the whole level into at most `max_cols × max_rows` braille
+/// characters. Each dot is a `block × block` square of world cells; a dot
+/// is lit if the block holds terrain or anything of interest.
+fn braille_overview(w: &WorldState, max_cols: usize, max_rows: usize) -> Overview {
+    let (max_dx, max_dy) = ((max_cols * 2).max(1), (max_rows * 4).max(1));
+    let block = w.width.div_ceil(max_dx).max(w.height.div_ceil(max_dy)).max(1);
+    let dots_w = w.width.div_ceil(block);
+    let dots_h = w.height.div_ceil(block);
+    let cols = dots_w.div_ceil(2).min(max_cols);
+    let rows = dots_h.div_ceil(4).min(max_rows);
+    let mut cells = vec![(0u8, Mark::Terrain); cols * rows];
+    let secret = w.secret_cells();
+
+    let mut mark = |x: usize, y: usize, m: Option<Mark>| {
+        let (dx, dy) = (x / block, y / block);
+        let (cx, cy) = (dx / 2, dy / 4);
+        if cx >= cols || cy >= rows { return; }
+        let cell = &mut cells[cy * cols + cx];
+        cell.0 |= braille_bit(dx % 2, dy % 4);
+        if let Some(m) = m {
+            cell.1 = cell.1.max(m);
+        }
+    };
+
+    for y in 0..w.height {
+        for x in 0..w.width {
+            match w.tiles[(x, y)] {
+                Tile::Brick | Tile::TrapBrick | Tile::FakeBrick | Tile::Concrete
+                | Tile::Ladder | Tile::Rope | Tile::Laser | Tile::Spawner => mark(x, y, None),
+                // What's behind a fake brick stays dark until it's found
+                _ if secret.get(x, y) => {}
+                Tile::HiddenLadder if w.exit_enabled => mark(x, y, Some(Mark::Exit)),
+                Tile::Gold(_) => mark(x, y, Some(Mark::Gold)),
+                t @ Tile::Custom(_) if t.is_solid() || t.is_climbable() || t.is_hangable() => {
+                    mark(x, y, None)
+                }
+                _ => {}
+            }
+        }
+    }
+    for g in &w.guards {
+        if g.state != ActorState::Dead && !secret.get(g.x, g.y) && w.guard_seen(g) {
+            mark(g.x, g.y, Some(Mark::Guard));
+        }
+    }
+    if w.player.alive {
+        mark(w.player.x, w.player.y, Some(Mark::Player));
+    }
+
+    Overview { cols, rows, block, cells }
 }
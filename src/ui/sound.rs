@@ -1,10 +1,15 @@
-/// Sound engine: procedural 8-bit style sound effects via rodio.
-///
-/// All sounds are generated as in-memory WAV buffers at init time.
-/// Playback is fire-and-forget (non-blocking) via rodio's Sink.
-///
-/// Compile with `--no-default-features` or without "sound" feature
-/// to disable audio entirely (the stub SoundEngine does nothing).
+//! Sound engine: procedural 8-bit style sound effects via rodio.
+//!
+//! All sounds are generated as in-memory WAV buffers at init time.
+//! Playback is fire-and-forget (non-blocking) via rodio's Sink.
+//!
+//! Compile with `--no-default-features` or without "sound" feature
+//! to disable audio entirely (the stub SoundEngine does nothing).
+
+use std::rc::Rc;
+
+use crate::sim::event::{EventSink, GameEvent};
+use crate::sim::world::{Phase, WorldState};
 
 #[cfg(feature = "sound")]
 mod inner {
@@ -25,6 +30,9 @@ mod inner {
         sfx_die: Arc<Vec<u8>>,
         sfx_clear: Arc<Vec<u8>>,
         sfx_all_gold: Arc<Vec<u8>>,
+        music_ending: Arc<Vec<u8>>,
+        /// Mono footstep, panned when played
+        step: Vec<f32>,
     }
 
     impl SoundEngine {
@@ -38,6 +46,8 @@ mod inner {
             let sfx_die = Arc::new(make_wav(&gen_die()));
             let sfx_clear = Arc::new(make_wav(&gen_clear()));
             let sfx_all_gold = Arc::new(make_wav(&gen_all_gold()));
+            let music_ending = Arc::new(make_wav(&gen_ending()));
+            let step = gen_step();
 
             Some(SoundEngine {
                 _stream: stream,
@@ -48,6 +58,8 @@ mod inner {
                 sfx_die,
                 sfx_clear,
                 sfx_all_gold,
+                music_ending,
+                step,
             })
         }
 
@@ -76,12 +88,30 @@ mod inner {
             }
         }
 
+        /// Footstep placed in stereo: `pan` from -1 (left) to 1 (right),
+        /// `volume` 0..=1
+        pub fn play_step(&self, pan: f32, volume: f32) {
+            // Constant-power pan
+            let angle = (pan.clamp(-1.0, 1.0) + 1.0) * std::f32::consts::FRAC_PI_4;
+            let (left, right) = (angle.cos() * volume, angle.sin() * volume);
+            let frames: Vec<f32> = self.step.iter().flat_map(|&s| [s * left, s * right]).collect();
+            let buf = make_wav_channels(&frames, 2);
+            if let Ok(sink) = Sink::try_new(&self.handle) {
+                let cursor = Cursor::new(buf);
+                if let Ok(src) = rodio::Decoder::new(cursor) {
+                    sink.append(src);
+                    sink.detach();
+                }
+            }
+        }
+
         pub fn play_gold(&self) { self.play(&self.sfx_gold); }
         pub fn play_dig(&self) { self.play(&self.sfx_dig); }
         pub fn play_fall(&self) { self.play(&self.sfx_fall); }
         pub fn play_die(&self) { self.play(&self.sfx_die); }
         pub fn play_clear(&self) { self.play(&self.sfx_clear); }
         pub fn play_all_gold(&self) { self.play(&self.sfx_all_gold); }
+        pub fn play_ending(&self) { self.play(&self.music_ending); }
     }
 
     // ════════════════════════════════════════════════════════════
@@ -223,12 +253,67 @@ mod inner {
         samples
     }
 
+    /// Footstep: a dull low thump
+    fn gen_step() -> Vec<f32> {
+        let duration = 0.04;
+        let n = (SAMPLE_RATE as f32 * duration) as usize;
+        (0..n)
+            .map(|i| {
+                let t = i as f32 / n as f32;
+                let freq = 140.0 - t * 60.0;
+                let ti = i as f32 / SAMPLE_RATE as f32;
+                let env = (1.0 - t).powi(2);
+                (ti * freq * 2.0 * std::f32::consts::PI).sin() * env * 0.35
+            })
+            .collect()
+    }
+
+    /// Ending theme: a slow square-ish melody over a bass line, long
+    /// enough to carry the credits roll
+    fn gen_ending() -> Vec<f32> {
+        // (melody, bass) in Hz per beat; 0.0 rests
+        let beats = [
+            (523.0_f32, 131.0_f32), (659.0, 131.0), (784.0, 196.0), (659.0, 196.0),
+            (698.0, 175.0), (880.0, 175.0), (784.0, 196.0), (0.0, 196.0),
+            (659.0, 165.0), (784.0, 165.0), (1047.0, 220.0), (988.0, 220.0),
+            (880.0, 175.0), (784.0, 196.0), (1047.0, 131.0), (0.0, 131.0),
+        ];
+        let beat = 0.35;
+        let mut samples = Vec::new();
+        for &(lead, bass) in &beats {
+            let n = (SAMPLE_RATE as f32 * beat) as usize;
+            for i in 0..n {
+                let t = i as f32 / SAMPLE_RATE as f32;
+                let env = 1.0 - (i as f32 / n as f32) * 0.6;
+                let phase = |f: f32| (t * f * 2.0 * std::f32::consts::PI).sin();
+                // Soft square: fundamental plus odd harmonics
+                let lead_wave = if lead > 0.0 {
+                    phase(lead) * 0.6 + phase(lead * 3.0) * 0.2 + phase(lead * 5.0) * 0.1
+                } else {
+                    0.0
+                };
+                samples.push((lead_wave * env * 0.25) + phase(bass) * 0.15);
+            }
+        }
+        // Fade out the tail so the last beat doesn't click
+        let fade_len = (SAMPLE_RATE as f32 * 0.2) as usize;
+        let total = samples.len();
+        for i in total.saturating_sub(fade_len)..total {
+            samples[i] *= (total - i) as f32 / fade_len as f32;
+        }
+        samples
+    }
+
     // ════════════════════════════════════════════════════════════
     //  WAV encoder — wraps f32 samples into a valid WAV buffer
     // ════════════════════════════════════════════════════════════
 
     fn make_wav(samples: &[f32]) -> Vec<u8> {
-        let num_channels: u16 = 1;
+        make_wav_channels(samples, 1)
+    }
+
+    /// `samples` interleaved across `num_channels`.
+    fn make_wav_channels(samples: &[f32], num_channels: u16) -> Vec<u8> {
         let bits_per_sample: u16 = 16;
         let byte_rate = SAMPLE_RATE * (num_channels as u32) * (bits_per_sample as u32) / 8;
         let block_align = num_channels * bits_per_sample / 8;
@@ -280,10 +365,49 @@ pub struct SoundEngine;
 impl SoundEngine {
     pub fn new() -> Option<Self> { Some(SoundEngine) }
     pub fn play_intro_blip(&self, _row: usize, _total: usize) {}
+    pub fn play_step(&self, _pan: f32, _volume: f32) {}
     pub fn play_gold(&self) {}
     pub fn play_dig(&self) {}
     pub fn play_fall(&self) {}
     pub fn play_die(&self) {}
     pub fn play_clear(&self) {}
     pub fn play_all_gold(&self) {}
+    pub fn play_ending(&self) {}
+}
+
+// ════════════════════════════════════════════════════════════
+//  Event subscriber
+// ════════════════════════════════════════════════════════════
+
+/// Sentinel footsteps fade out at this many cells from the player.
+const STEP_HEARING: f32 = 16.0;
+
+/// Plays the effect for each sim event. Shares the engine with the
+/// level intro, which plays its blips directly.
+pub struct SoundEvents(pub Rc<SoundEngine>);
+
+impl EventSink for SoundEvents {
+    fn on_events(&mut self, world: &WorldState, events: &[GameEvent]) {
+        let sfx = &self.0;
+        for event in events {
+            match event {
+                // Nightmare: unseen sentinels are heard from where they are
+                &GameEvent::GuardStepped { x, y, .. } if world.invisible_guards => {
+                    let p = &world.player;
+                    let dx = x as f32 - p.x as f32;
+                    let dist = dx.hypot(y as f32 - p.y as f32);
+                    let volume = 1.0 - dist / STEP_HEARING;
+                    if volume > 0.0 { sfx.play_step(dx / 8.0, volume); }
+                }
+                GameEvent::GoldPicked { .. } => sfx.play_gold(),
+                GameEvent::HoleCreated { .. } => sfx.play_dig(),
+                GameEvent::PlayerFallStart => sfx.play_fall(),
+                GameEvent::PlayerKilled | GameEvent::TimeUp => sfx.play_die(),
+                GameEvent::AllGoldCollected | GameEvent::SecretFound { .. } => sfx.play_all_gold(),
+                GameEvent::StageCleared => sfx.play_clear(),
+                GameEvent::PhaseChanged { to: Phase::GameComplete, .. } => sfx.play_ending(),
+                _ => {}
+            }
+        }
+    }
 }
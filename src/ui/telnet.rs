@@ -0,0 +1,425 @@
+//! Telnet sessions (`--serve-telnet`): a game per connection, for playing
+//! over the network or mounting the game as a BBS door.
+//!
+//! `Session::open` asks for what a full-screen game needs of the client:
+//! the server echoes (so the client doesn't), no go-ahead, and the client
+//! reports its window size (NAWS), which becomes the session's `Screen`
+//! size. A thread reads the connection into key events for its
+//! `EventSource`. Telnet has no key releases, so held keys time out as on
+//! terminals without the keyboard enhancement.
+//!
+//! With the CP437 profile (`--cp437`), output is re-encoded for BBS
+//! terminals: box drawing, blocks and shades go out as their CP437 codes,
+//! lines CP437 lacks as the nearest it has, wide glyphs (actors, tokens)
+//! as an ASCII stand-in and a space, and anything else as `?`.
+
+use std::collections::VecDeque;
+use std::io::{self, Read, Write};
+use std::net::TcpStream;
+use std::sync::atomic::{AtomicU32, Ordering};
+use std::sync::mpsc::{self, Receiver, RecvTimeoutError, Sender};
+use std::sync::Arc;
+use std::thread;
+use std::time::Duration;
+
+use crossterm::event::{Event, KeyCode, KeyEvent, KeyModifiers};
+use unicode_width::UnicodeWidthChar;
+
+use crate::domain::tile::TOKENS;
+use crate::ui::backend::{EventSource, Screen};
+
+const IAC: u8 = 255;
+const DONT: u8 = 254;
+const DO: u8 = 253;
+const WONT: u8 = 252;
+const WILL: u8 = 251;
+const SB: u8 = 250;
+const SE: u8 = 240;
+const ECHO: u8 = 1;
+const SGA: u8 = 3;
+const NAWS: u8 = 31;
+
+/// Window size until the client reports one.
+const DEFAULT_SIZE: (u16, u16) = (80, 24);
+/// Largest window size taken from a client: the screen buffers are
+/// allocated at the size it reports.
+const MAX_SIZE: (u16, u16) = (512, 256);
+/// Longest subnegotiation kept. Past this, the rest of it is read to its
+/// `IAC SE` and dropped.
+const MAX_SUBNEG: usize = 64;
+
+/// CP437 bytes 0x80-0xFE as Unicode (0xFF, a no-break space, is left out:
+/// it's telnet's IAC).
+const CP437_HIGH: &str = "ÇüéâäàåçêëèïîìÄÅÉæÆôöòûùÿÖÜ¢£¥₧ƒáíóúñÑªº¿⌐¬½¼¡«»\
+░▒▓│┤╡╢╖╕╣║╗╝╜╛┐└┴┬├─┼╞╟╚╔╩╦╠═╬╧╨╤╥╙╘╒╓╫╪┘┌█▄▌▐▀αßΓπΣσµτΦΘΩδ∞φε∩≡±≥≤⌠⌡÷≈°∙·√ⁿ²■";
+
+/// A connection's side of the game: where frames go, the screen size and
+/// the keys.
+pub struct Session {
+    pub out: Box<dyn Write>,
+    pub screen: TelnetScreen,
+    pub input: TelnetInput,
+}
+
+impl Session {
+    /// Negotiate with the client on `stream` and start reading its keys.
+    pub fn open(mut stream: TcpStream, cp437: bool) -> io::Result<Session> {
+        stream.set_nodelay(true)?;
+        stream.write_all(&[IAC, WILL, ECHO, IAC, WILL, SGA, IAC, DO, SGA, IAC, DO, NAWS])?;
+        let size = Arc::new(AtomicU32::new(pack_size(DEFAULT_SIZE)));
+        let (tx, rx) = mpsc::channel();
+        let reader = stream.try_clone()?;
+        let naws = Arc::clone(&size);
+        thread::spawn(move || read_keys(reader, cp437, naws, tx));
+        let out: Box<dyn Write> = if cp437 { Box::new(Cp437(stream, vec![])) } else { Box::new(stream) };
+        Ok(Session { out, screen: TelnetScreen(size), input: TelnetInput { rx, pending: VecDeque::new() } })
+    }
+}
+
+fn pack_size((w, h): (u16, u16)) -> u32 {
+    (w as u32) << 16 | h as u32
+}
+
+/// The client's window, as it last reported it.
+pub struct TelnetScreen(Arc<AtomicU32>);
+
+impl Screen for TelnetScreen {
+    fn enter(&mut self) -> io::Result<()> { Ok(()) }
+    fn leave(&mut self) -> io::Result<()> { Ok(()) }
+
+    fn size(&self) -> (u16, u16) {
+        let v = self.0.load(Ordering::Relaxed);
+        ((v >> 16) as u16, v as u16)
+    }
+}
+
+/// Keys from the client; once it's gone, a Ctrl+C.
+pub struct TelnetInput {
+    rx: Receiver<Event>,
+    /// What `poll` took off the channel, for `read`.
+    pending: VecDeque<Event>,
+}
+
+impl EventSource for TelnetInput {
+    fn poll(&mut self, timeout: Duration) -> bool {
+        if !self.pending.is_empty() { return true; }
+        match self.rx.recv_timeout(timeout) {
+            Ok(event) => {
+                self.pending.push_back(event);
+                true
+            }
+            Err(RecvTimeoutError::Timeout) => false,
+            Err(RecvTimeoutError::Disconnected) => {
+                thread::sleep(timeout);
+                false
+            }
+        }
+    }
+
+    fn read(&mut self) -> Option<Event> {
+        self.pending.pop_front().or_else(|| self.rx.try_recv().ok())
+    }
+}
+
+/// Read the connection until it closes, answering nothing (the options
+/// were offered up front) but window sizes, and sending on the keys.
+fn read_keys(mut stream: TcpStream, cp437: bool, size: Arc<AtomicU32>, tx: Sender<Event>) {
+    let mut parser = Parser { cp437, ..Parser::default() };
+    let mut buf = [0u8; 512];
+    loop {
+        let n = match stream.read(&mut buf) {
+            Ok(0) | Err(_) => break,
+            Ok(n) => n,
+        };
+        for event in parser.feed(&buf[..n]) {
+            match event {
+                Input::Size(w, h) => size.store(pack_size((w, h)), Ordering::Relaxed),
+                Input::Key(code, mods) => {
+                    if tx.send(Event::Key(KeyEvent::new(code, mods))).is_err() { return; }
+                }
+            }
+        }
+    }
+    let _ = tx.send(Event::Key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL)));
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Input {
+    Key(KeyCode, KeyModifiers),
+    Size(u16, u16),
+}
+
+/// Telnet commands and terminal key sequences, byte by byte. A read ends
+/// a lone ESC: escape sequences arrive whole.
+#[derive(Default)]
+struct Parser {
+    cp437: bool,
+    /// Bytes of a telnet command or subnegotiation not yet complete.
+    command: Vec<u8>,
+    /// Bytes of a UTF-8 character not yet complete.
+    utf8: Vec<u8>,
+    /// The last byte was a CR (whose LF or NUL is dropped).
+    cr: bool,
+}
+
+impl Parser {
+    fn feed(&mut self, bytes: &[u8]) -> Vec<Input> {
+        let mut out = vec![];
+        let mut data = vec![];
+        for &b in bytes {
+            if !self.command.is_empty() || b == IAC {
+                self.command.push(b);
+                if self.command.len() > MAX_SUBNEG {
+                    // Too long to be one we read: keep only the last byte,
+                    // to see where it ends
+                    let last = self.command.len() - 1;
+                    self.command.drain(2..last);
+                }
+                if let Some(done) = self.command_done() {
+                    if done == [IAC, IAC] { data.push(IAC); }
+                    if let [IAC, SB, NAWS, rest @ ..] = &done[..] {
+                        let raw: Vec<u8> = unescape(rest);
+                        if let [w1, w0, h1, h0, ..] = raw[..] {
+                            let w = u16::from_be_bytes([w1, w0]).clamp(1, MAX_SIZE.0);
+                            let h = u16::from_be_bytes([h1, h0]).clamp(1, MAX_SIZE.1);
+                            out.push(Input::Size(w, h));
+                        }
+                    }
+                }
+            } else {
+                data.push(b);
+            }
+        }
+        self.keys(&data, &mut out);
+        out
+    }
+
+    /// The command in `self.command`, taken, once it's complete.
+    fn command_done(&mut self) -> Option<Vec<u8>> {
+        let done = match self.command[..] {
+            [IAC] => false,
+            [IAC, WILL | WONT | DO | DONT] => false,
+            [IAC, SB, ..] => self.command.ends_with(&[IAC, SE]) && self.command.len() > 3,
+            _ => true,
+        };
+        done.then(|| std::mem::take(&mut self.command))
+    }
+
+    fn keys(&mut self, data: &[u8], out: &mut Vec<Input>) {
+        let key = |code| Input::Key(code, KeyModifiers::NONE);
+        let mut i = 0;
+        while i < data.len() {
+            let b = data[i];
+            i += 1;
+            if std::mem::take(&mut self.cr) && matches!(b, b'\n' | 0) { continue; }
+            match b {
+                0x1b => match data.get(i) {
+                    Some(b'[' | b'O') => {
+                        let end = data[i + 1..].iter().position(|c| (0x40..=0x7e).contains(c))
+                            .map_or(data.len(), |p| i + 1 + p);
+                        if let Some(code) = escape_key(&data[i..=end.min(data.len() - 1)]) {
+                            out.push(key(code));
+                        }
+                        i = end + 1;
+                    }
+                    _ => out.push(key(KeyCode::Esc)),
+                },
+                b'\r' => {
+                    self.cr = true;
+                    out.push(key(KeyCode::Enter));
+                }
+                b'\n' => out.push(key(KeyCode::Enter)),
+                b'\t' => out.push(key(KeyCode::Tab)),
+                0x08 | 0x7f => out.push(key(KeyCode::Backspace)),
+                0x01..=0x1a => out.push(Input::Key(KeyCode::Char((b - 1 + b'a') as char), KeyModifiers::CONTROL)),
+                0x80.. if self.cp437 => {
+                    let c = CP437_HIGH.chars().nth(b as usize - 0x80).unwrap_or('?');
+                    out.push(key(KeyCode::Char(c)));
+                }
+                0x80.. => {
+                    self.utf8.push(b);
+                    if let Ok(s) = std::str::from_utf8(&self.utf8) {
+                        out.extend(s.chars().map(|c| key(KeyCode::Char(c))));
+                        self.utf8.clear();
+                    } else if self.utf8.len() >= 4 {
+                        self.utf8.clear();
+                    }
+                }
+                0x20..=0x7e => out.push(Input::Key(KeyCode::Char(b as char), shift_for(b as char))),
+                _ => {}
+            }
+        }
+    }
+}
+
+/// What a terminal sends Shift with, as crossterm reports it.
+fn shift_for(c: char) -> KeyModifiers {
+    if c.is_ascii_uppercase() { KeyModifiers::SHIFT } else { KeyModifiers::NONE }
+}
+
+/// Subnegotiation data with its doubled IACs undone, up to the IAC SE.
+fn unescape(bytes: &[u8]) -> Vec<u8> {
+    let mut out = vec![];
+    let mut it = bytes.iter();
+    while let Some(&b) = it.next() {
+        if b == IAC {
+            match it.next() {
+                Some(&IAC) => out.push(IAC),
+                _ => break,
+            }
+        } else {
+            out.push(b);
+        }
+    }
+    out
+}
+
+/// The key for a CSI (`[`) or SS3 (`O`) sequence, after its ESC.
+fn escape_key(seq: &[u8]) -> Option<KeyCode> {
+    let (&last, body) = seq.split_last()?;
+    Some(match last {
+        b'A' => KeyCode::Up,
+        b'B' => KeyCode::Down,
+        b'C' => KeyCode::Right,
+        b'D' => KeyCode::Left,
+        b'H' => KeyCode::Home,
+        b'F' => KeyCode::End,
+        b'P' => KeyCode::F(1),
+        b'Q' => KeyCode::F(2),
+        b'R' => KeyCode::F(3),
+        b'S' => KeyCode::F(4),
+        b'~' => {
+            let n: u8 = std::str::from_utf8(&body[1..]).ok()?.split(';').next()?.parse().ok()?;
+            match n {
+                1 | 7 => KeyCode::Home,
+                2 => KeyCode::Insert,
+                3 => KeyCode::Delete,
+                4 | 8 => KeyCode::End,
+                5 => KeyCode::PageUp,
+                6 => KeyCode::PageDown,
+                11..=15 => KeyCode::F(n - 10),
+                17..=21 => KeyCode::F(n - 11),
+                23 | 24 => KeyCode::F(n - 12),
+                _ => return None,
+            }
+        }
+        _ => return None,
+    })
+}
+
+/// Output re-encoded as CP437. A character split across writes waits in
+/// the buffer for the rest of it.
+struct Cp437<W>(W, Vec<u8>);
+
+impl<W: Write> Write for Cp437<W> {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.1.extend_from_slice(buf);
+        let (text, rest) = match std::str::from_utf8(&self.1) {
+            Ok(s) => (s.to_string(), vec![]),
+            Err(e) if e.error_len().is_none() => {
+                let (ok, rest) = self.1.split_at(e.valid_up_to());
+                (String::from_utf8_lossy(ok).into_owned(), rest.to_vec())
+            }
+            Err(_) => (String::from_utf8_lossy(&self.1).into_owned(), vec![]),
+        };
+        self.1 = rest;
+        let mut out = Vec::with_capacity(text.len());
+        for c in text.chars() {
+            encode_cp437(c, &mut out);
+        }
+        self.0.write_all(&out)?;
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        self.0.flush()
+    }
+}
+
+/// `c` as CP437, taking as many columns as it would have.
+fn encode_cp437(c: char, out: &mut Vec<u8>) {
+    let byte = |c: char| match c {
+        ' '..='~' | '\x1b' | '\x07' | '\r' | '\n' => Some(c as u8),
+        _ => CP437_HIGH.chars().position(|h| h == c).map(|i| 0x80 + i as u8),
+    };
+    match c.width() {
+        Some(0) => {}
+        Some(2) => {
+            let token = TOKENS.iter().find(|k| k.glyph == c);
+            let stand_in = match c {
+                '🤺' => b'&',
+                '🏮' => b'i',
+                _ if token.is_some() => token.map_or(b'$', |k| k.narrow as u8),
+                '\u{1f300}'.. => b'@',  // the runner, standing or climbing
+                _ => b'?',
+            };
+            out.extend([stand_in, if stand_in == b'?' { b'?' } else { b' ' }]);
+        }
+        _ => out.push(byte(c).or_else(|| nearest(c).and_then(byte)).unwrap_or(b'?')),
+    }
+}
+
+/// A character CP437 has for one it lacks.
+fn nearest(c: char) -> Option<char> {
+    Some(match c {
+        '┃' | '╎' | '╏' | '┆' | '┇' | '┊' | '┋' => '│',
+        '━' | '╌' | '╍' | '┄' | '┅' | '┈' | '┉' => '─',
+        '╭' | '┏' => '┌',
+        '╮' | '┓' => '┐',
+        '╰' | '┗' => '└',
+        '╯' | '┛' => '┘',
+        '▲' | '△' => '^',
+        '▼' | '▽' => 'v',
+        '◀' | '◁' => '<',
+        '▶' | '▷' => '>',
+        '●' | '◆' | '◈' | '◉' | '•' => '■',
+        '○' | '◇' => 'o',
+        '♥' => '*',
+        '×' => 'x',
+        '…' => '.',
+        '✓' | '✔' => '√',
+        '\u{2800}' => ' ',
+        '\u{2801}'..='\u{28ff}' | '▖'..='▟' => '▒',
+        _ => return None,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn keys_sizes_and_cp437_output() {
+        assert_eq!(CP437_HIGH.chars().count(), 127);
+
+        let mut p = Parser::default();
+        let got = p.feed(b"a\x1b[A\r\0\xff\xfa\x1f\x00\x78\x00\x28\xff\xf0\x1b\x03");
+        assert_eq!(got, vec![
+            Input::Size(120, 40),
+            Input::Key(KeyCode::Char('a'), KeyModifiers::NONE),
+            Input::Key(KeyCode::Up, KeyModifiers::NONE),
+            Input::Key(KeyCode::Enter, KeyModifiers::NONE),
+            Input::Key(KeyCode::Esc, KeyModifiers::NONE),
+            Input::Key(KeyCode::Char('c'), KeyModifiers::CONTROL),
+        ]);
+        assert_eq!(p.feed(b"\x1b[5~\xff\xfd"), vec![Input::Key(KeyCode::PageUp, KeyModifiers::NONE)]);
+        assert_eq!(p.feed(b"\x01Q"), vec![Input::Key(KeyCode::Char('Q'), KeyModifiers::SHIFT)]);
+
+        // Sizes are held to what a screen can sensibly be; a subnegotiation
+        // that never ends is read through without being kept
+        assert_eq!(p.feed(b"\xff\xfa\x1f\xff\xff\xff\xff\x00\x00\xff\xf0"), vec![Input::Size(512, 1)]);
+        p.feed(b"\xff\xfa\x18");
+        for _ in 0..1000 {
+            p.feed(&[b'x'; 512]);
+            assert!(p.command.len() <= MAX_SUBNEG);
+        }
+        assert_eq!(p.feed(b"\xff\xf0z"), vec![Input::Key(KeyCode::Char('z'), KeyModifiers::NONE)]);
+
+        let mut out = Cp437(Vec::new(), vec![]);
+        let text = "╔═╗▓░🤺🧍╏é漢\x1b[1m".as_bytes();
+        let (a, b) = text.split_at(5);  // mid-character
+        out.write_all(a).unwrap();
+        out.write_all(b).unwrap();
+        assert_eq!(out.0, b"\xc9\xcd\xbb\xb2\xb0& @ \xb3\x82??\x1b[1m");
+    }
+}
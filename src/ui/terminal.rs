@@ -0,0 +1,90 @@
+//! Getting the terminal back however the game ends.
+//!
+//! `Renderer::init` puts the terminal in raw mode on the alternate screen
+//! with the cursor hidden, and `Renderer::cleanup` undoes that on a normal
+//! quit. `install` covers the other ways out:
+//!   - a panic restores the terminal before the message is printed, so it
+//!     lands on the user's screen instead of a discarded alternate one
+//!   - SIGTERM / SIGHUP (Unix) ask the game loop to quit, which then
+//!     cleans up as for Ctrl+C
+//!   - SIGTSTP (Unix) asks it to `suspend`, as Ctrl+Z does in raw mode;
+//!     SIGCONT after any stop asks it to take the terminal back
+
+use std::io;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use crossterm::{cursor, event::DisableFocusChange, execute, style::ResetColor, terminal};
+
+/// The terminal is in the game's mode (between `init` and `cleanup`).
+static ACTIVE: AtomicBool = AtomicBool::new(false);
+
+pub fn set_active(on: bool) {
+    ACTIVE.store(on, Ordering::SeqCst);
+}
+
+/// Undo `Renderer::init` on stdout, if it's in effect.
+pub fn restore() {
+    if !ACTIVE.swap(false, Ordering::SeqCst) { return; }
+    let _ = execute!(io::stdout(), ResetColor, cursor::Show, DisableFocusChange, terminal::LeaveAlternateScreen);
+    let _ = terminal::disable_raw_mode();
+}
+
+/// What signals have asked of the game loop since it last looked.
+#[derive(Default)]
+pub struct Signals {
+    quit: Arc<AtomicBool>,
+    stop: Arc<AtomicBool>,
+    cont: Arc<AtomicBool>,
+}
+
+impl Signals {
+    pub fn quit(&self) -> bool {
+        self.quit.load(Ordering::Relaxed)
+    }
+
+    /// A SIGTSTP arrived.
+    pub fn take_stop(&self) -> bool {
+        self.stop.swap(false, Ordering::Relaxed)
+    }
+
+    /// The process was continued after a stop.
+    pub fn take_cont(&self) -> bool {
+        self.cont.swap(false, Ordering::Relaxed)
+    }
+}
+
+/// Install the panic hook and signal handlers.
+pub fn install() -> Signals {
+    let report = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        restore();
+        report(info);
+    }));
+
+    let signals = Signals::default();
+    #[cfg(unix)]
+    {
+        use signal_hook::consts::{SIGCONT, SIGHUP, SIGTERM, SIGTSTP};
+        use signal_hook::flag::register;
+        for (sig, flag) in [(SIGTERM, &signals.quit), (SIGHUP, &signals.quit),
+                            (SIGTSTP, &signals.stop), (SIGCONT, &signals.cont)] {
+            let _ = register(sig, Arc::clone(flag));
+        }
+    }
+    signals
+}
+
+/// Hand the terminal back and stop the process, as the shell expects of
+/// Ctrl+Z; returns once it's continued, for the caller to re-init the
+/// terminal. False (and nothing done) where there's no job control.
+pub fn suspend() -> bool {
+    #[cfg(unix)]
+    {
+        restore();
+        let _ = signal_hook::low_level::emulate_default_handler(signal_hook::consts::SIGTSTP);
+        true
+    }
+    #[cfg(not(unix))]
+    false
+}
@@ -0,0 +1,121 @@
+//! Level transition strategies for the intro reveal and the outro wipe.
+//!
+//! A strategy decides, for each map cell, whether it is hidden, on the
+//! "frontier" (highlighted as it appears), or shown, `t` ticks into the
+//! transition. The intro reveals cells in that order; the outro hides them
+//! in the same order. Timing is in sim ticks so main.rs can drive phase
+//! changes and intro blips from the same numbers the renderer uses.
+
+use crate::config::Transition;
+
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub enum Reveal {
+    Hidden,
+    Frontier,
+    Shown,
+}
+
+pub trait TransitionStrategy {
+    /// Ticks until every cell of a `width × height` level is shown.
+    fn duration(&self, width: usize, height: usize) -> u32;
+
+    /// State of cell (gx, gy) `t` ticks into the transition.
+    fn reveal(&self, gx: usize, gy: usize, width: usize, height: usize, t: u32) -> Reveal;
+}
+
+/// Ticks the node name card is shown before the intro reveal starts.
+pub const NAME_CARD_TICKS: u32 = 8;
+
+/// Ticks between intro blips; each blip marks one "step" of the reveal.
+pub const STEP_TICKS: u32 = 2;
+
+pub fn strategy(kind: Transition) -> &'static dyn TransitionStrategy {
+    match kind {
+        Transition::Rows => &Rows,
+        Transition::Dissolve => &Dissolve,
+        Transition::Sweep => &Sweep,
+        Transition::Instant => &Instant,
+    }
+}
+
+/// Shown if the cell's reveal time is before `t`; frontier on the tick it appears.
+fn at(reveal_tick: u32, t: u32) -> Reveal {
+    if reveal_tick + 1 == t {
+        Reveal::Frontier
+    } else if reveal_tick < t {
+        Reveal::Shown
+    } else {
+        Reveal::Hidden
+    }
+}
+
+// ── Rows: bottom-up, one row every STEP_TICKS (the original intro) ──
+
+struct Rows;
+
+impl TransitionStrategy for Rows {
+    fn duration(&self, _width: usize, height: usize) -> u32 {
+        height as u32 * STEP_TICKS
+    }
+
+    fn reveal(&self, _gx: usize, gy: usize, _width: usize, height: usize, t: u32) -> Reveal {
+        let rows_visible = (t / STEP_TICKS) as usize;
+        let from_bottom = height - 1 - gy;
+        if from_bottom + 1 == rows_visible {
+            Reveal::Frontier
+        } else if from_bottom < rows_visible {
+            Reveal::Shown
+        } else {
+            Reveal::Hidden
+        }
+    }
+}
+
+// ── Sweep: left to right, one column per tick ──
+
+struct Sweep;
+
+impl TransitionStrategy for Sweep {
+    fn duration(&self, width: usize, _height: usize) -> u32 {
+        width as u32
+    }
+
+    fn reveal(&self, gx: usize, _gy: usize, _width: usize, _height: usize, t: u32) -> Reveal {
+        at(gx as u32, t)
+    }
+}
+
+// ── Dissolve: cells pop in at scattered, fixed pseudo-random times ──
+
+struct Dissolve;
+
+const DISSOLVE_TICKS: u32 = 32;
+
+impl TransitionStrategy for Dissolve {
+    fn duration(&self, _width: usize, _height: usize) -> u32 {
+        DISSOLVE_TICKS
+    }
+
+    fn reveal(&self, gx: usize, gy: usize, _width: usize, _height: usize, t: u32) -> Reveal {
+        // Integer hash of the position: stable across frames, no RNG state
+        let mut h = (gx as u32).wrapping_mul(0x9e37_79b1) ^ (gy as u32).wrapping_mul(0x85eb_ca6b);
+        h ^= h >> 15;
+        h = h.wrapping_mul(0x2c1b_3c6d);
+        h ^= h >> 12;
+        at(h % (DISSOLVE_TICKS - 1), t)
+    }
+}
+
+// ── Instant: everything at once ──
+
+struct Instant;
+
+impl TransitionStrategy for Instant {
+    fn duration(&self, _width: usize, _height: usize) -> u32 {
+        0
+    }
+
+    fn reveal(&self, _gx: usize, _gy: usize, _width: usize, _height: usize, _t: u32) -> Reveal {
+        Reveal::Shown
+    }
+}